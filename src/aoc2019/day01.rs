@@ -1,25 +1,27 @@
 // https://adventofcode.com/2019/day/1
 
-use crate::utils::get_lines;
+use crate::error::{AocResult, parse_token};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput, get_lines};
 
 struct Input {
     masses: Vec<u32>,
 }
 
-fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
+fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let lines = get_lines(puzzle_input)?;
 
     let mut masses: Vec<u32> = Vec::new();
 
-    for line in lines {
-        masses.push(line.parse::<u32>().unwrap());
+    for (line_idx, line) in lines.iter().enumerate() {
+        masses.push(parse_token(line_idx, line, line)?);
     }
 
-    Input { masses }
+    Ok(Input { masses })
 }
 
-pub fn get_sum_fuel_reqs(input_file: &str) -> u32 {
-    let input = parse_input(input_file);
+pub fn get_sum_fuel_reqs(puzzle_input: impl PuzzleInput) -> AocResult<u32> {
+    let input = parse_input(puzzle_input)?;
 
     let mut sum_fuel_reqs = 0;
 
@@ -27,11 +29,11 @@ pub fn get_sum_fuel_reqs(input_file: &str) -> u32 {
         sum_fuel_reqs += (mass / 3).saturating_sub(2);
     }
 
-    sum_fuel_reqs
+    Ok(sum_fuel_reqs)
 }
 
-pub fn get_sum_fuel_reqs_with_fuel(input_file: &str) -> u32 {
-    let input = parse_input(input_file);
+pub fn get_sum_fuel_reqs_with_fuel(puzzle_input: impl PuzzleInput) -> AocResult<u32> {
+    let input = parse_input(puzzle_input)?;
 
     let mut sum_fuel_reqs = 0;
 
@@ -43,7 +45,24 @@ pub fn get_sum_fuel_reqs_with_fuel(input_file: &str) -> u32 {
         }
     }
 
-    sum_fuel_reqs
+    Ok(sum_fuel_reqs)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_sum_fuel_reqs(Contents(input))?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_sum_fuel_reqs_with_fuel(Contents(input))?.into())
+    }
 }
 
 #[cfg(test)]
@@ -52,24 +71,30 @@ mod tests {
 
     #[test]
     fn test_get_sum_fuel_reqs_test01() {
-        assert_eq!(34241, get_sum_fuel_reqs("input/2019/day01_test01.txt"));
+        assert_eq!(
+            34241,
+            get_sum_fuel_reqs("input/2019/day01_test01.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_fuel_reqs() {
-        assert_eq!(3361976, get_sum_fuel_reqs("input/2019/day01.txt"));
+        assert_eq!(3361976, get_sum_fuel_reqs("input/2019/day01.txt").unwrap());
     }
 
     #[test]
     fn test_get_sum_fuel_reqs_with_fuel_test02() {
         assert_eq!(
             51314,
-            get_sum_fuel_reqs_with_fuel("input/2019/day01_test02.txt")
+            get_sum_fuel_reqs_with_fuel("input/2019/day01_test02.txt").unwrap()
         );
     }
 
     #[test]
     fn test_get_sum_fuel_reqs_with_fuel() {
-        assert_eq!(5040085, get_sum_fuel_reqs_with_fuel("input/2019/day01.txt"));
+        assert_eq!(
+            5040085,
+            get_sum_fuel_reqs_with_fuel("input/2019/day01.txt").unwrap()
+        );
     }
 }
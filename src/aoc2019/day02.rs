@@ -1,127 +1,146 @@
 // https://adventofcode.com/2019/day/2
 
+use crate::error::{AocError, AocResult};
+use crate::intcode::{IntcodeMachine, parse_intcode_input};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput};
+
+/// Runs the program with its noun and verb set, giving the value left at
+/// address 0.
+fn run_with_noun_verb(mut intcode: Vec<isize>, noun: isize, verb: isize) -> AocResult<isize> {
+    let Some([_, noun_addr, verb_addr]) = intcode.get_mut(..3) else {
+        return Err(AocError::invalid(format!(
+            "program has {} words, too short for a noun and verb",
+            intcode.len()
+        )));
+    };
+    *noun_addr = noun;
+    *verb_addr = verb;
+    let mut machine = IntcodeMachine::new(intcode);
+    machine.run_until_blocked()?;
+    Ok(machine.memory()[0])
+}
+
+pub fn get_value_at_pos_zero(
+    puzzle_input: impl PuzzleInput,
+    noun: isize,
+    verb: isize,
+) -> AocResult<isize> {
+    run_with_noun_verb(parse_intcode_input(puzzle_input)?, noun, verb)
+}
+
+pub fn get_noun_verb(puzzle_input: impl PuzzleInput, target: isize) -> AocResult<Option<isize>> {
+    let input_intcode = parse_intcode_input(puzzle_input)?;
+    for noun in 0..100 {
+        for verb in 0..100 {
+            if run_with_noun_verb(input_intcode.clone(), noun, verb)? == target {
+                return Ok(Some(100 * noun + verb));
+            }
+        }
+    }
+    Ok(None)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_intcode_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_value_at_pos_zero(Contents(input), 12, 2)?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_noun_verb(Contents(input), 19690720)?.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::intcode::parse_intcode_input;
-    use crate::intcode::run_intcode;
+    use super::*;
 
     #[test]
     fn test_run_intcode_test01() {
         let expected_intcode = vec![3500, 9, 10, 70, 2, 3, 11, 0, 99, 30, 40, 50];
-        let mut input_intcode = parse_intcode_input("input/2019/day02_test01.txt");
-        let mut outputs = vec![];
-        assert_eq!(
-            expected_intcode,
-            run_intcode(
-                &mut input_intcode,
-                &mut 0,
-                &mut 0,
-                &mut vec![],
-                &mut outputs
-            )
-        );
+        let input_intcode = parse_intcode_input("input/2019/day02_test01.txt").unwrap();
+        let mut machine = IntcodeMachine::new(input_intcode);
+        machine.run_until_blocked().unwrap();
+        assert_eq!(expected_intcode, machine.memory());
     }
 
     #[test]
     fn test_run_intcode_test02() {
         let expected_intcode = vec![2, 0, 0, 0, 99];
-        let mut input_intcode = parse_intcode_input("input/2019/day02_test02.txt");
-        let mut outputs = vec![];
-        assert_eq!(
-            expected_intcode,
-            run_intcode(
-                &mut input_intcode,
-                &mut 0,
-                &mut 0,
-                &mut vec![],
-                &mut outputs
-            )
-        );
+        let input_intcode = parse_intcode_input("input/2019/day02_test02.txt").unwrap();
+        let mut machine = IntcodeMachine::new(input_intcode);
+        machine.run_until_blocked().unwrap();
+        assert_eq!(expected_intcode, machine.memory());
     }
 
     #[test]
     fn test_run_intcode_test03() {
         let expected_intcode = vec![2, 3, 0, 6, 99];
-        let mut input_intcode = parse_intcode_input("input/2019/day02_test03.txt");
-        let mut outputs = vec![];
-        assert_eq!(
-            expected_intcode,
-            run_intcode(
-                &mut input_intcode,
-                &mut 0,
-                &mut 0,
-                &mut vec![],
-                &mut outputs
-            )
-        );
+        let input_intcode = parse_intcode_input("input/2019/day02_test03.txt").unwrap();
+        let mut machine = IntcodeMachine::new(input_intcode);
+        machine.run_until_blocked().unwrap();
+        assert_eq!(expected_intcode, machine.memory());
     }
 
     #[test]
     fn test_run_intcode_test04() {
         let expected_intcode = vec![2, 4, 4, 5, 99, 9801];
-        let mut input_intcode = parse_intcode_input("input/2019/day02_test04.txt");
-        let mut outputs = vec![];
-        assert_eq!(
-            expected_intcode,
-            run_intcode(
-                &mut input_intcode,
-                &mut 0,
-                &mut 0,
-                &mut vec![],
-                &mut outputs
-            )
-        );
+        let input_intcode = parse_intcode_input("input/2019/day02_test04.txt").unwrap();
+        let mut machine = IntcodeMachine::new(input_intcode);
+        machine.run_until_blocked().unwrap();
+        assert_eq!(expected_intcode, machine.memory());
     }
 
     #[test]
     fn test_run_intcode_test05() {
         let expected_intcode = vec![30, 1, 1, 4, 2, 5, 6, 0, 99];
-        let mut input_intcode = parse_intcode_input("input/2019/day02_test05.txt");
-        let mut outputs = vec![];
-        assert_eq!(
-            expected_intcode,
-            run_intcode(
-                &mut input_intcode,
-                &mut 0,
-                &mut 0,
-                &mut vec![],
-                &mut outputs
-            )
-        );
+        let input_intcode = parse_intcode_input("input/2019/day02_test05.txt").unwrap();
+        let mut machine = IntcodeMachine::new(input_intcode);
+        machine.run_until_blocked().unwrap();
+        assert_eq!(expected_intcode, machine.memory());
     }
 
     #[test]
     fn test_run_intcode_part01() {
-        let mut input_intcode = parse_intcode_input("input/2019/day02.txt");
+        let mut input_intcode = parse_intcode_input("input/2019/day02.txt").unwrap();
         input_intcode[1] = 12;
         input_intcode[2] = 2;
-        let mut outputs = vec![];
-        let result_intcode = run_intcode(
-            &mut input_intcode,
-            &mut 0,
-            &mut 0,
-            &mut vec![],
-            &mut outputs,
-        );
-        assert_eq!(10566835, result_intcode[0]);
+        let mut machine = IntcodeMachine::new(input_intcode);
+        machine.run_until_blocked().unwrap();
+        assert_eq!(10566835, machine.memory()[0]);
     }
 
     #[ignore]
     #[test]
     fn test_run_intcode_part02() {
-        let input_intcode = parse_intcode_input("input/2019/day02.txt");
-        let mut outputs = vec![];
-        for noun in 0..99 {
-            for verb in 0..99 {
+        let input_intcode = parse_intcode_input("input/2019/day02.txt").unwrap();
+        for noun in 0..100 {
+            for verb in 0..100 {
                 let mut intcode = input_intcode.clone();
                 intcode[1] = noun;
                 intcode[2] = verb;
-                let result_intcode =
-                    run_intcode(&mut intcode, &mut 0, &mut 0, &mut vec![], &mut outputs);
-                if result_intcode[0] == 19690720 {
+                let mut machine = IntcodeMachine::new(intcode);
+                machine.run_until_blocked().unwrap();
+                if machine.memory()[0] == 19690720 {
                     assert_eq!(2347, 100 * noun + verb)
                 }
             }
         }
     }
+
+    #[test]
+    fn test_get_value_at_pos_zero_short_program() {
+        let err = get_value_at_pos_zero(Contents("1,0\n"), 12, 2).unwrap_err();
+        assert_eq!(
+            "<input>: program has 2 words, too short for a noun and verb",
+            err.to_string()
+        );
+    }
 }
@@ -2,7 +2,9 @@
 
 use std::collections::HashSet;
 
-use crate::utils::{MoveDir, get_lines, manhattan_distance_i};
+use crate::error::{AocError, AocResult, parse_token, require};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, Direction, MoveDir, Point, PuzzleInput, get_lines};
 
 struct WireMove {
     dir: MoveDir,
@@ -14,92 +16,73 @@ struct Input {
     wire2_path: Vec<WireMove>,
 }
 
-fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
+fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let lines = get_lines(puzzle_input)?;
 
-    let wire1_path: Vec<WireMove> = parse_wire_path(&lines[0]);
-    let wire2_path: Vec<WireMove> = parse_wire_path(&lines[1]);
+    let wire1_path: Vec<WireMove> =
+        parse_wire_path(0, require(lines.first(), 0, "missing first wire")?)?;
+    let wire2_path: Vec<WireMove> =
+        parse_wire_path(1, require(lines.get(1), 1, "missing second wire")?)?;
 
-    Input {
+    Ok(Input {
         wire1_path,
         wire2_path,
-    }
+    })
 }
 
-fn parse_wire_path(line: &str) -> Vec<WireMove> {
+fn parse_wire_path(line_idx: usize, line: &str) -> AocResult<Vec<WireMove>> {
     line.split(',')
         .map(|s| {
-            let (dir, dist) = s.split_at(1);
+            let dir_len = s.chars().next().map_or(0, char::len_utf8);
+            let (dir, dist) = s.split_at(dir_len);
             let dir = match dir {
                 "U" => MoveDir::Up,
                 "D" => MoveDir::Down,
                 "L" => MoveDir::Left,
                 "R" => MoveDir::Right,
-                _ => panic!("Invalid direction"),
+                _ => {
+                    return Err(AocError::parse_token(
+                        line_idx,
+                        line,
+                        s,
+                        format!("invalid direction {dir:?}"),
+                    ));
+                }
             };
-            let dist = dist.parse::<isize>().expect("Invalid distance");
-            WireMove { dir, dist }
+            let dist = parse_token(line_idx, line, dist)?;
+            Ok(WireMove { dir, dist })
         })
         .collect()
 }
 
-fn get_wire_moves(wire_path: &Vec<WireMove>, start_pos: (isize, isize)) -> Vec<(isize, isize)> {
-    let mut wire_moves: Vec<(isize, isize)> = vec![];
+fn get_wire_moves(wire_path: &Vec<WireMove>, start_pos: Point<isize>) -> Vec<Point<isize>> {
+    let mut wire_moves: Vec<Point<isize>> = vec![];
     let mut wire_pos = start_pos;
 
     for wire_move in wire_path {
-        match wire_move.dir {
-            MoveDir::Up => {
-                let start_y = wire_pos.1;
-                for i in 1..wire_move.dist + 1 {
-                    let new_pos = (wire_pos.0, start_y + i);
-                    wire_moves.push(new_pos);
-                    wire_pos = new_pos;
-                }
-            }
-            MoveDir::Down => {
-                let start_y = wire_pos.1;
-                for i in 1..wire_move.dist + 1 {
-                    let new_pos = (wire_pos.0, start_y - i);
-                    wire_moves.push(new_pos);
-                    wire_pos = new_pos;
-                }
-            }
-            MoveDir::Left => {
-                let start_x = wire_pos.0;
-                for i in 1..wire_move.dist + 1 {
-                    let new_pos = (start_x - i, wire_pos.1);
-                    wire_moves.push(new_pos);
-                    wire_pos = new_pos;
-                }
-            }
-            MoveDir::Right => {
-                let start_x = wire_pos.0;
-                for i in 1..wire_move.dist + 1 {
-                    let new_pos = (start_x + i, wire_pos.1);
-                    wire_moves.push(new_pos);
-                    wire_pos = new_pos;
-                }
-            }
+        let step = Point::from(Direction::from(wire_move.dir));
+        for _ in 0..wire_move.dist {
+            wire_pos += step;
+            wire_moves.push(wire_pos);
         }
     }
 
     wire_moves
 }
 
-pub fn get_closest_dist(input_file: &str) -> isize {
-    let input = parse_input(input_file);
+pub fn get_closest_dist(puzzle_input: impl PuzzleInput) -> AocResult<isize> {
+    let input = parse_input(puzzle_input)?;
 
-    let start_pos = (0isize, 0isize);
+    let start_pos = Point::ORIGIN;
 
     let wire1_moves = get_wire_moves(&input.wire1_path, start_pos);
 
     let wire2_moves = get_wire_moves(&input.wire2_path, start_pos);
 
-    let wire1_moves_set: HashSet<(isize, isize)> = HashSet::from_iter(wire1_moves);
-    let wire2_moves_set: HashSet<(isize, isize)> = HashSet::from_iter(wire2_moves);
+    let wire1_moves_set: HashSet<Point<isize>> = HashSet::from_iter(wire1_moves);
+    let wire2_moves_set: HashSet<Point<isize>> = HashSet::from_iter(wire2_moves);
 
-    let intersections: Vec<(isize, isize)> = wire1_moves_set
+    let intersections: Vec<Point<isize>> = wire1_moves_set
         .intersection(&wire2_moves_set)
         .cloned()
         .collect();
@@ -107,13 +90,16 @@ pub fn get_closest_dist(input_file: &str) -> isize {
     let mut manhattan_distances = vec![];
 
     for intersection in intersections {
-        manhattan_distances.push(manhattan_distance_i(start_pos, intersection));
+        manhattan_distances.push(start_pos.manhattan_distance(intersection));
     }
 
-    *manhattan_distances.iter().min().unwrap()
+    manhattan_distances
+        .into_iter()
+        .min()
+        .ok_or(AocError::invalid("the wires never cross"))
 }
 
-fn get_combined_steps(wire_moves: &Vec<(isize, isize)>, intersection: (isize, isize)) -> isize {
+fn get_combined_steps(wire_moves: &Vec<Point<isize>>, intersection: Point<isize>) -> isize {
     let mut steps = 0;
     for wire_move in wire_moves {
         steps += 1;
@@ -125,19 +111,19 @@ fn get_combined_steps(wire_moves: &Vec<(isize, isize)>, intersection: (isize, is
     steps
 }
 
-pub fn get_closest_combined_steps(input_file: &str) -> isize {
-    let input = parse_input(input_file);
+pub fn get_closest_combined_steps(puzzle_input: impl PuzzleInput) -> AocResult<isize> {
+    let input = parse_input(puzzle_input)?;
 
-    let start_pos = (0isize, 0isize);
+    let start_pos = Point::ORIGIN;
 
     let wire1_moves = get_wire_moves(&input.wire1_path, start_pos);
 
     let wire2_moves = get_wire_moves(&input.wire2_path, start_pos);
 
-    let wire1_moves_set: HashSet<(isize, isize)> = HashSet::from_iter(wire1_moves.clone());
-    let wire2_moves_set: HashSet<(isize, isize)> = HashSet::from_iter(wire2_moves.clone());
+    let wire1_moves_set: HashSet<Point<isize>> = HashSet::from_iter(wire1_moves.clone());
+    let wire2_moves_set: HashSet<Point<isize>> = HashSet::from_iter(wire2_moves.clone());
 
-    let intersections: Vec<(isize, isize)> = wire1_moves_set
+    let intersections: Vec<Point<isize>> = wire1_moves_set
         .intersection(&wire2_moves_set)
         .cloned()
         .collect();
@@ -149,7 +135,27 @@ pub fn get_closest_combined_steps(input_file: &str) -> isize {
         combined_steps
             .push(wire1_combined_steps_for_intersection + wire2_combined_steps_for_intersection);
     }
-    *combined_steps.iter().min().unwrap()
+    combined_steps
+        .into_iter()
+        .min()
+        .ok_or(AocError::invalid("the wires never cross"))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_closest_dist(Contents(input))?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_closest_combined_steps(Contents(input))?.into())
+    }
 }
 
 #[cfg(test)]
@@ -158,29 +164,35 @@ mod tests {
 
     #[test]
     fn test_get_closest_dist_test01() {
-        assert_eq!(6, get_closest_dist("input/2019/day03_test01.txt"));
+        assert_eq!(6, get_closest_dist("input/2019/day03_test01.txt").unwrap());
     }
 
     #[test]
     fn test_get_closest_dist_test02() {
-        assert_eq!(159, get_closest_dist("input/2019/day03_test02.txt"));
+        assert_eq!(
+            159,
+            get_closest_dist("input/2019/day03_test02.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_closest_dist_test03() {
-        assert_eq!(135, get_closest_dist("input/2019/day03_test03.txt"));
+        assert_eq!(
+            135,
+            get_closest_dist("input/2019/day03_test03.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_closest_dist() {
-        assert_eq!(293, get_closest_dist("input/2019/day03.txt"));
+        assert_eq!(293, get_closest_dist("input/2019/day03.txt").unwrap());
     }
 
     #[test]
     fn test_get_closest_combined_steps_test01() {
         assert_eq!(
             30,
-            get_closest_combined_steps("input/2019/day03_test01.txt")
+            get_closest_combined_steps("input/2019/day03_test01.txt").unwrap()
         );
     }
 
@@ -188,7 +200,7 @@ mod tests {
     fn test_get_closest_combined_steps_test02() {
         assert_eq!(
             610,
-            get_closest_combined_steps("input/2019/day03_test02.txt")
+            get_closest_combined_steps("input/2019/day03_test02.txt").unwrap()
         );
     }
 
@@ -196,12 +208,15 @@ mod tests {
     fn test_get_closest_combined_steps_test03() {
         assert_eq!(
             410,
-            get_closest_combined_steps("input/2019/day03_test03.txt")
+            get_closest_combined_steps("input/2019/day03_test03.txt").unwrap()
         );
     }
 
     #[test]
     fn test_get_closest_combined_steps() {
-        assert_eq!(27306, get_closest_combined_steps("input/2019/day03.txt"));
+        assert_eq!(
+            27306,
+            get_closest_combined_steps("input/2019/day03.txt").unwrap()
+        );
     }
 }
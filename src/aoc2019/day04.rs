@@ -2,21 +2,24 @@
 
 use std::collections::HashMap;
 
-use crate::utils::get_lines;
+use crate::error::{AocResult, parse_token, require};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput, get_lines};
 use itertools::Itertools;
 
 struct Input {
     range: (usize, usize),
 }
 
-fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
-    let mut parts = lines[0].split('-');
-    let start = parts.next().unwrap().parse::<usize>().unwrap();
-    let end = parts.next().unwrap().parse::<usize>().unwrap();
-    Input {
+fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let lines = get_lines(puzzle_input)?;
+    let line = require(lines.first(), 0, "missing password range")?;
+    let (start, end) = require(line.split_once('-'), 0, "expected a range like 123-456")?;
+    let start = parse_token(0, line, start)?;
+    let end = parse_token(0, line, end)?;
+    Ok(Input {
         range: (start, end),
-    }
+    })
 }
 
 pub fn is_valid_password_part_one(password: &[usize]) -> bool {
@@ -77,8 +80,11 @@ pub fn is_valid_password_part_two(password: &[usize]) -> bool {
     is_adj_equal && !is_any_dec
 }
 
-pub fn is_valid_password_range(input_file: &str, is_part_two: bool) -> usize {
-    let input = parse_input(input_file);
+pub fn is_valid_password_range(
+    puzzle_input: impl PuzzleInput,
+    is_part_two: bool,
+) -> AocResult<usize> {
+    let input = parse_input(puzzle_input)?;
 
     let mut num_valid_passwords = 0usize;
 
@@ -98,10 +104,28 @@ pub fn is_valid_password_range(input_file: &str, is_part_two: bool) -> usize {
         }
     }
 
-    num_valid_passwords
+    Ok(num_valid_passwords)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(is_valid_password_range(Contents(input), false)?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(is_valid_password_range(Contents(input), true)?.into())
+    }
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use super::*;
 
@@ -147,11 +171,17 @@ mod tests {
 
     #[test]
     fn test_is_valid_password_range_part_one() {
-        assert_eq!(1929, is_valid_password_range("input/2019/day04.txt", false));
+        assert_eq!(
+            1929,
+            is_valid_password_range("input/2019/day04.txt", false).unwrap()
+        );
     }
 
     #[test]
     fn test_is_valid_password_range_part_two() {
-        assert_eq!(1306, is_valid_password_range("input/2019/day04.txt", true));
+        assert_eq!(
+            1306,
+            is_valid_password_range("input/2019/day04.txt", true).unwrap()
+        );
     }
 }
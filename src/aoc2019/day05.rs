@@ -1,285 +1,205 @@
 // https://adventofcode.com/2019/day/5
 
+use crate::error::AocResult;
+use crate::intcode::{IntcodeMachine, parse_intcode_input};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput};
+
+pub fn run_diagnostic_code(
+    puzzle_input: impl PuzzleInput,
+    system_id: isize,
+) -> AocResult<Option<isize>> {
+    let mut machine = IntcodeMachine::new(parse_intcode_input(puzzle_input)?);
+    machine.push_input(system_id);
+    Ok(machine.run_until_blocked()?.last().copied())
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_intcode_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(run_diagnostic_code(Contents(input), 1)?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(run_diagnostic_code(Contents(input), 5)?.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::intcode::parse_intcode_input;
-    use crate::intcode::run_intcode;
+    use crate::intcode::{IntcodeMachine, parse_intcode_input};
 
     #[test]
     fn test_run_intcode_test01() {
-        let mut input_intcode = parse_intcode_input("input/2019/day05_test01.txt");
-        let mut outputs = vec![];
-        run_intcode(
-            &mut input_intcode,
-            &mut 0,
-            &mut 0,
-            &mut vec![1],
-            &mut outputs,
-        );
+        let input_intcode = parse_intcode_input("input/2019/day05_test01.txt").unwrap();
+        let mut machine = IntcodeMachine::new(input_intcode);
+        machine.push_input(1);
+        let outputs = machine.run_until_blocked().unwrap();
         assert_eq!(vec![1], outputs)
     }
 
     #[test]
     fn test_run_intcode_test02() {
         let expected_intcode = vec![1002, 4, 3, 4, 99];
-        let mut input_intcode = parse_intcode_input("input/2019/day05_test02.txt");
-        let mut outputs = vec![];
-        assert_eq!(
-            expected_intcode,
-            run_intcode(
-                &mut input_intcode,
-                &mut 0,
-                &mut 0,
-                &mut vec![],
-                &mut outputs
-            )
-        );
+        let input_intcode = parse_intcode_input("input/2019/day05_test02.txt").unwrap();
+        let mut machine = IntcodeMachine::new(input_intcode);
+        machine.run_until_blocked().unwrap();
+        assert_eq!(expected_intcode, machine.memory());
     }
 
     #[test]
     fn test_run_intcode_test03() {
         let expected_intcode = vec![1101, 100, -1, 4, 99];
-        let mut input_intcode = parse_intcode_input("input/2019/day05_test03.txt");
-        let mut outputs = vec![];
-        assert_eq!(
-            expected_intcode,
-            run_intcode(
-                &mut input_intcode,
-                &mut 0,
-                &mut 0,
-                &mut vec![],
-                &mut outputs
-            )
-        );
+        let input_intcode = parse_intcode_input("input/2019/day05_test03.txt").unwrap();
+        let mut machine = IntcodeMachine::new(input_intcode);
+        machine.run_until_blocked().unwrap();
+        assert_eq!(expected_intcode, machine.memory());
     }
 
     #[test]
     fn test_run_diagnostic_code_part_one() {
-        let mut input_intcode = parse_intcode_input("input/2019/day05.txt");
-        let mut outputs = vec![];
-        run_intcode(
-            &mut input_intcode,
-            &mut 0,
-            &mut 0,
-            &mut vec![1],
-            &mut outputs,
-        );
+        let input_intcode = parse_intcode_input("input/2019/day05.txt").unwrap();
+        let mut machine = IntcodeMachine::new(input_intcode);
+        machine.push_input(1);
+        let outputs = machine.run_until_blocked().unwrap();
         assert_eq!(*outputs.last().unwrap(), 4601506);
     }
 
     #[test]
     fn test_run_intcode_test04_eq() {
         let expected_intcode_eq = vec![3, 9, 8, 9, 10, 9, 4, 9, 99, 1, 8];
-        let mut input_intcode = parse_intcode_input("input/2019/day05_test04.txt");
-        let mut outputs = vec![];
-        assert_eq!(
-            expected_intcode_eq,
-            run_intcode(
-                &mut input_intcode,
-                &mut 0,
-                &mut 0,
-                &mut vec![8],
-                &mut outputs
-            )
-        );
+        let input_intcode = parse_intcode_input("input/2019/day05_test04.txt").unwrap();
+        let mut machine = IntcodeMachine::new(input_intcode);
+        machine.push_input(8);
+        let outputs = machine.run_until_blocked().unwrap();
+        assert_eq!(expected_intcode_eq, machine.memory());
         assert_eq!(*outputs.last().unwrap(), 1);
     }
 
     #[test]
     fn test_run_intcode_test04_neq() {
         let expected_intcode_neq = vec![3, 9, 8, 9, 10, 9, 4, 9, 99, 0, 8];
-        let mut input_intcode = parse_intcode_input("input/2019/day05_test04.txt");
-        let mut outputs = vec![];
-        assert_eq!(
-            expected_intcode_neq,
-            run_intcode(
-                &mut input_intcode,
-                &mut 0,
-                &mut 0,
-                &mut vec![7],
-                &mut outputs
-            )
-        );
+        let input_intcode = parse_intcode_input("input/2019/day05_test04.txt").unwrap();
+        let mut machine = IntcodeMachine::new(input_intcode);
+        machine.push_input(7);
+        let outputs = machine.run_until_blocked().unwrap();
+        assert_eq!(expected_intcode_neq, machine.memory());
         assert_eq!(*outputs.last().unwrap(), 0);
     }
 
     #[test]
     fn test_run_intcode_test05_lt() {
         let expected_intcode_lt = vec![3, 9, 7, 9, 10, 9, 4, 9, 99, 1, 8];
-        let mut input_intcode = parse_intcode_input("input/2019/day05_test05.txt");
-        let mut outputs = vec![];
-        assert_eq!(
-            expected_intcode_lt,
-            run_intcode(
-                &mut input_intcode,
-                &mut 0,
-                &mut 0,
-                &mut vec![7],
-                &mut outputs
-            )
-        );
+        let input_intcode = parse_intcode_input("input/2019/day05_test05.txt").unwrap();
+        let mut machine = IntcodeMachine::new(input_intcode);
+        machine.push_input(7);
+        let outputs = machine.run_until_blocked().unwrap();
+        assert_eq!(expected_intcode_lt, machine.memory());
         assert_eq!(*outputs.last().unwrap(), 1);
     }
 
     #[test]
     fn test_run_intcode_test05_gt() {
         let expected_intcode_gt = vec![3, 9, 7, 9, 10, 9, 4, 9, 99, 0, 8];
-        let mut input_intcode = parse_intcode_input("input/2019/day05_test05.txt");
-        let mut outputs = vec![];
-        assert_eq!(
-            expected_intcode_gt,
-            run_intcode(
-                &mut input_intcode,
-                &mut 0,
-                &mut 0,
-                &mut vec![9],
-                &mut outputs
-            )
-        );
+        let input_intcode = parse_intcode_input("input/2019/day05_test05.txt").unwrap();
+        let mut machine = IntcodeMachine::new(input_intcode);
+        machine.push_input(9);
+        let outputs = machine.run_until_blocked().unwrap();
+        assert_eq!(expected_intcode_gt, machine.memory());
         assert_eq!(*outputs.last().unwrap(), 0);
     }
 
     #[test]
     fn test_run_intcode_test06_eq() {
         let expected_intcode_eq = vec![3, 3, 1108, 1, 8, 3, 4, 3, 99];
-        let mut input_intcode = parse_intcode_input("input/2019/day05_test06.txt");
-        let mut outputs = vec![];
-        assert_eq!(
-            expected_intcode_eq,
-            run_intcode(
-                &mut input_intcode,
-                &mut 0,
-                &mut 0,
-                &mut vec![8],
-                &mut outputs
-            )
-        );
+        let input_intcode = parse_intcode_input("input/2019/day05_test06.txt").unwrap();
+        let mut machine = IntcodeMachine::new(input_intcode);
+        machine.push_input(8);
+        let outputs = machine.run_until_blocked().unwrap();
+        assert_eq!(expected_intcode_eq, machine.memory());
         assert_eq!(*outputs.last().unwrap(), 1);
     }
 
     #[test]
     fn test_run_intcode_test06_neq() {
         let expected_intcode_neq = vec![3, 3, 1108, 0, 8, 3, 4, 3, 99];
-        let mut input_intcode = parse_intcode_input("input/2019/day05_test06.txt");
-        let mut outputs = vec![];
-        assert_eq!(
-            expected_intcode_neq,
-            run_intcode(
-                &mut input_intcode,
-                &mut 0,
-                &mut 0,
-                &mut vec![7],
-                &mut outputs
-            )
-        );
+        let input_intcode = parse_intcode_input("input/2019/day05_test06.txt").unwrap();
+        let mut machine = IntcodeMachine::new(input_intcode);
+        machine.push_input(7);
+        let outputs = machine.run_until_blocked().unwrap();
+        assert_eq!(expected_intcode_neq, machine.memory());
         assert_eq!(*outputs.last().unwrap(), 0);
     }
 
     #[test]
     fn test_run_intcode_test07_lt() {
         let expected_intcode_lt = vec![3, 3, 1107, 1, 8, 3, 4, 3, 99];
-        let mut input_intcode = parse_intcode_input("input/2019/day05_test07.txt");
-        let mut outputs = vec![];
-        assert_eq!(
-            expected_intcode_lt,
-            run_intcode(
-                &mut input_intcode,
-                &mut 0,
-                &mut 0,
-                &mut vec![7],
-                &mut outputs
-            )
-        );
+        let input_intcode = parse_intcode_input("input/2019/day05_test07.txt").unwrap();
+        let mut machine = IntcodeMachine::new(input_intcode);
+        machine.push_input(7);
+        let outputs = machine.run_until_blocked().unwrap();
+        assert_eq!(expected_intcode_lt, machine.memory());
         assert_eq!(*outputs.last().unwrap(), 1);
     }
 
     #[test]
     fn test_run_intcode_test07_gt() {
         let expected_intcode_gt = vec![3, 3, 1107, 0, 8, 3, 4, 3, 99];
-        let mut input_intcode = parse_intcode_input("input/2019/day05_test07.txt");
-        let mut outputs = vec![];
-        assert_eq!(
-            expected_intcode_gt,
-            run_intcode(
-                &mut input_intcode,
-                &mut 0,
-                &mut 0,
-                &mut vec![9],
-                &mut outputs
-            )
-        );
+        let input_intcode = parse_intcode_input("input/2019/day05_test07.txt").unwrap();
+        let mut machine = IntcodeMachine::new(input_intcode);
+        machine.push_input(9);
+        let outputs = machine.run_until_blocked().unwrap();
+        assert_eq!(expected_intcode_gt, machine.memory());
         assert_eq!(*outputs.last().unwrap(), 0);
     }
 
     #[test]
     fn test_run_intcode_test08_zero() {
         let expected_intcode_zero = vec![3, 12, 6, 12, 15, 1, 13, 14, 13, 4, 13, 99, 0, 0, 1, 9];
-        let mut input_intcode = parse_intcode_input("input/2019/day05_test08.txt");
-        let mut outputs = vec![];
-        assert_eq!(
-            expected_intcode_zero,
-            run_intcode(
-                &mut input_intcode,
-                &mut 0,
-                &mut 0,
-                &mut vec![0],
-                &mut outputs
-            )
-        );
+        let input_intcode = parse_intcode_input("input/2019/day05_test08.txt").unwrap();
+        let mut machine = IntcodeMachine::new(input_intcode);
+        machine.push_input(0);
+        let outputs = machine.run_until_blocked().unwrap();
+        assert_eq!(expected_intcode_zero, machine.memory());
         assert_eq!(*outputs.last().unwrap(), 0);
     }
 
     #[test]
     fn test_run_intcode_test08_nonzero() {
         let expected_intcode_nonzero = vec![3, 12, 6, 12, 15, 1, 13, 14, 13, 4, 13, 99, 1, 1, 1, 9];
-        let mut input_intcode = parse_intcode_input("input/2019/day05_test08.txt");
-        let mut outputs = vec![];
-        assert_eq!(
-            expected_intcode_nonzero,
-            run_intcode(
-                &mut input_intcode,
-                &mut 0,
-                &mut 0,
-                &mut vec![1],
-                &mut outputs
-            )
-        );
+        let input_intcode = parse_intcode_input("input/2019/day05_test08.txt").unwrap();
+        let mut machine = IntcodeMachine::new(input_intcode);
+        machine.push_input(1);
+        let outputs = machine.run_until_blocked().unwrap();
+        assert_eq!(expected_intcode_nonzero, machine.memory());
         assert_eq!(*outputs.last().unwrap(), 1);
     }
 
     #[test]
     fn test_run_intcode_test09_zero() {
         let expected_intcode_zero = vec![3, 3, 1105, 0, 9, 1101, 0, 0, 12, 4, 12, 99, 0];
-        let mut input_intcode = parse_intcode_input("input/2019/day05_test09.txt");
-        let mut outputs = vec![];
-        assert_eq!(
-            expected_intcode_zero,
-            run_intcode(
-                &mut input_intcode,
-                &mut 0,
-                &mut 0,
-                &mut vec![0],
-                &mut outputs
-            )
-        );
+        let input_intcode = parse_intcode_input("input/2019/day05_test09.txt").unwrap();
+        let mut machine = IntcodeMachine::new(input_intcode);
+        machine.push_input(0);
+        let outputs = machine.run_until_blocked().unwrap();
+        assert_eq!(expected_intcode_zero, machine.memory());
         assert_eq!(*outputs.last().unwrap(), 0);
     }
 
     #[test]
     fn test_run_intcode_test09_nonzero() {
         let expected_intcode_nonzero = vec![3, 3, 1105, 1, 9, 1101, 0, 0, 12, 4, 12, 99, 1];
-        let mut input_intcode = parse_intcode_input("input/2019/day05_test09.txt");
-        let mut outputs = vec![];
-        assert_eq!(
-            expected_intcode_nonzero,
-            run_intcode(
-                &mut input_intcode,
-                &mut 0,
-                &mut 0,
-                &mut vec![1],
-                &mut outputs
-            )
-        );
+        let input_intcode = parse_intcode_input("input/2019/day05_test09.txt").unwrap();
+        let mut machine = IntcodeMachine::new(input_intcode);
+        machine.push_input(1);
+        let outputs = machine.run_until_blocked().unwrap();
+        assert_eq!(expected_intcode_nonzero, machine.memory());
         assert_eq!(*outputs.last().unwrap(), 1);
     }
 
@@ -290,18 +210,11 @@ mod tests {
             7, 1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4,
             20, 1105, 1, 46, 98, 99,
         ];
-        let mut input_intcode = parse_intcode_input("input/2019/day05_test10.txt");
-        let mut outputs = vec![];
-        assert_eq!(
-            expected_intcode_lt,
-            run_intcode(
-                &mut input_intcode,
-                &mut 0,
-                &mut 0,
-                &mut vec![7],
-                &mut outputs
-            )
-        );
+        let input_intcode = parse_intcode_input("input/2019/day05_test10.txt").unwrap();
+        let mut machine = IntcodeMachine::new(input_intcode);
+        machine.push_input(7);
+        let outputs = machine.run_until_blocked().unwrap();
+        assert_eq!(expected_intcode_lt, machine.memory());
         assert_eq!(*outputs.last().unwrap(), 999);
     }
 
@@ -312,18 +225,11 @@ mod tests {
             1000, 8, 1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1,
             20, 4, 20, 1105, 1, 46, 98, 99,
         ];
-        let mut input_intcode = parse_intcode_input("input/2019/day05_test10.txt");
-        let mut outputs = vec![];
-        assert_eq!(
-            expected_intcode_eq,
-            run_intcode(
-                &mut input_intcode,
-                &mut 0,
-                &mut 0,
-                &mut vec![8],
-                &mut outputs
-            )
-        );
+        let input_intcode = parse_intcode_input("input/2019/day05_test10.txt").unwrap();
+        let mut machine = IntcodeMachine::new(input_intcode);
+        machine.push_input(8);
+        let outputs = machine.run_until_blocked().unwrap();
+        assert_eq!(expected_intcode_eq, machine.memory());
         assert_eq!(*outputs.last().unwrap(), 1000);
     }
 
@@ -334,32 +240,20 @@ mod tests {
             1001, 9, 1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1,
             20, 4, 20, 1105, 1, 46, 98, 99,
         ];
-        let mut input_intcode = parse_intcode_input("input/2019/day05_test10.txt");
-        let mut outputs = vec![];
-        assert_eq!(
-            expected_intcode_gt,
-            run_intcode(
-                &mut input_intcode,
-                &mut 0,
-                &mut 0,
-                &mut vec![9],
-                &mut outputs
-            )
-        );
+        let input_intcode = parse_intcode_input("input/2019/day05_test10.txt").unwrap();
+        let mut machine = IntcodeMachine::new(input_intcode);
+        machine.push_input(9);
+        let outputs = machine.run_until_blocked().unwrap();
+        assert_eq!(expected_intcode_gt, machine.memory());
         assert_eq!(*outputs.last().unwrap(), 1001);
     }
 
     #[test]
     fn test_run_diagnostic_code_part_two() {
-        let mut input_intcode = parse_intcode_input("input/2019/day05.txt");
-        let mut outputs = vec![];
-        run_intcode(
-            &mut input_intcode,
-            &mut 0,
-            &mut 0,
-            &mut vec![5],
-            &mut outputs,
-        );
+        let input_intcode = parse_intcode_input("input/2019/day05.txt").unwrap();
+        let mut machine = IntcodeMachine::new(input_intcode);
+        machine.push_input(5);
+        let outputs = machine.run_until_blocked().unwrap();
         assert_eq!(*outputs.last().unwrap(), 5525561);
     }
 }
@@ -4,7 +4,9 @@ use std::collections::HashSet;
 
 use petgraph::{algo, graph::UnGraph};
 
-use crate::utils::get_lines;
+use crate::error::{AocResult, require};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput, get_lines};
 
 struct Orbit {
     body: String,
@@ -16,16 +18,16 @@ struct Input {
     orbits: Vec<Orbit>,
 }
 
-fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
+fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let lines = get_lines(puzzle_input)?;
 
     let mut objects: HashSet<String> = HashSet::new();
     let mut orbits: Vec<Orbit> = Vec::new();
 
-    for line in lines {
-        let splits: Vec<&str> = line.split(')').collect();
-        let body: String = splits.first().map_or("", |v| v).to_string();
-        let satellite = splits.last().map_or("", |v| v).to_string();
+    for (line_idx, line) in lines.iter().enumerate() {
+        let (body, satellite) = require(line.split_once(')'), line_idx, "expected BODY)SATELLITE")?;
+        let body = body.to_string();
+        let satellite = satellite.to_string();
 
         objects.insert(body.clone());
         objects.insert(satellite.clone());
@@ -33,7 +35,7 @@ fn parse_input(input_file: &str) -> Input {
         orbits.push(Orbit { body, satellite });
     }
 
-    Input { objects, orbits }
+    Ok(Input { objects, orbits })
 }
 
 fn build_graph(
@@ -77,8 +79,8 @@ fn build_graph(
     (graph, maybe_com_index, maybe_san_index, maybe_you_index)
 }
 
-pub fn get_total_orbits(input_file: &str) -> u32 {
-    let input = parse_input(input_file);
+pub fn get_total_orbits(puzzle_input: impl PuzzleInput) -> AocResult<u32> {
+    let input = parse_input(puzzle_input)?;
 
     let (graph, maybe_com_index, _, _) = build_graph(&input);
 
@@ -100,11 +102,11 @@ pub fn get_total_orbits(input_file: &str) -> u32 {
         }
     }
 
-    total_orbits
+    Ok(total_orbits)
 }
 
-pub fn get_total_orbital_transfers(input_file: &str) -> u32 {
-    let input = parse_input(input_file);
+pub fn get_total_orbital_transfers(puzzle_input: impl PuzzleInput) -> AocResult<u32> {
+    let input = parse_input(puzzle_input)?;
 
     let (graph, _, maybe_san_index, maybe_you_index) = build_graph(&input);
 
@@ -122,7 +124,24 @@ pub fn get_total_orbital_transfers(input_file: &str) -> u32 {
         total_orbit_transfers += distance;
     }
 
-    total_orbit_transfers
+    Ok(total_orbit_transfers)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_total_orbits(Contents(input))?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_total_orbital_transfers(Contents(input))?.into())
+    }
 }
 
 #[cfg(test)]
@@ -131,26 +150,29 @@ mod tests {
 
     #[test]
     fn test_get_total_orbits_test01() {
-        assert_eq!(42, get_total_orbits("input/2019/day06_test01.txt"));
+        assert_eq!(42, get_total_orbits("input/2019/day06_test01.txt").unwrap());
     }
 
     #[ignore]
     #[test]
     fn test_get_total_orbits() {
-        assert_eq!(273985, get_total_orbits("input/2019/day06.txt"));
+        assert_eq!(273985, get_total_orbits("input/2019/day06.txt").unwrap());
     }
 
     #[test]
     fn test_get_total_orbital_transfers_test02() {
         assert_eq!(
             4,
-            get_total_orbital_transfers("input/2019/day06_test02.txt")
+            get_total_orbital_transfers("input/2019/day06_test02.txt").unwrap()
         );
     }
 
     #[ignore]
     #[test]
     fn test_get_total_orbital_transfers() {
-        assert_eq!(460, get_total_orbital_transfers("input/2019/day06.txt"));
+        assert_eq!(
+            460,
+            get_total_orbital_transfers("input/2019/day06.txt").unwrap()
+        );
     }
 }
@@ -1,113 +1,117 @@
 // https://adventofcode.com/2019/day/8
 
-use std::collections::HashMap;
-
 use itertools::Itertools;
+use log::debug;
 
-use crate::utils::get_lines;
+use crate::error::{AocError, AocResult};
+use crate::grid::Grid;
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput, get_lines};
 
 struct Input {
-    layers: Vec<Vec<Vec<u32>>>,
+    layers: Vec<Grid<u32>>,
 }
 
-fn parse_input(input_file: &str, width: usize, height: usize) -> Input {
-    let lines = get_lines(input_file);
+fn parse_input(puzzle_input: impl PuzzleInput, width: usize, height: usize) -> AocResult<Input> {
+    let lines = get_lines(puzzle_input)?;
 
-    let mut layers: Vec<Vec<Vec<u32>>> = Vec::new();
+    let mut layers: Vec<Grid<u32>> = Vec::new();
 
     if let Some(image) = lines.first() {
-        for layer in &image.chars().chunks(width * height) {
-            let layer_str = layer.collect::<String>();
-            let mut layer_vec = vec![];
-            for row in &layer_str.chars().chunks(width) {
-                let row_str = row.collect::<String>();
-                let row_vec = row_str
-                    .chars()
-                    .map(|c| c.to_digit(10).unwrap())
-                    .collect_vec();
-                layer_vec.push(row_vec);
-            }
-            layers.push(layer_vec);
+        let pixels: Vec<u32> = image
+            .chars()
+            .enumerate()
+            .map(|(i, c)| c.to_digit(10).ok_or(AocError::unexpected_char(0, i, c)))
+            .try_collect()?;
+        if !pixels.len().is_multiple_of(width * height) {
+            return Err(AocError::invalid(format!(
+                "{} pixels don't fill {width}x{height} layers",
+                pixels.len()
+            )));
+        }
+        for layer in pixels.chunks(width * height) {
+            layers.push(Grid::from_rows(
+                layer.chunks(width).map(|row| row.to_vec()).collect_vec(),
+            )?);
         }
     }
 
-    Input { layers }
+    Ok(Input { layers })
 }
 
-pub fn get_layer_with_fewest_zeros(layers: &[Vec<Vec<u32>>]) -> Option<Vec<Vec<u32>>> {
-    let mut zero_digits_count_by_layer: Vec<(u32, usize)> = vec![];
-    for (i, layer) in layers.iter().enumerate() {
-        let flat_layer: Vec<u32> = layer.iter().flatten().copied().collect();
-        let num_zero_digits = flat_layer.into_iter().filter(|&c| c == 0).count() as u32;
-        zero_digits_count_by_layer.push((num_zero_digits, i));
-    }
-    zero_digits_count_by_layer.sort();
-    if let Some((_, i)) = zero_digits_count_by_layer.first() {
-        Some(layers[*i].clone())
-    } else {
-        None
-    }
+fn count_digits(layer: &Grid<u32>, digit: u32) -> u32 {
+    layer.iter().filter(|&(_, &pixel)| pixel == digit).count() as u32
+}
+
+pub fn get_layer_with_fewest_zeros(layers: &[Grid<u32>]) -> Option<&Grid<u32>> {
+    layers.iter().min_by_key(|layer| count_digits(layer, 0))
 }
 
-pub fn get_layer_num_of_one_digits_mult_num_two_digits(layer: &[Vec<u32>]) -> u32 {
-    let flat_layer: Vec<u32> = layer.iter().flatten().copied().collect();
-    let num_one_digits = flat_layer.iter().filter(|&&c| c == 1).count() as u32;
-    let num_two_digits = flat_layer.iter().filter(|&&c| c == 2).count() as u32;
-    num_one_digits * num_two_digits
+pub fn get_layer_num_of_one_digits_mult_num_two_digits(layer: &Grid<u32>) -> u32 {
+    count_digits(layer, 1) * count_digits(layer, 2)
 }
 
 pub fn get_num_of_one_digits_mult_num_two_digits(
-    input_file: &str,
+    puzzle_input: impl PuzzleInput,
     width: usize,
     height: usize,
-) -> u32 {
-    let input = parse_input(input_file, width, height);
+) -> AocResult<u32> {
+    let input = parse_input(puzzle_input, width, height)?;
 
     let layer_with_fewest_zeros = get_layer_with_fewest_zeros(&input.layers);
 
-    match layer_with_fewest_zeros {
-        Some(layer) => get_layer_num_of_one_digits_mult_num_two_digits(&layer),
+    Ok(match layer_with_fewest_zeros {
+        Some(layer) => get_layer_num_of_one_digits_mult_num_two_digits(layer),
         None => 0,
-    }
+    })
 }
 
-pub fn decode_image(input_file: &str, width: usize, height: usize) -> Vec<Vec<u32>> {
-    let input = parse_input(input_file, width, height);
+pub fn decode_image(
+    puzzle_input: impl PuzzleInput,
+    width: usize,
+    height: usize,
+) -> AocResult<Grid<u32>> {
+    let input = parse_input(puzzle_input, width, height)?;
 
-    let mut image_map: HashMap<(usize, usize), u32> = HashMap::new();
+    // Layers are stacked front to back, so a pixel shows the first layer
+    // that isn't transparent there
+    let mut image = Grid::new(height, width, 2);
     for layer in input.layers.iter() {
-        for (i, row) in layer.iter().enumerate() {
-            for (j, pixel) in row.iter().enumerate() {
-                if let Some(top_pixel) = image_map.get(&(i, j)) {
-                    if *top_pixel == 2 {
-                        image_map.insert((i, j), *pixel);
-                    }
-                } else {
-                    image_map.insert((i, j), *pixel);
-                }
+        for (pos, &pixel) in layer.iter() {
+            if image[pos] == 2 {
+                image[pos] = pixel;
             }
         }
     }
 
-    let mut image = vec![];
-
-    for i in 0..height {
-        let mut row = vec![];
-        for j in 0..width {
-            if let Some(pixel) = image_map.get(&(i, j)) {
-                if *pixel == 1 {
-                    print!("{:?}", pixel);
-                } else {
-                    print!(" ");
-                }
-                row.push(*pixel);
-            }
-        }
-        println!();
-        image.push(row);
-    }
+    debug!("\n{}", render_image(&image));
+
+    Ok(image)
+}
 
+pub fn render_image(image: &Grid<u32>) -> String {
     image
+        .map(|&pixel| if pixel == 1 { '#' } else { ' ' })
+        .to_string()
+        .trim_end_matches('\n')
+        .to_string()
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input), 25, 6)?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_num_of_one_digits_mult_num_two_digits(Contents(input), 25, 6)?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(render_image(&decode_image(Contents(input), 25, 6)?).into())
+    }
 }
 
 #[cfg(test)]
@@ -118,7 +122,7 @@ mod tests {
     fn test_get_num_of_one_digits_mult_num_two_digits_test01() {
         assert_eq!(
             1,
-            get_num_of_one_digits_mult_num_two_digits("input/2019/day08_test01.txt", 3, 2)
+            get_num_of_one_digits_mult_num_two_digits("input/2019/day08_test01.txt", 3, 2).unwrap()
         );
     }
 
@@ -126,22 +130,22 @@ mod tests {
     fn test_get_num_of_one_digits_mult_num_two_digits() {
         assert_eq!(
             1820,
-            get_num_of_one_digits_mult_num_two_digits("input/2019/day08.txt", 25, 6)
+            get_num_of_one_digits_mult_num_two_digits("input/2019/day08.txt", 25, 6).unwrap()
         );
     }
 
     #[test]
     fn test_decode_image_test02() {
         assert_eq!(
-            vec![vec![0, 1], vec![1, 0]],
-            decode_image("input/2019/day08_test02.txt", 2, 2)
+            Grid::from_rows(vec![vec![0, 1], vec![1, 0]]).unwrap(),
+            decode_image("input/2019/day08_test02.txt", 2, 2).unwrap()
         );
     }
 
     #[test]
     fn test_decode_image() {
         assert_eq!(
-            vec![
+            Grid::from_rows(vec![
                 vec![
                     1, 1, 1, 1, 0, 1, 0, 0, 1, 0, 1, 0, 0, 1, 0, 0, 1, 1, 0, 0, 0, 0, 1, 1, 0
                 ],
@@ -160,8 +164,9 @@ mod tests {
                 vec![
                     1, 1, 1, 1, 0, 0, 1, 1, 0, 0, 1, 0, 0, 1, 0, 0, 1, 1, 0, 0, 0, 1, 1, 0, 0
                 ],
-            ],
-            decode_image("input/2019/day08.txt", 25, 6)
+            ])
+            .unwrap(),
+            decode_image("input/2019/day08.txt", 25, 6).unwrap()
         );
     }
 }
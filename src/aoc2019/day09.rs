@@ -1,207 +1,155 @@
 // https://adventofcode.com/2019/day/9
 
+use crate::error::AocResult;
+use crate::intcode::{IntcodeMachine, parse_intcode_input};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput};
+
+pub fn get_boost_keycode(puzzle_input: impl PuzzleInput, input: isize) -> AocResult<Vec<isize>> {
+    let mut machine = IntcodeMachine::new(parse_intcode_input(puzzle_input)?);
+    machine.push_input(input);
+    machine.run_until_blocked()
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_intcode_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_boost_keycode(Contents(input), 1)?
+            .last()
+            .copied()
+            .into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_boost_keycode(Contents(input), 2)?
+            .last()
+            .copied()
+            .into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::intcode::parse_intcode_input;
-    use crate::intcode::run_intcode;
+    use crate::intcode::{IntcodeMachine, parse_intcode_input};
 
     #[test]
     fn test_run_intcode_test01() {
         let expected_intcode = vec![
             109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
         ];
-        let mut input_intcode = parse_intcode_input("input/2019/day09_test01.txt");
-        input_intcode.extend(vec![0; 1000]);
-        let mut outputs = vec![];
-        run_intcode(
-            &mut input_intcode,
-            &mut 0,
-            &mut 0,
-            &mut vec![],
-            &mut outputs,
-        );
+        let input_intcode = parse_intcode_input("input/2019/day09_test01.txt").unwrap();
+        let mut machine = IntcodeMachine::new(input_intcode);
+        let outputs = machine.run_until_blocked().unwrap();
         assert_eq!(expected_intcode, outputs)
     }
 
     #[test]
     fn test_run_intcode_test02() {
         let expected_intcode = vec![1102, 34915192, 34915192, 7, 4, 7, 99, 1219070632396864];
-        let mut input_intcode = parse_intcode_input("input/2019/day09_test02.txt");
-        let mut outputs = vec![];
-        assert_eq!(
-            expected_intcode,
-            run_intcode(
-                &mut input_intcode,
-                &mut 0,
-                &mut 0,
-                &mut vec![],
-                &mut outputs
-            )
-        );
+        let input_intcode = parse_intcode_input("input/2019/day09_test02.txt").unwrap();
+        let mut machine = IntcodeMachine::new(input_intcode);
+        let outputs = machine.run_until_blocked().unwrap();
+        assert_eq!(expected_intcode, machine.memory());
         assert_eq!(vec![1219070632396864], outputs)
     }
 
     #[test]
     fn test_run_intcode_test03() {
         let expected_intcode = vec![104, 1125899906842624, 99];
-        let mut input_intcode = parse_intcode_input("input/2019/day09_test03.txt");
-        let mut outputs = vec![];
-        assert_eq!(
-            expected_intcode,
-            run_intcode(
-                &mut input_intcode,
-                &mut 0,
-                &mut 0,
-                &mut vec![],
-                &mut outputs
-            )
-        );
+        let input_intcode = parse_intcode_input("input/2019/day09_test03.txt").unwrap();
+        let mut machine = IntcodeMachine::new(input_intcode);
+        let outputs = machine.run_until_blocked().unwrap();
+        assert_eq!(expected_intcode, machine.memory());
         assert_eq!(vec![1125899906842624], outputs)
     }
 
     #[test]
     fn test_run_intcode_test04() {
-        let mut input_intcode = vec![109, -1, 4, 1, 99];
-        let mut outputs = vec![];
-        run_intcode(
-            &mut input_intcode,
-            &mut 0,
-            &mut 0,
-            &mut vec![],
-            &mut outputs,
-        );
+        let input_intcode = vec![109, -1, 4, 1, 99];
+        let mut machine = IntcodeMachine::new(input_intcode);
+        let outputs = machine.run_until_blocked().unwrap();
         assert_eq!(vec![-1], outputs)
     }
 
     #[test]
     fn test_run_intcode_test05() {
-        let mut input_intcode = vec![109, -1, 104, 1, 99];
-        let mut outputs = vec![];
-        run_intcode(
-            &mut input_intcode,
-            &mut 0,
-            &mut 0,
-            &mut vec![],
-            &mut outputs,
-        );
+        let input_intcode = vec![109, -1, 104, 1, 99];
+        let mut machine = IntcodeMachine::new(input_intcode);
+        let outputs = machine.run_until_blocked().unwrap();
         assert_eq!(vec![1], outputs)
     }
 
     #[test]
     fn test_run_intcode_test06() {
-        let mut input_intcode = vec![109, -1, 204, 1, 99];
-        let mut outputs = vec![];
-        run_intcode(
-            &mut input_intcode,
-            &mut 0,
-            &mut 0,
-            &mut vec![],
-            &mut outputs,
-        );
+        let input_intcode = vec![109, -1, 204, 1, 99];
+        let mut machine = IntcodeMachine::new(input_intcode);
+        let outputs = machine.run_until_blocked().unwrap();
         assert_eq!(vec![109], outputs)
     }
 
     #[test]
     fn test_run_intcode_test07() {
-        let mut input_intcode = vec![109, 1, 9, 2, 204, -6, 99];
-        let mut outputs = vec![];
-        run_intcode(
-            &mut input_intcode,
-            &mut 0,
-            &mut 0,
-            &mut vec![],
-            &mut outputs,
-        );
+        let input_intcode = vec![109, 1, 9, 2, 204, -6, 99];
+        let mut machine = IntcodeMachine::new(input_intcode);
+        let outputs = machine.run_until_blocked().unwrap();
         assert_eq!(vec![204], outputs)
     }
 
     #[test]
     fn test_run_intcode_test08() {
-        let mut input_intcode = vec![109, 1, 109, 9, 204, -6, 99];
-        let mut outputs = vec![];
-        run_intcode(
-            &mut input_intcode,
-            &mut 0,
-            &mut 0,
-            &mut vec![],
-            &mut outputs,
-        );
+        let input_intcode = vec![109, 1, 109, 9, 204, -6, 99];
+        let mut machine = IntcodeMachine::new(input_intcode);
+        let outputs = machine.run_until_blocked().unwrap();
         assert_eq!(vec![204], outputs)
     }
 
     #[test]
     fn test_run_intcode_test09() {
-        let mut input_intcode = vec![109, 1, 209, -1, 204, -106, 99];
-        let mut outputs = vec![];
-        run_intcode(
-            &mut input_intcode,
-            &mut 0,
-            &mut 0,
-            &mut vec![],
-            &mut outputs,
-        );
+        let input_intcode = vec![109, 1, 209, -1, 204, -106, 99];
+        let mut machine = IntcodeMachine::new(input_intcode);
+        let outputs = machine.run_until_blocked().unwrap();
         assert_eq!(vec![204], outputs)
     }
 
     #[test]
     fn test_run_intcode_test10() {
-        let mut input_intcode = vec![109, 1, 3, 3, 204, 2, 99];
-        let mut outputs = vec![];
-        run_intcode(
-            &mut input_intcode,
-            &mut 0,
-            &mut 0,
-            &mut vec![1],
-            &mut outputs,
-        );
+        let input_intcode = vec![109, 1, 3, 3, 204, 2, 99];
+        let mut machine = IntcodeMachine::new(input_intcode);
+        machine.push_input(1);
+        let outputs = machine.run_until_blocked().unwrap();
         assert_eq!(vec![1], outputs)
     }
 
     #[test]
     fn test_run_intcode_test11() {
-        let mut input_intcode = vec![109, 1, 203, 2, 204, 2, 99];
-        let mut outputs = vec![];
-        run_intcode(
-            &mut input_intcode,
-            &mut 0,
-            &mut 0,
-            &mut vec![1],
-            &mut outputs,
-        );
+        let input_intcode = vec![109, 1, 203, 2, 204, 2, 99];
+        let mut machine = IntcodeMachine::new(input_intcode);
+        machine.push_input(1);
+        let outputs = machine.run_until_blocked().unwrap();
         assert_eq!(vec![1], outputs)
     }
 
     #[test]
     fn test_run_intcode_part01() {
-        let mut input_intcode = parse_intcode_input("input/2019/day09.txt");
-        input_intcode.extend(vec![0; 1000]);
-        let mut outputs = vec![];
-        run_intcode(
-            &mut input_intcode,
-            &mut 0,
-            &mut 0,
-            &mut vec![1],
-            &mut outputs,
-        );
+        let input_intcode = parse_intcode_input("input/2019/day09.txt").unwrap();
+        let mut machine = IntcodeMachine::new(input_intcode);
+        machine.push_input(1);
+        let outputs = machine.run_until_blocked().unwrap();
         assert_eq!(vec![2204990589], outputs);
     }
 
-    #[ignore]
     #[test]
     fn test_run_intcode_part02() {
-        // Requires 1GB of stack space!
-        stacker::grow(1024 * 1024 * 1024, || {
-            let mut input_intcode = parse_intcode_input("input/2019/day09.txt");
-            input_intcode.extend(vec![0; 10000]);
-            let mut outputs = vec![];
-            run_intcode(
-                &mut input_intcode,
-                &mut 0,
-                &mut 0,
-                &mut vec![2],
-                &mut outputs,
-            );
-            assert_eq!(vec![50008], outputs);
-        });
+        let input_intcode = parse_intcode_input("input/2019/day09.txt").unwrap();
+        let mut machine = IntcodeMachine::new(input_intcode);
+        machine.push_input(2);
+        let outputs = machine.run_until_blocked().unwrap();
+        assert_eq!(vec![50008], outputs);
     }
 }
@@ -2,9 +2,12 @@
 
 use std::collections::HashMap;
 
+use log::{Level, debug, log_enabled};
 use ordered_float::OrderedFloat;
 
-use crate::utils::get_lines;
+use crate::error::AocResult;
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, Grid, PuzzleInput};
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
 #[repr(u8)]
@@ -26,23 +29,15 @@ impl TryFrom<u8> for SpaceLocation {
 }
 
 struct Input {
-    space: Vec<Vec<SpaceLocation>>,
+    space: Grid<SpaceLocation>,
 }
 
-fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
+fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let space = Grid::parse(puzzle_input, |c| {
+        SpaceLocation::try_from(u8::try_from(c).ok()?).ok()
+    })?;
 
-    let mut space: Vec<Vec<SpaceLocation>> = vec![];
-
-    for line in lines {
-        let row: Vec<SpaceLocation> = line
-            .chars()
-            .map(|c| SpaceLocation::try_from(c as u8).unwrap())
-            .collect();
-        space.push(row);
-    }
-
-    Input { space }
+    Ok(Input { space })
 }
 
 /// Generates points along a line using Bresenham's line algorithm
@@ -117,14 +112,14 @@ pub fn check_visibility(gradients: &[f64]) -> bool {
     }
 }
 
-pub fn is_asteroid_detectable(space: &[Vec<SpaceLocation>], points: &[(usize, usize)]) -> bool {
+pub fn is_asteroid_detectable(space: &Grid<SpaceLocation>, points: &[(usize, usize)]) -> bool {
     //println!("Origin: {:?}, Dest: {:?}", origin, dest);
 
     if let Some(origin) = points.first() {
         let mut grads: Vec<f64> = vec![];
 
         for (x, y) in points.iter() {
-            match space[*y][*x] {
+            match space[(*y, *x)] {
                 SpaceLocation::Asteroid => {
                     if (*x, *y) != *origin {
                         let grad = calculate_gradient(*origin, (*x, *y));
@@ -155,19 +150,17 @@ pub fn is_asteroid_detectable(space: &[Vec<SpaceLocation>], points: &[(usize, us
 }
 
 pub fn get_detected_asteroids_for_entry(
-    space: &[Vec<SpaceLocation>],
+    space: &Grid<SpaceLocation>,
     origin: (usize, usize),
 ) -> u64 {
     let mut detected_asteroids = 0;
 
-    for (i, space_col) in space.iter().enumerate() {
-        for (j, space_entry) in space_col.iter().enumerate() {
-            let dest = (j, i);
-            if space_entry == &SpaceLocation::Asteroid && origin != dest {
-                let points = bresenham_line(origin, dest);
-                if is_asteroid_detectable(space, &points) {
-                    detected_asteroids += 1;
-                }
+    for ((i, j), space_entry) in space.iter() {
+        let dest = (j, i);
+        if space_entry == &SpaceLocation::Asteroid && origin != dest {
+            let points = bresenham_line(origin, dest);
+            if is_asteroid_detectable(space, &points) {
+                detected_asteroids += 1;
             }
         }
     }
@@ -175,35 +168,34 @@ pub fn get_detected_asteroids_for_entry(
     detected_asteroids
 }
 
-pub fn get_detected_asteroids(input_file: &str) -> u64 {
-    let input = parse_input(input_file);
+pub fn get_detected_asteroids(puzzle_input: impl PuzzleInput) -> AocResult<u64> {
+    let input = parse_input(puzzle_input)?;
 
     let mut detected_asteroids: Vec<u64> = vec![];
 
-    for (i, space_col) in input.space.iter().enumerate() {
-        for (j, space_entry) in space_col.iter().enumerate() {
-            if space_entry == &SpaceLocation::Asteroid {
-                detected_asteroids.push(get_detected_asteroids_for_entry(&input.space, (j, i)));
-            }
+    for ((i, j), space_entry) in input.space.iter() {
+        if space_entry == &SpaceLocation::Asteroid {
+            detected_asteroids.push(get_detected_asteroids_for_entry(&input.space, (j, i)));
         }
     }
 
-    *detected_asteroids.iter().max().unwrap_or(&0)
+    Ok(*detected_asteroids.iter().max().unwrap_or(&0))
 }
 
-pub fn get_vaporised_asteroids(input_file: &str, stop_at: usize) -> Option<u64> {
-    let input = parse_input(input_file);
+pub fn get_vaporised_asteroids(
+    puzzle_input: impl PuzzleInput,
+    stop_at: usize,
+) -> AocResult<Option<u64>> {
+    let input = parse_input(puzzle_input)?;
 
     let mut detected_asteroids: Vec<(u64, (usize, usize))> = vec![];
 
-    for (i, space_col) in input.space.iter().enumerate() {
-        for (j, space_entry) in space_col.iter().enumerate() {
-            if space_entry == &SpaceLocation::Asteroid {
-                detected_asteroids.push((
-                    get_detected_asteroids_for_entry(&input.space, (j, i)),
-                    (j, i),
-                ));
-            }
+    for ((i, j), space_entry) in input.space.iter() {
+        if space_entry == &SpaceLocation::Asteroid {
+            detected_asteroids.push((
+                get_detected_asteroids_for_entry(&input.space, (j, i)),
+                (j, i),
+            ));
         }
     }
 
@@ -211,18 +203,18 @@ pub fn get_vaporised_asteroids(input_file: &str, stop_at: usize) -> Option<u64>
         detected_asteroids.iter().max()
     {
         //println!("Max visible asteroids pos {:?}", *max_visible_asteroids_pos);
-        return vaporise_asteroids(
+        return Ok(vaporise_asteroids(
             &mut input.space.clone(),
             *max_visible_asteroids_pos,
             stop_at,
-        );
+        ));
     }
 
-    None
+    Ok(None)
 }
 
 fn vaporise_asteroids(
-    space: &mut [Vec<SpaceLocation>],
+    space: &mut Grid<SpaceLocation>,
     station_point: (usize, usize),
     stop_at: usize,
 ) -> Option<u64> {
@@ -231,33 +223,31 @@ fn vaporise_asteroids(
     let mut num_asteroids = 0;
     let mut vaporised_asteroids = 0;
 
-    for (y, space_row) in space.iter().enumerate() {
-        for (x, space_entry) in space_row.iter().enumerate() {
-            let asteroid_point = (x, y);
+    for ((y, x), space_entry) in space.iter() {
+        let asteroid_point = (x, y);
 
-            if station_point != asteroid_point
-                && let SpaceLocation::Asteroid = *space_entry
-            {
-                let x_diff = x as isize - station_point.0 as isize;
-                let y_diff = y as isize - station_point.1 as isize;
+        if station_point != asteroid_point
+            && let SpaceLocation::Asteroid = *space_entry
+        {
+            let x_diff = x as isize - station_point.0 as isize;
+            let y_diff = y as isize - station_point.1 as isize;
 
-                let mut angle =
-                    (f64::atan2(y_diff as f64, x_diff as f64).to_degrees() + 90.0) % 360.0;
-                if angle < 0.0 {
-                    angle += 360.0;
-                }
+            let mut angle = (f64::atan2(y_diff as f64, x_diff as f64).to_degrees() + 90.0) % 360.0;
+            if angle < 0.0 {
+                angle += 360.0;
+            }
 
-                num_asteroids += 1;
+            num_asteroids += 1;
 
-                asteroid_angles
-                    .entry(OrderedFloat(angle))
-                    .or_default()
-                    .push((x, y));
-            }
+            asteroid_angles
+                .entry(OrderedFloat(angle))
+                .or_default()
+                .push((x, y));
         }
     }
 
-    loop {
+    // Stop once every asteroid is gone, including when there were none
+    while vaporised_asteroids < num_asteroids {
         let mut finished = false;
         //println!("Starting new rotation...");
 
@@ -269,7 +259,7 @@ fn vaporise_asteroids(
             if let Some(asteroids) = asteroid_angles.get_mut(&angle)
                 && let Some((x, y)) = asteroids.pop()
             {
-                space[y][x] = SpaceLocation::Space;
+                space[(y, x)] = SpaceLocation::Space;
                 //println!("Vaporised asteroid at: ({}, {})", x, y);
 
                 vaporised_asteroids += 1;
@@ -301,12 +291,12 @@ fn vaporise_asteroids(
     None
 }
 
-pub fn print_space(space: &[Vec<SpaceLocation>]) {
-    if space.is_empty() {
+pub fn print_space(space: &Grid<SpaceLocation>) {
+    if !log_enabled!(Level::Debug) {
         return;
     }
 
-    for row in space.iter() {
+    for row in space.rows() {
         let mut line = String::with_capacity(row.len());
         for cell in row.iter() {
             match cell {
@@ -314,7 +304,24 @@ pub fn print_space(space: &[Vec<SpaceLocation>]) {
                 SpaceLocation::Space => line.push('.'),
             }
         }
-        println!("{}", line);
+        debug!("{}", line);
+    }
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_detected_asteroids(Contents(input))?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_vaporised_asteroids(Contents(input), 200)?.into())
     }
 }
 
@@ -324,44 +331,62 @@ mod tests {
 
     #[test]
     fn test_get_detected_asteroids_test01() {
-        assert_eq!(8, get_detected_asteroids("input/2019/day10_test01.txt"));
+        assert_eq!(
+            8,
+            get_detected_asteroids("input/2019/day10_test01.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_detected_asteroids_test02() {
-        assert_eq!(33, get_detected_asteroids("input/2019/day10_test02.txt"))
+        assert_eq!(
+            33,
+            get_detected_asteroids("input/2019/day10_test02.txt").unwrap()
+        )
     }
 
     #[test]
     fn test_get_detected_asteroids_test03() {
-        assert_eq!(35, get_detected_asteroids("input/2019/day10_test03.txt"));
+        assert_eq!(
+            35,
+            get_detected_asteroids("input/2019/day10_test03.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_detected_asteroids_test04() {
-        assert_eq!(41, get_detected_asteroids("input/2019/day10_test04.txt"));
+        assert_eq!(
+            41,
+            get_detected_asteroids("input/2019/day10_test04.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_detected_asteroids_test05() {
-        assert_eq!(210, get_detected_asteroids("input/2019/day10_test05.txt"));
+        assert_eq!(
+            210,
+            get_detected_asteroids("input/2019/day10_test05.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_detected_asteroids_test06() {
-        assert_eq!(3, get_detected_asteroids("input/2019/day10_test06.txt"));
+        assert_eq!(
+            3,
+            get_detected_asteroids("input/2019/day10_test06.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_detected_asteroids() {
-        assert_eq!(284, get_detected_asteroids("input/2019/day10.txt"));
+        assert_eq!(284, get_detected_asteroids("input/2019/day10.txt").unwrap());
     }
 
     #[test]
     fn test_get_vaporised_asteroids_test01() {
         assert_eq!(
             Some(1203),
-            get_vaporised_asteroids("input/2019/day10_test07.txt", 36)
+            get_vaporised_asteroids("input/2019/day10_test07.txt", 36).unwrap()
         );
     }
 
@@ -369,7 +394,7 @@ mod tests {
     fn test_get_vaporised_asteroids_test02() {
         assert_eq!(
             Some(802),
-            get_vaporised_asteroids("input/2019/day10_test05.txt", 200)
+            get_vaporised_asteroids("input/2019/day10_test05.txt", 200).unwrap()
         );
     }
 
@@ -377,7 +402,12 @@ mod tests {
     fn test_get_vaporised_asteroids() {
         assert_eq!(
             Some(404),
-            get_vaporised_asteroids("input/2019/day10.txt", 200)
+            get_vaporised_asteroids("input/2019/day10.txt", 200).unwrap()
         );
     }
+
+    #[test]
+    fn test_get_vaporised_asteroids_lone_station() {
+        assert_eq!(None, get_vaporised_asteroids(Contents("#\n"), 200).unwrap());
+    }
 }
@@ -1,51 +1,70 @@
 // https://adventofcode.com/2022/day/1
 
-use crate::utils::get_lines;
+use crate::error::{AocError, AocResult, parse_token};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput, get_lines};
 
-pub fn get_highest_elf_calories(input_file: &str) -> i32 {
-    let lines = get_lines(input_file);
+/// The total calories carried by each elf, in the order they are listed.
+fn get_calories_per_elf(puzzle_input: impl PuzzleInput) -> AocResult<Vec<i32>> {
+    let lines = get_lines(puzzle_input)?;
 
     let mut calorie_counter = Vec::new();
 
-    let mut max_calories = 0;
+    let mut calories_per_elf = Vec::new();
 
-    for line in lines {
+    for (line_idx, line) in lines.iter().enumerate() {
         if line.is_empty() {
-            let calories = calorie_counter.iter().sum();
-            if calories > max_calories {
-                max_calories = calories;
-            }
+            calories_per_elf.push(calorie_counter.iter().sum());
             calorie_counter.clear();
         } else {
-            calorie_counter.push(line.parse::<i32>().unwrap_or_default());
+            calorie_counter.push(parse_token::<i32>(line_idx, line, line)?);
         }
     }
 
-    max_calories
+    // The last elf needn't be followed by a blank line
+    if !calorie_counter.is_empty() {
+        calories_per_elf.push(calorie_counter.iter().sum());
+    }
+
+    Ok(calories_per_elf)
 }
 
-pub fn get_sum_top_three_elf_calories(input_file: &str) -> i32 {
-    let lines = get_lines(input_file);
+pub fn get_highest_elf_calories(puzzle_input: impl PuzzleInput) -> AocResult<i32> {
+    let calories_per_elf = get_calories_per_elf(puzzle_input)?;
 
-    let mut calorie_counter = Vec::new();
+    calories_per_elf
+        .into_iter()
+        .max()
+        .ok_or(AocError::invalid("no elves in the list"))
+}
 
-    let mut calories_per_elf = Vec::new();
+pub fn get_sum_top_three_elf_calories(puzzle_input: impl PuzzleInput) -> AocResult<i32> {
+    let mut calories_per_elf = get_calories_per_elf(puzzle_input)?;
 
-    for line in lines {
-        if line.is_empty() {
-            let calories: i32 = calorie_counter.iter().sum();
-            calories_per_elf.push(calories);
-            calorie_counter.clear();
-        } else {
-            calorie_counter.push(line.parse::<i32>().unwrap_or_default());
-        }
+    if calories_per_elf.len() < 3 {
+        return Err(AocError::invalid(format!(
+            "expected at least three elves, found {}",
+            calories_per_elf.len()
+        )));
     }
 
     // Flip the comparison operator here
     calories_per_elf.sort_by(|a, b| b.cmp(a));
 
     // Return the sum of the top 3
-    calories_per_elf[0..3].iter().sum()
+    Ok(calories_per_elf[0..3].iter().sum())
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_highest_elf_calories(Contents(input))?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_sum_top_three_elf_calories(Contents(input))?.into())
+    }
 }
 
 #[cfg(test)]
@@ -54,14 +73,36 @@ mod tests {
 
     #[test]
     fn test_get_highest_elf_calories() {
-        assert_eq!(69206, get_highest_elf_calories("input/2022/day01.txt"));
+        assert_eq!(
+            69206,
+            get_highest_elf_calories("input/2022/day01.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_top_three_elf_calories() {
         assert_eq!(
             197400,
-            get_sum_top_three_elf_calories("input/2022/day01.txt")
+            get_sum_top_three_elf_calories("input/2022/day01.txt").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_get_sum_top_three_elf_calories_without_trailing_blank_line() {
+        assert_eq!(
+            60,
+            get_sum_top_three_elf_calories(Contents("10\n\n20\n\n15\n15")).unwrap()
+        );
+        let err = get_sum_top_three_elf_calories(Contents("10\n\n20\n")).unwrap_err();
+        assert_eq!(
+            "<input>: expected at least three elves, found 2",
+            err.to_string()
         );
     }
+
+    #[test]
+    fn test_get_highest_elf_calories_bad_line() {
+        let err = get_highest_elf_calories(Contents("10\n1o\n")).unwrap_err();
+        assert_eq!("<input>:2:1: couldn't parse \"1o\" as i32", err.to_string());
+    }
 }
@@ -2,7 +2,9 @@
 
 use std::collections::HashMap;
 
-use crate::utils::get_lines;
+use crate::error::{AocError, AocResult, require};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput, get_lines};
 
 lazy_static! {
     static ref MY_PLAY_SCORE_TABLE: HashMap<&'static str, i32> = {
@@ -28,31 +30,42 @@ lazy_static! {
     };
 }
 
-pub fn get_total_rps_score(input_file: &str) -> i32 {
-    let lines = get_lines(input_file);
+pub fn get_total_rps_score(puzzle_input: impl PuzzleInput) -> AocResult<i32> {
+    let lines = get_lines(puzzle_input)?;
 
     let mut total_score = 0;
 
-    for line in lines {
+    for (line_idx, line) in lines.iter().enumerate() {
         let mut rps_strategy = line.split_whitespace();
 
-        let opp_play = rps_strategy.next().unwrap();
+        let opp_play = require(rps_strategy.next(), line_idx, "missing opponent's play")?;
 
-        let my_play = rps_strategy.next().unwrap();
+        let my_play = require(rps_strategy.next(), line_idx, "missing my play")?;
 
-        let my_play_score = MY_PLAY_SCORE_TABLE.get(my_play).unwrap();
+        let my_play_score = MY_PLAY_SCORE_TABLE.get(my_play).ok_or_else(|| {
+            AocError::parse_token(line_idx, line, my_play, format!("unknown play {my_play:?}"))
+        })?;
 
         let game_check = format!("{}{}", opp_play, my_play);
 
-        let opp_play_score = OPP_PLAY_SCORE_TABLE.get(game_check.as_str()).unwrap();
+        let opp_play_score = OPP_PLAY_SCORE_TABLE
+            .get(game_check.as_str())
+            .ok_or_else(|| {
+                AocError::parse_token(
+                    line_idx,
+                    line,
+                    opp_play,
+                    format!("unknown play {opp_play:?}"),
+                )
+            })?;
 
         total_score += my_play_score + opp_play_score;
     }
 
-    total_score
+    Ok(total_score)
 }
 
-pub fn get_total_rps_score_part_two(input_file: &str) -> i32 {
+pub fn get_total_rps_score_part_two(puzzle_input: impl PuzzleInput) -> AocResult<i32> {
     enum RpsResult {
         Lose,
         Draw,
@@ -65,18 +78,24 @@ pub fn get_total_rps_score_part_two(input_file: &str) -> i32 {
         ("Z", RpsResult::Win),
     ]);
 
-    let lines = get_lines(input_file);
+    let lines = get_lines(puzzle_input)?;
 
     let mut total_score = 0;
 
-    for line in lines {
+    for (line_idx, line) in lines.iter().enumerate() {
         let mut rps_strategy = line.split_whitespace();
 
-        let opp_play = rps_strategy.next().unwrap();
+        let opp_play = require(rps_strategy.next(), line_idx, "missing opponent's play")?;
 
-        let my_play_result = my_play_result_table
-            .get(rps_strategy.next().unwrap())
-            .unwrap();
+        let my_result = require(rps_strategy.next(), line_idx, "missing round result")?;
+        let my_play_result = my_play_result_table.get(my_result).ok_or_else(|| {
+            AocError::parse_token(
+                line_idx,
+                line,
+                my_result,
+                format!("unknown result {my_result:?}"),
+            )
+        })?;
 
         let my_play = match (opp_play, my_play_result) {
             ("A", RpsResult::Lose) => "Z", // Opp plays Rock, my play should be Scissors
@@ -88,19 +107,38 @@ pub fn get_total_rps_score_part_two(input_file: &str) -> i32 {
             ("C", RpsResult::Lose) => "Y", // Opp plays Scissors, my play should be Paper
             ("C", RpsResult::Draw) => "Z", // Opp plays Scissors, my play should be Scissors
             ("C", RpsResult::Win) => "X",  // Opp plays Scissors, my play should be Rock
-            (&_, _) => "",
+            (&_, _) => {
+                return Err(AocError::parse_token(
+                    line_idx,
+                    line,
+                    opp_play,
+                    format!("unknown play {opp_play:?}"),
+                ));
+            }
         };
 
-        let my_play_score = MY_PLAY_SCORE_TABLE.get(my_play).unwrap();
+        let my_play_score = MY_PLAY_SCORE_TABLE[my_play];
 
         let game_check = format!("{}{}", opp_play, my_play);
 
-        let opp_play_score = OPP_PLAY_SCORE_TABLE.get(game_check.as_str()).unwrap();
+        let opp_play_score = OPP_PLAY_SCORE_TABLE[game_check.as_str()];
 
         total_score += my_play_score + opp_play_score;
     }
 
-    total_score
+    Ok(total_score)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_total_rps_score(Contents(input))?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_total_rps_score_part_two(Contents(input))?.into())
+    }
 }
 
 #[cfg(test)]
@@ -109,11 +147,14 @@ mod tests {
 
     #[test]
     fn test_get_total_rps_score() {
-        assert_eq!(15337, get_total_rps_score("input/2022/day02.txt"));
+        assert_eq!(15337, get_total_rps_score("input/2022/day02.txt").unwrap());
     }
 
     #[test]
     fn test_get_total_rps_score_part_two() {
-        assert_eq!(11696, get_total_rps_score_part_two("input/2022/day02.txt"));
+        assert_eq!(
+            11696,
+            get_total_rps_score_part_two("input/2022/day02.txt").unwrap()
+        );
     }
 }
@@ -2,27 +2,24 @@
 
 use std::collections::{HashMap, HashSet};
 
-use crate::utils::get_lines;
+use crate::error::{AocResult, require};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput, get_lines};
 
-pub fn get_priority_for_char(c: char) -> i32 {
+pub fn get_priority_for_char(c: char) -> Option<i32> {
     let alphabet: Vec<char> = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ"
         .chars()
         .collect();
-    let pos: i32 = alphabet
-        .iter()
-        .position(|&r| r == c)
-        .unwrap()
-        .try_into()
-        .unwrap();
-    pos + 1
+    let pos = alphabet.iter().position(|&r| r == c)? as i32;
+    Some(pos + 1)
 }
 
-pub fn get_sum_priorities_of_item_types(input_file: &str) -> i32 {
-    let lines = get_lines(input_file);
+pub fn get_sum_priorities_of_item_types(puzzle_input: impl PuzzleInput) -> AocResult<i32> {
+    let lines = get_lines(puzzle_input)?;
 
     let mut sum_priorities = 0;
 
-    for line in lines {
+    for (line_idx, line) in lines.iter().enumerate() {
         let (first_comp, second_comp) = line.split_at(line.len() / 2);
 
         // Count the chars in the second comp string
@@ -35,21 +32,25 @@ pub fn get_sum_priorities_of_item_types(input_file: &str) -> i32 {
         // Iterate through the first string, checking for a match for counted chars in the second
         for c in first_comp.chars() {
             if second_comp_counts.contains_key(&c) {
-                sum_priorities += get_priority_for_char(c);
+                sum_priorities += require(
+                    get_priority_for_char(c),
+                    line_idx,
+                    "item types must be letters",
+                )?;
                 break;
             }
         }
     }
 
-    sum_priorities
+    Ok(sum_priorities)
 }
 
-pub fn get_sum_of_priorities_of_item_types_part2(input_file: &str) -> i32 {
-    let lines = get_lines(input_file);
+pub fn get_sum_of_priorities_of_item_types_part2(puzzle_input: impl PuzzleInput) -> AocResult<i32> {
+    let lines = get_lines(puzzle_input)?;
 
     let mut sum_priorities = 0;
 
-    for group in lines.chunks(3) {
+    for (group_idx, group) in lines.chunks(3).enumerate() {
         // Create a group of hash sets
         let group_sets = group
             .iter()
@@ -60,12 +61,32 @@ pub fn get_sum_of_priorities_of_item_types_part2(input_file: &str) -> i32 {
         let intersect = group_sets
             .into_iter()
             .reduce(|a, b| a.intersection(&b).cloned().collect())
-            .unwrap();
+            .unwrap_or_default();
+
+        let badge = require(
+            intersect
+                .iter()
+                .next()
+                .and_then(|&c| get_priority_for_char(c)),
+            group_idx * 3,
+            "group of three has no common badge",
+        )?;
+        sum_priorities += badge;
+    }
+
+    Ok(sum_priorities)
+}
 
-        sum_priorities += get_priority_for_char(*intersect.iter().next().unwrap());
+pub struct Solver;
+
+impl Solution for Solver {
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_sum_priorities_of_item_types(Contents(input))?.into())
     }
 
-    sum_priorities
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_sum_of_priorities_of_item_types_part2(Contents(input))?.into())
+    }
 }
 
 #[cfg(test)]
@@ -74,39 +95,39 @@ mod tests {
 
     #[test]
     fn get_priority_for_char_16() {
-        assert_eq!(16, get_priority_for_char('p'));
+        assert_eq!(Some(16), get_priority_for_char('p'));
     }
 
     #[test]
     fn get_priority_for_char_38() {
-        assert_eq!(38, get_priority_for_char('L'));
+        assert_eq!(Some(38), get_priority_for_char('L'));
     }
 
     #[test]
     fn get_priority_for_char_42() {
-        assert_eq!(42, get_priority_for_char('P'));
+        assert_eq!(Some(42), get_priority_for_char('P'));
     }
 
     #[test]
     fn get_priority_for_char_22() {
-        assert_eq!(22, get_priority_for_char('v'));
+        assert_eq!(Some(22), get_priority_for_char('v'));
     }
 
     #[test]
     fn get_priority_for_char_20() {
-        assert_eq!(20, get_priority_for_char('t'));
+        assert_eq!(Some(20), get_priority_for_char('t'));
     }
 
     #[test]
     fn get_priority_for_char_19() {
-        assert_eq!(19, get_priority_for_char('s'));
+        assert_eq!(Some(19), get_priority_for_char('s'));
     }
 
     #[test]
     fn test_get_sum_priorities_of_item_types() {
         assert_eq!(
             7568,
-            get_sum_priorities_of_item_types("input/2022/day03.txt")
+            get_sum_priorities_of_item_types("input/2022/day03.txt").unwrap()
         );
     }
 
@@ -114,7 +135,7 @@ mod tests {
     fn test_get_sum_of_priorities_of_item_types_part2() {
         assert_eq!(
             2780,
-            get_sum_of_priorities_of_item_types_part2("input/2022/day03.txt")
+            get_sum_of_priorities_of_item_types_part2("input/2022/day03.txt").unwrap()
         );
     }
 }
@@ -2,25 +2,35 @@
 
 use std::ops::Range;
 
-use crate::utils::get_lines;
-
-pub fn create_range(range_str: &str) -> Range<i32> {
-    let split: Vec<&str> = range_str.splitn(2, "-").collect();
-    let lower: i32 = split[0].parse().unwrap();
-    let upper: i32 = split[1].parse().unwrap();
+use crate::error::{AocError, AocResult, require};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput, get_lines};
+
+pub fn create_range(range_str: &str) -> Option<Range<i32>> {
+    let (lower, upper) = range_str.split_once('-')?;
+    let lower: i32 = lower.parse().ok()?;
+    let upper: i32 = upper.parse().ok()?;
     let num = upper + 1;
-    lower..num
+    Some(lower..num)
+}
+
+fn parse_range_pair(line_idx: usize, line: &str) -> AocResult<(Range<i32>, Range<i32>)> {
+    let (first, second) = require(line.split_once(','), line_idx, "expected two ranges")?;
+    let parse_range = |range_str| {
+        create_range(range_str).ok_or_else(|| {
+            AocError::parse_token(line_idx, line, range_str, "expected a range like 2-8")
+        })
+    };
+    Ok((parse_range(first)?, parse_range(second)?))
 }
 
-pub fn count_contained_assignment_pairs(input_file: &str) -> i32 {
-    let lines = get_lines(input_file);
+pub fn count_contained_assignment_pairs(puzzle_input: impl PuzzleInput) -> AocResult<i32> {
+    let lines = get_lines(puzzle_input)?;
 
     let mut count = 0;
 
-    for line in lines {
-        let split: Vec<&str> = line.splitn(2, ",").collect();
-        let first_range = create_range(split[0]);
-        let second_range = create_range(split[1]);
+    for (line_idx, line) in lines.iter().enumerate() {
+        let (first_range, second_range) = parse_range_pair(line_idx, line)?;
 
         if (first_range.start >= second_range.start && first_range.end <= second_range.end)
             || (second_range.start >= first_range.start && second_range.end <= first_range.end)
@@ -29,18 +39,16 @@ pub fn count_contained_assignment_pairs(input_file: &str) -> i32 {
         }
     }
 
-    count
+    Ok(count)
 }
 
-pub fn count_overlap_assignment_pairs(input_file: &str) -> i32 {
-    let lines = get_lines(input_file);
+pub fn count_overlap_assignment_pairs(puzzle_input: impl PuzzleInput) -> AocResult<i32> {
+    let lines = get_lines(puzzle_input)?;
 
     let mut count = 0;
 
-    for line in lines {
-        let split: Vec<&str> = line.splitn(2, ",").collect();
-        let first_range = create_range(split[0]);
-        let second_range = create_range(split[1]);
+    for (line_idx, line) in lines.iter().enumerate() {
+        let (first_range, second_range) = parse_range_pair(line_idx, line)?;
 
         if (first_range.start > second_range.start && first_range.start < second_range.end)
             || (first_range.end > second_range.start && first_range.end <= second_range.end)
@@ -51,7 +59,19 @@ pub fn count_overlap_assignment_pairs(input_file: &str) -> i32 {
         }
     }
 
-    count
+    Ok(count)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(count_contained_assignment_pairs(Contents(input))?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(count_overlap_assignment_pairs(Contents(input))?.into())
+    }
 }
 
 #[cfg(test)]
@@ -60,19 +80,22 @@ mod tests {
 
     #[test]
     fn test_create_range() {
-        assert_eq!(2..9, create_range("2-8"));
+        assert_eq!(Some(2..9), create_range("2-8"));
     }
 
     #[test]
     fn test_count_contained_assignment_pairs() {
         assert_eq!(
             453,
-            count_contained_assignment_pairs("input/2022/day04.txt")
+            count_contained_assignment_pairs("input/2022/day04.txt").unwrap()
         );
     }
 
     #[test]
     fn test_count_overlap_assignment_pairs() {
-        assert_eq!(919, count_overlap_assignment_pairs("input/2022/day04.txt"));
+        assert_eq!(
+            919,
+            count_overlap_assignment_pairs("input/2022/day04.txt").unwrap()
+        );
     }
 }
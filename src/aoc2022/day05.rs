@@ -2,10 +2,13 @@
 
 use itertools::Itertools;
 use lazy_static::lazy_static;
+use log::debug;
 use regex::Regex;
 use std::{collections::HashMap, str};
 
-use crate::utils::get_lines;
+use crate::error::{AocError, AocResult, parse_token, require};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput, get_lines};
 
 #[derive(Debug, PartialEq)]
 pub struct Move {
@@ -20,34 +23,35 @@ pub struct Input {
     moves: Vec<Move>,
 }
 
-pub fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
+pub fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let lines = get_lines(puzzle_input)?;
 
-    let mut iter = lines.split(|e| e.is_empty());
+    let blank_idx = lines
+        .iter()
+        .position(|e| e.is_empty())
+        .ok_or_else(|| AocError::invalid("missing blank line between crates and moves"))?;
 
-    Input {
-        crates: parse_crates(iter.next().unwrap().to_owned()),
-        moves: parse_moves(iter.next().unwrap().to_owned()),
-    }
+    Ok(Input {
+        crates: parse_crates(lines[..blank_idx].to_owned())?,
+        moves: parse_moves(blank_idx + 1, &lines[blank_idx + 1..])?,
+    })
 }
 
-pub fn parse_crates(crates_lines: Vec<String>) -> HashMap<u32, Vec<char>> {
+pub fn parse_crates(crates_lines: Vec<String>) -> AocResult<HashMap<u32, Vec<char>>> {
     lazy_static! {
         static ref RE_INDEX: Regex = Regex::new(r"(?P<index>[1-9])").unwrap();
         static ref RE_CRATE: Regex = Regex::new(r"(?P<crate>[A-Z])").unwrap();
     }
 
-    let crates_lines_rev: Vec<String> = crates_lines.into_iter().rev().collect();
-
     let mut crates: HashMap<u32, Vec<char>> = HashMap::new();
 
-    for crate_line in crates_lines_rev.into_iter() {
+    for (line_idx, crate_line) in crates_lines.iter().enumerate().rev() {
         let table_row = crate_line
             .as_bytes()
             .chunks(4)
             .map(str::from_utf8)
             .collect::<Result<Vec<&str>, _>>()
-            .unwrap();
+            .map_err(|why| AocError::parse(line_idx, why.valid_up_to(), why.to_string()))?;
 
         for (i, table_col) in table_row.iter().enumerate() {
             let caps_index = RE_INDEX.captures(table_col);
@@ -75,13 +79,13 @@ pub fn parse_crates(crates_lines: Vec<String>) -> HashMap<u32, Vec<char>> {
         }
     }
 
-    crates
+    Ok(crates)
 }
 
-fn parse_moves(moves_lines: Vec<String>) -> Vec<Move> {
+fn parse_moves(first_line_idx: usize, moves_lines: &[String]) -> AocResult<Vec<Move>> {
     let mut moves: Vec<Move> = vec![];
 
-    for moves_line in moves_lines.into_iter() {
+    for (line_idx, moves_line) in (first_line_idx..).zip(moves_lines) {
         let mut moves_line_split = moves_line.split(' ');
 
         moves_line_split.next();
@@ -91,14 +95,22 @@ fn parse_moves(moves_lines: Vec<String>) -> Vec<Move> {
         moves_line_split.next();
         let to_val = moves_line_split.next();
 
+        let parse_val = |val: Option<&str>| {
+            parse_token(
+                line_idx,
+                moves_line,
+                require(val, line_idx, "expected move N from A to B")?,
+            )
+        };
+
         moves.push(Move {
-            num_to_move: num_to_move_val.unwrap().parse().unwrap(),
-            from: from_val.unwrap().parse().unwrap(),
-            to: to_val.unwrap().parse().unwrap(),
+            num_to_move: parse_val(num_to_move_val)?,
+            from: parse_val(from_val)?,
+            to: parse_val(to_val)?,
         })
     }
 
-    moves
+    Ok(moves)
 }
 
 pub fn get_result_stack(crates: HashMap<u32, Vec<char>>) -> String {
@@ -117,13 +129,13 @@ pub fn get_result_stack(crates: HashMap<u32, Vec<char>>) -> String {
     result.iter().collect()
 }
 
-pub fn get_supply_stack(input_file: &str) -> String {
-    let mut input = parse_input(input_file);
+pub fn get_supply_stack(puzzle_input: impl PuzzleInput) -> AocResult<String> {
+    let mut input = parse_input(puzzle_input)?;
 
     for move_step in input.moves {
         let moves: Vec<u32> = (0..move_step.num_to_move).collect();
         for i in moves {
-            println!(
+            debug!(
                 "{0}: moving item from stack {1} to stack {2}",
                 i, move_step.from, move_step.to
             );
@@ -146,16 +158,16 @@ pub fn get_supply_stack(input_file: &str) -> String {
         }
     }
 
-    println!("{:?}", input.crates);
+    debug!("{:?}", input.crates);
 
-    get_result_stack(input.crates)
+    Ok(get_result_stack(input.crates))
 }
 
-pub fn get_supply_stack_alt(input_file: &str) -> String {
-    let mut input = parse_input(input_file);
+pub fn get_supply_stack_alt(puzzle_input: impl PuzzleInput) -> AocResult<String> {
+    let mut input = parse_input(puzzle_input)?;
 
     for move_step in input.moves {
-        println!("BEFORE: {:?}", input.crates);
+        debug!("BEFORE: {:?}", input.crates);
 
         let from_stack = input.crates.get_mut(&move_step.from);
 
@@ -176,10 +188,27 @@ pub fn get_supply_stack_alt(input_file: &str) -> String {
             to_stack.extend(drainage_values);
         }
 
-        println!("AFTER: {:?}", input.crates);
+        debug!("AFTER: {:?}", input.crates);
     }
 
-    get_result_stack(input.crates)
+    Ok(get_result_stack(input.crates))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_supply_stack(Contents(input))?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_supply_stack_alt(Contents(input))?.into())
+    }
 }
 
 #[cfg(test)]
@@ -199,27 +228,39 @@ mod tests {
             (8, vec!['L', 'G', 'J', 'M', 'D', 'N', 'V']),
             (9, vec!['T', 'P', 'M', 'F', 'Z', 'C', 'G']),
         ]);
-        let input = parse_input("input/2022/day05.txt");
+        let input = parse_input("input/2022/day05.txt").unwrap();
         assert_eq!(expected_crates, input.crates);
     }
 
     #[test]
     fn test_get_supply_stack_sample() {
-        assert_eq!("CMZ", get_supply_stack("input/2022/day05_test01.txt"));
+        assert_eq!(
+            "CMZ",
+            get_supply_stack("input/2022/day05_test01.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_supply_stack() {
-        assert_eq!("ZBDRNPMVH", get_supply_stack("input/2022/day05.txt"));
+        assert_eq!(
+            "ZBDRNPMVH",
+            get_supply_stack("input/2022/day05.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_supply_stack_alt_sample() {
-        assert_eq!("MCD", get_supply_stack_alt("input/2022/day05_test01.txt"));
+        assert_eq!(
+            "MCD",
+            get_supply_stack_alt("input/2022/day05_test01.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_supply_stack_alt() {
-        assert_eq!("WDLPFNNNB", get_supply_stack_alt("input/2022/day05.txt"));
+        assert_eq!(
+            "WDLPFNNNB",
+            get_supply_stack_alt("input/2022/day05.txt").unwrap()
+        );
     }
 }
@@ -1,6 +1,8 @@
 // https://adventofcode.com/2022/day/6
 
-use crate::utils::get_lines;
+use crate::error::{AocError, AocResult};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput, get_lines};
 
 pub fn get_num_chars_before_sop_marker<const N: usize>(input: &str) -> i32 {
     let mut array = Vec::<char>::with_capacity(N);
@@ -32,10 +34,28 @@ pub fn is_unique<T: PartialEq>(slice: &[T]) -> bool {
     true
 }
 
-pub fn get_num_chars_before_sop_marker_file<const N: usize>(input_file: &str) -> i32 {
-    let lines = get_lines(input_file);
+pub fn get_num_chars_before_sop_marker_file<const N: usize>(
+    puzzle_input: impl PuzzleInput,
+) -> AocResult<i32> {
+    let lines = get_lines(puzzle_input)?;
 
-    get_num_chars_before_sop_marker::<N>(&lines[0])
+    let Some(datastream) = lines.first() else {
+        return Err(AocError::invalid("no datastream in the input"));
+    };
+
+    Ok(get_num_chars_before_sop_marker::<N>(datastream))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_num_chars_before_sop_marker_file::<4>(Contents(input))?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_num_chars_before_sop_marker_file::<14>(Contents(input))?.into())
+    }
 }
 
 #[cfg(test)]
@@ -126,7 +146,7 @@ mod tests {
     fn test_get_num_chars_before_sop_marker() {
         assert_eq!(
             1833,
-            get_num_chars_before_sop_marker_file::<4>("input/2022/day06.txt")
+            get_num_chars_before_sop_marker_file::<4>("input/2022/day06.txt").unwrap()
         );
     }
 
@@ -134,7 +154,7 @@ mod tests {
     fn test_get_num_chars_before_sop_marker_alt() {
         assert_eq!(
             3425,
-            get_num_chars_before_sop_marker_file::<14>("input/2022/day06.txt")
+            get_num_chars_before_sop_marker_file::<14>("input/2022/day06.txt").unwrap()
         );
     }
 }
@@ -2,9 +2,12 @@
 
 use std::sync::atomic::{AtomicUsize, Ordering};
 
+use log::debug;
 use regex::Regex;
 
-use crate::utils::get_lines;
+use crate::error::{AocResult, parse_token};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput, get_lines};
 
 #[derive(Clone, Debug, PartialEq)]
 enum FileKind {
@@ -119,7 +122,7 @@ impl DirectoryTreeNode {
     }
 }
 
-pub(crate) fn parse_input(input_file: &str) -> DirectoryTreeNode {
+pub(crate) fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<DirectoryTreeNode> {
     lazy_static! {
         static ref RE_CD: Regex = Regex::new(r"\$ cd (?P<path>.+)").unwrap();
         static ref RE_LS: Regex = Regex::new(r"\$ (?P<command>ls)").unwrap();
@@ -131,10 +134,10 @@ pub(crate) fn parse_input(input_file: &str) -> DirectoryTreeNode {
 
     let mut current = root.value.clone();
 
-    let lines = get_lines(input_file);
+    let lines = get_lines(puzzle_input)?;
 
-    for line in lines {
-        if let Some(caps_cd) = RE_CD.captures(&line) {
+    for (line_idx, line) in lines.iter().enumerate() {
+        if let Some(caps_cd) = RE_CD.captures(line) {
             let path = &caps_cd["path"];
 
             match path {
@@ -149,7 +152,7 @@ pub(crate) fn parse_input(input_file: &str) -> DirectoryTreeNode {
                             if let Some(parent) = &node.parent {
                                 current = parent.clone();
                             } else {
-                                println!("Node {0} has no parent", node.value.name);
+                                debug!("Node {0} has no parent", node.value.name);
                             }
                         }
                     });
@@ -167,10 +170,10 @@ pub(crate) fn parse_input(input_file: &str) -> DirectoryTreeNode {
                 }
             }
         }
-        if let Some(_caps_ls) = RE_LS.captures(&line) {
+        if let Some(_caps_ls) = RE_LS.captures(line) {
             // Drop this
         }
-        if let Some(caps_dir) = RE_DIR.captures(&line) {
+        if let Some(caps_dir) = RE_DIR.captures(line) {
             let dir = &caps_dir["dir"];
 
             root.dfs(&mut |node| {
@@ -182,8 +185,9 @@ pub(crate) fn parse_input(input_file: &str) -> DirectoryTreeNode {
                 }
             });
         }
-        if let Some(caps_file) = RE_FILE.captures(&line) {
-            let size: usize = caps_file["size"].parse().unwrap();
+        if let Some(caps_file) = RE_FILE.captures(line) {
+            let size: usize =
+                parse_token(line_idx, line, caps_file.name("size").unwrap().as_str())?;
             let file = &caps_file["file"];
 
             root.dfs(&mut |node| {
@@ -197,33 +201,50 @@ pub(crate) fn parse_input(input_file: &str) -> DirectoryTreeNode {
             });
         }
     }
-    root
+    Ok(root)
 }
 
-pub fn get_sum_of_directories(input_file: &str) -> i32 {
-    let mut root = parse_input(input_file);
+pub fn get_sum_of_directories(puzzle_input: impl PuzzleInput) -> AocResult<i32> {
+    let mut root = parse_input(puzzle_input)?;
 
-    root.get_sum_of_directories()
+    Ok(root.get_sum_of_directories())
 }
 
-pub fn get_all_directory_sizes(input_file: &str) -> Vec<i32> {
-    let mut root = parse_input(input_file);
+pub fn get_all_directory_sizes(puzzle_input: impl PuzzleInput) -> AocResult<Vec<i32>> {
+    let mut root = parse_input(puzzle_input)?;
 
-    root.get_all_directory_sizes()
+    Ok(root.get_all_directory_sizes())
 }
 
-pub fn get_size_deleted_directory(input_file: &str) -> i32 {
-    let sizes = get_all_directory_sizes(input_file);
+pub fn get_size_deleted_directory(puzzle_input: impl PuzzleInput) -> AocResult<i32> {
+    let sizes = get_all_directory_sizes(puzzle_input)?;
     let root_size = sizes.iter().max().unwrap();
     let unused_space = 70000000 - root_size;
     let required_space = 30000000 - unused_space;
-    println!(
+    debug!(
         "Root size: {0}, unused space: {1}, required space {2}",
         root_size, unused_space, required_space
     );
     let potential_sizes: Vec<i32> = sizes.into_iter().filter(|x| x >= &required_space).collect();
     let chosen_size = potential_sizes.iter().min().unwrap();
-    *chosen_size
+    Ok(*chosen_size)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_sum_of_directories(Contents(input))?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_size_deleted_directory(Contents(input))?.into())
+    }
 }
 
 #[cfg(test)]
@@ -232,34 +253,49 @@ mod tests {
 
     #[test]
     fn test_get_sum_of_directories_test01() {
-        assert_eq!(95437, get_sum_of_directories("input/2022/day07_test01.txt"));
+        assert_eq!(
+            95437,
+            get_sum_of_directories("input/2022/day07_test01.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_of_directories_test02() {
-        assert_eq!(773, get_sum_of_directories("input/2022/day07_test02.txt"));
+        assert_eq!(
+            773,
+            get_sum_of_directories("input/2022/day07_test02.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_of_directories_test03() {
-        assert_eq!(99999, get_sum_of_directories("input/2022/day07_test03.txt"));
+        assert_eq!(
+            99999,
+            get_sum_of_directories("input/2022/day07_test03.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_of_directories() {
-        assert_eq!(1667443, get_sum_of_directories("input/2022/day07.txt"));
+        assert_eq!(
+            1667443,
+            get_sum_of_directories("input/2022/day07.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_all_directory_sizes_test01() {
         assert_eq!(
             24933642,
-            get_size_deleted_directory("input/2022/day07_test01.txt")
+            get_size_deleted_directory("input/2022/day07_test01.txt").unwrap()
         );
     }
 
     #[test]
     fn test_get_all_directory_sizes() {
-        assert_eq!(8998590, get_size_deleted_directory("input/2022/day07.txt"));
+        assert_eq!(
+            8998590,
+            get_size_deleted_directory("input/2022/day07.txt").unwrap()
+        );
     }
 }
@@ -1,77 +1,29 @@
 // https://adventofcode.com/2022/day/8
 
-use crate::utils::get_lines;
-
-pub fn parse_input(input_file: &str) -> Vec<u32> {
-    let lines = get_lines(input_file);
-
-    let rows = lines.len();
-    let cols = lines.first().unwrap().len();
-
-    let grid: Vec<u32> = vec![0; rows * cols];
-
-    lines
-        .into_iter()
-        .enumerate()
-        .fold(grid, |mut x, (i, line)| {
-            let start_idx = i * cols;
-            let end_idx = start_idx + cols;
-            x[start_idx..end_idx].copy_from_slice(
-                &line
-                    .chars()
-                    .map(|c| c.to_digit(10).unwrap())
-                    .collect::<Vec<u32>>(),
-            );
-            x
-        })
+use crate::error::{AocError, AocResult};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Compass, Contents, Grid, PuzzleInput};
+
+const DIRECTIONS: [Compass; 4] = [Compass::North, Compass::East, Compass::South, Compass::West];
+
+pub fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Grid<u32>> {
+    Grid::parse(puzzle_input, |c| c.to_digit(10))
 }
 
-pub fn get_visible_trees(input_file: &str) -> u32 {
-    let grid: Vec<u32> = parse_input(input_file);
-
-    let rows = (grid.len() as f64).sqrt() as usize;
-    let cols = rows;
-    let outer_visible_trees: u32 = (cols + (rows - 1) + (cols - 1) + (rows - 2))
-        .try_into()
-        .unwrap();
-
-    let mut inner_visible_trees: u32 = 0;
-
-    let rows = (grid.len() as f64).sqrt() as usize;
-    let cols = rows;
-    for i in 0..rows {
-        for j in 0..cols {
-            if i > 0 && i < rows - 1 && j > 0 && j < cols - 1 {
-                let val = grid.get(i * cols + j);
-                let row_view = &grid[i * cols..(i + 1) * cols];
-                let col_view: Vec<u32> = (0..rows).map(|row| grid[row * cols + j]).collect();
-
-                let slice_left = &row_view[0..j];
-                let slice_right = &row_view[j + 1..];
-                let slice_up = &col_view[0..i];
-                let slice_down = &col_view[i + 1..];
-
-                if val > slice_left.iter().max() {
-                    inner_visible_trees += 1;
-                    continue;
-                }
-                if val > slice_right.iter().max() {
-                    inner_visible_trees += 1;
-                    continue;
-                }
-                if val > slice_up.iter().max() {
-                    inner_visible_trees += 1;
-                    continue;
-                }
-                if val > slice_down.iter().max() {
-                    inner_visible_trees += 1;
-                    continue;
-                }
-            }
-        }
-    }
+pub fn get_visible_trees(puzzle_input: impl PuzzleInput) -> AocResult<u32> {
+    let grid = parse_input(puzzle_input)?;
 
-    outer_visible_trees + inner_visible_trees
+    // Trees on the edge have nothing in the way on at least one side
+    let visible_trees = grid
+        .iter()
+        .filter(|&(pos, &val)| {
+            DIRECTIONS
+                .iter()
+                .any(|&dir| grid.line(pos, dir).skip(1).all(|&tree| tree < val))
+        })
+        .count();
+
+    Ok(visible_trees as u32)
 }
 
 pub fn get_viewing_distance(val: u32, slice: Vec<u32>) -> u32 {
@@ -85,43 +37,37 @@ pub fn get_viewing_distance(val: u32, slice: Vec<u32>) -> u32 {
     count
 }
 
-pub fn get_highest_scenic_score(input_file: &str) -> u32 {
-    let grid: Vec<u32> = parse_input(input_file);
-
-    let mut scenic_scores: Vec<u32> = vec![];
-
-    let rows = (grid.len() as f64).sqrt() as usize;
-    for i in 0..rows {
-        let cols = (grid.len() as f64).sqrt() as usize;
-        for j in 0..cols {
-            let val = grid.get(i * cols + j);
-            let row_view = &grid[i * rows..(i + 1) * rows];
-            let col_view: Vec<u32> = (0..rows).map(|row| grid[row * rows + j]).collect();
-
-            let slice_left = &row_view[0..j];
-            let slice_right = &row_view[j + 1..];
-            let slice_up = &col_view[0..i];
-            let slice_down = &col_view[i + 1..];
-
-            let viewing_distance_left =
-                get_viewing_distance(*val.unwrap(), slice_left.iter().copied().rev().collect());
-            let viewing_distance_right = get_viewing_distance(*val.unwrap(), slice_right.to_vec());
-            let viewing_distance_up = get_viewing_distance(
-                *val.unwrap(),
-                slice_up.to_vec().to_vec().into_iter().rev().collect(),
-            );
-            let viewing_distance_down = get_viewing_distance(*val.unwrap(), slice_down.to_vec());
-
-            let scenic_score = viewing_distance_left
-                * viewing_distance_right
-                * viewing_distance_up
-                * viewing_distance_down;
-
-            scenic_scores.push(scenic_score);
-        }
+pub fn get_highest_scenic_score(puzzle_input: impl PuzzleInput) -> AocResult<u32> {
+    let grid = parse_input(puzzle_input)?;
+
+    grid.iter()
+        .map(|(pos, &val)| {
+            DIRECTIONS
+                .iter()
+                .map(|&dir| {
+                    get_viewing_distance(val, grid.line(pos, dir).skip(1).copied().collect())
+                })
+                .product()
+        })
+        .max()
+        .ok_or(AocError::invalid("empty tree map"))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_visible_trees(Contents(input))?.into())
     }
 
-    scenic_scores.into_iter().max().unwrap()
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_highest_scenic_score(Contents(input))?.into())
+    }
 }
 
 #[cfg(test)]
@@ -130,12 +76,15 @@ mod tests {
 
     #[test]
     fn test_get_visible_trees01() {
-        assert_eq!(21, get_visible_trees("input/2022/day08_test01.txt"));
+        assert_eq!(
+            21,
+            get_visible_trees("input/2022/day08_test01.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_visible_trees() {
-        assert_eq!(1715, get_visible_trees("input/2022/day08.txt"));
+        assert_eq!(1715, get_visible_trees("input/2022/day08.txt").unwrap());
     }
 
     #[test]
@@ -166,11 +115,17 @@ mod tests {
 
     #[test]
     fn test_get_highest_scenic_score01() {
-        assert_eq!(8, get_highest_scenic_score("input/2022/day08_test01.txt"));
+        assert_eq!(
+            8,
+            get_highest_scenic_score("input/2022/day08_test01.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_highest_scenic_score() {
-        assert_eq!(374400, get_highest_scenic_score("input/2022/day08.txt"));
+        assert_eq!(
+            374400,
+            get_highest_scenic_score("input/2022/day08.txt").unwrap()
+        );
     }
 }
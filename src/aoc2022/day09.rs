@@ -2,9 +2,12 @@
 
 use std::collections::HashSet;
 
+use log::debug;
 use regex::Regex;
 
-use crate::utils::{MoveDir, get_lines};
+use crate::error::{AocError, AocResult, parse_token};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, MoveDir, Point, PuzzleInput, get_lines};
 
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum MoveCondition {
@@ -19,77 +22,65 @@ pub struct Move {
     steps: u32,
 }
 
-pub fn parse_input(input_file: &str) -> Vec<Move> {
+pub fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Vec<Move>> {
     lazy_static! {
         static ref RE_MOVE: Regex = Regex::new(r"(?P<dir>[A-Z]) (?P<steps>[0-9]+)").unwrap();
     }
 
     let mut moves: Vec<Move> = vec![];
 
-    let lines = get_lines(input_file);
+    let lines = get_lines(puzzle_input)?;
 
-    for line in lines {
-        let caps_move = RE_MOVE.captures(&line);
+    for (line_idx, line) in lines.iter().enumerate() {
+        let caps_move = RE_MOVE.captures(line);
 
         if let Some(caps_move) = caps_move {
-            let dir_char = caps_move["dir"]
-                .chars()
-                .next()
-                .expect("the string is empty");
-
-            let dir = match dir_char {
-                'L' => Some(MoveDir::Left),
-                'R' => Some(MoveDir::Right),
-                'U' => Some(MoveDir::Up),
-                'D' => Some(MoveDir::Down),
-                _ => None,
+            let dir_match = caps_move.name("dir").unwrap();
+
+            let dir = match dir_match.as_str() {
+                "L" => MoveDir::Left,
+                "R" => MoveDir::Right,
+                "U" => MoveDir::Up,
+                "D" => MoveDir::Down,
+                other => {
+                    return Err(AocError::parse(
+                        line_idx,
+                        dir_match.start(),
+                        format!("unknown direction {other:?}"),
+                    ));
+                }
             };
 
-            let steps = caps_move["steps"].to_string().parse().unwrap();
+            let steps = parse_token(line_idx, line, caps_move.name("steps").unwrap().as_str())?;
 
-            moves.push(Move {
-                dir: dir.unwrap(),
-                steps,
-            })
+            moves.push(Move { dir, steps })
         }
     }
 
-    moves
-}
-
-#[derive(Clone, Debug, Eq, Hash, PartialEq)]
-pub struct Point2D {
-    pub x: i32,
-    pub y: i32,
+    Ok(moves)
 }
 
-impl Point2D {
-    pub fn manhattan_distance(&self, rhs: &Point2D) -> i32 {
-        (self.x - rhs.x).abs() + (self.y - rhs.y).abs()
-    }
-
-    pub fn is_diagonal_move(&self, rhs: &Point2D) -> bool {
-        let dist = self.manhattan_distance(rhs);
+fn is_diagonal_move(knot: Point<i32>, knot_next: Point<i32>) -> bool {
+    let dist = knot.manhattan_distance(knot_next);
 
-        dist > 2 && (self.x != rhs.x && self.y != rhs.y)
-    }
+    dist > 2 && (knot.x != knot_next.x && knot.y != knot_next.y)
+}
 
-    pub fn is_lateral_move(&self, rhs: &Point2D) -> bool {
-        let dist = self.manhattan_distance(rhs);
+fn is_lateral_move(knot: Point<i32>, knot_next: Point<i32>) -> bool {
+    let dist = knot.manhattan_distance(knot_next);
 
-        dist > 1 && (self.x == rhs.x || self.y == rhs.y)
-    }
+    dist > 1 && (knot.x == knot_next.x || knot.y == knot_next.y)
 }
 
 #[derive(Debug, PartialEq)]
 pub struct PlankTracker {
-    knots: Vec<Point2D>,
-    tail_visited_positions: HashSet<Point2D>,
+    knots: Vec<Point<i32>>,
+    tail_visited_positions: HashSet<Point<i32>>,
 }
 
 impl PlankTracker {
     pub fn move_knots(&mut self, next_move: Move) {
-        fn move_head(move_dir: MoveDir, knot: &mut Point2D) {
+        fn move_head(move_dir: MoveDir, knot: &mut Point<i32>) {
             match move_dir {
                 MoveDir::Left => {
                     knot.x -= 1;
@@ -106,16 +97,20 @@ impl PlankTracker {
             }
         }
 
-        fn get_move_condition(knot: Point2D, knot_next: Point2D) -> MoveCondition {
-            if knot.is_lateral_move(&knot_next) {
+        fn get_move_condition(knot: Point<i32>, knot_next: Point<i32>) -> MoveCondition {
+            if is_lateral_move(knot, knot_next) {
                 return MoveCondition::Lateral;
-            } else if knot.is_diagonal_move(&knot_next) {
+            } else if is_diagonal_move(knot, knot_next) {
                 return MoveCondition::Diagonal;
             }
             MoveCondition::Adjacent
         }
 
-        fn move_next_knot(move_condition: MoveCondition, knot: &Point2D, knot_next: &mut Point2D) {
+        fn move_next_knot(
+            move_condition: MoveCondition,
+            knot: &Point<i32>,
+            knot_next: &mut Point<i32>,
+        ) {
             // H=head, T=tail (prev), t=tail (next)
             match move_condition {
                 MoveCondition::Lateral => {
@@ -245,7 +240,7 @@ impl PlankTracker {
         }
 
         for move_steps_i in 0..next_move.steps {
-            println!(
+            debug!(
                 "----- START MOVE {0:?} STEP {1} -----",
                 next_move.dir, move_steps_i
             );
@@ -255,22 +250,19 @@ impl PlankTracker {
             let mut it = self.knots.iter_mut().enumerate().peekable();
             while let Some((knots_i, knot)) = it.next() {
                 if let Some((_, knot_next)) = it.peek_mut() {
-                    let move_condition = get_move_condition(knot.clone(), knot_next.clone());
-                    println!(
+                    let move_condition = get_move_condition(*knot, **knot_next);
+                    debug!(
                         "Knot {0}: move condition {1:?}, knot {2:?}, knot_next {3:?}",
-                        knots_i,
-                        move_condition,
-                        knot.clone(),
-                        knot_next.clone()
+                        knots_i, move_condition, knot, knot_next
                     );
                     move_next_knot(move_condition, knot, knot_next);
                 } else {
-                    self.tail_visited_positions.insert(knot.clone());
+                    self.tail_visited_positions.insert(*knot);
                 }
             }
 
-            println!("{:?}", self.knots);
-            println!(
+            debug!("{:?}", self.knots);
+            debug!(
                 "----- END MOVE {0:?} STEP {1} -----",
                 next_move.dir, move_steps_i
             );
@@ -278,11 +270,11 @@ impl PlankTracker {
     }
 }
 
-pub fn get_rope_tail_visits(num_knots: usize, input_file: &str) -> u32 {
-    let moves = parse_input(input_file);
+pub fn get_rope_tail_visits(num_knots: usize, puzzle_input: impl PuzzleInput) -> AocResult<u32> {
+    let moves = parse_input(puzzle_input)?;
 
     let mut plank = PlankTracker {
-        knots: vec![Point2D { x: 0, y: 0 }; num_knots],
+        knots: vec![Point::ORIGIN; num_knots],
         tail_visited_positions: HashSet::new(),
     };
 
@@ -290,7 +282,24 @@ pub fn get_rope_tail_visits(num_knots: usize, input_file: &str) -> u32 {
         plank.move_knots(next_move);
     }
 
-    plank.tail_visited_positions.len() as u32
+    Ok(plank.tail_visited_positions.len() as u32)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_rope_tail_visits(2, Contents(input))?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_rope_tail_visits(10, Contents(input))?.into())
+    }
 }
 
 #[cfg(test)]
@@ -301,7 +310,7 @@ mod tests {
     fn test_get_rope_tail_visits_example() {
         assert_eq!(
             13,
-            get_rope_tail_visits(2, "input/2022/day09_test_example.txt")
+            get_rope_tail_visits(2, "input/2022/day09_test_example.txt").unwrap()
         );
     }
 
@@ -309,7 +318,7 @@ mod tests {
     fn test_get_rope_tail_visits_direct_left() {
         assert_eq!(
             10,
-            get_rope_tail_visits(2, "input/2022/day09_test_direct_left.txt")
+            get_rope_tail_visits(2, "input/2022/day09_test_direct_left.txt").unwrap()
         );
     }
 
@@ -317,7 +326,7 @@ mod tests {
     fn test_get_rope_tail_visits_direct_right() {
         assert_eq!(
             1,
-            get_rope_tail_visits(10, "input/2022/day09_test_direct_right.txt")
+            get_rope_tail_visits(10, "input/2022/day09_test_direct_right.txt").unwrap()
         );
     }
 
@@ -325,7 +334,7 @@ mod tests {
     fn test_get_rope_tail_visits_direct_up() {
         assert_eq!(
             2,
-            get_rope_tail_visits(2, "input/2022/day09_test_direct_up.txt")
+            get_rope_tail_visits(2, "input/2022/day09_test_direct_up.txt").unwrap()
         );
     }
 
@@ -333,7 +342,7 @@ mod tests {
     fn test_get_rope_tail_visits_direct_down() {
         assert_eq!(
             2,
-            get_rope_tail_visits(2, "input/2022/day09_test_direct_down.txt")
+            get_rope_tail_visits(2, "input/2022/day09_test_direct_down.txt").unwrap()
         );
     }
 
@@ -341,7 +350,7 @@ mod tests {
     fn test_get_rope_tail_visits_diagonal_down_left() {
         assert_eq!(
             2,
-            get_rope_tail_visits(2, "input/2022/day09_test_diagonal_down_left.txt")
+            get_rope_tail_visits(2, "input/2022/day09_test_diagonal_down_left.txt").unwrap()
         );
     }
 
@@ -349,7 +358,7 @@ mod tests {
     fn test_get_rope_tail_visits_diagonal_down_right() {
         assert_eq!(
             2,
-            get_rope_tail_visits(2, "input/2022/day09_test_diagonal_down_right.txt")
+            get_rope_tail_visits(2, "input/2022/day09_test_diagonal_down_right.txt").unwrap()
         );
     }
 
@@ -357,7 +366,7 @@ mod tests {
     fn test_get_rope_tail_visits_diagonal_left_down() {
         assert_eq!(
             2,
-            get_rope_tail_visits(2, "input/2022/day09_test_diagonal_left_down.txt")
+            get_rope_tail_visits(2, "input/2022/day09_test_diagonal_left_down.txt").unwrap()
         );
     }
 
@@ -365,7 +374,7 @@ mod tests {
     fn test_get_rope_tail_visits_diagonal_left_up() {
         assert_eq!(
             2,
-            get_rope_tail_visits(2, "input/2022/day09_test_diagonal_left_up.txt")
+            get_rope_tail_visits(2, "input/2022/day09_test_diagonal_left_up.txt").unwrap()
         );
     }
 
@@ -373,7 +382,7 @@ mod tests {
     fn test_get_rope_tail_visits_diagonal_right_down() {
         assert_eq!(
             2,
-            get_rope_tail_visits(2, "input/2022/day09_test_diagonal_right_down.txt")
+            get_rope_tail_visits(2, "input/2022/day09_test_diagonal_right_down.txt").unwrap()
         );
     }
 
@@ -381,7 +390,7 @@ mod tests {
     fn test_get_rope_tail_visits_diagonal_right_up() {
         assert_eq!(
             2,
-            get_rope_tail_visits(2, "input/2022/day09_test_diagonal_right_up.txt")
+            get_rope_tail_visits(2, "input/2022/day09_test_diagonal_right_up.txt").unwrap()
         );
     }
 
@@ -389,7 +398,7 @@ mod tests {
     fn test_get_rope_tail_visits_diagonal_up_left() {
         assert_eq!(
             2,
-            get_rope_tail_visits(2, "input/2022/day09_test_diagonal_up_left.txt")
+            get_rope_tail_visits(2, "input/2022/day09_test_diagonal_up_left.txt").unwrap()
         );
     }
 
@@ -397,7 +406,7 @@ mod tests {
     fn test_get_rope_tail_visits_diagonal_up_right() {
         assert_eq!(
             2,
-            get_rope_tail_visits(2, "input/2022/day09_test_diagonal_up_right.txt")
+            get_rope_tail_visits(2, "input/2022/day09_test_diagonal_up_right.txt").unwrap()
         );
     }
 
@@ -405,20 +414,23 @@ mod tests {
     fn test_get_rope_tail_visits_diagonal_then_lateral() {
         assert_eq!(
             1,
-            get_rope_tail_visits(2, "input/2022/day09_test_diagonal_then_lateral.txt")
+            get_rope_tail_visits(2, "input/2022/day09_test_diagonal_then_lateral.txt").unwrap()
         );
     }
 
     #[test]
     fn test_get_rope_tail_visits_part_1() {
-        assert_eq!(6044, get_rope_tail_visits(2, "input/2022/day09.txt"));
+        assert_eq!(
+            6044,
+            get_rope_tail_visits(2, "input/2022/day09.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_rope_tail_visits_part_2_test01() {
         assert_eq!(
             36,
-            get_rope_tail_visits(10, "input/2022/day09_part02_test01.txt")
+            get_rope_tail_visits(10, "input/2022/day09_part02_test01.txt").unwrap()
         );
     }
 
@@ -426,7 +438,7 @@ mod tests {
     fn test_get_rope_tail_visits_part_2_test02() {
         assert_eq!(
             1,
-            get_rope_tail_visits(10, "input/2022/day09_part02_test02.txt")
+            get_rope_tail_visits(10, "input/2022/day09_part02_test02.txt").unwrap()
         );
     }
 
@@ -571,12 +583,15 @@ mod tests {
     fn test_get_rope_tail_visits_part_2_test03() {
         assert_eq!(
             2,
-            get_rope_tail_visits(10, "input/2022/day09_part02_test03.txt")
+            get_rope_tail_visits(10, "input/2022/day09_part02_test03.txt").unwrap()
         );
     }
 
     #[test]
     fn test_get_rope_tail_visits_part_2() {
-        assert_eq!(2384, get_rope_tail_visits(10, "input/2022/day09.txt"));
+        assert_eq!(
+            2384,
+            get_rope_tail_visits(10, "input/2022/day09.txt").unwrap()
+        );
     }
 }
@@ -1,6 +1,10 @@
 // https://adventofcode.com/2022/day/10
 
-use crate::utils::get_lines;
+use log::debug;
+
+use crate::error::{AocError, AocResult, parse_token};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput, get_lines};
 
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum InstructionType {
@@ -14,37 +18,43 @@ pub struct Instruction {
     instruction_val: Option<i32>,
 }
 
-pub fn parse_input(input_file: &str) -> Vec<Instruction> {
+pub fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Vec<Instruction>> {
     let mut moves: Vec<Instruction> = vec![];
 
-    let lines = get_lines(input_file);
+    let lines = get_lines(puzzle_input)?;
 
-    for line in lines {
+    for (line_idx, line) in lines.iter().enumerate() {
         let split_line: Vec<&str> = line.split(' ').collect();
 
         let instruction_type = match split_line[0] {
-            "noop" => Some(InstructionType::Noop),
-            "addx" => Some(InstructionType::Addx),
-            _ => None,
+            "noop" => InstructionType::Noop,
+            "addx" => InstructionType::Addx,
+            other => {
+                return Err(AocError::parse(
+                    line_idx,
+                    0,
+                    format!("unknown instruction {other:?}"),
+                ));
+            }
         };
 
         let mut instruction_val = None;
 
         if split_line.len() > 1 {
-            instruction_val = Some(split_line[1].parse().unwrap());
+            instruction_val = Some(parse_token(line_idx, line, split_line[1])?);
         }
 
         moves.push(Instruction {
-            instruction_type: instruction_type.unwrap(),
+            instruction_type,
             instruction_val,
         });
     }
 
-    moves
+    Ok(moves)
 }
 
-pub fn get_sum_signal_strengths(input_file: &str) -> i32 {
-    let instructions = parse_input(input_file);
+pub fn get_sum_signal_strengths(puzzle_input: impl PuzzleInput) -> AocResult<i32> {
+    let instructions = parse_input(puzzle_input)?;
 
     let mut x = 1;
 
@@ -52,12 +62,14 @@ pub fn get_sum_signal_strengths(input_file: &str) -> i32 {
 
     let mut signal_strength_sum = 0;
 
+    let mut crt = String::new();
+
     for instruction in instructions {
         match instruction.instruction_type {
             InstructionType::Noop => {
                 let signal_strength = get_signal_strength(cycle, x);
                 signal_strength_sum += signal_strength;
-                render_cycle(cycle, x);
+                render_cycle(&mut crt, cycle, x);
                 cycle += 1;
             }
             InstructionType::Addx => {
@@ -69,25 +81,26 @@ pub fn get_sum_signal_strengths(input_file: &str) -> i32 {
                     {
                         x += val;
                     }
-                    render_cycle(cycle, x);
+                    render_cycle(&mut crt, cycle, x);
                     cycle += 1;
                 }
             }
         }
     }
 
-    signal_strength_sum
+    Ok(signal_strength_sum)
 }
 
-fn render_cycle(cycle: i32, x: i32) {
+fn render_cycle(crt: &mut String, cycle: i32, x: i32) {
     let offset = 40 * (cycle / 40);
     if cycle == x + offset - 1 || cycle == x + offset || cycle == x + offset + 1 {
-        print!("#");
+        crt.push('#');
     } else {
-        print!(".");
+        crt.push('.');
     }
     if cycle % 40 == 0 {
-        println!();
+        debug!("{crt}");
+        crt.clear();
     }
 }
 
@@ -99,25 +112,44 @@ fn get_signal_strength(cycle: i32, x: i32) -> i32 {
     signal_strength
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_sum_signal_strengths(Contents(input))?.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_get_sum_signal_strengths_test01() {
-        assert_eq!(0, get_sum_signal_strengths("input/2022/day10_test01.txt"));
+        assert_eq!(
+            0,
+            get_sum_signal_strengths("input/2022/day10_test01.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_signal_strengths_test02() {
         assert_eq!(
             13140,
-            get_sum_signal_strengths("input/2022/day10_test02.txt")
+            get_sum_signal_strengths("input/2022/day10_test02.txt").unwrap()
         );
     }
 
     #[test]
     fn test_get_sum_signal_strengths() {
-        assert_eq!(12520, get_sum_signal_strengths("input/2022/day10.txt"));
+        assert_eq!(
+            12520,
+            get_sum_signal_strengths("input/2022/day10.txt").unwrap()
+        );
     }
 }
@@ -8,3 +8,18 @@ pub mod day07;
 pub mod day08;
 pub mod day09;
 pub mod day10;
+
+use crate::solution::YearSolutions;
+
+pub const SOLUTIONS: YearSolutions = &[
+    (1, &day01::Solver),
+    (2, &day02::Solver),
+    (3, &day03::Solver),
+    (4, &day04::Solver),
+    (5, &day05::Solver),
+    (6, &day06::Solver),
+    (7, &day07::Solver),
+    (8, &day08::Solver),
+    (9, &day09::Solver),
+    (10, &day10::Solver),
+];
@@ -1,19 +1,28 @@
 // https://adventofcode.com/2023/day/1
 
-use crate::utils::get_lines;
+use crate::error::{AocResult, require};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput, get_lines};
 
-pub fn get_sum_calibration_values(input_file: &str, digits_can_be_text: bool) -> u32 {
-    let lines = get_lines(input_file);
+pub fn get_sum_calibration_values(
+    puzzle_input: impl PuzzleInput,
+    digits_can_be_text: bool,
+) -> AocResult<u32> {
+    let lines = get_lines(puzzle_input)?;
 
     let mut sum_calibration_values = 0;
 
-    for line in lines {
+    for (line_idx, line) in lines.iter().enumerate() {
         if !line.is_empty() {
-            sum_calibration_values += get_calibration_value(&line, digits_can_be_text);
+            sum_calibration_values += require(
+                get_calibration_value(line, digits_can_be_text),
+                line_idx,
+                "no digits in calibration line",
+            )?;
         }
     }
 
-    sum_calibration_values
+    Ok(sum_calibration_values)
 }
 
 pub fn get_digit(line: &str, digits: &mut Vec<u32>, digits_can_be_text: bool) {
@@ -68,18 +77,25 @@ fn get_digits(line: &str, digits_can_be_text: bool) -> Vec<u32> {
     digits
 }
 
-fn get_calibration_value(line: &str, digits_can_be_text: bool) -> u32 {
+fn get_calibration_value(line: &str, digits_can_be_text: bool) -> Option<u32> {
     let digits = get_digits(line, digits_can_be_text);
 
-    let first_digit = digits.first();
-    let last_digit = digits.last();
+    let first_digit = digits.first()?;
+    let last_digit = digits.last()?;
 
-    let mut calibration_value: String = String::new();
+    Some(first_digit * 10 + last_digit)
+}
+
+pub struct Solver;
 
-    calibration_value += &first_digit.ok_or("").unwrap().to_string();
-    calibration_value += &last_digit.ok_or("").unwrap().to_string();
+impl Solution for Solver {
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_sum_calibration_values(Contents(input), false)?.into())
+    }
 
-    calibration_value.parse().unwrap()
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_sum_calibration_values(Contents(input), true)?.into())
+    }
 }
 
 #[cfg(test)]
@@ -90,7 +106,7 @@ mod tests {
     fn test_sum_calibration_values_test01() {
         assert_eq!(
             142,
-            get_sum_calibration_values("input/2023/day01_test01.txt", false)
+            get_sum_calibration_values("input/2023/day01_test01.txt", false).unwrap()
         );
     }
 
@@ -98,7 +114,7 @@ mod tests {
     fn test_sum_calibration_values_test02() {
         assert_eq!(
             281,
-            get_sum_calibration_values("input/2023/day01_test02.txt", true)
+            get_sum_calibration_values("input/2023/day01_test02.txt", true).unwrap()
         );
     }
 
@@ -106,7 +122,7 @@ mod tests {
     fn test_sum_calibration_values_part01() {
         assert_eq!(
             54390,
-            get_sum_calibration_values("input/2023/day01.txt", false)
+            get_sum_calibration_values("input/2023/day01.txt", false).unwrap()
         );
     }
 
@@ -114,7 +130,7 @@ mod tests {
     fn test_sum_calibration_values_part02() {
         assert_eq!(
             54277,
-            get_sum_calibration_values("input/2023/day01.txt", true)
+            get_sum_calibration_values("input/2023/day01.txt", true).unwrap()
         );
     }
 }
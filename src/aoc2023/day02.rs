@@ -1,6 +1,8 @@
 // https://adventofcode.com/2023/day/2
 
-use crate::utils::get_lines;
+use crate::error::{AocError, AocResult, parse_token, require};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput, get_lines};
 
 #[derive(Debug, PartialEq)]
 pub struct CubeCount {
@@ -14,33 +16,32 @@ pub struct Input {
     games: Vec<(u32, Vec<CubeCount>)>,
 }
 
-pub fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
+pub fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let lines = get_lines(puzzle_input)?;
 
-    let mut iter = lines.split(|e| e.is_empty());
+    let games_lines = lines.split(|e| e.is_empty()).next().unwrap_or_default();
 
-    Input {
-        games: parse_games(iter.next().unwrap().to_owned()),
-    }
+    Ok(Input {
+        games: parse_games(games_lines.to_owned())?,
+    })
 }
 
-pub fn parse_games(games_lines: Vec<String>) -> Vec<(u32, Vec<CubeCount>)> {
+pub fn parse_games(games_lines: Vec<String>) -> AocResult<Vec<(u32, Vec<CubeCount>)>> {
     let mut games: Vec<(u32, Vec<CubeCount>)> = Vec::new();
 
-    for game_line in games_lines.into_iter() {
-        let game_split: Vec<&str> = game_line.split(':').collect();
-
-        let game_id_part = game_split.first().unwrap();
-
-        let game_id: u32 = game_id_part
-            .split_whitespace()
-            .collect::<Vec<&str>>()
-            .last()
-            .unwrap()
-            .parse()
-            .unwrap();
+    for (line_idx, game_line) in games_lines.iter().enumerate() {
+        let (game_id_part, game_result_part) = require(
+            game_line.split_once(':'),
+            line_idx,
+            "expected \"Game N: ...\"",
+        )?;
 
-        let game_result_part = game_split.last().unwrap();
+        let game_id_str = require(
+            game_id_part.split_whitespace().last(),
+            line_idx,
+            "missing game id",
+        )?;
+        let game_id: u32 = parse_token(line_idx, game_line, game_id_str)?;
 
         let game_result_sets: Vec<&str> = game_result_part.split(';').collect();
 
@@ -54,16 +55,25 @@ pub fn parse_games(games_lines: Vec<String>) -> Vec<(u32, Vec<CubeCount>)> {
             };
             let game_result_cubes: Vec<&str> = game_result_set.split(',').collect();
             for game_result_cube in game_result_cubes {
-                let game_result_cube_split: Vec<&str> =
-                    game_result_cube.split_whitespace().collect();
+                let (game_result_cube_count, game_result_cube_color) = require(
+                    game_result_cube.trim().split_once(' '),
+                    line_idx,
+                    "expected a cube count and color",
+                )?;
                 let game_result_cube_count: u32 =
-                    game_result_cube_split.first().unwrap().parse().unwrap();
-                let game_result_cube_color = game_result_cube_split.last().unwrap();
-                match *game_result_cube_color {
+                    parse_token(line_idx, game_line, game_result_cube_count)?;
+                match game_result_cube_color {
                     "red" => cube_count.red = game_result_cube_count,
                     "green" => cube_count.green = game_result_cube_count,
                     "blue" => cube_count.blue = game_result_cube_count,
-                    _ => panic!("invalid game result"),
+                    color => {
+                        return Err(AocError::parse_token(
+                            line_idx,
+                            game_line,
+                            color,
+                            format!("unknown cube color {color:?}"),
+                        ));
+                    }
                 }
             }
             cube_counts.push(cube_count);
@@ -72,18 +82,18 @@ pub fn parse_games(games_lines: Vec<String>) -> Vec<(u32, Vec<CubeCount>)> {
         games.push((game_id, cube_counts));
     }
 
-    games
+    Ok(games)
 }
 
-pub fn get_sum_ids_of_valid_games(input_file: &str) -> u32 {
-    let input = parse_input(input_file);
+pub fn get_sum_ids_of_valid_games(puzzle_input: impl PuzzleInput) -> AocResult<u32> {
+    let input = parse_input(puzzle_input)?;
     let mut sum_ids: u32 = 0;
     for (game_id, cube_counts) in input.games {
         if possible_game(cube_counts) {
             sum_ids += game_id;
         }
     }
-    sum_ids
+    Ok(sum_ids)
 }
 
 pub fn possible_game(cube_counts: Vec<CubeCount>) -> bool {
@@ -102,8 +112,8 @@ pub fn possible_game(cube_counts: Vec<CubeCount>) -> bool {
     result
 }
 
-pub fn get_sum_power_sets(input_file: &str) -> u32 {
-    let input = parse_input(input_file);
+pub fn get_sum_power_sets(puzzle_input: impl PuzzleInput) -> AocResult<u32> {
+    let input = parse_input(puzzle_input)?;
     let mut sum_power_sets: u32 = 0;
     for (_, cube_counts) in input.games {
         let mut reds: Vec<u32> = Vec::new();
@@ -120,7 +130,24 @@ pub fn get_sum_power_sets(input_file: &str) -> u32 {
         let power = max_red * max_green * max_blue;
         sum_power_sets += power;
     }
-    sum_power_sets
+    Ok(sum_power_sets)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_sum_ids_of_valid_games(Contents(input))?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_sum_power_sets(Contents(input))?.into())
+    }
 }
 
 #[cfg(test)]
@@ -129,21 +156,30 @@ mod tests {
 
     #[test]
     fn test_sum_ids_of_valid_games_test01() {
-        assert_eq!(8, get_sum_ids_of_valid_games("input/2023/day02_test01.txt"));
+        assert_eq!(
+            8,
+            get_sum_ids_of_valid_games("input/2023/day02_test01.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_sum_ids_of_valid_games() {
-        assert_eq!(2369, get_sum_ids_of_valid_games("input/2023/day02.txt"));
+        assert_eq!(
+            2369,
+            get_sum_ids_of_valid_games("input/2023/day02.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_sum_power_sets_test01() {
-        assert_eq!(2286, get_sum_power_sets("input/2023/day02_test01.txt"));
+        assert_eq!(
+            2286,
+            get_sum_power_sets("input/2023/day02_test01.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_sum_power_sets_values() {
-        assert_eq!(66363, get_sum_power_sets("input/2023/day02.txt"));
+        assert_eq!(66363, get_sum_power_sets("input/2023/day02.txt").unwrap());
     }
 }
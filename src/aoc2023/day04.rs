@@ -2,7 +2,9 @@
 
 use std::collections::HashMap;
 
-use crate::utils::get_lines;
+use crate::error::{AocError, AocResult, parse_token, require};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput, get_lines};
 
 use regex::Regex;
 
@@ -17,52 +19,57 @@ pub struct Input {
     pub games: HashMap<u32, Game>,
 }
 
-pub fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
+pub fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let lines = get_lines(puzzle_input)?;
 
-    Input {
-        games: parse_games(lines),
-    }
+    Ok(Input {
+        games: parse_games(lines)?,
+    })
 }
 
-pub fn parse_games(game_lines: Vec<String>) -> HashMap<u32, Game> {
+pub fn parse_games(game_lines: Vec<String>) -> AocResult<HashMap<u32, Game>> {
     lazy_static! {
         static ref RE_GAME: Regex = Regex::new(r"\d+").unwrap();
     }
 
     let mut games: HashMap<u32, Game> = HashMap::new();
 
-    for game_line in game_lines {
+    for (line_idx, game_line) in game_lines.iter().enumerate() {
         let game_line_parts: Vec<&str> = game_line.split([':', '|']).collect();
-        assert!(game_line_parts.len() == 3);
-
-        let card_num: Vec<u32> = RE_GAME
-            .find_iter(game_line_parts[0])
-            .map(|m| m.as_str().parse::<u32>().unwrap())
-            .collect();
+        if game_line_parts.len() != 3 {
+            return Err(AocError::parse(
+                line_idx,
+                0,
+                "expected \"Card N: winning | numbers\"",
+            ));
+        }
 
-        let winning_nums: Vec<u32> = RE_GAME
-            .find_iter(game_line_parts[1])
-            .map(|m| m.as_str().parse::<u32>().unwrap())
-            .collect();
+        let parse_nums = |part| -> AocResult<Vec<u32>> {
+            RE_GAME
+                .find_iter(part)
+                .map(|m| parse_token(line_idx, game_line, m.as_str()))
+                .collect()
+        };
 
-        let nums: Vec<u32> = RE_GAME
-            .find_iter(game_line_parts[2])
-            .map(|m| m.as_str().parse::<u32>().unwrap())
-            .collect();
+        let card_num = parse_nums(game_line_parts[0])?;
+        let winning_nums = parse_nums(game_line_parts[1])?;
+        let nums = parse_nums(game_line_parts[2])?;
 
         let game = Game { winning_nums, nums };
 
-        games.insert(*card_num.first().unwrap(), game);
+        games.insert(
+            *require(card_num.first(), line_idx, "missing card number")?,
+            game,
+        );
     }
 
-    games
+    Ok(games)
 }
 
-pub fn get_scratchcard_score(input_file: &str) -> u32 {
+pub fn get_scratchcard_score(puzzle_input: impl PuzzleInput) -> AocResult<u32> {
     let mut sum_scratchcard_points: u32 = 0;
 
-    let input = parse_input(input_file);
+    let input = parse_input(puzzle_input)?;
 
     for (_, game) in input.games.into_iter() {
         let mut game_score: u32 = 0;
@@ -78,18 +85,18 @@ pub fn get_scratchcard_score(input_file: &str) -> u32 {
         sum_scratchcard_points += game_score;
     }
 
-    sum_scratchcard_points
+    Ok(sum_scratchcard_points)
 }
 
-pub fn get_total_scratchcards(input_file: &str) -> u32 {
-    let input = parse_input(input_file);
+pub fn get_total_scratchcards(puzzle_input: impl PuzzleInput) -> AocResult<u32> {
+    let input = parse_input(puzzle_input)?;
 
     let mut total_scratchcards: u32 = input.games.len() as u32;
 
     total_scratchcards +=
         get_total_scratchcards_rec(input.games.keys().cloned().collect(), &input.games);
 
-    total_scratchcards
+    Ok(total_scratchcards)
 }
 
 fn get_total_scratchcards_rec(game_ids: Vec<u32>, all_games: &HashMap<u32, Game>) -> u32 {
@@ -124,27 +131,56 @@ fn num_winning_nums(game: &Game) -> u32 {
     winning_nums
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_scratchcard_score(Contents(input))?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_total_scratchcards(Contents(input))?.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_get_scratchcard_score_test01() {
-        assert_eq!(13, get_scratchcard_score("input/2023/day04_test01.txt"));
+        assert_eq!(
+            13,
+            get_scratchcard_score("input/2023/day04_test01.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_scratchcard_score() {
-        assert_eq!(20667, get_scratchcard_score("input/2023/day04.txt"));
+        assert_eq!(
+            20667,
+            get_scratchcard_score("input/2023/day04.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_total_scratchcards_test01() {
-        assert_eq!(30, get_total_scratchcards("input/2023/day04_test01.txt"));
+        assert_eq!(
+            30,
+            get_total_scratchcards("input/2023/day04_test01.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_total_scratchcards() {
-        assert_eq!(5833065, get_total_scratchcards("input/2023/day04.txt"));
+        assert_eq!(
+            5833065,
+            get_total_scratchcards("input/2023/day04.txt").unwrap()
+        );
     }
 }
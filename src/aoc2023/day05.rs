@@ -2,7 +2,9 @@
 
 use itertools::Itertools;
 
-use crate::utils::get_lines;
+use crate::error::{AocError, AocResult, parse_token, require};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput, get_lines};
 
 pub enum ParseStage {
     Seeds,
@@ -32,19 +34,19 @@ pub struct Input {
     pub almanac: Almanac,
 }
 
-pub fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
+pub fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let lines = get_lines(puzzle_input)?;
 
-    Input {
-        almanac: parse_almanac(lines),
-    }
+    Ok(Input {
+        almanac: parse_almanac(lines)?,
+    })
 }
 
-fn parse_almanac(lines: Vec<String>) -> Almanac {
+fn parse_almanac(lines: Vec<String>) -> AocResult<Almanac> {
     let mut parse_stage: ParseStage = ParseStage::Seeds;
     let mut almanac = Almanac::default();
     let mut parse_nums;
-    for almanac_line in lines {
+    for (line_idx, almanac_line) in lines.iter().enumerate() {
         let split_line: Vec<&str> = almanac_line.split(':').collect();
         match split_line[0] {
             "seeds" => {
@@ -52,8 +54,8 @@ fn parse_almanac(lines: Vec<String>) -> Almanac {
                 let seeds: Vec<u64> = split_line[1]
                     .trim()
                     .split(' ')
-                    .map(|seed| seed.parse().unwrap())
-                    .collect();
+                    .map(|seed| parse_token(line_idx, almanac_line, seed))
+                    .collect::<AocResult<_>>()?;
                 almanac.seeds = seeds;
                 continue;
             }
@@ -94,12 +96,16 @@ fn parse_almanac(lines: Vec<String>) -> Almanac {
         }
 
         if parse_nums {
-            let nums: (u64, u64, u64) = split_line[0]
+            let nums: Vec<u64> = split_line[0]
                 .trim()
                 .split(' ')
-                .map(|seed| seed.parse().unwrap())
-                .collect_tuple()
-                .unwrap();
+                .map(|num| parse_token(line_idx, almanac_line, num))
+                .collect::<AocResult<_>>()?;
+            let nums: (u64, u64, u64) = require(
+                nums.into_iter().collect_tuple(),
+                line_idx,
+                "expected three numbers in a map range",
+            )?;
 
             match parse_stage {
                 ParseStage::Seeds => (),
@@ -127,11 +133,14 @@ fn parse_almanac(lines: Vec<String>) -> Almanac {
             }
         }
     }
-    almanac
+    Ok(almanac)
 }
 
-pub fn get_lowest_location(input_file: &str, seeds_as_ranges: bool) -> u64 {
-    let input = parse_input(input_file);
+pub fn get_lowest_location(
+    puzzle_input: impl PuzzleInput,
+    seeds_as_ranges: bool,
+) -> AocResult<u64> {
+    let input = parse_input(puzzle_input)?;
 
     let mut seeds: Vec<u64> = vec![];
 
@@ -190,7 +199,10 @@ pub fn get_lowest_location(input_file: &str, seeds_as_ranges: bool) -> u64 {
         })
         .collect();
 
-    *locations.iter().min().unwrap()
+    locations
+        .into_iter()
+        .min()
+        .ok_or(AocError::invalid("no seeds in the almanac"))
 }
 
 fn get_destinations(lookup_val: u64, ranges: &Vec<(u64, u64, u64)>) -> Option<u64> {
@@ -211,6 +223,23 @@ fn get_destination(lookup_val: u64, (dest, source, length): &(u64, u64, u64)) ->
     None
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_lowest_location(Contents(input), false)?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_lowest_location(Contents(input), true)?.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -258,7 +287,7 @@ mod tests {
     fn test_get_lowest_location_test01() {
         assert_eq!(
             35,
-            get_lowest_location("input/2023/day05_test01.txt", false)
+            get_lowest_location("input/2023/day05_test01.txt", false).unwrap()
         );
     }
 
@@ -266,7 +295,7 @@ mod tests {
     fn test_get_lowest_location_test02() {
         assert_eq!(
             13,
-            get_lowest_location("input/2023/day05_test02.txt", false)
+            get_lowest_location("input/2023/day05_test02.txt", false).unwrap()
         );
     }
 
@@ -274,18 +303,24 @@ mod tests {
     fn test_get_lowest_location_score() {
         assert_eq!(
             388071289,
-            get_lowest_location("input/2023/day05.txt", false)
+            get_lowest_location("input/2023/day05.txt", false).unwrap()
         );
     }
 
     #[test]
     fn test_get_lowest_location_seeds_as_ranges_test01() {
-        assert_eq!(46, get_lowest_location("input/2023/day05_test01.txt", true));
+        assert_eq!(
+            46,
+            get_lowest_location("input/2023/day05_test01.txt", true).unwrap()
+        );
     }
 
     #[ignore]
     #[test]
     fn test_get_lowest_location_seeds_as_ranges() {
-        assert_eq!(84206669, get_lowest_location("input/2023/day05.txt", true));
+        assert_eq!(
+            84206669,
+            get_lowest_location("input/2023/day05.txt", true).unwrap()
+        );
     }
 }
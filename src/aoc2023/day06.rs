@@ -1,6 +1,8 @@
 // https://adventofcode.com/2023/day/6
 
-use crate::utils::get_lines;
+use crate::error::{AocResult, parse_token, require};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput, get_lines};
 
 #[derive(Debug, Default)]
 pub struct Races {
@@ -12,37 +14,55 @@ pub struct Input {
     races: Races,
 }
 
-pub fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
+pub fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let lines = get_lines(puzzle_input)?;
 
-    Input {
-        races: parse_races(lines),
-    }
+    Ok(Input {
+        races: parse_races(lines)?,
+    })
 }
 
-fn parse_races(lines: Vec<String>) -> Races {
+fn parse_races(lines: Vec<String>) -> AocResult<Races> {
     let mut races = Races::default();
-    let times_str = &lines[0].rsplit_once(':').unwrap().1.trim();
-    let times: Vec<u64> = times_str
-        .split_whitespace()
-        .map(|time| time.parse().unwrap())
-        .collect();
-    let distances_str = &lines[1].rsplit_once(':').unwrap().1.trim();
-    let distances: Vec<u64> = distances_str
-        .split_whitespace()
-        .map(|time| time.parse().unwrap())
-        .collect();
+    let parse_nums = |line_idx: usize, message: &str| -> AocResult<Vec<u64>> {
+        let line = require(lines.get(line_idx), line_idx, message)?;
+        let (_, nums_str) = require(line.rsplit_once(':'), line_idx, message)?;
+        nums_str
+            .split_whitespace()
+            .map(|num| parse_token(line_idx, line, num))
+            .collect()
+    };
+    let times = parse_nums(0, "expected \"Time: ...\"")?;
+    let distances = parse_nums(1, "expected \"Distance: ...\"")?;
     races.races = times.into_iter().zip(distances).collect();
-    races
+    Ok(races)
+}
+
+/// Reads each line as a single number, ignoring the spaces between its digits.
+fn parse_single_race(lines: Vec<String>) -> AocResult<(u64, u64)> {
+    let parse_num = |line_idx: usize, message: &str| -> AocResult<u64> {
+        let line = require(lines.get(line_idx), line_idx, message)?;
+        let (_, nums_str) = require(line.rsplit_once(':'), line_idx, message)?;
+        let digits: String = nums_str.split_whitespace().collect();
+        parse_token(line_idx, &digits, &digits)
+    };
+    let time = parse_num(0, "expected \"Time: ...\"")?;
+    let distance = parse_num(1, "expected \"Distance: ...\"")?;
+    Ok((time, distance))
 }
 
-pub fn get_multiple_beaten_records(input_file: &str) -> u64 {
+pub fn get_multiple_beaten_records(puzzle_input: impl PuzzleInput) -> AocResult<u64> {
     let mut beaten_records: u64 = 1;
-    let input = parse_input(input_file);
+    let input = parse_input(puzzle_input)?;
     for race in input.races.races {
         beaten_records *= get_beaten_records(race);
     }
-    beaten_records
+    Ok(beaten_records)
+}
+
+pub fn get_single_race_beaten_records(puzzle_input: impl PuzzleInput) -> AocResult<u64> {
+    let race = parse_single_race(get_lines(puzzle_input)?)?;
+    Ok(get_beaten_records(race))
 }
 
 fn get_beaten_records((time, distance): (u64, u64)) -> u64 {
@@ -56,6 +76,23 @@ fn get_beaten_records((time, distance): (u64, u64)) -> u64 {
     beaten_records
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_multiple_beaten_records(Contents(input))?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_single_race_beaten_records(Contents(input))?.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -64,7 +101,7 @@ mod tests {
     fn test_get_multiple_beaten_records_test01() {
         assert_eq!(
             288,
-            get_multiple_beaten_records("input/2023/day06_test01.txt")
+            get_multiple_beaten_records("input/2023/day06_test01.txt").unwrap()
         );
     }
 
@@ -72,7 +109,7 @@ mod tests {
     fn test_get_multiple_beaten_records_test02() {
         assert_eq!(
             4,
-            get_multiple_beaten_records("input/2023/day06_test02.txt")
+            get_multiple_beaten_records("input/2023/day06_test02.txt").unwrap()
         );
     }
 
@@ -80,7 +117,7 @@ mod tests {
     fn test_get_multiple_beaten_records_test03() {
         assert_eq!(
             8,
-            get_multiple_beaten_records("input/2023/day06_test03.txt")
+            get_multiple_beaten_records("input/2023/day06_test03.txt").unwrap()
         );
     }
 
@@ -88,7 +125,7 @@ mod tests {
     fn test_get_multiple_beaten_records_test04() {
         assert_eq!(
             9,
-            get_multiple_beaten_records("input/2023/day06_test04.txt")
+            get_multiple_beaten_records("input/2023/day06_test04.txt").unwrap()
         );
     }
 
@@ -96,15 +133,23 @@ mod tests {
     fn test_get_multiple_beaten_records_part01() {
         assert_eq!(
             140220,
-            get_multiple_beaten_records("input/2023/day06_part01.txt")
+            get_multiple_beaten_records("input/2023/day06.txt").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_get_single_race_beaten_records_test01() {
+        assert_eq!(
+            71503,
+            get_single_race_beaten_records("input/2023/day06_test01.txt").unwrap()
         );
     }
 
     #[test]
-    fn test_get_multiple_beaten_records_part02() {
+    fn test_get_single_race_beaten_records() {
         assert_eq!(
             39570185,
-            get_multiple_beaten_records("input/2023/day06_part02.txt")
+            get_single_race_beaten_records("input/2023/day06.txt").unwrap()
         );
     }
 }
@@ -1,6 +1,8 @@
 // https://adventofcode.com/2023/day/7
 
-use crate::utils::get_lines;
+use crate::error::{AocError, AocResult, parse_token};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput, get_lines};
 use itertools::Itertools;
 use std::cmp::Ordering;
 
@@ -170,33 +172,34 @@ pub struct Input {
     hands: Vec<Hand>,
 }
 
-pub fn parse_input(input_file: &str, use_joker: bool) -> Input {
-    let lines = get_lines(input_file);
+pub fn parse_input(puzzle_input: impl PuzzleInput, use_joker: bool) -> AocResult<Input> {
+    let lines = get_lines(puzzle_input)?;
 
     let mut input = Input::default();
 
-    for line in lines {
-        let line_parts: Vec<&str> = line.split_whitespace().collect();
+    for (line_idx, line) in lines.iter().enumerate() {
+        let Some((hand_str, bid_str)) = line.split_once(' ') else {
+            return Err(AocError::parse(line_idx, 0, "expected a hand and a bid"));
+        };
+        let cards = hand_str
+            .chars()
+            .enumerate()
+            .map(|(i, card)| {
+                parse_card(card, use_joker).ok_or(AocError::unexpected_char(line_idx, i, card))
+            })
+            .collect::<AocResult<_>>()?;
         let hand = Hand {
-            cards: parse_hand(line_parts.first().unwrap(), use_joker),
-            bid: line_parts.last().unwrap().parse().unwrap(),
+            cards,
+            bid: parse_token(line_idx, line, bid_str)?,
         };
         input.hands.push(hand);
     }
 
-    input
-}
-
-fn parse_hand(hand_str: &str, use_joker: bool) -> Vec<Card> {
-    let mut hand: Vec<Card> = vec![];
-    for card in hand_str.chars() {
-        hand.push(parse_card(card, use_joker));
-    }
-    hand
+    Ok(input)
 }
 
-fn parse_card(card_char: char, use_joker: bool) -> Card {
-    match card_char {
+fn parse_card(card_char: char, use_joker: bool) -> Option<Card> {
+    let card = match card_char {
         'A' => Card::Ace,
         'K' => Card::King,
         'Q' => Card::Queen,
@@ -216,18 +219,36 @@ fn parse_card(card_char: char, use_joker: bool) -> Card {
         '4' => Card::Four,
         '3' => Card::Three,
         '2' => Card::Two,
-        _ => panic!("Invalid card: {}", card_char),
-    }
+        _ => return None,
+    };
+    Some(card)
 }
 
-pub fn get_total_winnings(input_file: &str, use_joker: bool) -> u64 {
+pub fn get_total_winnings(puzzle_input: impl PuzzleInput, use_joker: bool) -> AocResult<u64> {
     let mut total_winnings: u64 = 0;
-    let mut input = parse_input(input_file, use_joker);
+    let mut input = parse_input(puzzle_input, use_joker)?;
     input.hands.sort();
     for (rank, hand) in input.hands.iter().enumerate() {
         total_winnings += hand.bid * (rank as u64 + 1);
     }
-    total_winnings
+    Ok(total_winnings)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input), false)?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_total_winnings(Contents(input), false)?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_total_winnings(Contents(input), true)?.into())
+    }
 }
 
 #[cfg(test)]
@@ -238,40 +259,55 @@ mod tests {
     fn test_get_total_winnings_test01() {
         assert_eq!(
             6440,
-            get_total_winnings("input/2023/day07_test01.txt", false)
+            get_total_winnings("input/2023/day07_test01.txt", false).unwrap()
         );
     }
 
     #[test]
     fn test_get_total_winnings_test02() {
-        assert_eq!(8, get_total_winnings("input/2023/day07_test02.txt", false));
+        assert_eq!(
+            8,
+            get_total_winnings("input/2023/day07_test02.txt", false).unwrap()
+        );
     }
 
     #[test]
     fn test_get_total_winnings_test03() {
-        assert_eq!(8, get_total_winnings("input/2023/day07_test03.txt", false));
+        assert_eq!(
+            8,
+            get_total_winnings("input/2023/day07_test03.txt", false).unwrap()
+        );
     }
 
     #[test]
     fn test_get_total_winnings() {
-        assert_eq!(251545216, get_total_winnings("input/2023/day07.txt", false));
+        assert_eq!(
+            251545216,
+            get_total_winnings("input/2023/day07.txt", false).unwrap()
+        );
     }
 
     #[test]
     fn test_get_total_winnings_joker_test01() {
         assert_eq!(
             5905,
-            get_total_winnings("input/2023/day07_test01.txt", true)
+            get_total_winnings("input/2023/day07_test01.txt", true).unwrap()
         );
     }
 
     #[test]
     fn test_get_total_winnings_joker_test04() {
-        assert_eq!(65, get_total_winnings("input/2023/day07_test04.txt", true));
+        assert_eq!(
+            65,
+            get_total_winnings("input/2023/day07_test04.txt", true).unwrap()
+        );
     }
 
     #[test]
     fn test_get_total_winnings_joker() {
-        assert_eq!(250384185, get_total_winnings("input/2023/day07.txt", true));
+        assert_eq!(
+            250384185,
+            get_total_winnings("input/2023/day07.txt", true).unwrap()
+        );
     }
 }
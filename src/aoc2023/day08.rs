@@ -2,7 +2,9 @@
 
 use regex::Regex;
 
-use crate::utils::get_lines;
+use crate::error::{AocError, AocResult, require};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput, get_lines};
 use std::collections::HashMap;
 
 #[derive(Debug, PartialEq)]
@@ -18,21 +20,21 @@ pub struct Input {
     pub tree_nodes: HashMap<String, (String, String)>,
 }
 
-pub fn parse_input(input_file: &str) -> Input {
+pub fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
     lazy_static! {
         static ref RE_TREENODE: Regex =
             Regex::new(r"(?P<root>\w+)\s+=\s+\((?P<left>\w+),\s+(?P<right>\w+)\)").unwrap();
     }
 
-    let lines = get_lines(input_file);
+    let lines = get_lines(puzzle_input)?;
 
-    let instructions = parse_instructions(lines.first().unwrap());
+    let instructions = parse_instructions(require(lines.first(), 0, "missing instructions")?)?;
 
     let mut tree_node_names: Vec<String> = vec![];
     let mut tree_nodes: HashMap<String, (String, String)> = HashMap::new();
 
-    for line in lines.into_iter().skip(2) {
-        let caps_tree_nodes = RE_TREENODE.captures(&line);
+    for line in lines.iter().skip(2) {
+        let caps_tree_nodes = RE_TREENODE.captures(line);
         if let Some(caps_tree_nodes) = caps_tree_nodes {
             let root = caps_tree_nodes["root"].to_string();
             let left = caps_tree_nodes["left"].to_string();
@@ -42,34 +44,59 @@ pub fn parse_input(input_file: &str) -> Input {
         }
     }
 
+    // Every node referenced must be defined, otherwise traversal has nowhere to go
+    for (line_idx, line) in lines.iter().enumerate().skip(2) {
+        if let Some(caps_tree_nodes) = RE_TREENODE.captures(line) {
+            for side in ["left", "right"] {
+                let node = caps_tree_nodes.name(side).unwrap();
+                if !tree_nodes.contains_key(node.as_str()) {
+                    return Err(AocError::parse(
+                        line_idx,
+                        node.start(),
+                        format!("undefined node {:?}", node.as_str()),
+                    ));
+                }
+            }
+        }
+    }
+
+    if tree_node_names.is_empty() {
+        return Err(AocError::invalid("no nodes defined"));
+    }
     tree_node_names.sort();
 
-    Input {
+    Ok(Input {
         instructions,
         tree_node_names,
         tree_nodes,
-    }
+    })
 }
 
-fn parse_instructions(instructions_str: &str) -> Vec<Instruction> {
+fn parse_instructions(instructions_str: &str) -> AocResult<Vec<Instruction>> {
     let mut instructions: Vec<Instruction> = vec![];
-    for instruction_str in instructions_str.chars() {
-        instructions.push(parse_instruction(instruction_str));
+    for (i, instruction_char) in instructions_str.chars().enumerate() {
+        instructions.push(
+            parse_instruction(instruction_char).ok_or(AocError::unexpected_char(
+                0,
+                i,
+                instruction_char,
+            ))?,
+        );
     }
-    instructions
+    Ok(instructions)
 }
 
-fn parse_instruction(instruction_char: char) -> Instruction {
+fn parse_instruction(instruction_char: char) -> Option<Instruction> {
     match instruction_char {
-        'L' => Instruction::Left,
-        'R' => Instruction::Right,
-        _ => panic!("Invalid instruction: {}", instruction_char),
+        'L' => Some(Instruction::Left),
+        'R' => Some(Instruction::Right),
+        _ => None,
     }
 }
 
-pub fn get_num_steps(input_file: &str) -> u64 {
+pub fn get_num_steps(puzzle_input: impl PuzzleInput) -> AocResult<u64> {
     let mut num_steps: u64 = 0;
-    let input = parse_input(input_file);
+    let input = parse_input(puzzle_input)?;
     let mut current_node = Some(input.tree_node_names.first().unwrap().clone());
     for instruction in input.instructions.iter().cycle() {
         current_node = traverse_tree(
@@ -77,20 +104,20 @@ pub fn get_num_steps(input_file: &str) -> u64 {
             current_node.unwrap(),
             input.tree_node_names.last().unwrap().clone(),
             instruction,
-        );
+        )?;
         num_steps += 1;
         if current_node.is_none() {
             break;
         }
     }
 
-    num_steps
+    Ok(num_steps)
 }
 
-pub fn get_num_steps_end_with_z(input_file: &str) -> u64 {
+pub fn get_num_steps_end_with_z(puzzle_input: impl PuzzleInput) -> AocResult<u64> {
     let mut num_steps_vec: Vec<u64> = vec![];
 
-    let input = parse_input(input_file);
+    let input = parse_input(puzzle_input)?;
     let start_nodes: Vec<String> = input
         .tree_node_names
         .into_iter()
@@ -101,7 +128,7 @@ pub fn get_num_steps_end_with_z(input_file: &str) -> u64 {
         let mut current_node = Some(start_node);
         for instruction in input.instructions.iter().cycle() {
             current_node =
-                traverse_tree_ends_with_z(&input.tree_nodes, current_node.unwrap(), instruction);
+                traverse_tree_ends_with_z(&input.tree_nodes, current_node.unwrap(), instruction)?;
             num_steps += 1;
             if current_node.is_none() {
                 num_steps_vec.push(num_steps);
@@ -110,7 +137,7 @@ pub fn get_num_steps_end_with_z(input_file: &str) -> u64 {
         }
     }
 
-    num_steps_vec.into_iter().fold(1, lcm)
+    Ok(num_steps_vec.into_iter().fold(1, lcm))
 }
 
 fn gcd(a: u64, b: u64) -> u64 {
@@ -121,33 +148,37 @@ fn lcm(a: u64, b: u64) -> u64 {
     a / gcd(a, b) * b
 }
 
+fn undefined_node(node: &str) -> AocError {
+    AocError::invalid(format!("undefined node {node:?}"))
+}
+
 fn traverse_tree(
     tree_nodes: &HashMap<String, (String, String)>,
     current_node: String,
     leaf_node: String,
     instruction: &Instruction,
-) -> Option<String> {
+) -> AocResult<Option<String>> {
     match tree_nodes.get(&current_node) {
         Some((left, right)) => {
             if *instruction == Instruction::Left && *current_node != *left && *left != leaf_node {
-                return Some(left.clone());
+                return Ok(Some(left.clone()));
             } else if *instruction == Instruction::Right
                 && *current_node != *right
                 && *right != leaf_node
             {
-                return Some(right.clone());
+                return Ok(Some(right.clone()));
             }
         }
-        None => panic!("Invalid current_node"),
+        None => return Err(undefined_node(&current_node)),
     }
-    None
+    Ok(None)
 }
 
 fn traverse_tree_ends_with_z(
     tree_nodes: &HashMap<String, (String, String)>,
     current_node: String,
     instruction: &Instruction,
-) -> Option<String> {
+) -> AocResult<Option<String>> {
     match tree_nodes.get(&current_node) {
         Some((left, right)) => {
             if *instruction == Instruction::Left
@@ -155,18 +186,35 @@ fn traverse_tree_ends_with_z(
                 && !left.ends_with('Z')
                 && !current_node.ends_with('Z')
             {
-                return Some(left.clone());
+                return Ok(Some(left.clone()));
             } else if *instruction == Instruction::Right
                 && *current_node != *right
                 && !right.ends_with('Z')
                 && !current_node.ends_with('Z')
             {
-                return Some(right.clone());
+                return Ok(Some(right.clone()));
             }
         }
-        None => panic!("Invalid current_node"),
+        None => return Err(undefined_node(&current_node)),
+    }
+    Ok(None)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_num_steps(Contents(input))?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_num_steps_end_with_z(Contents(input))?.into())
     }
-    None
 }
 
 #[cfg(test)]
@@ -175,29 +223,32 @@ mod tests {
 
     #[test]
     fn test_get_num_steps_test01() {
-        assert_eq!(2, get_num_steps("input/2023/day08_test01.txt"));
+        assert_eq!(2, get_num_steps("input/2023/day08_test01.txt").unwrap());
     }
 
     #[test]
     fn test_get_num_steps_test02() {
-        assert_eq!(6, get_num_steps("input/2023/day08_test02.txt"));
+        assert_eq!(6, get_num_steps("input/2023/day08_test02.txt").unwrap());
     }
 
     #[test]
     fn test_get_num_steps() {
-        assert_eq!(21883, get_num_steps("input/2023/day08.txt"));
+        assert_eq!(21883, get_num_steps("input/2023/day08.txt").unwrap());
     }
 
     #[test]
     fn test_get_num_steps_end_with_z_test03() {
-        assert_eq!(6, get_num_steps_end_with_z("input/2023/day08_test03.txt"));
+        assert_eq!(
+            6,
+            get_num_steps_end_with_z("input/2023/day08_test03.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_num_steps_end_with_z() {
         assert_eq!(
             12833235391111,
-            get_num_steps_end_with_z("input/2023/day08.txt")
+            get_num_steps_end_with_z("input/2023/day08.txt").unwrap()
         );
     }
 }
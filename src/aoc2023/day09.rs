@@ -1,39 +1,44 @@
 // https://adventofcode.com/2023/day/9
 
-use crate::utils::get_lines;
+use crate::error::{AocError, AocResult, parse_token};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput, get_lines};
 
 #[derive(Debug)]
 pub struct Input {
     reports: Vec<Vec<i64>>,
 }
 
-pub fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
+pub fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let lines = get_lines(puzzle_input)?;
 
-    Input {
-        reports: parse_reports(lines),
-    }
+    Ok(Input {
+        reports: parse_reports(lines)?,
+    })
 }
 
-pub fn parse_reports(lines: Vec<String>) -> Vec<Vec<i64>> {
+pub fn parse_reports(lines: Vec<String>) -> AocResult<Vec<Vec<i64>>> {
     let mut reports = vec![];
-    for line in lines {
+    for (line_idx, line) in lines.iter().enumerate() {
         let report: Vec<i64> = line
             .split_ascii_whitespace()
-            .map(|x| x.parse().unwrap())
-            .collect();
+            .map(|x| parse_token(line_idx, line, x))
+            .collect::<AocResult<_>>()?;
+        if report.is_empty() {
+            return Err(AocError::parse(line_idx, 0, "empty report"));
+        }
         reports.push(report)
     }
-    reports
+    Ok(reports)
 }
 
-pub fn get_sum_extrapolated_values(input_file: &str) -> i64 {
-    let input = parse_input(input_file);
+pub fn get_sum_extrapolated_values(puzzle_input: impl PuzzleInput) -> AocResult<i64> {
+    let input = parse_input(puzzle_input)?;
     let mut extrapolated_values: Vec<i64> = vec![];
     for report in input.reports {
         extrapolated_values.push(extrapolate_next_value(&report));
     }
-    extrapolated_values.iter().sum()
+    Ok(extrapolated_values.iter().sum())
 }
 
 pub fn extrapolate_next_value(report: &[i64]) -> i64 {
@@ -51,14 +56,31 @@ pub fn extrapolate_next_value(report: &[i64]) -> i64 {
     }
 }
 
-pub fn get_sum_back_extrapolated_values(input_file: &str) -> i64 {
-    let input = parse_input(input_file);
+pub fn get_sum_back_extrapolated_values(puzzle_input: impl PuzzleInput) -> AocResult<i64> {
+    let input = parse_input(puzzle_input)?;
     let mut extrapolated_values: Vec<i64> = vec![];
     for report in input.reports {
         let reversed_report: Vec<i64> = report.into_iter().rev().collect();
         extrapolated_values.push(extrapolate_next_value(&reversed_report));
     }
-    extrapolated_values.iter().sum()
+    Ok(extrapolated_values.iter().sum())
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_sum_extrapolated_values(Contents(input))?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_sum_back_extrapolated_values(Contents(input))?.into())
+    }
 }
 
 #[cfg(test)]
@@ -69,7 +91,7 @@ mod tests {
     fn test_get_sum_extrapolated_values_test01() {
         assert_eq!(
             18,
-            get_sum_extrapolated_values("input/2023/day09_test01.txt")
+            get_sum_extrapolated_values("input/2023/day09_test01.txt").unwrap()
         );
     }
 
@@ -77,7 +99,7 @@ mod tests {
     fn test_get_sum_extrapolated_values_test02() {
         assert_eq!(
             28,
-            get_sum_extrapolated_values("input/2023/day09_test02.txt")
+            get_sum_extrapolated_values("input/2023/day09_test02.txt").unwrap()
         );
     }
 
@@ -85,7 +107,7 @@ mod tests {
     fn test_get_sum_extrapolated_values_test03() {
         assert_eq!(
             68,
-            get_sum_extrapolated_values("input/2023/day09_test03.txt")
+            get_sum_extrapolated_values("input/2023/day09_test03.txt").unwrap()
         );
     }
 
@@ -93,7 +115,7 @@ mod tests {
     fn test_get_sum_extrapolated_values_test04() {
         assert_eq!(
             114,
-            get_sum_extrapolated_values("input/2023/day09_test04.txt")
+            get_sum_extrapolated_values("input/2023/day09_test04.txt").unwrap()
         );
     }
 
@@ -101,7 +123,7 @@ mod tests {
     fn test_get_sum_extrapolated_values_test05() {
         assert_eq!(
             -106,
-            get_sum_extrapolated_values("input/2023/day09_test05.txt")
+            get_sum_extrapolated_values("input/2023/day09_test05.txt").unwrap()
         );
     }
 
@@ -109,7 +131,7 @@ mod tests {
     fn test_get_sum_extrapolated_values_test06() {
         assert_eq!(
             0,
-            get_sum_extrapolated_values("input/2023/day09_test06.txt")
+            get_sum_extrapolated_values("input/2023/day09_test06.txt").unwrap()
         );
     }
 
@@ -117,7 +139,7 @@ mod tests {
     fn test_get_sum_extrapolated_values() {
         assert_eq!(
             1708206096,
-            get_sum_extrapolated_values("input/2023/day09.txt")
+            get_sum_extrapolated_values("input/2023/day09.txt").unwrap()
         );
     }
 
@@ -125,7 +147,7 @@ mod tests {
     fn test_get_sum_back_extrapolated_values_test01() {
         assert_eq!(
             5,
-            get_sum_back_extrapolated_values("input/2023/day09_test03.txt")
+            get_sum_back_extrapolated_values("input/2023/day09_test03.txt").unwrap()
         );
     }
 
@@ -133,7 +155,7 @@ mod tests {
     fn test_get_sum_back_extrapolated_values() {
         assert_eq!(
             1050,
-            get_sum_back_extrapolated_values("input/2023/day09.txt")
+            get_sum_back_extrapolated_values("input/2023/day09.txt").unwrap()
         );
     }
 }
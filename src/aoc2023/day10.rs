@@ -1,6 +1,10 @@
 // https://adventofcode.com/2023/day/10
 
-use crate::utils::{Direction, get_lines};
+use std::collections::HashSet;
+
+use crate::error::{AocError, AocResult};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, Direction, Grid, Pos, PuzzleInput};
 
 #[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
 #[repr(u8)]
@@ -40,79 +44,48 @@ impl TryFrom<u8> for Pipe {
 
 #[derive(Debug)]
 pub struct Input {
-    pub tiles: Vec<Vec<Pipe>>,
+    pub tiles: Grid<Pipe>,
 }
 
-pub fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
-
-    let mut iter = lines.split(|e| e.is_empty());
-
-    Input {
-        tiles: parse_tiles(iter.next().unwrap().to_owned()),
-    }
+pub fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    Ok(Input {
+        tiles: Grid::parse(puzzle_input, |c| Pipe::try_from(u8::try_from(c).ok()?).ok())?,
+    })
 }
 
-pub fn parse_tiles(tiles_lines: Vec<String>) -> Vec<Vec<Pipe>> {
-    let mut tiles = Vec::new();
-    for tiles_line in tiles_lines.into_iter() {
-        let mut tiles_entries: Vec<Pipe> = Vec::new();
-        for tiles_entry in tiles_line.chars() {
-            match Pipe::try_from(tiles_entry as u8) {
-                Ok(pipe) => tiles_entries.push(pipe),
-                Err(_) => panic!("Invalid pipe"),
-            }
-        }
-        tiles.push(tiles_entries)
-    }
+fn get_start_pos(tiles: &Grid<Pipe>) -> AocResult<Pos> {
     tiles
+        .position(|pipe| *pipe == Pipe::StartPos)
+        .ok_or_else(|| AocError::invalid("no start position in the pipe map"))
 }
 
-pub fn get_farthest_steps(input_file: &str) -> usize {
-    let input = parse_input(input_file);
-
-    let mut start_pos: Option<(usize, usize)> = None;
-    for (row, tile_row) in input.tiles.iter().enumerate() {
-        for (col, pipe) in tile_row.iter().enumerate() {
-            if *pipe == Pipe::StartPos {
-                start_pos = Some((row, col));
-                break;
-            }
-        }
-        if start_pos.is_some() {
-            break;
-        }
-    }
+pub fn get_farthest_steps(puzzle_input: impl PuzzleInput) -> AocResult<usize> {
+    let input = parse_input(puzzle_input)?;
 
-    if let Some(start) = start_pos {
-        let loop_path = find_main_loop(&input.tiles, start);
-        loop_path.len() / 2
-    } else {
-        panic!("Invalid start node");
-    }
+    let start = get_start_pos(&input.tiles)?;
+    let loop_path = find_main_loop(&input.tiles, start);
+    Ok(loop_path.len() / 2)
 }
 
 // Find the main loop by following connected pipes
-fn find_main_loop(tiles: &[Vec<Pipe>], start: (usize, usize)) -> Vec<(usize, usize)> {
+fn find_main_loop(tiles: &Grid<Pipe>, start: Pos) -> Vec<Pos> {
     let mut path = vec![start];
     let mut current = start;
-    let mut came_from: Option<(usize, usize)> = None;
+    let mut came_from: Option<Pos> = None;
 
     loop {
         let mut found_next = false;
 
         // Check all 4 directions
         for direction in [Direction::N, Direction::S, Direction::E, Direction::W] {
-            if let Some(next_pos) =
-                get_next_position(current, direction, tiles.len(), tiles[0].len())
-            {
+            if let Some(next_pos) = tiles.neighbor(current, direction) {
                 // Don't go back where we came from
                 if Some(next_pos) == came_from {
                     continue;
                 }
 
-                let current_pipe = tiles[current.0][current.1];
-                let next_pipe = tiles[next_pos.0][next_pos.1];
+                let current_pipe = tiles[current];
+                let next_pipe = tiles[next_pos];
 
                 if is_pipe_connected(current_pipe, next_pipe, direction) {
                     // If we've reached the start again, we've completed the loop
@@ -140,46 +113,6 @@ fn find_main_loop(tiles: &[Vec<Pipe>], start: (usize, usize)) -> Vec<(usize, usi
     path
 }
 
-fn get_next_position(
-    pos: (usize, usize),
-    direction: Direction,
-    max_row: usize,
-    max_col: usize,
-) -> Option<(usize, usize)> {
-    let (row, col) = pos;
-    match direction {
-        Direction::N => {
-            if row > 0 {
-                Some((row - 1, col))
-            } else {
-                None
-            }
-        }
-        Direction::S => {
-            if row + 1 < max_row {
-                Some((row + 1, col))
-            } else {
-                None
-            }
-        }
-        Direction::E => {
-            if col + 1 < max_col {
-                Some((row, col + 1))
-            } else {
-                None
-            }
-        }
-        Direction::W => {
-            if col > 0 {
-                Some((row, col - 1))
-            } else {
-                None
-            }
-        }
-        Direction::Stop => None, // Handle the Stop case
-    }
-}
-
 fn is_pipe_connected(current_pipe: Pipe, next_pipe: Pipe, next_direction: Direction) -> bool {
     fn is_north_pipe(next_pipe: Pipe) -> bool {
         matches!(
@@ -248,82 +181,54 @@ fn is_pipe_connected(current_pipe: Pipe, next_pipe: Pipe, next_direction: Direct
     }
 }
 
-pub fn get_enclosed_by_loop(input_file: &str) -> usize {
-    let input = parse_input(input_file);
-
-    let mut start_pos: Option<(usize, usize)> = None;
-    for (row, tile_row) in input.tiles.iter().enumerate() {
-        for (col, pipe) in tile_row.iter().enumerate() {
-            if *pipe == Pipe::StartPos {
-                start_pos = Some((row, col));
-                break;
+pub fn get_enclosed_by_loop(puzzle_input: impl PuzzleInput) -> AocResult<usize> {
+    let input = parse_input(puzzle_input)?;
+
+    let start = get_start_pos(&input.tiles)?;
+    let loop_path = find_main_loop(&input.tiles, start);
+    let loop_set: HashSet<Pos> = loop_path.into_iter().collect();
+
+    let cleaned_tiles = clean_tiles_simple(&input.tiles, &loop_set, start);
+
+    // Use the ray casting approach to count inside tiles
+    let mut inside = false;
+    Ok(cleaned_tiles
+        .rows()
+        .flatten()
+        .filter(|tile| match tile {
+            Pipe::Ground => inside,
+            Pipe::VerticalNS | Pipe::NW90DegJSym | Pipe::NE90DegLSym => {
+                inside = !inside;
+                false
             }
-        }
-        if start_pos.is_some() {
-            break;
-        }
-    }
-
-    if let Some(start) = start_pos {
-        let loop_path = find_main_loop(&input.tiles, start);
-        let loop_set: std::collections::HashSet<(usize, usize)> = loop_path.into_iter().collect();
-
-        let cleaned_tiles = clean_tiles_simple(&input.tiles, &loop_set, start);
-
-        // Use the ray casting approach to count inside tiles
-        let mut inside = false;
-        cleaned_tiles
-            .iter()
-            .flatten()
-            .filter(|tile| match tile {
-                Pipe::Ground => inside,
-                Pipe::VerticalNS | Pipe::NW90DegJSym | Pipe::NE90DegLSym => {
-                    inside = !inside;
-                    false
-                }
-                _ => false,
-            })
-            .count()
-    } else {
-        panic!("Invalid start node");
-    }
+            _ => false,
+        })
+        .count())
 }
 
-fn clean_tiles_simple(
-    tiles: &[Vec<Pipe>],
-    loop_set: &std::collections::HashSet<(usize, usize)>,
-    start: (usize, usize),
-) -> Vec<Vec<Pipe>> {
-    let rows = tiles.len();
-    let cols = if rows > 0 { tiles[0].len() } else { 0 };
-    let mut cleaned_tiles: Vec<Vec<Pipe>> = vec![vec![Pipe::Ground; cols]; rows];
-
-    for (row, tile_row) in tiles.iter().enumerate() {
-        for (col, pipe) in tile_row.iter().enumerate() {
-            let pos = (row, col);
-            if loop_set.contains(&pos) {
-                if pos == start {
-                    cleaned_tiles[row][col] = infer_start_pipe(tiles, start);
-                } else {
-                    cleaned_tiles[row][col] = *pipe;
-                }
-            }
-            // Everything else stays as Ground
-        }
+fn clean_tiles_simple(tiles: &Grid<Pipe>, loop_set: &HashSet<Pos>, start: Pos) -> Grid<Pipe> {
+    let mut cleaned_tiles = Grid::new(tiles.height(), tiles.width(), Pipe::Ground);
+
+    // Everything off the loop stays as Ground
+    for &pos in loop_set {
+        cleaned_tiles[pos] = if pos == start {
+            infer_start_pipe(tiles, start)
+        } else {
+            tiles[pos]
+        };
     }
     cleaned_tiles
 }
 
-fn infer_start_pipe(tiles: &[Vec<Pipe>], start: (usize, usize)) -> Pipe {
+fn infer_start_pipe(tiles: &Grid<Pipe>, start: Pos) -> Pipe {
     let mut connections = Vec::new();
 
     // Check all four directions to see what connects to the start
     for direction in [Direction::N, Direction::S, Direction::E, Direction::W] {
-        if let Some(next_pos) = get_next_position(start, direction, tiles.len(), tiles[0].len()) {
-            let next_pipe = tiles[next_pos.0][next_pos.1];
-            if is_pipe_connected(Pipe::StartPos, next_pipe, direction) {
-                connections.push(direction);
-            }
+        if let Some(next_pos) = tiles.neighbor(start, direction)
+            && is_pipe_connected(Pipe::StartPos, tiles[next_pos], direction)
+        {
+            connections.push(direction);
         }
     }
 
@@ -339,87 +244,146 @@ fn infer_start_pipe(tiles: &[Vec<Pipe>], start: (usize, usize)) -> Pipe {
     }
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_farthest_steps(Contents(input))?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_enclosed_by_loop(Contents(input))?.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_get_farthest_steps_test01() {
-        assert_eq!(4, get_farthest_steps("input/2023/day10_test01.txt"));
+        assert_eq!(
+            4,
+            get_farthest_steps("input/2023/day10_test01.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_farthest_steps_test02() {
-        assert_eq!(8, get_farthest_steps("input/2023/day10_test02.txt"));
+        assert_eq!(
+            8,
+            get_farthest_steps("input/2023/day10_test02.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_farthest_steps() {
-        assert_eq!(6823, get_farthest_steps("input/2023/day10.txt"));
+        assert_eq!(6823, get_farthest_steps("input/2023/day10.txt").unwrap());
     }
 
     #[test]
     fn test_get_enclosed_by_loop_test01() {
-        assert_eq!(1, get_enclosed_by_loop("input/2023/day10_test01.txt"));
+        assert_eq!(
+            1,
+            get_enclosed_by_loop("input/2023/day10_test01.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_enclosed_by_loop_test02() {
-        assert_eq!(1, get_enclosed_by_loop("input/2023/day10_test02.txt"));
+        assert_eq!(
+            1,
+            get_enclosed_by_loop("input/2023/day10_test02.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_enclosed_by_loop_test03() {
-        assert_eq!(4, get_enclosed_by_loop("input/2023/day10_test03.txt"));
+        assert_eq!(
+            4,
+            get_enclosed_by_loop("input/2023/day10_test03.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_enclosed_by_loop_test04() {
-        assert_eq!(4, get_enclosed_by_loop("input/2023/day10_test04.txt"));
+        assert_eq!(
+            4,
+            get_enclosed_by_loop("input/2023/day10_test04.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_enclosed_by_loop_test05() {
-        assert_eq!(8, get_enclosed_by_loop("input/2023/day10_test05.txt"));
+        assert_eq!(
+            8,
+            get_enclosed_by_loop("input/2023/day10_test05.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_enclosed_by_loop_test06() {
-        assert_eq!(10, get_enclosed_by_loop("input/2023/day10_test06.txt"));
+        assert_eq!(
+            10,
+            get_enclosed_by_loop("input/2023/day10_test06.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_enclosed_by_loop_test07() {
-        assert_eq!(4, get_enclosed_by_loop("input/2023/day10_test07.txt"));
+        assert_eq!(
+            4,
+            get_enclosed_by_loop("input/2023/day10_test07.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_enclosed_by_loop_test08() {
-        assert_eq!(4, get_enclosed_by_loop("input/2023/day10_test08.txt"));
+        assert_eq!(
+            4,
+            get_enclosed_by_loop("input/2023/day10_test08.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_enclosed_by_loop_test09() {
-        assert_eq!(4, get_enclosed_by_loop("input/2023/day10_test09.txt"));
+        assert_eq!(
+            4,
+            get_enclosed_by_loop("input/2023/day10_test09.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_enclosed_by_loop_test10() {
-        assert_eq!(4, get_enclosed_by_loop("input/2023/day10_test10.txt"));
+        assert_eq!(
+            4,
+            get_enclosed_by_loop("input/2023/day10_test10.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_enclosed_by_loop_test11() {
-        assert_eq!(4, get_enclosed_by_loop("input/2023/day10_test11.txt"));
+        assert_eq!(
+            4,
+            get_enclosed_by_loop("input/2023/day10_test11.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_enclosed_by_loop_test12() {
-        assert_eq!(4, get_enclosed_by_loop("input/2023/day10_test12.txt"));
+        assert_eq!(
+            4,
+            get_enclosed_by_loop("input/2023/day10_test12.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_enclosed_by_loop_steps() {
-        assert_eq!(415, get_enclosed_by_loop("input/2023/day10.txt"));
+        assert_eq!(415, get_enclosed_by_loop("input/2023/day10.txt").unwrap());
     }
 }
@@ -2,10 +2,15 @@
 
 use std::collections::HashSet;
 
-use crate::utils::get_lines;
-
-pub fn get_sum_shortest_paths(input_file: &str, expansion: usize) -> usize {
-    let lines = get_lines(input_file);
+use crate::error::AocResult;
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput, get_lines};
+
+pub fn get_sum_shortest_paths(
+    puzzle_input: impl PuzzleInput,
+    expansion: usize,
+) -> AocResult<usize> {
+    let lines = get_lines(puzzle_input)?;
     let mut iter = lines.split(|e| e.is_empty());
     let image_lines = iter.next().unwrap().to_owned();
 
@@ -55,7 +60,19 @@ pub fn get_sum_shortest_paths(input_file: &str, expansion: usize) -> usize {
         }
     }
 
-    sum_shortest_paths
+    Ok(sum_shortest_paths)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_sum_shortest_paths(Contents(input), 2)?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_sum_shortest_paths(Contents(input), 1000000)?.into())
+    }
 }
 
 #[cfg(test)]
@@ -66,20 +83,23 @@ mod tests {
     fn test_get_sum_shortest_paths_test01() {
         assert_eq!(
             374,
-            get_sum_shortest_paths("input/2023/day11_test01.txt", 2)
+            get_sum_shortest_paths("input/2023/day11_test01.txt", 2).unwrap()
         );
     }
 
     #[test]
     fn test_get_sum_shortest_paths() {
-        assert_eq!(10313550, get_sum_shortest_paths("input/2023/day11.txt", 2));
+        assert_eq!(
+            10313550,
+            get_sum_shortest_paths("input/2023/day11.txt", 2).unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_shortest_paths_part02_test01() {
         assert_eq!(
             1030,
-            get_sum_shortest_paths("input/2023/day11_test01.txt", 10)
+            get_sum_shortest_paths("input/2023/day11_test01.txt", 10).unwrap()
         );
     }
 
@@ -87,7 +107,7 @@ mod tests {
     fn test_get_sum_shortest_paths_part02_test02() {
         assert_eq!(
             8410,
-            get_sum_shortest_paths("input/2023/day11_test01.txt", 100)
+            get_sum_shortest_paths("input/2023/day11_test01.txt", 100).unwrap()
         );
     }
 
@@ -95,7 +115,7 @@ mod tests {
     fn test_get_sum_shortest_paths_part02() {
         assert_eq!(
             611998089572,
-            get_sum_shortest_paths("input/2023/day11.txt", 1000000)
+            get_sum_shortest_paths("input/2023/day11.txt", 1000000).unwrap()
         );
     }
 }
@@ -2,7 +2,9 @@
 
 use std::{collections::HashMap, str::FromStr};
 
-use crate::utils::get_lines;
+use crate::error::{AocError, AocResult, parse_token};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput, get_lines};
 
 #[derive(Debug, PartialEq, Copy, Clone)]
 pub enum SpringCondition {
@@ -22,23 +24,22 @@ pub struct Input {
     pub condition_records: Vec<ConditionRecord>,
 }
 
-pub fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
+pub fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let lines = get_lines(puzzle_input)?;
 
-    let condition_records = parse_condition_records(lines);
-    Input { condition_records }
+    let condition_records = parse_condition_records(lines)?;
+    Ok(Input { condition_records })
 }
 
-fn parse_condition_records(lines: Vec<String>) -> Vec<ConditionRecord> {
+fn parse_condition_records(lines: Vec<String>) -> AocResult<Vec<ConditionRecord>> {
     let mut condition_records = vec![];
-    for line in lines {
-        let maybe_condition_record = line.parse::<ConditionRecord>();
-        match maybe_condition_record {
-            Ok(condition_record) => condition_records.push(condition_record),
-            Err(_) => todo!(),
-        }
+    for (line_idx, line) in lines.iter().enumerate() {
+        let condition_record = line
+            .parse::<ConditionRecord>()
+            .map_err(|err| err.on_line(line_idx))?;
+        condition_records.push(condition_record);
     }
-    condition_records
+    Ok(condition_records)
 }
 
 impl TryFrom<char> for SpringCondition {
@@ -55,19 +56,21 @@ impl TryFrom<char> for SpringCondition {
 }
 
 impl FromStr for ConditionRecord {
-    type Err = ();
+    type Err = AocError;
 
     fn from_str(line: &str) -> Result<Self, Self::Err> {
         if let Some((springs_str, groups_str)) = line.split_once(' ') {
             let mut spring_conditions = Vec::new();
-            for spring in springs_str.chars() {
-                let spring = spring.try_into()?;
+            for (col_idx, spring) in springs_str.chars().enumerate() {
+                let spring = spring
+                    .try_into()
+                    .map_err(|_| AocError::unexpected_char(0, col_idx, spring))?;
                 spring_conditions.push(spring);
             }
 
             let mut contiguous_groups = Vec::new();
             for group in groups_str.split(',') {
-                let group = group.parse().map_err(|_| ())?;
+                let group = parse_token(0, line, group)?;
                 contiguous_groups.push(group);
             }
 
@@ -76,14 +79,14 @@ impl FromStr for ConditionRecord {
                 contiguous_groups,
             })
         } else {
-            Err(())
+            Err(AocError::parse(0, 0, "expected springs and group sizes"))
         }
     }
 }
 
-pub fn sum_spring_arrangements(input_file: &str, unfold: bool) -> u64 {
+pub fn sum_spring_arrangements(puzzle_input: impl PuzzleInput, unfold: bool) -> AocResult<u64> {
     let mut spring_arrangements: u64 = 0;
-    let input = parse_input(input_file);
+    let input = parse_input(puzzle_input)?;
     for condition_record in input.condition_records {
         if unfold {
             spring_arrangements += condition_record
@@ -94,7 +97,7 @@ pub fn sum_spring_arrangements(input_file: &str, unfold: bool) -> u64 {
                 condition_record.count_spring_arrangements(&mut HashMap::new(), (0, 0));
         }
     }
-    spring_arrangements
+    Ok(spring_arrangements)
 }
 
 impl ConditionRecord {
@@ -170,6 +173,23 @@ impl ConditionRecord {
     }
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(sum_spring_arrangements(Contents(input), false)?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(sum_spring_arrangements(Contents(input), true)?.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -178,7 +198,7 @@ mod tests {
     fn test_sum_spring_arrangements_test01() {
         assert_eq!(
             21,
-            sum_spring_arrangements("input/2023/day12_test01.txt", false)
+            sum_spring_arrangements("input/2023/day12_test01.txt", false).unwrap()
         );
     }
 
@@ -186,7 +206,7 @@ mod tests {
     fn test_sum_spring_arrangements_test02() {
         assert_eq!(
             1,
-            sum_spring_arrangements("input/2023/day12_test02.txt", false)
+            sum_spring_arrangements("input/2023/day12_test02.txt", false).unwrap()
         );
     }
 
@@ -194,7 +214,7 @@ mod tests {
     fn test_sum_spring_arrangements_test03() {
         assert_eq!(
             4,
-            sum_spring_arrangements("input/2023/day12_test03.txt", false)
+            sum_spring_arrangements("input/2023/day12_test03.txt", false).unwrap()
         );
     }
 
@@ -202,7 +222,7 @@ mod tests {
     fn test_sum_spring_arrangements_test04() {
         assert_eq!(
             1,
-            sum_spring_arrangements("input/2023/day12_test04.txt", false)
+            sum_spring_arrangements("input/2023/day12_test04.txt", false).unwrap()
         );
     }
 
@@ -210,7 +230,7 @@ mod tests {
     fn test_sum_spring_arrangements_test05() {
         assert_eq!(
             1,
-            sum_spring_arrangements("input/2023/day12_test05.txt", false)
+            sum_spring_arrangements("input/2023/day12_test05.txt", false).unwrap()
         );
     }
 
@@ -218,7 +238,7 @@ mod tests {
     fn test_sum_spring_arrangements_test06() {
         assert_eq!(
             4,
-            sum_spring_arrangements("input/2023/day12_test06.txt", false)
+            sum_spring_arrangements("input/2023/day12_test06.txt", false).unwrap()
         );
     }
 
@@ -226,20 +246,23 @@ mod tests {
     fn test_sum_spring_arrangements_test07() {
         assert_eq!(
             10,
-            sum_spring_arrangements("input/2023/day12_test07.txt", false)
+            sum_spring_arrangements("input/2023/day12_test07.txt", false).unwrap()
         );
     }
 
     #[test]
     fn test_sum_spring_arrangements_part01() {
-        assert_eq!(6958, sum_spring_arrangements("input/2023/day12.txt", false));
+        assert_eq!(
+            6958,
+            sum_spring_arrangements("input/2023/day12.txt", false).unwrap()
+        );
     }
 
     #[test]
     fn test_sum_spring_arrangements_part02() {
         assert_eq!(
             6555315065024,
-            sum_spring_arrangements("input/2023/day12.txt", true)
+            sum_spring_arrangements("input/2023/day12.txt", true).unwrap()
         );
     }
 }
@@ -7,8 +7,11 @@ use std::{
 };
 
 use itertools::Itertools;
+use log::debug;
 
-use crate::utils::get_lines;
+use crate::error::{AocError, AocResult};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, Grid, PuzzleInput, get_lines};
 
 #[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq)]
 pub enum Ground {
@@ -17,80 +20,75 @@ pub enum Ground {
     Rocks,
 }
 
-pub fn get_column(matrix: &[Vec<Ground>], col_index: usize) -> Vec<Ground> {
-    matrix.iter().map(|row| row[col_index]).collect()
-}
-
 #[derive(Debug)]
 pub struct Input {
-    pub mirrors: Vec<Vec<Vec<Ground>>>,
+    pub mirrors: Vec<Grid<Ground>>,
 }
 
-pub fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
+pub fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let lines = get_lines(puzzle_input)?;
 
     let iter = lines.split(|e| e.is_empty());
 
-    let mut mirrors: Vec<Vec<Vec<Ground>>> = vec![];
+    let mut mirrors: Vec<Grid<Ground>> = vec![];
+    let mut first_line_idx = 0;
     for ground_strs in iter {
-        let mirror = parse_ground(ground_strs.iter().collect_vec());
+        if ground_strs.is_empty() {
+            return Err(AocError::parse(first_line_idx, 0, "empty pattern"));
+        }
+        let mirror = parse_ground(first_line_idx, ground_strs.iter().collect_vec())?;
         mirrors.push(mirror);
+        first_line_idx += ground_strs.len() + 1;
     }
-    Input { mirrors }
+    Ok(Input { mirrors })
 }
 
-pub fn parse_ground(ground_lines: Vec<&String>) -> Vec<Vec<Ground>> {
+pub fn parse_ground(first_line_idx: usize, ground_lines: Vec<&String>) -> AocResult<Grid<Ground>> {
     let mut ground = Vec::new();
-    for ground_line in ground_lines.into_iter() {
+    for (line_idx, ground_line) in (first_line_idx..).zip(ground_lines) {
         let mut ground_entries: Vec<Ground> = Vec::new();
-        for ground_entry in ground_line.chars() {
+        for (col_idx, ground_entry) in ground_line.chars().enumerate() {
             match ground_entry {
                 '.' => ground_entries.push(Ground::Ash),
                 '#' => ground_entries.push(Ground::Rocks),
-                _ => panic!("invalid ground"),
+                _ => return Err(AocError::unexpected_char(line_idx, col_idx, ground_entry)),
             }
         }
         ground.push(ground_entries)
     }
-    ground
+    Grid::from_rows(ground)
 }
 
 pub fn get_reflections(
     row_cache: &mut HashSet<(usize, usize)>,
     col_cache: &mut HashSet<(usize, usize)>,
-    mirror: &[Vec<Ground>],
+    mirror: &Grid<Ground>,
     find_different: bool,
 ) -> u32 {
+    let transposed = mirror.transpose();
+    let rows = mirror.rows().collect_vec();
+    let cols = transposed.rows().collect_vec();
+
     let mut row_reflection_lens: HashMap<usize, usize> = HashMap::new();
     let mut col_reflection_lens: HashMap<usize, usize> = HashMap::new();
 
     let mut reflections = 0;
 
     let mut row_reflections: Vec<usize> = vec![];
-    for (row_index, (row_a, row_b)) in mirror.iter().tuple_windows().enumerate() {
-        if find_different
-            && off_by_one_or_equal(row_a.iter().collect_vec(), row_b.iter().collect_vec())
-        {
+    for (row_index, (row_a, row_b)) in rows.iter().tuple_windows().enumerate() {
+        if find_different && off_by_one_or_equal(row_a, row_b) {
             row_reflections.push(row_index);
         }
 
-        if !find_different && row_a.iter().collect_vec() == row_b.iter().collect_vec() {
+        if !find_different && row_a == row_b {
             row_reflections.push(row_index);
         }
     }
-    println!("row_reflections: {:?}", row_reflections);
+    debug!("row_reflections: {:?}", row_reflections);
 
     let mut col_reflections: Vec<usize> = vec![];
-    for col_index in 0..mirror[0].len() {
-        let col_a = get_column(mirror, col_index);
-        let col_b = if col_index + 1 < mirror[0].len() {
-            get_column(mirror, col_index + 1)
-        } else {
-            continue;
-        };
-        if find_different
-            && off_by_one_or_equal(col_a.iter().collect_vec(), col_b.iter().collect_vec())
-        {
+    for (col_index, (col_a, col_b)) in cols.iter().tuple_windows().enumerate() {
+        if find_different && off_by_one_or_equal(col_a, col_b) {
             col_reflections.push(col_index);
         }
 
@@ -98,25 +96,18 @@ pub fn get_reflections(
             col_reflections.push(col_index);
         }
     }
-    println!("col_reflections: {:?}", col_reflections);
+    debug!("col_reflections: {:?}", col_reflections);
 
     for row_reflection_index in row_reflections {
         let mut row_reflection_len = 0;
         let up_range = (0..row_reflection_index + 1).rev();
-        let down_range = row_reflection_index + 1..mirror.len();
+        let down_range = row_reflection_index + 1..rows.len();
         for (row_a, row_b) in zip(up_range.clone(), down_range.clone()) {
-            if find_different
-                && off_by_one_or_equal(
-                    mirror[row_a].iter().collect_vec(),
-                    mirror[row_b].iter().collect_vec(),
-                )
-            {
+            if find_different && off_by_one_or_equal(rows[row_a], rows[row_b]) {
                 row_reflection_len += 1;
             }
 
-            if !find_different
-                && mirror[row_a].iter().collect_vec() == mirror[row_b].iter().collect_vec()
-            {
+            if !find_different && rows[row_a] == rows[row_b] {
                 row_reflection_len += 1;
             }
         }
@@ -130,18 +121,13 @@ pub fn get_reflections(
     for col_reflection_index in col_reflections {
         let mut col_reflection_len = 0;
         let left_range = (0..col_reflection_index + 1).rev();
-        let right_range = col_reflection_index + 1..mirror[0].len();
+        let right_range = col_reflection_index + 1..cols.len();
         for (col_a, col_b) in zip(left_range.clone(), right_range.clone()) {
-            if find_different
-                && off_by_one_or_equal(
-                    get_column(mirror, col_a).iter().collect_vec(),
-                    get_column(mirror, col_b).iter().collect_vec(),
-                )
-            {
+            if find_different && off_by_one_or_equal(cols[col_a], cols[col_b]) {
                 col_reflection_len += 1;
             }
 
-            if !find_different && get_column(mirror, col_a) == get_column(mirror, col_b) {
+            if !find_different && cols[col_a] == cols[col_b] {
                 col_reflection_len += 1;
             }
         }
@@ -152,22 +138,22 @@ pub fn get_reflections(
         }
     }
 
-    println!("row_reflection_lens: {:?}", row_reflection_lens);
-    println!("col_reflection_lens: {:?}", col_reflection_lens);
+    debug!("row_reflection_lens: {:?}", row_reflection_lens);
+    debug!("col_reflection_lens: {:?}", col_reflection_lens);
 
     let max_row_reflection_len = *(row_reflection_lens.keys().clone().max().unwrap_or(&0));
     let max_col_reflection_len = *(col_reflection_lens.keys().clone().max().unwrap_or(&0));
 
     if max_col_reflection_len > max_row_reflection_len && !col_reflection_lens.is_empty() {
         if let Some(num_left_cols) = col_reflection_lens.get(&max_col_reflection_len) {
-            println!("num_left_cols: {:?}", num_left_cols + 1);
+            debug!("num_left_cols: {:?}", num_left_cols + 1);
             reflections = (num_left_cols + 1) as u32;
             col_cache.insert((max_col_reflection_len, *num_left_cols));
         }
     } else if !row_reflection_lens.is_empty()
         && let Some(num_above_rows) = row_reflection_lens.get(&max_row_reflection_len)
     {
-        println!("num_above_rows: {:?}", num_above_rows + 1);
+        debug!("num_above_rows: {:?}", num_above_rows + 1);
         reflections = 100 * (num_above_rows + 1) as u32;
         row_cache.insert((max_row_reflection_len, *num_above_rows));
     }
@@ -175,12 +161,12 @@ pub fn get_reflections(
     reflections
 }
 
-pub fn get_sum_reflections(input_file: &str, find_different: bool) -> u32 {
+pub fn get_sum_reflections(puzzle_input: impl PuzzleInput, find_different: bool) -> AocResult<u32> {
     let mut sum_reflections: u32 = 0;
 
-    let input = parse_input(input_file);
+    let input = parse_input(puzzle_input)?;
     for mirror in input.mirrors {
-        println!("***** checking mirror *****");
+        debug!("***** checking mirror *****");
 
         if find_different {
             let mut row_cache: HashSet<(usize, usize)> = HashSet::new();
@@ -192,10 +178,10 @@ pub fn get_sum_reflections(input_file: &str, find_different: bool) -> u32 {
                 get_reflections(&mut HashSet::new(), &mut HashSet::new(), &mirror, false);
         }
     }
-    sum_reflections
+    Ok(sum_reflections)
 }
 
-fn off_by_one_or_equal(vec_a: Vec<&Ground>, vec_b: Vec<&Ground>) -> bool {
+fn off_by_one_or_equal(vec_a: &[Ground], vec_b: &[Ground]) -> bool {
     let mut diff_count = 0;
     for (a, b) in zip(vec_a, vec_b) {
         if a != b {
@@ -205,20 +191,40 @@ fn off_by_one_or_equal(vec_a: Vec<&Ground>, vec_b: Vec<&Ground>) -> bool {
     diff_count == 1 || diff_count == 0
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_sum_reflections(Contents(input), false)?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_sum_reflections(Contents(input), true)?.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_get_sum_reflections_test01() {
-        assert_eq!(5, get_sum_reflections("input/2023/day13_test01.txt", false));
+        assert_eq!(
+            5,
+            get_sum_reflections("input/2023/day13_test01.txt", false).unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_reflections_test02() {
         assert_eq!(
             400,
-            get_sum_reflections("input/2023/day13_test02.txt", false)
+            get_sum_reflections("input/2023/day13_test02.txt", false).unwrap()
         );
     }
 
@@ -226,38 +232,47 @@ mod tests {
     fn test_get_sum_reflections_test03() {
         assert_eq!(
             405,
-            get_sum_reflections("input/2023/day13_test03.txt", false)
+            get_sum_reflections("input/2023/day13_test03.txt", false).unwrap()
         );
     }
 
     #[test]
     fn test_get_sum_reflections_test04() {
-        assert_eq!(3, get_sum_reflections("input/2023/day13_test04.txt", false));
+        assert_eq!(
+            3,
+            get_sum_reflections("input/2023/day13_test04.txt", false).unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_reflections_test05() {
         assert_eq!(
             100,
-            get_sum_reflections("input/2023/day13_test05.txt", false)
+            get_sum_reflections("input/2023/day13_test05.txt", false).unwrap()
         );
     }
 
     #[test]
     fn test_get_sum_reflections_test06() {
-        assert_eq!(1, get_sum_reflections("input/2023/day13_test06.txt", false));
+        assert_eq!(
+            1,
+            get_sum_reflections("input/2023/day13_test06.txt", false).unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_reflections_part01() {
-        assert_eq!(35210, get_sum_reflections("input/2023/day13.txt", false));
+        assert_eq!(
+            35210,
+            get_sum_reflections("input/2023/day13.txt", false).unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_reflections_part02_test01() {
         assert_eq!(
             300,
-            get_sum_reflections("input/2023/day13_test01.txt", true)
+            get_sum_reflections("input/2023/day13_test01.txt", true).unwrap()
         );
     }
 
@@ -265,7 +280,7 @@ mod tests {
     fn test_get_sum_reflections_part02_test02() {
         assert_eq!(
             100,
-            get_sum_reflections("input/2023/day13_test02.txt", true)
+            get_sum_reflections("input/2023/day13_test02.txt", true).unwrap()
         );
     }
 
@@ -273,30 +288,39 @@ mod tests {
     fn test_get_sum_reflections_part02_test03() {
         assert_eq!(
             400,
-            get_sum_reflections("input/2023/day13_test03.txt", true)
+            get_sum_reflections("input/2023/day13_test03.txt", true).unwrap()
         );
     }
 
     #[test]
     fn test_get_sum_reflections_part02_test04() {
-        assert_eq!(14, get_sum_reflections("input/2023/day13_test04.txt", true));
+        assert_eq!(
+            14,
+            get_sum_reflections("input/2023/day13_test04.txt", true).unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_reflections_part02_test05() {
-        assert_eq!(12, get_sum_reflections("input/2023/day13_test05.txt", true));
+        assert_eq!(
+            12,
+            get_sum_reflections("input/2023/day13_test05.txt", true).unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_reflections_part02_test06() {
         assert_eq!(
             1400,
-            get_sum_reflections("input/2023/day13_test06.txt", true)
+            get_sum_reflections("input/2023/day13_test06.txt", true).unwrap()
         );
     }
 
     #[test]
     fn test_get_sum_reflections_part02() {
-        assert_eq!(31974, get_sum_reflections("input/2023/day13.txt", true));
+        assert_eq!(
+            31974,
+            get_sum_reflections("input/2023/day13.txt", true).unwrap()
+        );
     }
 }
@@ -2,7 +2,11 @@
 
 use std::collections::HashMap;
 
-use crate::utils::{Direction, get_lines};
+use log::{Level, debug, log_enabled};
+
+use crate::error::{AocError, AocResult};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, Direction, Grid, PuzzleInput, get_lines};
 
 #[derive(Debug, Default, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
 #[repr(u8)]
@@ -28,47 +32,46 @@ impl TryFrom<u8> for Rock {
 
 #[derive(Debug)]
 pub struct Input {
-    pub rocks: Vec<Vec<Rock>>,
+    pub rocks: Grid<Rock>,
 }
 
-pub fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
-
-    let mut iter = lines.split(|e| e.is_empty());
+pub fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let lines = get_lines(puzzle_input)?;
 
-    Input {
-        rocks: parse_rocks(iter.next().unwrap().to_owned()),
+    let rock_lines = lines.split(|e| e.is_empty()).next().unwrap_or_default();
+    if rock_lines.is_empty() {
+        return Err(AocError::invalid("no rocks in the input"));
     }
+
+    Ok(Input {
+        rocks: parse_rocks(rock_lines)?,
+    })
 }
 
-pub fn parse_rocks(rock_lines: Vec<String>) -> Vec<Vec<Rock>> {
-    let mut rocks = Vec::new();
-    for rock_line in rock_lines.into_iter() {
-        let mut rock_entries: Vec<Rock> = Vec::new();
-        for rock_entry in rock_line.chars() {
-            match Rock::try_from(rock_entry as u8) {
-                Ok(rock) => rock_entries.push(rock),
-                Err(_) => panic!("Invalid rock"),
-            }
-        }
-        rocks.push(rock_entries)
-    }
-    rocks
+pub fn parse_rocks(rock_lines: &[String]) -> AocResult<Grid<Rock>> {
+    Grid::from_lines(rock_lines, |c| Rock::try_from(u8::try_from(c).ok()?).ok())
 }
 
-pub fn print_rocks(rocks: &[Vec<Rock>]) {
-    println!("rocks:");
-    for rock_row in rocks.iter() {
-        for rock in rock_row {
-            print!("{:#}", *rock as u8 as char);
-        }
-        println!();
+pub fn print_rocks(rocks: &Grid<Rock>) {
+    if !log_enabled!(Level::Debug) {
+        return;
+    }
+
+    debug!("rocks:");
+    for rock_row in rocks.rows() {
+        debug!(
+            "{}",
+            rock_row
+                .iter()
+                .map(|rock| *rock as u8 as char)
+                .collect::<String>()
+        );
     }
 }
 
-pub fn rocks_to_str(rocks: &[Vec<Rock>]) -> String {
+pub fn rocks_to_str(rocks: &Grid<Rock>) -> String {
     let mut str: String = String::new();
-    for rock_row in rocks.iter() {
+    for rock_row in rocks.rows() {
         for rock in rock_row {
             str += &(*rock as u8 as char).to_string();
         }
@@ -76,10 +79,15 @@ pub fn rocks_to_str(rocks: &[Vec<Rock>]) -> String {
     str
 }
 
-pub fn get_total_load(input_file: &str, dir: Direction, pre_cycle_len: u32, part_two: bool) -> u32 {
+pub fn get_total_load(
+    puzzle_input: impl PuzzleInput,
+    dir: Direction,
+    pre_cycle_len: u32,
+    part_two: bool,
+) -> AocResult<u32> {
     let mut total_load: u32 = 0;
 
-    let input = parse_input(input_file);
+    let input = parse_input(puzzle_input)?;
 
     if part_two {
         let mut rocks_states: HashMap<String, u32> = HashMap::new();
@@ -120,106 +128,25 @@ pub fn get_total_load(input_file: &str, dir: Direction, pre_cycle_len: u32, part
         total_load += load;
     }
 
-    total_load
+    Ok(total_load)
 }
 
-fn iter_cols<T>(matrix: &[Vec<T>]) -> Vec<Vec<T>>
-where
-    T: Clone,
-{
-    let mut cols = vec![vec![]; matrix[0].len()];
-    for row in matrix.iter() {
-        for (i, item) in row.iter().enumerate() {
-            cols[i].push(item.clone());
-        }
-    }
-    cols
-}
-
-fn process_cycle(rocks: &[Vec<Rock>], dir: Direction) -> (Vec<Vec<Rock>>, u32) {
-    let mut rocks_ord: Vec<Vec<Rock>> = Vec::new();
-
-    match dir {
+fn process_cycle(rocks: &Grid<Rock>, dir: Direction) -> (Grid<Rock>, u32) {
+    let rocks_ord = match dir {
+        // For North/South, we tilt the columns as rows of the transpose
         Direction::N | Direction::S => {
-            // For North/South, we process columns
-            let cols = iter_cols(rocks);
-            for col in cols {
-                let mut rock_indices: Vec<usize> = vec![];
-                for (pos, rock) in col.iter().enumerate() {
-                    if *rock == Rock::Cube {
-                        rock_indices.push(pos);
-                    }
-                }
-
-                let col_vec: Vec<Rock> = col.to_vec();
-                let split_col_vecs: Vec<Vec<Rock>> = col_vec
-                    .split(|x| *x == Rock::Cube)
-                    .map(|x| x.to_vec())
-                    .collect();
-
-                let mut col_ord: Vec<Rock> = vec![];
-                for mut split_col_vec in split_col_vecs {
-                    split_col_vec.sort();
-                    if dir == Direction::N {
-                        split_col_vec.reverse();
-                    }
-                    col_ord.extend(split_col_vec);
-                }
-
-                for rock_index in rock_indices {
-                    col_ord.insert(rock_index, Rock::Cube);
-                }
-
-                rocks_ord.push(col_ord);
-            }
-
-            // Transpose back to rows
-            rocks_ord = iter_cols(&rocks_ord);
+            tilt_rows(&rocks.transpose(), dir == Direction::N).transpose()
         }
-        Direction::E | Direction::W => {
-            // For East/West, we process rows directly
-            for row in rocks.iter() {
-                let mut rock_indices: Vec<usize> = vec![];
-                for (pos, rock) in row.iter().enumerate() {
-                    if *rock == Rock::Cube {
-                        rock_indices.push(pos);
-                    }
-                }
-
-                let row_vec: Vec<Rock> = row.to_vec();
-                let split_row_vecs: Vec<Vec<Rock>> = row_vec
-                    .split(|x| *x == Rock::Cube)
-                    .map(|x| x.to_vec())
-                    .collect();
-
-                let mut row_ord: Vec<Rock> = vec![];
-                for mut split_row_vec in split_row_vecs {
-                    split_row_vec.sort();
-                    if dir == Direction::W {
-                        split_row_vec.reverse();
-                    }
-                    row_ord.extend(split_row_vec);
-                }
-
-                for rock_index in rock_indices {
-                    row_ord.insert(rock_index, Rock::Cube);
-                }
-
-                rocks_ord.push(row_ord);
-            }
-        }
-        Direction::Stop => {
-            // Handle Stop direction
-            rocks_ord = rocks.to_vec();
-        }
-    }
+        Direction::E | Direction::W => tilt_rows(rocks, dir == Direction::W),
+        Direction::Stop => rocks.clone(),
+    };
 
     let mut total_load: u32 = 0;
 
-    for (row, row_vec) in rocks_ord.iter().enumerate() {
+    for (row, row_vec) in rocks_ord.rows().enumerate() {
         for entry in row_vec.iter() {
             if let Rock::Rounded = entry {
-                let load = rocks_ord.len() as u32 - row as u32;
+                let load = rocks_ord.height() as u32 - row as u32;
                 total_load += load;
             }
         }
@@ -228,6 +155,42 @@ fn process_cycle(rocks: &[Vec<Rock>], dir: Direction) -> (Vec<Vec<Rock>>, u32) {
     (rocks_ord, total_load)
 }
 
+// Rolls the rounded rocks in every row towards the start of the row when
+// `to_start` is set, and towards its end otherwise. Cube rocks stay put.
+fn tilt_rows(rocks: &Grid<Rock>, to_start: bool) -> Grid<Rock> {
+    let mut tilted = rocks.clone();
+    for (row_idx, row) in rocks.rows().enumerate() {
+        let mut row_ord = row.to_vec();
+        for split_row in row_ord.split_mut(|x| *x == Rock::Cube) {
+            split_row.sort();
+            if to_start {
+                split_row.reverse();
+            }
+        }
+        for (col_idx, rock) in row_ord.into_iter().enumerate() {
+            tilted[(row_idx, col_idx)] = rock;
+        }
+    }
+    tilted
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_total_load(Contents(input), Direction::N, 0, false)?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_total_load(Contents(input), Direction::N, 1000, true)?.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -236,7 +199,7 @@ mod tests {
     fn test_get_total_load_part01_test01() {
         assert_eq!(
             136,
-            get_total_load("input/2023/day14_test01.txt", Direction::N, 0, false)
+            get_total_load("input/2023/day14_test01.txt", Direction::N, 0, false).unwrap()
         );
     }
 
@@ -244,7 +207,7 @@ mod tests {
     fn test_get_total_load_part01_test02() {
         assert_eq!(
             66,
-            get_total_load("input/2023/day14_test01.txt", Direction::S, 0, false)
+            get_total_load("input/2023/day14_test01.txt", Direction::S, 0, false).unwrap()
         );
     }
 
@@ -252,7 +215,7 @@ mod tests {
     fn test_get_total_load_part01_test03() {
         assert_eq!(
             104,
-            get_total_load("input/2023/day14_test01.txt", Direction::E, 0, false)
+            get_total_load("input/2023/day14_test01.txt", Direction::E, 0, false).unwrap()
         );
     }
 
@@ -260,7 +223,7 @@ mod tests {
     fn test_get_total_load_part01_test04() {
         assert_eq!(
             104,
-            get_total_load("input/2023/day14_test01.txt", Direction::W, 0, false)
+            get_total_load("input/2023/day14_test01.txt", Direction::W, 0, false).unwrap()
         );
     }
 
@@ -268,7 +231,7 @@ mod tests {
     fn test_get_sum_reflections_part01() {
         assert_eq!(
             109345,
-            get_total_load("input/2023/day14.txt", Direction::N, 0, false)
+            get_total_load("input/2023/day14.txt", Direction::N, 0, false).unwrap()
         );
     }
 
@@ -276,7 +239,7 @@ mod tests {
     fn test_get_total_load_part02_test01() {
         assert_eq!(
             64,
-            get_total_load("input/2023/day14_test01.txt", Direction::N, 50, true)
+            get_total_load("input/2023/day14_test01.txt", Direction::N, 50, true).unwrap()
         );
     }
 
@@ -284,7 +247,7 @@ mod tests {
     fn test_get_sum_reflections_part02() {
         assert_eq!(
             112452,
-            get_total_load("input/2023/day14.txt", Direction::N, 1000, true)
+            get_total_load("input/2023/day14.txt", Direction::N, 1000, true).unwrap()
         );
     }
 }
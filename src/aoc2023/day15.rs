@@ -5,7 +5,9 @@ use std::collections::HashMap;
 use csv::ReaderBuilder;
 use regex::Regex;
 
-use crate::utils::get_lines;
+use crate::error::{AocError, AocResult};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput, get_lines};
 
 #[derive(Debug)]
 pub struct Input {
@@ -31,11 +33,11 @@ pub struct Slot {
     pub focal: u32,
 }
 
-pub fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
+pub fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let lines = get_lines(puzzle_input)?;
 
     let mut steps: Vec<String> = vec![];
-    for line in lines.into_iter() {
+    for (line_idx, line) in lines.iter().enumerate() {
         let mut reader = ReaderBuilder::new()
             .has_headers(false)
             .from_reader(line.as_bytes());
@@ -46,23 +48,23 @@ pub fn parse_input(input_file: &str) -> Input {
                         steps.push(record.to_owned());
                     }
                 }
-                Err(_) => panic!("invalid step"),
+                Err(why) => return Err(AocError::parse(line_idx, 0, why.to_string())),
             }
         }
     }
 
-    Input { steps }
+    Ok(Input { steps })
 }
 
-pub fn get_sum_steps(input_file: &str) -> u32 {
-    let input = parse_input(input_file);
+pub fn get_sum_steps(puzzle_input: impl PuzzleInput) -> AocResult<u32> {
+    let input = parse_input(puzzle_input)?;
 
     let mut sum_steps = 0;
 
     for step in input.steps {
         sum_steps += compute_hash(&step);
     }
-    sum_steps
+    Ok(sum_steps)
 }
 
 pub fn compute_hash(step: &str) -> u32 {
@@ -80,12 +82,12 @@ pub fn compute_hash(step: &str) -> u32 {
     current_value
 }
 
-pub fn get_focusing_power(input_file: &str) -> u32 {
+pub fn get_focusing_power(puzzle_input: impl PuzzleInput) -> AocResult<u32> {
     lazy_static! {
         static ref RE_HASHMAP: Regex =
             Regex::new(r"(?P<label>[a-z]+)(?P<operation>-|={1})(?P<focal>\d*)").unwrap();
     }
-    let input = parse_input(input_file);
+    let input = parse_input(puzzle_input)?;
 
     let mut steps: Vec<Step> = vec![];
     for step in input.steps {
@@ -96,14 +98,14 @@ pub fn get_focusing_power(input_file: &str) -> u32 {
             let focal_str = caps_hashmap["focal"].to_string();
 
             let operation = match operation_str.as_str() {
-                "-" => Some(Operation::Dash),
-                "=" => Some(Operation::Equals),
-                _ => None,
-            }
-            .unwrap();
+                "-" => Operation::Dash,
+                _ => Operation::Equals,
+            };
 
             let focal = match operation {
-                Operation::Equals => Some(focal_str.parse().unwrap()),
+                Operation::Equals => Some(focal_str.parse().map_err(|_| {
+                    AocError::invalid(format!("step {step:?} has no valid focal length"))
+                })?),
                 Operation::Dash => None,
             };
 
@@ -169,7 +171,24 @@ pub fn get_focusing_power(input_file: &str) -> u32 {
         }
     }
 
-    focusing_power
+    Ok(focusing_power)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_sum_steps(Contents(input))?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_focusing_power(Contents(input))?.into())
+    }
 }
 
 #[cfg(test)]
@@ -238,21 +257,24 @@ mod tests {
 
     #[test]
     fn test_get_num_steps_test01() {
-        assert_eq!(1320, get_sum_steps("input/2023/day15_test01.txt"));
+        assert_eq!(1320, get_sum_steps("input/2023/day15_test01.txt").unwrap());
     }
 
     #[test]
     fn test_get_sum_steps() {
-        assert_eq!(507666, get_sum_steps("input/2023/day15.txt"));
+        assert_eq!(507666, get_sum_steps("input/2023/day15.txt").unwrap());
     }
 
     #[test]
     fn test_get_focusing_power_test01() {
-        assert_eq!(145, get_focusing_power("input/2023/day15_test01.txt"));
+        assert_eq!(
+            145,
+            get_focusing_power("input/2023/day15_test01.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_focusing_power() {
-        assert_eq!(233537, get_focusing_power("input/2023/day15.txt"));
+        assert_eq!(233537, get_focusing_power("input/2023/day15.txt").unwrap());
     }
 }
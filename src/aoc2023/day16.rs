@@ -2,11 +2,15 @@
 
 use std::collections::HashSet;
 
-use crate::utils::{Direction, get_lines};
+use log::debug;
+
+use crate::error::{AocError, AocResult};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, Direction, Grid, Pos, PuzzleInput, get_lines};
 
 #[derive(Debug)]
 pub struct Input {
-    pub tiles: Vec<Vec<Tile>>,
+    pub tiles: Grid<Tile>,
 }
 
 #[derive(Debug, PartialEq, Eq, Default, Copy, Clone)]
@@ -36,52 +40,44 @@ impl TryFrom<u8> for Tile {
     }
 }
 
-pub fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
-
-    let mut iter = lines.split(|e| e.is_empty());
+pub fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let lines = get_lines(puzzle_input)?;
 
-    Input {
-        tiles: parse_tiles(iter.next().unwrap().to_owned()),
+    let tiles_lines = lines.split(|e| e.is_empty()).next().unwrap_or_default();
+    if tiles_lines.is_empty() {
+        return Err(AocError::invalid("no tiles in the input"));
     }
+
+    Ok(Input {
+        tiles: parse_tiles(tiles_lines)?,
+    })
 }
 
-pub fn parse_tiles(tiles_lines: Vec<String>) -> Vec<Vec<Tile>> {
-    let mut tiles: Vec<Vec<Tile>> = Vec::new();
-    for tiles_line in tiles_lines.into_iter() {
-        let mut tiles_entries: Vec<Tile> = Vec::new();
-        for tiles_entry in tiles_line.chars() {
-            match Tile::try_from(tiles_entry as u8) {
-                Ok(tile) => tiles_entries.push(tile),
-                Err(_) => panic!("Invalid tile"),
-            }
-        }
-        tiles.push(tiles_entries)
-    }
-    tiles
+pub fn parse_tiles(tiles_lines: &[String]) -> AocResult<Grid<Tile>> {
+    Grid::from_lines(tiles_lines, |c| Tile::try_from(u8::try_from(c).ok()?).ok())
 }
 
 pub struct Raytracer {
-    tiles: Vec<Vec<Tile>>,
+    tiles: Grid<Tile>,
 }
 
 impl Raytracer {
     pub fn raytrace(
-        &mut self,
+        &self,
         index: &mut usize,
-        (mut row, mut col): (usize, usize),
+        mut pos: Pos,
         mut dir: Direction,
         visited_tiles: &mut HashSet<(usize, usize, Direction)>,
     ) {
         *index += 1;
         loop {
-            if !visited_tiles.contains(&(row, col, dir)) {
-                visited_tiles.insert((row, col, dir));
+            if !visited_tiles.contains(&(pos.0, pos.1, dir)) {
+                visited_tiles.insert((pos.0, pos.1, dir));
             } else {
                 break;
             }
 
-            if let Some(tile) = self.tiles.get(row).and_then(|r| r.get(col)) {
+            if let Some(&tile) = self.tiles.get(pos) {
                 match tile {
                     Tile::MirrorForward => match dir {
                         Direction::N => dir = Direction::E,
@@ -100,27 +96,19 @@ impl Raytracer {
                     Tile::SplitterVert => match dir {
                         Direction::N | Direction::S => (),
                         Direction::E | Direction::W => {
-                            let mut rt = Raytracer {
-                                tiles: self.tiles.clone(),
-                            };
-
                             dir = Direction::Stop;
 
-                            rt.raytrace(index, (row, col), Direction::N, visited_tiles);
-                            rt.raytrace(index, (row, col), Direction::S, visited_tiles);
+                            self.raytrace(index, pos, Direction::N, visited_tiles);
+                            self.raytrace(index, pos, Direction::S, visited_tiles);
                         }
                         Direction::Stop => (),
                     },
                     Tile::SplitterHoriz => match dir {
                         Direction::N | Direction::S => {
-                            let mut rt = Raytracer {
-                                tiles: self.tiles.clone(),
-                            };
-
                             dir = Direction::Stop;
 
-                            rt.raytrace(index, (row, col), Direction::E, visited_tiles);
-                            rt.raytrace(index, (row, col), Direction::W, visited_tiles);
+                            self.raytrace(index, pos, Direction::E, visited_tiles);
+                            self.raytrace(index, pos, Direction::W, visited_tiles);
                         }
                         Direction::E | Direction::W => (),
                         Direction::Stop => (),
@@ -129,95 +117,68 @@ impl Raytracer {
                 }
             }
 
-            match dir {
-                Direction::N => {
-                    if row > 0 {
-                        row -= 1;
-                    } else {
-                        break;
-                    }
-                }
-                Direction::S => {
-                    if row < self.tiles.len() - 1 {
-                        row += 1;
-                    } else {
-                        break;
-                    }
-                }
-                Direction::E => {
-                    if col < self.tiles[row].len() - 1 {
-                        col += 1;
-                    } else {
-                        break;
-                    }
-                }
-                Direction::W => {
-                    if col > 0 {
-                        col -= 1;
-                    } else {
-                        break;
-                    }
-                }
-                Direction::Stop => (),
+            match self.tiles.neighbor(pos, dir) {
+                Some(next_pos) => pos = next_pos,
+                None => break,
             }
         }
     }
 }
 
-pub fn get_energized_tiles(input_file: &str) -> usize {
-    let input = parse_input(input_file);
+pub fn get_energized_tiles(puzzle_input: impl PuzzleInput) -> AocResult<usize> {
+    let input = parse_input(puzzle_input)?;
 
-    get_energized_tiles_count((0, 0), Direction::E, &input.tiles)
+    Ok(get_energized_tiles_count(
+        (0, 0),
+        Direction::E,
+        &input.tiles,
+    ))
 }
 
-pub fn print_tiles(tiles: &[Vec<Tile>]) {
-    for tile_row in tiles.iter() {
-        for tile in tile_row {
-            print!("{:#}", *tile as u8 as char);
-        }
-        println!();
+pub fn print_tiles(tiles: &Grid<Tile>) {
+    for tile_row in tiles.rows() {
+        debug!(
+            "{}",
+            tile_row
+                .iter()
+                .map(|tile| *tile as u8 as char)
+                .collect::<String>()
+        );
     }
 }
 
-pub fn get_energized_tiles_count(
-    pos: (usize, usize),
-    dir: Direction,
-    tiles: &[Vec<Tile>],
-) -> usize {
+pub fn get_energized_tiles_count(pos: Pos, dir: Direction, tiles: &Grid<Tile>) -> usize {
     let mut visited_tiles: HashSet<(usize, usize, Direction)> = HashSet::new();
     let mut rt = Raytracer {
-        tiles: tiles.to_vec(),
+        tiles: tiles.clone(),
     };
 
     let mut index = 0;
     rt.raytrace(&mut index, pos, dir, &mut visited_tiles);
 
-    for (row, col, _) in &visited_tiles {
-        if let Some(energized_tile) = rt.tiles.get_mut(*row).and_then(|r| r.get_mut(*col)) {
+    for &(row, col, _) in &visited_tiles {
+        if let Some(energized_tile) = rt.tiles.get_mut((row, col)) {
             *energized_tile = Tile::Energized;
         }
     }
 
-    let mut energized_tile_count = 0;
-    for tile in rt.tiles.iter() {
-        for t in tile {
-            if *t == Tile::Energized {
-                energized_tile_count += 1;
-            }
-        }
-    }
+    let energized_tile_count = rt
+        .tiles
+        .iter()
+        .filter(|(_, tile)| **tile == Tile::Energized)
+        .count();
 
     //print_tiles(&rt.tiles);
 
     energized_tile_count
 }
 
-pub fn get_max_energized_tiles(input_file: &str) -> usize {
-    let input = parse_input(input_file);
+pub fn get_max_energized_tiles(puzzle_input: impl PuzzleInput) -> AocResult<usize> {
+    let input = parse_input(puzzle_input)?;
 
     let mut energized_tiles_vec: Vec<usize> = vec![];
 
-    for left_index in 0..input.tiles.len() {
+    for left_index in 0..input.tiles.height() {
         energized_tiles_vec.push(get_energized_tiles_count(
             (left_index, 0),
             Direction::E,
@@ -225,15 +186,15 @@ pub fn get_max_energized_tiles(input_file: &str) -> usize {
         ));
     }
 
-    for right_index in 0..input.tiles.len() {
+    for right_index in 0..input.tiles.height() {
         energized_tiles_vec.push(get_energized_tiles_count(
-            (right_index, input.tiles[0].len() - 1),
+            (right_index, input.tiles.width() - 1),
             Direction::W,
             &input.tiles,
         ));
     }
 
-    for top_index in 0..input.tiles[0].len() {
+    for top_index in 0..input.tiles.width() {
         energized_tiles_vec.push(get_energized_tiles_count(
             (0, top_index),
             Direction::S,
@@ -241,15 +202,35 @@ pub fn get_max_energized_tiles(input_file: &str) -> usize {
         ));
     }
 
-    for bottom_index in 0..input.tiles[0].len() {
+    for bottom_index in 0..input.tiles.width() {
         energized_tiles_vec.push(get_energized_tiles_count(
-            (input.tiles.len() - 1, bottom_index),
+            (input.tiles.height() - 1, bottom_index),
             Direction::N,
             &input.tiles,
         ));
     }
 
-    *energized_tiles_vec.iter().max().unwrap()
+    energized_tiles_vec
+        .into_iter()
+        .max()
+        .ok_or(AocError::invalid("no tiles in the input"))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_energized_tiles(Contents(input))?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_max_energized_tiles(Contents(input))?.into())
+    }
 }
 
 #[cfg(test)]
@@ -258,41 +239,62 @@ mod tests {
 
     #[test]
     fn test_get_energized_tiles_test01() {
-        assert_eq!(46, get_energized_tiles("input/2023/day16_test01.txt"));
+        assert_eq!(
+            46,
+            get_energized_tiles("input/2023/day16_test01.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_energized_tiles_test02() {
-        assert_eq!(9, get_energized_tiles("input/2023/day16_test02.txt"));
+        assert_eq!(
+            9,
+            get_energized_tiles("input/2023/day16_test02.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_energized_tiles_test03() {
-        assert_eq!(18, get_energized_tiles("input/2023/day16_test03.txt"));
+        assert_eq!(
+            18,
+            get_energized_tiles("input/2023/day16_test03.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_energized_tiles_test04() {
-        assert_eq!(16, get_energized_tiles("input/2023/day16_test04.txt"));
+        assert_eq!(
+            16,
+            get_energized_tiles("input/2023/day16_test04.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_energized_tiles_test05() {
-        assert_eq!(41, get_energized_tiles("input/2023/day16_test05.txt"));
+        assert_eq!(
+            41,
+            get_energized_tiles("input/2023/day16_test05.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_steps() {
-        assert_eq!(8539, get_energized_tiles("input/2023/day16.txt"));
+        assert_eq!(8539, get_energized_tiles("input/2023/day16.txt").unwrap());
     }
 
     #[test]
     fn test_get_max_energized_tiles_test01() {
-        assert_eq!(51, get_max_energized_tiles("input/2023/day16_test01.txt"));
+        assert_eq!(
+            51,
+            get_max_energized_tiles("input/2023/day16_test01.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_max_energized_tiles() {
-        assert_eq!(8674, get_max_energized_tiles("input/2023/day16.txt"));
+        assert_eq!(
+            8674,
+            get_max_energized_tiles("input/2023/day16.txt").unwrap()
+        );
     }
 }
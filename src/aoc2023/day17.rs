@@ -1,14 +1,14 @@
 // https://adventofcode.com/2023/day/17
 
-use std::{collections::BinaryHeap, fs};
+use std::collections::BinaryHeap;
 
-use itertools::Itertools;
-
-use crate::utils::Direction;
+use crate::error::AocResult;
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, Direction, Grid, Pos, PuzzleInput};
 
 #[derive(PartialEq, Eq)]
 pub struct Node {
-    pos: usize,
+    pos: Pos,
     dir: Option<Direction>,
     distance: usize,
     cost: usize,
@@ -26,42 +26,24 @@ impl PartialOrd for Node {
     }
 }
 
-#[derive(Debug)]
-pub struct Input {
-    pub blocks: Vec<Vec<usize>>,
-}
-
-pub fn parse_input(input_file: &str) -> (Vec<usize>, usize, usize) {
-    let input = fs::read_to_string(input_file).unwrap();
-    let input = input.trim();
-    let cols = input.find('\n').unwrap();
-    let vals = input
-        .lines()
-        .flat_map(|l| l.chars().map(|c| c.to_digit(10).unwrap() as usize))
-        .collect_vec();
-    assert_eq!(vals.len() % cols, 0);
-    let rows = vals.len() / cols;
-    (vals, rows, cols)
-}
-
-pub fn parse_blocks(blocks_lines: Vec<String>) -> Vec<Vec<usize>> {
-    let mut blocks: Vec<Vec<usize>> = Vec::new();
-    for blocks_line in blocks_lines.into_iter() {
-        let mut blocks_entries: Vec<usize> = Vec::new();
-        for blocks_entry in blocks_line.chars() {
-            blocks_entries.push(blocks_entry.to_digit(10).unwrap() as usize);
-        }
-        blocks.push(blocks_entries)
-    }
-    blocks
+pub fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Grid<usize>> {
+    Grid::parse(puzzle_input, |c| c.to_digit(10).map(|val| val as usize))
 }
 
-pub fn get_least_heat_loss<const MIN: usize, const MAX: usize>(input_file: &str) -> usize {
-    let (tiles, rows, cols) = parse_input(input_file);
+pub fn get_least_heat_loss<const MIN: usize, const MAX: usize>(
+    puzzle_input: impl PuzzleInput,
+) -> AocResult<usize> {
+    let tiles = parse_input(puzzle_input)?;
+    let cols = tiles.width();
+    // Where in the history to record arriving at a tile after moving
+    // `distance` times in a row in `dir`.
+    let key = |(row, col): Pos, dir: Direction, distance: usize| {
+        (row * cols + col) * (4 * MAX) + dir.index() * MAX + distance
+    };
     let mut open = BinaryHeap::<Node>::new();
-    let mut history = vec![(false, usize::MAX); tiles.len() * 4 * MAX];
+    let mut history = vec![(false, usize::MAX); tiles.height() * cols * 4 * MAX];
     open.push(Node {
-        pos: 0,
+        pos: (0, 0),
         dir: None,
         distance: 0,
         cost: 0,
@@ -75,63 +57,61 @@ pub fn get_least_heat_loss<const MIN: usize, const MAX: usize>(input_file: &str)
     {
         match dir {
             // Mark node as visited.
-            Some(d) => history[pos * 4 * MAX + d.index() * MAX + distance].0 = true,
+            Some(d) => history[key(pos, d, distance)].0 = true,
             None => {
-                for d in 0..4 {
-                    history[pos * 4 * MAX + d * MAX + distance].0 = true;
+                for d in Direction::all() {
+                    history[key(pos, d, distance)].0 = true;
                 }
             }
         };
-        open.extend(
-            [Direction::N, Direction::E, Direction::S, Direction::W]
-                .iter()
-                .filter_map(|&d| {
-                    let (same_dir, opp_dir) = match dir {
-                        Some(pdir) => (pdir == d, pdir.opposite() == d),
-                        None => (true, false),
-                    };
-                    if (distance < MIN && !same_dir)
-                    || (distance > MAX - 1 && same_dir) // constraints
-                    || opp_dir // no backtracking.
-                    || match d { // don't go outside grid.
-                    Direction::N => pos < cols,
-                    Direction::E => pos % cols == cols - 1,
-                    Direction::S => pos / cols == rows - 1,
-                    Direction::W => pos % cols == 0,
-                    Direction::Stop => false, // Stop does not move outside the grid.
-                    } {
-                        return None;
-                    }
-                    let npos = match d {
-                        Direction::N => pos - cols,
-                        Direction::E => pos + 1,
-                        Direction::S => pos + cols,
-                        Direction::W => pos - 1,
-                        Direction::Stop => pos, // Handle Stop by keeping the position unchanged.
-                    };
-                    let ndist = 1 + if same_dir { distance } else { 0 };
-                    let nkey = npos * (4 * MAX) + d.index() * MAX + ndist;
-                    let ncost = cost + tiles[npos];
-                    let (visited, prevcost) = history[nkey];
-                    if visited || prevcost <= ncost {
-                        return None;
-                    }
-                    history[nkey].1 = ncost;
-                    Some(Node {
-                        pos: npos,
-                        dir: Some(d),
-                        distance: ndist,
-                        cost: ncost,
-                    })
-                }),
-        );
+        open.extend(tiles.neighbors4(pos).filter_map(|(d, npos)| {
+            let (same_dir, opp_dir) = match dir {
+                Some(pdir) => (pdir == d, pdir.opposite() == d),
+                None => (true, false),
+            };
+            // Keep to the constraints, and no backtracking.
+            if (distance < MIN && !same_dir) || (distance > MAX - 1 && same_dir) || opp_dir {
+                return None;
+            }
+            let ndist = 1 + if same_dir { distance } else { 0 };
+            let nkey = key(npos, d, ndist);
+            let ncost = cost + tiles[npos];
+            let (visited, prevcost) = history[nkey];
+            if visited || prevcost <= ncost {
+                return None;
+            }
+            history[nkey].1 = ncost;
+            Some(Node {
+                pos: npos,
+                dir: Some(d),
+                distance: ndist,
+                cost: ncost,
+            })
+        }));
     }
     // Get min cost of last tile.
-    history[(tiles.len() - 1) * 4 * MAX..]
+    Ok(history[history.len() - 4 * MAX..]
         .iter()
         .map(|(_visited, cost)| *cost)
         .min()
-        .unwrap()
+        .unwrap())
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_least_heat_loss::<0, 3>(Contents(input))?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_least_heat_loss::<4, 10>(Contents(input))?.into())
+    }
 }
 
 #[cfg(test)]
@@ -142,7 +122,7 @@ mod tests {
     fn test_get_least_heat_loss_test01() {
         assert_eq!(
             102,
-            get_least_heat_loss::<0, 3>("input/2023/day17_test01.txt")
+            get_least_heat_loss::<0, 3>("input/2023/day17_test01.txt").unwrap()
         );
     }
 
@@ -150,7 +130,7 @@ mod tests {
     fn test_get_least_heat_loss_test02() {
         assert_eq!(
             14,
-            get_least_heat_loss::<0, 3>("input/2023/day17_test02.txt")
+            get_least_heat_loss::<0, 3>("input/2023/day17_test02.txt").unwrap()
         );
     }
 
@@ -158,7 +138,7 @@ mod tests {
     fn test_get_least_heat_loss_test03() {
         assert_eq!(
             28,
-            get_least_heat_loss::<0, 3>("input/2023/day17_test03.txt")
+            get_least_heat_loss::<0, 3>("input/2023/day17_test03.txt").unwrap()
         );
     }
 
@@ -166,17 +146,23 @@ mod tests {
     fn test_get_least_heat_loss_test04() {
         assert_eq!(
             11,
-            get_least_heat_loss::<0, 3>("input/2023/day17_test04.txt")
+            get_least_heat_loss::<0, 3>("input/2023/day17_test04.txt").unwrap()
         );
     }
 
     #[test]
     fn test_get_least_heat_loss_part01() {
-        assert_eq!(1099, get_least_heat_loss::<0, 3>("input/2023/day17.txt"));
+        assert_eq!(
+            1099,
+            get_least_heat_loss::<0, 3>("input/2023/day17.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_least_heat_loss_part02() {
-        assert_eq!(1266, get_least_heat_loss::<4, 10>("input/2023/day17.txt"));
+        assert_eq!(
+            1266,
+            get_least_heat_loss::<4, 10>("input/2023/day17.txt").unwrap()
+        );
     }
 }
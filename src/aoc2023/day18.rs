@@ -1,10 +1,13 @@
 // https://adventofcode.com/2023/day/18
 
-use std::cmp;
+use std::collections::HashSet;
 
 use colorsys::Rgb;
+use log::{Level, debug, log_enabled};
 
-use crate::utils::{MoveDir, get_lines};
+use crate::error::{AocError, AocResult, parse_token, require};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, Direction, Grid, MoveDir, Point, Pos, PuzzleInput, get_lines};
 
 #[derive(Debug)]
 pub struct Input {
@@ -24,187 +27,141 @@ pub struct Tile {
     pub color: Option<Rgb>,
 }
 
-pub fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
+pub fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let lines = get_lines(puzzle_input)?;
 
     let mut iter = lines.split(|e| e.is_empty());
 
-    Input {
-        dig_plan: parse_dig_plan(iter.next().unwrap().to_owned()),
-    }
+    Ok(Input {
+        dig_plan: parse_dig_plan(iter.next().unwrap().to_owned())?,
+    })
 }
 
-pub fn parse_dig_plan(dig_plan_lines: Vec<String>) -> Vec<DigStep> {
+pub fn parse_dig_plan(dig_plan_lines: Vec<String>) -> AocResult<Vec<DigStep>> {
     let mut dig_plan: Vec<DigStep> = vec![];
-    for dig_plan_line in dig_plan_lines.into_iter() {
+    for (line_idx, dig_plan_line) in dig_plan_lines.iter().enumerate() {
         let dig_plan_parts: Vec<&str> = dig_plan_line.split(" ").collect();
+        let [dir_str, steps_str, color_str] = dig_plan_parts[..] else {
+            return Err(AocError::parse(
+                line_idx,
+                0,
+                "expected a direction, steps and color",
+            ));
+        };
+        let dir = require(dir_str.chars().next(), line_idx, "missing direction")?;
+        let color_hex = color_str.trim().replace(&['(', ')'][..], "");
         dig_plan.push(DigStep {
-            dir: match MoveDir::try_from(dig_plan_parts[0].chars().next().unwrap() as u8) {
-                Ok(dir) => dir,
-                Err(_) => panic!("Invalid dir"),
-            },
-            steps: dig_plan_parts[1].trim().parse().unwrap(),
-            color: Rgb::from_hex_str(
-                dig_plan_parts[2]
-                    .trim()
-                    .replace(&['(', ')'][..], "")
-                    .as_str(),
-            )
-            .unwrap(),
+            dir: u8::try_from(dir)
+                .ok()
+                .and_then(|dir| MoveDir::try_from(dir).ok())
+                .ok_or(AocError::unexpected_char(line_idx, 0, dir))?,
+            steps: parse_token(line_idx, dig_plan_line, steps_str)?,
+            color: Rgb::from_hex_str(&color_hex).map_err(|_| {
+                AocError::parse_token(
+                    line_idx,
+                    dig_plan_line,
+                    color_str,
+                    format!("invalid color {color_str:?}"),
+                )
+            })?,
         });
     }
-    dig_plan
+    Ok(dig_plan)
 }
 
-pub fn print_trench(trench: &[Vec<Tile>]) {
-    println!("TRENCH:");
-    for tile_row in trench.iter() {
-        for tile in tile_row {
-            if tile.dig_level == 0 {
-                print!(".");
-            } else if tile.dig_level == 1 {
-                print!("#");
-            } else {
-                print!("{:#}", tile.dig_level);
-            }
-        }
-        println!();
+pub fn print_trench(trench: &Grid<Tile>) {
+    if !log_enabled!(Level::Debug) {
+        return;
     }
-}
 
-pub fn get_cubic_meters_lava(input_file: &str) -> usize {
-    let input = parse_input(input_file);
-
-    let (mut row, mut col): (i64, i64) = (0, 0);
-    let (mut max_row, mut max_col): (i64, i64) = (0, 0);
-
-    for dig_step in &input.dig_plan {
-        for _step in 0..dig_step.steps {
-            match dig_step.dir {
-                MoveDir::Up => {
-                    row -= 1;
-                    if row < 0 {
-                        max_row += 1;
-                    }
-                }
-                MoveDir::Down => {
-                    row += 1;
-                    if row > max_row {
-                        max_row += 1;
-                    }
-                }
-                MoveDir::Left => {
-                    col -= 1;
-                    if col < 0 {
-                        max_col += 1;
-                    }
-                }
-                MoveDir::Right => {
-                    col += 1;
-                    if col > max_col {
-                        max_col += 1;
-                    }
-                }
-            }
-        }
+    debug!("TRENCH:");
+    for tile_row in trench.rows() {
+        let line: String = tile_row
+            .iter()
+            .map(|tile| match tile.dig_level {
+                0 => ".".to_string(),
+                1 => "#".to_string(),
+                dig_level => dig_level.to_string(),
+            })
+            .collect();
+        debug!("{}", line);
     }
+}
 
-    let mut trench: Vec<Vec<Tile>> =
-        vec![vec![Tile::default(); max_col as usize + 1]; max_row as usize + 1];
-
-    let (mut row, mut col): (i64, i64) = (0, 0);
+pub fn get_cubic_meters_lava(puzzle_input: impl PuzzleInput) -> AocResult<usize> {
+    let input = parse_input(puzzle_input)?;
 
-    for dig_step in input.dig_plan {
+    // Walk the dig plan from the origin, remembering every dug cube
+    let mut pos: Point<i64> = Point::ORIGIN;
+    let mut dug: Vec<(Point<i64>, Rgb)> = vec![];
+    for dig_step in &input.dig_plan {
         for _step in 0..dig_step.steps {
-            match dig_step.dir {
-                MoveDir::Up => {
-                    row -= 1;
-                }
-                MoveDir::Down => {
-                    row += 1;
-                }
-                MoveDir::Left => {
-                    col -= 1;
-                }
-                MoveDir::Right => {
-                    col += 1;
-                }
-            }
-
-            if let Some(ground) = trench
-                .get_mut(row as usize)
-                .and_then(|row_vec| row_vec.get_mut(col as usize))
-            {
-                ground.dig_level = 1;
-                ground.color = Some(dig_step.color.clone());
-            }
+            pos = pos
+                .neighbor(Direction::from(dig_step.dir))
+                .ok_or(AocError::invalid("the trench runs off the map"))?;
+            dug.push((pos, dig_step.color.clone()));
         }
     }
 
-    let mut cubit_meters_lava = 0;
-
-    for (row, tile_row) in trench.iter().enumerate() {
-        for (col, tile) in tile_row.iter().enumerate() {
-            if tile.dig_level == 0 {
-                let mut trench_copy = trench.clone();
-                let is_inside = flood_fill((row as i64, col as i64), &mut trench_copy);
-                if is_inside {
-                    cubit_meters_lava = trench_copy
-                        .iter()
-                        .flatten()
-                        .filter(|tile| tile.dig_level == 1)
-                        .count();
-                    break;
-                }
-            }
+    // Shift the trench so there's a ring of undug ground around it, with
+    // the top left corner of the ring at row 0, column 0
+    let min = dug.iter().fold(Point::ORIGIN, |min: Point<i64>, (pos, _)| {
+        Point::new(min.x.min(pos.x), min.y.min(pos.y))
+    }) - Point::new(1, 1);
+    let max = dug.iter().fold(Point::ORIGIN, |max: Point<i64>, (pos, _)| {
+        Point::new(max.x.max(pos.x), max.y.max(pos.y))
+    }) + Point::new(1, 1);
+    let (height, width) = (max - min)
+        .to_pos()
+        .ok_or(AocError::invalid("the trench is too large"))?;
+
+    let mut trench = Grid::new(height + 1, width + 1, Tile::default());
+    for (pos, color) in dug {
+        if let Some(ground) = (pos - min).to_pos().and_then(|pos| trench.get_mut(pos)) {
+            ground.dig_level = 1;
+            ground.color = Some(color);
         }
     }
 
     print_trench(&trench);
 
-    let cubit_meters_all_dug = trench
-        .iter()
-        .flatten()
-        .filter(|tile| tile.dig_level == 1)
-        .count();
+    // Everything that can't be reached from the ring is either the trench
+    // or inside it
+    let outside = flood_fill((0, 0), &trench);
 
-    cmp::max(cubit_meters_lava, cubit_meters_all_dug)
+    Ok(trench.height() * trench.width() - outside)
 }
 
-fn flood_fill((start_row, start_col): (i64, i64), trench: &mut [Vec<Tile>]) -> bool {
-    let mut stack = vec![(start_row, start_col)];
-    let mut visited = std::collections::HashSet::new();
+/// The number of undug tiles reachable from `start` without crossing the
+/// trench.
+fn flood_fill(start: Pos, trench: &Grid<Tile>) -> usize {
+    let mut stack = vec![start];
+    let mut visited = HashSet::new();
 
-    while let Some((row, col)) = stack.pop() {
-        // Check bounds
-        if row < 0 || col < 0 || row >= trench.len() as i64 || col >= trench[0].len() as i64 {
-            return false; // Hit boundary, so this is outside
-        }
-
-        // Check if already visited
-        if visited.contains(&(row, col)) {
+    while let Some(pos) = stack.pop() {
+        // If we hit a dug tile, this is a boundary
+        if trench[pos].dig_level == 1 || !visited.insert(pos) {
             continue;
         }
-        visited.insert((row, col));
 
-        let tile = &mut trench[row as usize][col as usize];
+        // Add neighbors to stack
+        stack.extend(trench.neighbors4(pos).map(|(_, next_pos)| next_pos));
+    }
 
-        // If we hit a dug tile, this is a boundary
-        if tile.dig_level == 1 {
-            continue;
-        }
+    visited.len()
+}
 
-        // Mark as filled
-        tile.dig_level = 1;
+pub struct Solver;
 
-        // Add neighbors to stack
-        stack.push((row + 1, col));
-        stack.push((row - 1, col));
-        stack.push((row, col + 1));
-        stack.push((row, col - 1));
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
     }
 
-    true // Successfully filled the area (it was inside)
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_cubic_meters_lava(Contents(input))?.into())
+    }
 }
 
 #[cfg(test)]
@@ -213,27 +170,41 @@ mod tests {
 
     #[test]
     fn test_get_cubic_meters_lava_test01() {
-        assert_eq!(62, get_cubic_meters_lava("input/2023/day18_test01.txt"));
+        assert_eq!(
+            62,
+            get_cubic_meters_lava("input/2023/day18_test01.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_cubic_meters_lava_test02() {
-        assert_eq!(4, get_cubic_meters_lava("input/2023/day18_test02.txt"));
+        assert_eq!(
+            4,
+            get_cubic_meters_lava("input/2023/day18_test02.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_cubic_meters_lava_test03() {
-        assert_eq!(9, get_cubic_meters_lava("input/2023/day18_test03.txt"));
+        assert_eq!(
+            9,
+            get_cubic_meters_lava("input/2023/day18_test03.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_cubic_meters_lava_test04() {
-        assert_eq!(16, get_cubic_meters_lava("input/2023/day18_test04.txt"));
+        assert_eq!(
+            16,
+            get_cubic_meters_lava("input/2023/day18_test04.txt").unwrap()
+        );
     }
 
-    #[ignore]
     #[test]
     fn test_get_cubic_meters_lava() {
-        assert_eq!(9716, get_cubic_meters_lava("input/2023/day18.txt"));
+        assert_eq!(
+            36725,
+            get_cubic_meters_lava("input/2023/day18.txt").unwrap()
+        );
     }
 }
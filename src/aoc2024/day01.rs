@@ -1,6 +1,8 @@
 // https://adventofcode.com/2024/day/1
 
-use crate::utils::get_lines;
+use crate::error::{AocResult, parse_token};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput, get_lines};
 
 use std::iter::zip;
 
@@ -9,31 +11,31 @@ struct Input {
     right_list: Vec<u32>,
 }
 
-fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
+fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let lines = get_lines(puzzle_input)?;
 
     let mut left_list: Vec<u32> = Vec::new();
     let mut right_list: Vec<u32> = Vec::new();
 
-    for line in lines {
+    for (line_idx, line) in lines.iter().enumerate() {
         let line_parts: Vec<&str> = line.split_whitespace().take(2).collect();
-        if let [left, right] = &line_parts[..] {
-            left_list.push(left.parse::<u32>().unwrap());
-            right_list.push(right.parse::<u32>().unwrap());
+        if let [left, right] = line_parts[..] {
+            left_list.push(parse_token(line_idx, line, left)?);
+            right_list.push(parse_token(line_idx, line, right)?);
         }
     }
 
     left_list.sort();
     right_list.sort();
 
-    Input {
+    Ok(Input {
         left_list,
         right_list,
-    }
+    })
 }
 
-pub fn get_total_distance(input_file: &str) -> u32 {
-    let input = parse_input(input_file);
+pub fn get_total_distance(puzzle_input: impl PuzzleInput) -> AocResult<u32> {
+    let input = parse_input(puzzle_input)?;
 
     let mut total_distance = 0;
 
@@ -43,11 +45,11 @@ pub fn get_total_distance(input_file: &str) -> u32 {
         total_distance += right.abs_diff(left)
     }
 
-    total_distance
+    Ok(total_distance)
 }
 
-pub fn get_similarity_score(input_file: &str) -> u32 {
-    let input = parse_input(input_file);
+pub fn get_similarity_score(puzzle_input: impl PuzzleInput) -> AocResult<u32> {
+    let input = parse_input(puzzle_input)?;
 
     let mut similarity_score = 0;
 
@@ -56,7 +58,24 @@ pub fn get_similarity_score(input_file: &str) -> u32 {
         similarity_score += left * right_count as u32;
     }
 
-    similarity_score
+    Ok(similarity_score)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_total_distance(Contents(input))?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_similarity_score(Contents(input))?.into())
+    }
 }
 
 #[cfg(test)]
@@ -65,21 +84,37 @@ mod tests {
 
     #[test]
     fn test_get_total_distance_test01() {
-        assert_eq!(11, get_total_distance("input/2024/day01_test01.txt"));
+        assert_eq!(
+            11,
+            get_total_distance("input/2024/day01_test01.txt").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_get_total_distance_inline() {
+        let example = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+        assert_eq!(11, get_total_distance(Contents(example)).unwrap());
+        assert_eq!(31, get_similarity_score(Contents(example)).unwrap());
     }
 
     #[test]
     fn test_get_total_distance() {
-        assert_eq!(1151792, get_total_distance("input/2024/day01.txt"));
+        assert_eq!(1151792, get_total_distance("input/2024/day01.txt").unwrap());
     }
 
     #[test]
     fn test_get_similarity_score_test01() {
-        assert_eq!(31, get_similarity_score("input/2024/day01_test01.txt"));
+        assert_eq!(
+            31,
+            get_similarity_score("input/2024/day01_test01.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_similarity_score() {
-        assert_eq!(21790168, get_similarity_score("input/2024/day01.txt"));
+        assert_eq!(
+            21790168,
+            get_similarity_score("input/2024/day01.txt").unwrap()
+        );
     }
 }
@@ -1,25 +1,27 @@
 // https://adventofcode.com/2024/day/2
 
-use crate::utils::get_lines;
+use crate::error::{AocResult, parse_token};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput, get_lines};
 
 struct Input {
     reports: Vec<Vec<i32>>,
 }
 
-fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
+fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let lines = get_lines(puzzle_input)?;
 
     let mut reports: Vec<Vec<i32>> = Vec::new();
 
-    for line in lines {
+    for (line_idx, line) in lines.iter().enumerate() {
         let report: Vec<i32> = line
             .split_whitespace()
-            .map(|level| level.parse::<i32>().unwrap())
-            .collect();
+            .map(|level| parse_token(line_idx, line, level))
+            .collect::<AocResult<_>>()?;
         reports.push(report);
     }
 
-    Input { reports }
+    Ok(Input { reports })
 }
 
 fn is_all_increasing(arr: &[i32]) -> bool {
@@ -34,8 +36,8 @@ fn is_all_adj_diff_in_limits(arr: &[i32]) -> bool {
     arr.iter().all(|elem| elem.abs() >= 1 && elem.abs() <= 3)
 }
 
-pub fn get_num_safe_reports(input_file: &str) -> u32 {
-    let input = parse_input(input_file);
+pub fn get_num_safe_reports(puzzle_input: impl PuzzleInput) -> AocResult<u32> {
+    let input = parse_input(puzzle_input)?;
 
     let mut num_safe_reports = 0;
 
@@ -57,11 +59,11 @@ pub fn get_num_safe_reports(input_file: &str) -> u32 {
         }
     }
 
-    num_safe_reports
+    Ok(num_safe_reports)
 }
 
-pub fn get_num_safe_reports_with_prob_damp(input_file: &str) -> u32 {
-    let input = parse_input(input_file);
+pub fn get_num_safe_reports_with_prob_damp(puzzle_input: impl PuzzleInput) -> AocResult<u32> {
+    let input = parse_input(puzzle_input)?;
 
     let mut num_safe_reports = 0;
 
@@ -93,7 +95,24 @@ pub fn get_num_safe_reports_with_prob_damp(input_file: &str) -> u32 {
         }
     }
 
-    num_safe_reports
+    Ok(num_safe_reports)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_num_safe_reports(Contents(input))?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_num_safe_reports_with_prob_damp(Contents(input))?.into())
+    }
 }
 
 #[cfg(test)]
@@ -102,19 +121,22 @@ mod tests {
 
     #[test]
     fn test_get_num_safe_reports_test01() {
-        assert_eq!(2, get_num_safe_reports("input/2024/day02_test01.txt"));
+        assert_eq!(
+            2,
+            get_num_safe_reports("input/2024/day02_test01.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_num_safe_reports() {
-        assert_eq!(686, get_num_safe_reports("input/2024/day02.txt"));
+        assert_eq!(686, get_num_safe_reports("input/2024/day02.txt").unwrap());
     }
 
     #[test]
     fn test_get_num_safe_reports_with_prob_damp_test01() {
         assert_eq!(
             4,
-            get_num_safe_reports_with_prob_damp("input/2024/day02_test01.txt")
+            get_num_safe_reports_with_prob_damp("input/2024/day02_test01.txt").unwrap()
         );
     }
 
@@ -122,7 +144,7 @@ mod tests {
     fn test_get_num_safe_reports_with_prob_damp() {
         assert_eq!(
             717,
-            get_num_safe_reports_with_prob_damp("input/2024/day02.txt")
+            get_num_safe_reports_with_prob_damp("input/2024/day02.txt").unwrap()
         );
     }
 }
@@ -1,34 +1,42 @@
 // https://adventofcode.com/2024/day/3
 
 use regex::Regex;
-use std::fs;
 
-pub fn get_sum_mul(input_file: &str) -> u32 {
+use crate::error::{AocError, AocResult};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput};
+
+fn parse_mul_operands(operation: &str, left_str: &str, right_str: &str) -> AocResult<u32> {
+    match (left_str.parse::<u32>(), right_str.parse::<u32>()) {
+        (Ok(left), Ok(right)) => Ok(left * right),
+        _ => Err(AocError::invalid(format!(
+            "operands of {operation} out of range"
+        ))),
+    }
+}
+
+pub fn get_sum_mul(puzzle_input: impl PuzzleInput) -> AocResult<u32> {
     lazy_static! {
         static ref RE_MUL: Regex =
-            Regex::new(r"(?P<operation>mul)\((?P<left>\d*),(?P<right>\d*)\)").unwrap();
+            Regex::new(r"(?P<operation>mul)\((?P<left>\d+),(?P<right>\d+)\)").unwrap();
     }
-    let line: String = fs::read_to_string(input_file).unwrap();
+    let line: String = puzzle_input.read_to_string()?;
 
     let mut sum_mul = 0;
 
     for cap_mul in RE_MUL.captures_iter(&line) {
-        let _ = cap_mul["operation"].to_string();
-        let left_str = cap_mul["left"].to_string();
-        let right_str = cap_mul["right"].to_string();
-
-        sum_mul += left_str.parse::<u32>().unwrap() * right_str.parse::<u32>().unwrap();
+        sum_mul += parse_mul_operands(&cap_mul[0], &cap_mul["left"], &cap_mul["right"])?;
     }
 
-    sum_mul
+    Ok(sum_mul)
 }
 
-pub fn get_sum_mul_cond(input_file: &str) -> u32 {
+pub fn get_sum_mul_cond(puzzle_input: impl PuzzleInput) -> AocResult<u32> {
     lazy_static! {
         static ref RE_MUL_COND: Regex =
-            Regex::new(r"(?P<operation>mul\((?P<left>\d*),(?P<right>\d*)\))|(?P<enable>do\(\))|(?P<disable>don't\(\))").unwrap();
+            Regex::new(r"(?P<operation>mul\((?P<left>\d+),(?P<right>\d+)\))|(?P<enable>do\(\))|(?P<disable>don't\(\))").unwrap();
     }
-    let line: String = fs::read_to_string(input_file).unwrap();
+    let line: String = puzzle_input.read_to_string()?;
 
     let mut sum_mul = 0;
 
@@ -41,15 +49,23 @@ pub fn get_sum_mul_cond(input_file: &str) -> u32 {
         } else if caps.name("disable").is_some() {
             is_enabled = false;
         } else if is_enabled {
-            let _ = caps["operation"].to_string();
-            let left_str = caps["left"].to_string();
-            let right_str = caps["right"].to_string();
-
-            sum_mul += left_str.parse::<u32>().unwrap() * right_str.parse::<u32>().unwrap();
+            sum_mul += parse_mul_operands(&caps["operation"], &caps["left"], &caps["right"])?;
         }
     }
 
-    sum_mul
+    Ok(sum_mul)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_sum_mul(Contents(input))?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_sum_mul_cond(Contents(input))?.into())
+    }
 }
 
 #[cfg(test)]
@@ -58,21 +74,27 @@ mod tests {
 
     #[test]
     fn test_get_sum_mul_test01() {
-        assert_eq!(161, get_sum_mul("input/2024/day03_test01.txt"));
+        assert_eq!(161, get_sum_mul("input/2024/day03_test01.txt").unwrap());
+    }
+
+    #[test]
+    fn test_get_sum_mul_inline() {
+        let example = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+        assert_eq!(161, get_sum_mul(Contents(example)).unwrap());
     }
 
     #[test]
     fn test_get_sum_mul() {
-        assert_eq!(180233229, get_sum_mul("input/2024/day03.txt"));
+        assert_eq!(180233229, get_sum_mul("input/2024/day03.txt").unwrap());
     }
 
     #[test]
     fn test_get_sum_mul_cond_test01() {
-        assert_eq!(48, get_sum_mul_cond("input/2024/day03_test02.txt"));
+        assert_eq!(48, get_sum_mul_cond("input/2024/day03_test02.txt").unwrap());
     }
 
     #[test]
     fn test_get_sum_mul_cond() {
-        assert_eq!(95411583, get_sum_mul_cond("input/2024/day03.txt"));
+        assert_eq!(95411583, get_sum_mul_cond("input/2024/day03.txt").unwrap());
     }
 }
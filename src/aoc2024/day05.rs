@@ -1,22 +1,27 @@
 // https://adventofcode.com/2024/day/5
 
-use crate::utils::get_lines;
+use crate::error::{AocResult, parse_token};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput, get_lines};
 
 struct Input {
     page_order_rules: Vec<(u32, u32)>,
     updates: Vec<Vec<u32>>,
 }
 
-fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
+fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let lines = get_lines(puzzle_input)?;
 
     let mut page_order_rules: Vec<(u32, u32)> = Vec::new();
     let mut updates: Vec<Vec<u32>> = Vec::new();
 
-    for line in lines {
+    for (line_idx, line) in lines.iter().enumerate() {
         let page_order_rule_parts: Vec<&str> = line.split(['|']).take(2).collect();
-        if let [left, right] = &page_order_rule_parts[..] {
-            page_order_rules.push((left.parse::<u32>().unwrap(), right.parse::<u32>().unwrap()));
+        if let [left, right] = page_order_rule_parts[..] {
+            page_order_rules.push((
+                parse_token(line_idx, line, left)?,
+                parse_token(line_idx, line, right)?,
+            ));
         }
 
         let update_parts: Vec<&str> = line.split([',']).collect();
@@ -24,16 +29,16 @@ fn parse_input(input_file: &str) -> Input {
             updates.push(
                 update_parts
                     .iter()
-                    .map(|part| part.parse::<u32>().unwrap())
-                    .collect(),
+                    .map(|part| parse_token(line_idx, line, part))
+                    .collect::<AocResult<_>>()?,
             );
         }
     }
 
-    Input {
+    Ok(Input {
         page_order_rules,
         updates,
-    }
+    })
 }
 
 fn check_update_order(update: &[u32], page_order_rules: &Vec<(u32, u32)>) -> bool {
@@ -53,8 +58,8 @@ fn check_update_order(update: &[u32], page_order_rules: &Vec<(u32, u32)>) -> boo
     true
 }
 
-pub fn get_sum_middle_page_num(input_file: &str) -> u32 {
-    let input = parse_input(input_file);
+pub fn get_sum_middle_page_num(puzzle_input: impl PuzzleInput) -> AocResult<u32> {
+    let input = parse_input(puzzle_input)?;
 
     let mut sum_middle_page_num = 0;
 
@@ -65,7 +70,7 @@ pub fn get_sum_middle_page_num(input_file: &str) -> u32 {
         }
     }
 
-    sum_middle_page_num
+    Ok(sum_middle_page_num)
 }
 
 fn correct_update(update: &mut [u32], page_order_rules: &Vec<(u32, u32)>) {
@@ -84,8 +89,8 @@ fn correct_update(update: &mut [u32], page_order_rules: &Vec<(u32, u32)>) {
     }
 }
 
-pub fn get_sum_corrected_middle_page_num(input_file: &str) -> u32 {
-    let input = parse_input(input_file);
+pub fn get_sum_corrected_middle_page_num(puzzle_input: impl PuzzleInput) -> AocResult<u32> {
+    let input = parse_input(puzzle_input)?;
 
     let mut sum_middle_page_num = 0;
 
@@ -103,7 +108,24 @@ pub fn get_sum_corrected_middle_page_num(input_file: &str) -> u32 {
         }
     }
 
-    sum_middle_page_num
+    Ok(sum_middle_page_num)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_sum_middle_page_num(Contents(input))?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_sum_corrected_middle_page_num(Contents(input))?.into())
+    }
 }
 
 #[cfg(test)]
@@ -112,19 +134,25 @@ mod tests {
 
     #[test]
     fn test_get_sum_middle_page_num_test01() {
-        assert_eq!(143, get_sum_middle_page_num("input/2024/day05_test01.txt"));
+        assert_eq!(
+            143,
+            get_sum_middle_page_num("input/2024/day05_test01.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_middle_page_num() {
-        assert_eq!(4996, get_sum_middle_page_num("input/2024/day05.txt"));
+        assert_eq!(
+            4996,
+            get_sum_middle_page_num("input/2024/day05.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_corrected_middle_page_num_test01() {
         assert_eq!(
             123,
-            get_sum_corrected_middle_page_num("input/2024/day05_test01.txt")
+            get_sum_corrected_middle_page_num("input/2024/day05_test01.txt").unwrap()
         );
     }
 
@@ -132,7 +160,7 @@ mod tests {
     fn test_get_sum_corrected_middle_page_num() {
         assert_eq!(
             6311,
-            get_sum_corrected_middle_page_num("input/2024/day05.txt")
+            get_sum_corrected_middle_page_num("input/2024/day05.txt").unwrap()
         );
     }
 }
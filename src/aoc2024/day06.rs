@@ -2,9 +2,10 @@
 
 use std::collections::HashSet;
 
-use crate::utils::Direction;
+use crate::error::{AocError, AocResult};
+use crate::solution::{Answer, Solution};
 use crate::utils::ListNode;
-use crate::utils::get_lines;
+use crate::utils::{Contents, Direction, Grid, Pos, PuzzleInput};
 
 #[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
 #[repr(u8)]
@@ -29,53 +30,36 @@ impl TryFrom<u8> for MapEntry {
 }
 
 struct Input {
-    map: Vec<Vec<MapEntry>>,
+    map: Grid<MapEntry>,
 }
 
-fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
+fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let map = Grid::parse(puzzle_input, |c| {
+        MapEntry::try_from(u8::try_from(c).ok()?).ok()
+    })?;
 
-    let mut iter = lines.split(|e| e.is_empty());
-
-    Input {
-        map: parse_map(iter.next().unwrap().to_owned()),
-    }
-}
-
-fn parse_map(map_lines: Vec<String>) -> Vec<Vec<MapEntry>> {
-    let mut map = vec![];
-    for map_line in map_lines.into_iter() {
-        let mut map_entries: Vec<MapEntry> = Vec::new();
-        for map_entry in map_line.chars() {
-            match MapEntry::try_from(map_entry as u8) {
-                Ok(pipe) => map_entries.push(pipe),
-                Err(_) => panic!("Invalid map entry {map_entry}"),
-            }
-        }
-        map.push(map_entries);
-    }
-    map
+    Ok(Input { map })
 }
 
 struct ListNodeEntry {
-    pos: (usize, usize),
+    pos: Pos,
     direction: Direction,
 }
 
 struct ListNodePos {
-    next_pos: (usize, usize),
+    next_pos: Pos,
     next_dir: Direction,
     alternate_dir: Direction,
 }
 
 #[derive(PartialEq)]
 struct VisitedNodeEntry {
-    pos: (usize, usize),
+    pos: Pos,
     direction: Direction,
 }
 
 fn process_list_entry(
-    map: &Vec<Vec<MapEntry>>,
+    map: &Grid<MapEntry>,
     list_node: &mut ListNode<ListNodeEntry>,
     visited_nodes: &mut Vec<VisitedNodeEntry>,
     has_loop: &mut bool,
@@ -83,7 +67,7 @@ fn process_list_entry(
 ) {
     let (curr_row, curr_col) = list_node.val.pos;
     let (next_pos_row, next_pos_col) = list_node_pos.next_pos;
-    let next_pri_map_entry = map[next_pos_row][next_pos_col];
+    let next_pri_map_entry = map[list_node_pos.next_pos];
     if next_pri_map_entry == MapEntry::Obstruction {
         /*println!(
             "Found obstruction at pos {:?}, changing direction from {:?} to {:?}, pos {:?}",
@@ -126,24 +110,25 @@ fn process_list_entry(
 }
 
 fn build_list(
-    map: &Vec<Vec<MapEntry>>,
+    map: &Grid<MapEntry>,
     list_node: &mut ListNode<ListNodeEntry>,
     visited_nodes: &mut Vec<VisitedNodeEntry>,
     has_loop: &mut bool,
 ) {
-    let (curr_row, curr_col) = list_node.val.pos;
-    if curr_row as i32 - 1 < 0 {
+    let pos = list_node.val.pos;
+    // The guard leaves once they reach any edge of the map
+    let Some(n_dir) = map.neighbor(pos, Direction::N) else {
         return;
-    }
-    if curr_col as i32 - 1 < 0 {
+    };
+    let Some(s_dir) = map.neighbor(pos, Direction::S) else {
         return;
-    }
-    if curr_row as i32 + 1 >= map.len() as i32 {
+    };
+    let Some(e_dir) = map.neighbor(pos, Direction::E) else {
         return;
-    }
-    if curr_col as i32 + 1 >= map[0].len() as i32 {
+    };
+    let Some(w_dir) = map.neighbor(pos, Direction::W) else {
         return;
-    }
+    };
     if detect_loop(visited_nodes) {
         *has_loop = true;
         /*println!(
@@ -152,10 +137,6 @@ fn build_list(
         );*/
         return;
     }
-    let n_dir = (curr_row - 1, curr_col);
-    let s_dir = (curr_row + 1, curr_col);
-    let e_dir = (curr_row, curr_col + 1);
-    let w_dir = (curr_row, curr_col - 1);
     match list_node.val.direction {
         Direction::N => {
             process_list_entry(
@@ -215,21 +196,7 @@ fn build_list(
     }
 }
 
-fn get_start_pos(map: &[Vec<MapEntry>]) -> Option<(usize, usize)> {
-    for (row, map_col) in map.iter().enumerate() {
-        for (col, map_entry) in map_col.iter().enumerate() {
-            if *map_entry == MapEntry::GuardN {
-                return Some((row, col));
-            }
-        }
-    }
-    None
-}
-
-fn get_distinct_pos_vec(
-    map: &Vec<Vec<MapEntry>>,
-    (start_row, start_col): (usize, usize),
-) -> (HashSet<(usize, usize)>, bool) {
+fn get_distinct_pos_vec(map: &Grid<MapEntry>, (start_row, start_col): Pos) -> (HashSet<Pos>, bool) {
     let mut list_root = ListNode::new(ListNodeEntry {
         pos: (start_row, start_col),
         direction: Direction::N,
@@ -243,23 +210,23 @@ fn get_distinct_pos_vec(
     let mut has_loop = false;
     build_list(map, &mut list_root, &mut visited_nodes, &mut has_loop);
 
-    let unique_visited_nodes: HashSet<(usize, usize)> =
+    let unique_visited_nodes: HashSet<Pos> =
         visited_nodes.drain(..).map(|entry| entry.pos).collect();
     (unique_visited_nodes, has_loop)
 }
 
-pub fn get_distinct_pos(input_file: &str) -> usize {
-    let input = parse_input(input_file);
+pub fn get_distinct_pos(puzzle_input: impl PuzzleInput) -> AocResult<usize> {
+    let input = parse_input(puzzle_input)?;
 
-    if let Some((start_row, start_col)) = get_start_pos(&input.map) {
-        //println!("Start node found at pos {:?}", (start_row, start_col));
+    let Some((start_row, start_col)) = input.map.position(|entry| *entry == MapEntry::GuardN)
+    else {
+        return Err(AocError::invalid("no guard on the map"));
+    };
+    //println!("Start node found at pos {:?}", (start_row, start_col));
 
-        get_distinct_pos_vec(&input.map, (start_row, start_col))
-            .0
-            .len()
-    } else {
-        panic!("Invalid start node");
-    }
+    Ok(get_distinct_pos_vec(&input.map, (start_row, start_col))
+        .0
+        .len())
 }
 
 // Implements Floyd's cycle-finding algorithm using indices
@@ -285,42 +252,61 @@ fn detect_loop(visited_nodes: &mut [VisitedNodeEntry]) -> bool {
     false
 }
 
-pub fn get_sum_time_loop_pos(input_file: &str) -> usize {
-    let input = parse_input(input_file);
+pub fn get_sum_time_loop_pos(puzzle_input: impl PuzzleInput) -> AocResult<usize> {
+    let input = parse_input(puzzle_input)?;
 
     let mut sum_time_loop_pos = 0usize;
-    if let Some((start_row, start_col)) = get_start_pos(&input.map) {
-        //println!("Start node found at pos {:?}", (start_row, start_col));
+    let Some((start_row, start_col)) = input.map.position(|entry| *entry == MapEntry::GuardN)
+    else {
+        return Err(AocError::invalid("no guard on the map"));
+    };
+    //println!("Start node found at pos {:?}", (start_row, start_col));
 
-        let res = get_distinct_pos_vec(&input.map, (start_row, start_col));
+    let res = get_distinct_pos_vec(&input.map, (start_row, start_col));
 
-        // Remove start node from map as instructed
-        let mut distinct_pos = res.0;
-        distinct_pos.remove(&(start_row, start_col));
+    // Remove start node from map as instructed
+    let mut distinct_pos = res.0;
+    distinct_pos.remove(&(start_row, start_col));
 
-        //println!("Processing {} potential maps ", distinct_pos.len());
+    //println!("Processing {} potential maps ", distinct_pos.len());
 
-        for (visited_row, visited_col) in distinct_pos {
-            let mut pot_map = input.map.clone();
-            pot_map[visited_row][visited_col] = MapEntry::Obstruction;
+    for (visited_row, visited_col) in distinct_pos {
+        let mut pot_map = input.map.clone();
+        pot_map[(visited_row, visited_col)] = MapEntry::Obstruction;
 
-            /*println!(
-                "Processing map with new obstruction at {:?}",
-                (visited_row, visited_col)
-            );
+        /*println!(
+            "Processing map with new obstruction at {:?}",
+            (visited_row, visited_col)
+        );
 
-            print_map(&pot_map);*/
+        print_map(&pot_map);*/
 
-            let new_res = get_distinct_pos_vec(&pot_map, (start_row, start_col));
-            if new_res.1 {
-                sum_time_loop_pos += 1;
-            }
+        let new_res = get_distinct_pos_vec(&pot_map, (start_row, start_col));
+        if new_res.1 {
+            sum_time_loop_pos += 1;
         }
-    } else {
-        panic!("Invalid start node");
     }
 
-    sum_time_loop_pos
+    Ok(sum_time_loop_pos)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        // Requires 8MB of stack space
+        Ok(stacker::grow(8 * 1024 * 1024, || get_distinct_pos(Contents(input)))?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        // Requires 16MB of stack space
+        Ok(stacker::grow(16 * 1024 * 1024, || get_sum_time_loop_pos(Contents(input)))?.into())
+    }
 }
 
 #[cfg(test)]
@@ -329,68 +315,71 @@ mod tests {
 
     #[test]
     fn test_get_distinct_pos_vec_test02() {
-        let input = parse_input("input/2024/day06_test02.txt");
+        let input = parse_input("input/2024/day06_test02.txt").unwrap();
         assert!(get_distinct_pos_vec(&input.map, (6, 4)).1);
     }
 
     #[test]
     fn test_get_distinct_pos_vec_test03() {
-        let input = parse_input("input/2024/day06_test03.txt");
+        let input = parse_input("input/2024/day06_test03.txt").unwrap();
         assert!(get_distinct_pos_vec(&input.map, (6, 4)).1);
     }
 
     #[test]
     fn test_get_distinct_pos_vec_test04() {
-        let input = parse_input("input/2024/day06_test04.txt");
+        let input = parse_input("input/2024/day06_test04.txt").unwrap();
         assert!(get_distinct_pos_vec(&input.map, (6, 4)).1);
     }
 
     #[test]
     fn test_get_distinct_pos_vec_test05() {
-        let input = parse_input("input/2024/day06_test05.txt");
+        let input = parse_input("input/2024/day06_test05.txt").unwrap();
         assert!(get_distinct_pos_vec(&input.map, (6, 4)).1);
     }
 
     #[test]
     fn test_get_distinct_pos_vec_test06() {
-        let input = parse_input("input/2024/day06_test06.txt");
+        let input = parse_input("input/2024/day06_test06.txt").unwrap();
         assert!(get_distinct_pos_vec(&input.map, (6, 4)).1);
     }
 
     #[test]
     fn test_get_distinct_pos_vec_test07() {
-        let input = parse_input("input/2024/day06_test07.txt");
+        let input = parse_input("input/2024/day06_test07.txt").unwrap();
         assert!(get_distinct_pos_vec(&input.map, (6, 4)).1);
     }
 
     #[test]
     fn test_get_distinct_pos_vec_test08() {
-        let input = parse_input("input/2024/day06_test08.txt");
+        let input = parse_input("input/2024/day06_test08.txt").unwrap();
         assert!(!get_distinct_pos_vec(&input.map, (6, 4)).1);
     }
 
     #[test]
     fn test_get_distinct_pos_vec_test09() {
-        let input = parse_input("input/2024/day06_test09.txt");
+        let input = parse_input("input/2024/day06_test09.txt").unwrap();
         assert!(!get_distinct_pos_vec(&input.map, (6, 4)).1);
     }
 
     #[test]
     fn test_get_distinct_pos_test01() {
-        assert_eq!(41, get_distinct_pos("input/2024/day06_test01.txt"));
+        assert_eq!(41, get_distinct_pos("input/2024/day06_test01.txt").unwrap());
     }
 
     #[test]
     fn test_get_distinct_pos() {
         // Requires 8MB of stack space
         stacker::grow(8 * 1024 * 1024, || {
-            assert_eq!(5564, get_distinct_pos("input/2024/day06.txt"));
+            assert_eq!(5564, get_distinct_pos("input/2024/day06.txt").unwrap());
         });
     }
 
     #[test]
     fn test_get_sum_time_loop_pos_test01() {
-        assert_eq!(6, get_sum_time_loop_pos("input/2024/day06_test01.txt"));
+        assert_eq!(
+            6,
+            get_sum_time_loop_pos("input/2024/day06_test01.txt").unwrap()
+        );
     }
 
     // This test takes a while so ignore in CI
@@ -400,7 +389,7 @@ mod tests {
     fn test_get_sum_time_loop_pos() {
         // Requires 16MB of stack space
         stacker::grow(16 * 1024 * 1024, || {
-            assert_eq!(1976, get_sum_time_loop_pos("input/2024/day06.txt"));
+            assert_eq!(1976, get_sum_time_loop_pos("input/2024/day06.txt").unwrap());
         });
     }
 }
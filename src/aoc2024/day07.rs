@@ -2,7 +2,9 @@
 
 use itertools::Itertools;
 
-use crate::utils::get_lines;
+use crate::error::{AocResult, parse_token};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput, get_lines};
 
 struct Input {
     equations: Vec<CalibrationEquation>,
@@ -21,24 +23,24 @@ enum Operator {
     Concat,
 }
 
-fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
+fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let lines = get_lines(puzzle_input)?;
 
     let mut equations: Vec<CalibrationEquation> = Vec::new();
 
-    for line in lines {
+    for (line_idx, line) in lines.iter().enumerate() {
         let line_parts: Vec<&str> = line.split(':').take(2).collect();
-        if let [left, right] = &line_parts[..] {
-            let result = left.parse::<u64>().unwrap();
+        if let [left, right] = line_parts[..] {
+            let result = parse_token(line_idx, line, left)?;
             let terms: Vec<u64> = right
                 .split_whitespace()
-                .map(|x| x.parse::<u64>().unwrap())
-                .collect();
+                .map(|x| parse_token(line_idx, line, x))
+                .collect::<AocResult<_>>()?;
             equations.push(CalibrationEquation { result, terms });
         }
     }
 
-    Input { equations }
+    Ok(Input { equations })
 }
 
 fn is_valid_calibration_result(equation: &CalibrationEquation) -> bool {
@@ -77,8 +79,8 @@ fn is_valid_calibration_result(equation: &CalibrationEquation) -> bool {
     false
 }
 
-pub fn get_total_calibration_result(input_file: &str) -> u64 {
-    let input = parse_input(input_file);
+pub fn get_total_calibration_result(puzzle_input: impl PuzzleInput) -> AocResult<u64> {
+    let input = parse_input(puzzle_input)?;
 
     let mut total_calibration_result = 0;
 
@@ -89,7 +91,7 @@ pub fn get_total_calibration_result(input_file: &str) -> u64 {
         }
     }
 
-    total_calibration_result
+    Ok(total_calibration_result)
 }
 
 fn is_valid_calibration_result_concat(equation: &CalibrationEquation) -> bool {
@@ -145,8 +147,8 @@ fn is_valid_calibration_result_concat(equation: &CalibrationEquation) -> bool {
     false
 }
 
-pub fn get_total_calibration_result_with_concat(input_file: &str) -> u64 {
-    let input = parse_input(input_file);
+pub fn get_total_calibration_result_with_concat(puzzle_input: impl PuzzleInput) -> AocResult<u64> {
+    let input = parse_input(puzzle_input)?;
 
     let mut total_calibration_result = 0;
 
@@ -156,7 +158,24 @@ pub fn get_total_calibration_result_with_concat(input_file: &str) -> u64 {
         }
     }
 
-    total_calibration_result
+    Ok(total_calibration_result)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_total_calibration_result(Contents(input))?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_total_calibration_result_with_concat(Contents(input))?.into())
+    }
 }
 
 #[cfg(test)]
@@ -167,7 +186,7 @@ mod tests {
     fn test_get_total_calibration_result_test01() {
         assert_eq!(
             3749,
-            get_total_calibration_result("input/2024/day07_test01.txt")
+            get_total_calibration_result("input/2024/day07_test01.txt").unwrap()
         );
     }
 
@@ -175,7 +194,7 @@ mod tests {
     fn test_get_total_calibration() {
         assert_eq!(
             3245122495150,
-            get_total_calibration_result("input/2024/day07.txt")
+            get_total_calibration_result("input/2024/day07.txt").unwrap()
         );
     }
 
@@ -183,7 +202,7 @@ mod tests {
     fn test_get_total_calibration_result_test02() {
         assert_eq!(
             0,
-            get_total_calibration_result("input/2024/day07_test02.txt")
+            get_total_calibration_result("input/2024/day07_test02.txt").unwrap()
         );
     }
 
@@ -191,7 +210,7 @@ mod tests {
     fn test_get_total_calibration_result_test03() {
         assert_eq!(
             0,
-            get_total_calibration_result("input/2024/day07_test03.txt")
+            get_total_calibration_result("input/2024/day07_test03.txt").unwrap()
         );
     }
 
@@ -199,7 +218,7 @@ mod tests {
     fn test_get_total_calibration_result_test04() {
         assert_eq!(
             0,
-            get_total_calibration_result("input/2024/day07_test04.txt")
+            get_total_calibration_result("input/2024/day07_test04.txt").unwrap()
         );
     }
 
@@ -207,7 +226,7 @@ mod tests {
     fn test_get_total_calibration_result_test05() {
         assert_eq!(
             0,
-            get_total_calibration_result("input/2024/day07_test05.txt")
+            get_total_calibration_result("input/2024/day07_test05.txt").unwrap()
         );
     }
 
@@ -215,7 +234,7 @@ mod tests {
     fn test_get_total_calibration_result_test06() {
         assert_eq!(
             0,
-            get_total_calibration_result("input/2024/day07_test06.txt")
+            get_total_calibration_result("input/2024/day07_test06.txt").unwrap()
         );
     }
 
@@ -223,7 +242,7 @@ mod tests {
     fn test_get_total_calibration_result_test07() {
         assert_eq!(
             3267,
-            get_total_calibration_result("input/2024/day07_test07.txt")
+            get_total_calibration_result("input/2024/day07_test07.txt").unwrap()
         );
     }
 
@@ -231,7 +250,7 @@ mod tests {
     fn test_get_total_calibration_result_test08() {
         assert_eq!(
             10,
-            get_total_calibration_result("input/2024/day07_test08.txt")
+            get_total_calibration_result("input/2024/day07_test08.txt").unwrap()
         );
     }
 
@@ -239,7 +258,7 @@ mod tests {
     fn test_get_total_calibration_result_test09() {
         assert_eq!(
             0,
-            get_total_calibration_result("input/2024/day07_test09.txt")
+            get_total_calibration_result("input/2024/day07_test09.txt").unwrap()
         );
     }
 
@@ -247,7 +266,7 @@ mod tests {
     fn test_get_total_calibration_result_with_concat_test01() {
         assert_eq!(
             11387,
-            get_total_calibration_result_with_concat("input/2024/day07_test01.txt")
+            get_total_calibration_result_with_concat("input/2024/day07_test01.txt").unwrap()
         );
     }
 
@@ -255,7 +274,7 @@ mod tests {
     fn test_get_total_calibration_result_with_concat_test02() {
         assert_eq!(
             156,
-            get_total_calibration_result_with_concat("input/2024/day07_test02.txt")
+            get_total_calibration_result_with_concat("input/2024/day07_test02.txt").unwrap()
         );
     }
 
@@ -263,7 +282,7 @@ mod tests {
     fn test_get_total_calibration_result_with_concat_test03() {
         assert_eq!(
             7290,
-            get_total_calibration_result_with_concat("input/2024/day07_test03.txt")
+            get_total_calibration_result_with_concat("input/2024/day07_test03.txt").unwrap()
         );
     }
 
@@ -271,7 +290,7 @@ mod tests {
     fn test_get_total_calibration_result_with_concat_test04() {
         assert_eq!(
             192,
-            get_total_calibration_result_with_concat("input/2024/day07_test04.txt")
+            get_total_calibration_result_with_concat("input/2024/day07_test04.txt").unwrap()
         );
     }
 
@@ -279,7 +298,7 @@ mod tests {
     fn test_get_total_calibration_result_with_concat_test05() {
         assert_eq!(
             2,
-            get_total_calibration_result_with_concat("input/2024/day07_test05.txt")
+            get_total_calibration_result_with_concat("input/2024/day07_test05.txt").unwrap()
         );
     }
 
@@ -287,7 +306,7 @@ mod tests {
     fn test_get_total_calibration_result_with_concat_test06() {
         assert_eq!(
             507905413443,
-            get_total_calibration_result_with_concat("input/2024/day07_test06.txt")
+            get_total_calibration_result_with_concat("input/2024/day07_test06.txt").unwrap()
         );
     }
 
@@ -295,7 +314,7 @@ mod tests {
     fn test_get_total_calibration_result_with_concat_test07() {
         assert_eq!(
             3267,
-            get_total_calibration_result_with_concat("input/2024/day07_test07.txt")
+            get_total_calibration_result_with_concat("input/2024/day07_test07.txt").unwrap()
         );
     }
 
@@ -303,7 +322,7 @@ mod tests {
     fn test_get_total_calibration_result_with_concat_test08() {
         assert_eq!(
             10,
-            get_total_calibration_result_with_concat("input/2024/day07_test08.txt")
+            get_total_calibration_result_with_concat("input/2024/day07_test08.txt").unwrap()
         );
     }
 
@@ -311,7 +330,7 @@ mod tests {
     fn test_get_total_calibration_result_with_concat_test09() {
         assert_eq!(
             123,
-            get_total_calibration_result_with_concat("input/2024/day07_test09.txt")
+            get_total_calibration_result_with_concat("input/2024/day07_test09.txt").unwrap()
         );
     }
 
@@ -322,7 +341,7 @@ mod tests {
     fn test_get_total_distance_with_concat() {
         assert_eq!(
             105517128211543,
-            get_total_calibration_result_with_concat("input/2024/day07.txt")
+            get_total_calibration_result_with_concat("input/2024/day07.txt").unwrap()
         );
     }
 }
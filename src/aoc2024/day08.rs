@@ -2,97 +2,55 @@
 
 use std::collections::{HashMap, HashSet};
 
-use crate::utils::get_lines;
+use crate::error::AocResult;
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, Grid, Pos, PuzzleInput};
 
 struct Input {
-    map: Vec<Vec<char>>,
+    map: Grid<char>,
 }
 
-fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
+fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let map = Grid::parse(puzzle_input, Some)?;
 
-    let mut map: Vec<Vec<char>> = Vec::new();
-
-    for line in lines {
-        let map_line: Vec<char> = line.chars().collect();
-        map.push(map_line);
-    }
-
-    Input { map }
+    Ok(Input { map })
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 struct Node {
-    pos: (usize, usize),
+    pos: Pos,
     val: char,
 }
 
-fn get_node_map(map: &[Vec<char>]) -> HashMap<char, Vec<Node>> {
+fn get_node_map(map: &Grid<char>) -> HashMap<char, Vec<Node>> {
     map.iter()
-        .enumerate()
-        .flat_map(|(i, row)| {
-            row.iter()
-                .enumerate()
-                .filter(|(_, c)| **c != '.')
-                .map(move |(j, &val)| (val, Node { pos: (i, j), val }))
-        })
+        .filter(|(_, c)| **c != '.')
+        .map(|(pos, &val)| (val, Node { pos, val }))
         .fold(HashMap::new(), |mut acc, (key, node)| {
             acc.entry(key).or_default().push(node);
             acc
         })
 }
 
-fn get_antinode_pos(
-    (row, col): (usize, usize),
-    (other_row, other_col): (usize, usize),
-    antinode_map: &[Vec<char>],
-) -> Option<(usize, usize)> {
-    let row_diff = row as i32 - other_row as i32;
-    let col_diff = col as i32 - other_col as i32;
-    if row_diff.is_negative() && col_diff.is_negative() {
-        let new_row = row as i32 - row_diff.abs();
-        let new_col = col as i32 - col_diff.abs();
-        if new_row < 0 || new_col < 0 {
-            None
-        } else {
-            Some((new_row as usize, new_col as usize))
-        }
-    } else if row_diff.is_negative() && col_diff.is_positive() {
-        let new_row = row as i32 - row_diff.abs();
-        let new_col = col as i32 + col_diff.abs();
-        if new_row < 0 || new_col >= antinode_map[0].len() as i32 {
-            None
-        } else {
-            Some((new_row as usize, new_col as usize))
-        }
-    } else if row_diff.is_positive() && col_diff.is_negative() {
-        let new_row = row as i32 + row_diff.abs();
-        let new_col = col as i32 - col_diff.abs();
-        if new_row >= antinode_map.len() as i32 || new_col < 0 {
-            None
-        } else {
-            Some((new_row as usize, new_col as usize))
-        }
-    } else if row_diff.is_positive() && col_diff.is_positive() {
-        let new_row = row as i32 + row_diff.abs();
-        let new_col = col as i32 + col_diff.abs();
-        if new_row >= antinode_map.len() as i32 || new_col >= antinode_map[0].len() as i32 {
-            None
-        } else {
-            Some((new_row as usize, new_col as usize))
-        }
-    } else {
-        None
-    }
+/// The `(dx, dy)` step from `other` to `pos`.
+fn get_step((row, col): Pos, (other_row, other_col): Pos) -> (isize, isize) {
+    (
+        col as isize - other_col as isize,
+        row as isize - other_row as isize,
+    )
 }
 
-pub fn get_unique_antinodes(input_file: &str) -> u32 {
-    let input = parse_input(input_file);
+fn get_antinode_pos(pos: Pos, other: Pos, antinode_map: &Grid<char>) -> Option<Pos> {
+    antinode_map.offset(pos, get_step(pos, other))
+}
+
+pub fn get_unique_antinodes(puzzle_input: impl PuzzleInput) -> AocResult<u32> {
+    let input = parse_input(puzzle_input)?;
 
     let node_map: HashMap<char, Vec<Node>> = get_node_map(&input.map);
 
     let mut unique_antinodes: u32 = 0;
-    let mut antinode_pos_set: HashSet<(usize, usize)> = HashSet::new();
+    let mut antinode_pos_set: HashSet<Pos> = HashSet::new();
     let mut antinode_map = input.map.clone();
 
     for (_c, nodes) in node_map {
@@ -103,13 +61,11 @@ pub fn get_unique_antinodes(input_file: &str) -> u32 {
                 .cloned()
                 .collect::<Vec<Node>>();
             for other_node in other_nodes {
-                let antinode_pos: Option<(usize, usize)> =
+                let antinode_pos: Option<Pos> =
                     get_antinode_pos(node.pos, other_node.pos, &antinode_map);
 
                 if let Some((antinode_row, antinode_col)) = antinode_pos
-                    && let Some(map_node) = antinode_map
-                        .get(antinode_row)
-                        .and_then(|row| row.get(antinode_col))
+                    && let Some(map_node) = antinode_map.get((antinode_row, antinode_col))
                 {
                     match *map_node {
                         '.' => {
@@ -119,7 +75,7 @@ pub fn get_unique_antinodes(input_file: &str) -> u32 {
                                     *map_node,
                                     (antinode_row, antinode_col)
                                 );*/
-                                antinode_map[antinode_row][antinode_col] = '#';
+                                antinode_map[(antinode_row, antinode_col)] = '#';
                                 unique_antinodes += 1;
                                 antinode_pos_set.insert((antinode_row, antinode_col));
                             }
@@ -152,52 +108,25 @@ pub fn get_unique_antinodes(input_file: &str) -> u32 {
 
     //print_map(&antinode_map);
 
-    unique_antinodes
+    Ok(unique_antinodes)
 }
 
-fn get_antinode_pos_vec(
-    (row, col): (usize, usize),
-    (other_row, other_col): (usize, usize),
-    antinode_map: &[Vec<char>],
-) -> Vec<(usize, usize)> {
-    let mut antinode_pos_vec = vec![];
-    antinode_pos_vec.push((row, col));
-    antinode_pos_vec.push((other_row, other_col));
-    let row_diff = row as i32 - other_row as i32;
-    let col_diff = col as i32 - other_col as i32;
-    let mut count = 0;
-    loop {
-        let (curr_row, curr_col) = (row as i32 + row_diff * count, col as i32 + col_diff * count);
-        let (next_row, next_col) = (
-            other_row as i32 + row_diff * count,
-            other_col as i32 + col_diff * count,
-        );
-        let antinode_pos = get_antinode_pos(
-            (curr_row as usize, curr_col as usize),
-            (next_row as usize, next_col as usize),
-            antinode_map,
-        );
-        if let Some((antinode_row, antinode_col)) = antinode_pos {
-            antinode_pos_vec.push((antinode_row, antinode_col));
-            if antinode_row == other_row && antinode_col == other_col {
-                break;
-            }
-        } else {
-            break;
-        }
-
-        count += 1;
-    }
+fn get_antinode_pos_vec(pos: Pos, other: Pos, antinode_map: &Grid<char>) -> Vec<Pos> {
+    let step = get_step(pos, other);
+    let mut antinode_pos_vec = vec![other];
+    antinode_pos_vec.extend(std::iter::successors(Some(pos), |&pos| {
+        antinode_map.offset(pos, step)
+    }));
     antinode_pos_vec
 }
 
-pub fn get_unique_antinodes_with_hr(input_file: &str) -> u32 {
-    let input = parse_input(input_file);
+pub fn get_unique_antinodes_with_hr(puzzle_input: impl PuzzleInput) -> AocResult<u32> {
+    let input = parse_input(puzzle_input)?;
 
     let node_map: HashMap<char, Vec<Node>> = get_node_map(&input.map);
 
     let mut unique_antinodes: u32 = 0;
-    let mut antinode_pos_set: HashSet<(usize, usize)> = HashSet::new();
+    let mut antinode_pos_set: HashSet<Pos> = HashSet::new();
     let mut antinode_map = input.map.clone();
 
     for (_c, nodes) in node_map {
@@ -208,14 +137,11 @@ pub fn get_unique_antinodes_with_hr(input_file: &str) -> u32 {
                 .cloned()
                 .collect::<Vec<Node>>();
             for other_node in other_nodes {
-                let antinode_pos_vec: Vec<(usize, usize)> =
+                let antinode_pos_vec: Vec<Pos> =
                     get_antinode_pos_vec(node.pos, other_node.pos, &antinode_map);
 
                 for (antinode_row, antinode_col) in antinode_pos_vec {
-                    if let Some(map_node) = antinode_map
-                        .get(antinode_row)
-                        .and_then(|row| row.get(antinode_col))
-                    {
+                    if let Some(map_node) = antinode_map.get((antinode_row, antinode_col)) {
                         match *map_node {
                             '.' => {
                                 if !antinode_pos_set.contains(&(antinode_row, antinode_col)) {
@@ -224,7 +150,7 @@ pub fn get_unique_antinodes_with_hr(input_file: &str) -> u32 {
                                         *map_node,
                                         (antinode_row, antinode_col)
                                     );*/
-                                    antinode_map[antinode_row][antinode_col] = '#';
+                                    antinode_map[(antinode_row, antinode_col)] = '#';
                                     unique_antinodes += 1;
                                     antinode_pos_set.insert((antinode_row, antinode_col));
                                 }
@@ -258,7 +184,24 @@ pub fn get_unique_antinodes_with_hr(input_file: &str) -> u32 {
 
     //print_map(&antinode_map);
 
-    unique_antinodes
+    Ok(unique_antinodes)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_unique_antinodes(Contents(input))?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_unique_antinodes_with_hr(Contents(input))?.into())
+    }
 }
 
 #[cfg(test)]
@@ -267,34 +210,46 @@ mod tests {
 
     #[test]
     fn test_get_unique_antinodes_test01() {
-        assert_eq!(14, get_unique_antinodes("input/2024/day08_test01.txt"));
+        assert_eq!(
+            14,
+            get_unique_antinodes("input/2024/day08_test01.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_unique_antinodes_test02() {
-        assert_eq!(2, get_unique_antinodes("input/2024/day08_test02.txt"));
+        assert_eq!(
+            2,
+            get_unique_antinodes("input/2024/day08_test02.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_unique_antinodes_test03() {
-        assert_eq!(4, get_unique_antinodes("input/2024/day08_test03.txt"));
+        assert_eq!(
+            4,
+            get_unique_antinodes("input/2024/day08_test03.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_unique_antinodes_test04() {
-        assert_eq!(4, get_unique_antinodes("input/2024/day08_test04.txt"));
+        assert_eq!(
+            4,
+            get_unique_antinodes("input/2024/day08_test04.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_unique_antinodes() {
-        assert_eq!(261, get_unique_antinodes("input/2024/day08.txt"));
+        assert_eq!(261, get_unique_antinodes("input/2024/day08.txt").unwrap());
     }
 
     #[test]
     fn test_get_unique_antinodes_with_hr_test01() {
         assert_eq!(
             34,
-            get_unique_antinodes_with_hr("input/2024/day08_test01.txt")
+            get_unique_antinodes_with_hr("input/2024/day08_test01.txt").unwrap()
         );
     }
 
@@ -302,7 +257,7 @@ mod tests {
     fn test_get_unique_antinodes_with_hr_test05() {
         assert_eq!(
             9,
-            get_unique_antinodes_with_hr("input/2024/day08_test05.txt")
+            get_unique_antinodes_with_hr("input/2024/day08_test05.txt").unwrap()
         );
     }
 
@@ -310,12 +265,15 @@ mod tests {
     fn test_get_unique_antinodes_with_hr_test06() {
         assert_eq!(
             9,
-            get_unique_antinodes_with_hr("input/2024/day08_test06.txt")
+            get_unique_antinodes_with_hr("input/2024/day08_test06.txt").unwrap()
         );
     }
 
     #[test]
     fn test_get_unique_antinodes_with_hr() {
-        assert_eq!(898, get_unique_antinodes_with_hr("input/2024/day08.txt"));
+        assert_eq!(
+            898,
+            get_unique_antinodes_with_hr("input/2024/day08.txt").unwrap()
+        );
     }
 }
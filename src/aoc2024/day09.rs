@@ -2,7 +2,9 @@
 
 use std::collections::HashSet;
 
-use crate::utils::get_lines;
+use crate::error::{AocError, AocResult};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput, get_lines};
 
 struct InputPartOne {
     disk: Vec<DiskEntry>,
@@ -31,69 +33,73 @@ struct DiskEntryWithLen {
     len: usize,
 }
 
-fn parse_input_part_one(input_file: &str) -> InputPartOne {
-    let lines = get_lines(input_file);
-
-    if let Some(line) = lines.first() {
-        let chars: Vec<char> = line.chars().collect();
-
-        let mut id = 0usize;
-        let disk: Vec<DiskEntry> =
-            chars
-                .into_iter()
-                .enumerate()
-                .fold(vec![], |mut acc: Vec<DiskEntry>, (i, c)| {
-                    if i % 2 != 0 {
-                        id += 1;
-                    }
-                    for _ in 0..c.to_digit(10).unwrap_or(0) {
-                        acc.push(DiskEntry {
-                            id: if i % 2 == 0 { Some(id) } else { None },
-                            entry: if i % 2 == 0 {
-                                DiskEntryType::File
-                            } else {
-                                DiskEntryType::FreeSpace
-                            },
-                        });
-                    }
-                    acc
-                });
-        InputPartOne { disk }
-    } else {
-        panic!("Invalid input file: {input_file}");
-    }
+fn parse_disk_map(lines: &[String]) -> AocResult<Vec<usize>> {
+    let line = lines
+        .first()
+        .ok_or_else(|| AocError::invalid("no disk map"))?;
+    line.chars()
+        .enumerate()
+        .map(|(i, c)| {
+            c.to_digit(10)
+                .map(|len| len as usize)
+                .ok_or(AocError::unexpected_char(0, i, c))
+        })
+        .collect()
 }
 
-fn parse_input_part_two(input_file: &str) -> InputPartTwo {
-    let lines = get_lines(input_file);
-
-    if let Some(line) = lines.first() {
-        let chars: Vec<char> = line.chars().collect();
-
-        let mut id = 0usize;
-        let disk: Vec<DiskEntryWithLen> =
-            chars
-                .into_iter()
-                .enumerate()
-                .fold(vec![], |mut acc: Vec<DiskEntryWithLen>, (i, c)| {
-                    if i % 2 != 0 {
-                        id += 1;
-                    }
-                    acc.push(DiskEntryWithLen {
+fn parse_input_part_one(puzzle_input: impl PuzzleInput) -> AocResult<InputPartOne> {
+    let lines = get_lines(puzzle_input)?;
+
+    let lens = parse_disk_map(&lines)?;
+
+    let mut id = 0usize;
+    let disk: Vec<DiskEntry> =
+        lens.into_iter()
+            .enumerate()
+            .fold(vec![], |mut acc: Vec<DiskEntry>, (i, len)| {
+                if i % 2 != 0 {
+                    id += 1;
+                }
+                for _ in 0..len {
+                    acc.push(DiskEntry {
                         id: if i % 2 == 0 { Some(id) } else { None },
                         entry: if i % 2 == 0 {
                             DiskEntryType::File
                         } else {
                             DiskEntryType::FreeSpace
                         },
-                        len: c.to_digit(10).unwrap() as usize,
                     });
-                    acc
+                }
+                acc
+            });
+    Ok(InputPartOne { disk })
+}
+
+fn parse_input_part_two(puzzle_input: impl PuzzleInput) -> AocResult<InputPartTwo> {
+    let lines = get_lines(puzzle_input)?;
+
+    let lens = parse_disk_map(&lines)?;
+
+    let mut id = 0usize;
+    let disk: Vec<DiskEntryWithLen> =
+        lens.into_iter()
+            .enumerate()
+            .fold(vec![], |mut acc: Vec<DiskEntryWithLen>, (i, len)| {
+                if i % 2 != 0 {
+                    id += 1;
+                }
+                acc.push(DiskEntryWithLen {
+                    id: if i % 2 == 0 { Some(id) } else { None },
+                    entry: if i % 2 == 0 {
+                        DiskEntryType::File
+                    } else {
+                        DiskEntryType::FreeSpace
+                    },
+                    len,
                 });
-        InputPartTwo { disk }
-    } else {
-        panic!("Invalid input file: {input_file}");
-    }
+                acc
+            });
+    Ok(InputPartTwo { disk })
 }
 
 fn find_first_free_space_block(blocks: &[DiskEntry]) -> Option<usize> {
@@ -130,8 +136,8 @@ fn calc_checksum(blocks: &[DiskEntry]) -> usize {
         .sum()
 }
 
-pub fn get_checksum(input_file: &str) -> usize {
-    let input = parse_input_part_one(input_file);
+pub fn get_checksum(puzzle_input: impl PuzzleInput) -> AocResult<usize> {
+    let input = parse_input_part_one(puzzle_input)?;
 
     let mut blocks = input.disk.clone();
 
@@ -153,7 +159,7 @@ pub fn get_checksum(input_file: &str) -> usize {
 
     //println!("{:?}", blocks);
 
-    calc_checksum(&blocks)
+    Ok(calc_checksum(&blocks))
 }
 
 /*fn print_blocks(blocks: &Vec<DiskEntryWithLen>) {
@@ -210,8 +216,8 @@ fn calc_checksum_whole_files(blocks: &[DiskEntryWithLen]) -> usize {
     checksum
 }
 
-pub fn get_checksum_whole_files(input_file: &str) -> usize {
-    let input = parse_input_part_two(input_file);
+pub fn get_checksum_whole_files(puzzle_input: impl PuzzleInput) -> AocResult<usize> {
+    let input = parse_input_part_two(puzzle_input)?;
 
     let mut blocks = input.disk.clone();
 
@@ -283,7 +289,24 @@ pub fn get_checksum_whole_files(input_file: &str) -> usize {
         }
     }
 
-    calc_checksum_whole_files(&blocks)
+    Ok(calc_checksum_whole_files(&blocks))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_disk_map(&get_lines(Contents(input))?)?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_checksum(Contents(input))?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_checksum_whole_files(Contents(input))?.into())
+    }
 }
 
 #[cfg(test)]
@@ -292,22 +315,22 @@ mod tests {
 
     #[test]
     fn test_get_checksum_test01() {
-        assert_eq!(1928, get_checksum("input/2024/day09_test01.txt"));
+        assert_eq!(1928, get_checksum("input/2024/day09_test01.txt").unwrap());
     }
 
     #[test]
     fn test_get_checksum_test02() {
-        assert_eq!(60, get_checksum("input/2024/day09_test02.txt"));
+        assert_eq!(60, get_checksum("input/2024/day09_test02.txt").unwrap());
     }
 
     #[test]
     fn test_get_checksum_test03() {
-        assert_eq!(513, get_checksum("input/2024/day09_test03.txt"));
+        assert_eq!(513, get_checksum("input/2024/day09_test03.txt").unwrap());
     }
 
     #[test]
     fn test_get_checksum_test04() {
-        assert_eq!(57, get_checksum("input/2024/day09_test04.txt"));
+        assert_eq!(57, get_checksum("input/2024/day09_test04.txt").unwrap());
     }
 
     // This test takes a while so ignore in CI
@@ -315,14 +338,14 @@ mod tests {
     #[ignore]
     #[test]
     fn test_get_checksum() {
-        assert_eq!(6435922584968, get_checksum("input/2024/day09.txt"));
+        assert_eq!(6435922584968, get_checksum("input/2024/day09.txt").unwrap());
     }
 
     #[test]
     fn test_get_checksum_whole_files_test01() {
         assert_eq!(
             2858,
-            get_checksum_whole_files("input/2024/day09_test01.txt")
+            get_checksum_whole_files("input/2024/day09_test01.txt").unwrap()
         );
     }
 
@@ -333,7 +356,7 @@ mod tests {
     fn test_get_checksum_whole_files() {
         assert_eq!(
             6469636832766,
-            get_checksum_whole_files("input/2024/day09.txt")
+            get_checksum_whole_files("input/2024/day09.txt").unwrap()
         );
     }
 }
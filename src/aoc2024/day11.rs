@@ -2,7 +2,11 @@
 
 use std::collections::HashMap;
 
-use crate::utils::get_lines;
+use log::debug;
+
+use crate::error::{AocResult, parse_token};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput, get_lines};
 
 use itertools::Itertools;
 
@@ -10,23 +14,23 @@ struct Input {
     stones: Vec<usize>,
 }
 
-fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
+fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let lines = get_lines(puzzle_input)?;
 
     let mut stones: Vec<usize> = Vec::new();
 
-    for line in lines {
+    for (line_idx, line) in lines.iter().enumerate() {
         let line_parts: Vec<&str> = line.split_whitespace().collect();
         for line_part in line_parts {
-            stones.push(line_part.parse::<usize>().unwrap());
+            stones.push(parse_token(line_idx, line, line_part)?);
         }
     }
 
-    Input { stones }
+    Ok(Input { stones })
 }
 
 fn print_stones(stones: &[usize]) {
-    println!("{}", stones.iter().format(" "));
+    debug!("{}", stones.iter().format(" "));
 }
 
 fn get_new_stones(stone: usize) -> Vec<usize> {
@@ -51,12 +55,11 @@ fn get_new_stones(stone: usize) -> Vec<usize> {
     new_stones
 }
 
-pub fn get_num_stones(input_file: &str, blinks: usize) -> usize {
-    let input = parse_input(input_file);
+pub fn get_num_stones(puzzle_input: impl PuzzleInput, blinks: usize) -> AocResult<usize> {
+    let input = parse_input(puzzle_input)?;
 
-    println!("Initial arrangement:");
+    debug!("Initial arrangement:");
     print_stones(&input.stones);
-    println!();
 
     let mut stones: Vec<usize> = input.stones.clone();
 
@@ -71,7 +74,7 @@ pub fn get_num_stones(input_file: &str, blinks: usize) -> usize {
         print_stones(&stones);
     }
 
-    stones.len()
+    Ok(stones.len())
 }
 
 fn count_stones(
@@ -110,8 +113,8 @@ fn count_stones(
     num_stones
 }
 
-pub fn get_num_stones_memoize(input_file: &str, blinks: usize) -> usize {
-    let input = parse_input(input_file);
+pub fn get_num_stones_memoize(puzzle_input: impl PuzzleInput, blinks: usize) -> AocResult<usize> {
+    let input = parse_input(puzzle_input)?;
 
     /*println!("Initial arrangement:");
     print_stones(&input.stones);
@@ -127,7 +130,24 @@ pub fn get_num_stones_memoize(input_file: &str, blinks: usize) -> usize {
         num_stones += count_stones(stone, blinks, &mut stones_cache)
     }
 
-    num_stones
+    Ok(num_stones)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_num_stones_memoize(Contents(input), 25)?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_num_stones_memoize(Contents(input), 75)?.into())
+    }
 }
 
 #[cfg(test)]
@@ -136,102 +156,135 @@ mod tests {
 
     #[test]
     fn test_get_num_stones_test01() {
-        assert_eq!(7, get_num_stones("input/2024/day11_test01.txt", 1));
+        assert_eq!(7, get_num_stones("input/2024/day11_test01.txt", 1).unwrap());
     }
 
     #[test]
     fn test_get_num_stones_test02() {
-        assert_eq!(3, get_num_stones("input/2024/day11_test02.txt", 1));
+        assert_eq!(3, get_num_stones("input/2024/day11_test02.txt", 1).unwrap());
     }
 
     #[test]
     fn test_get_num_stones_test03() {
-        assert_eq!(4, get_num_stones("input/2024/day11_test02.txt", 2));
+        assert_eq!(4, get_num_stones("input/2024/day11_test02.txt", 2).unwrap());
     }
 
     #[test]
     fn test_get_num_stones_test04() {
-        assert_eq!(5, get_num_stones("input/2024/day11_test02.txt", 3));
+        assert_eq!(5, get_num_stones("input/2024/day11_test02.txt", 3).unwrap());
     }
 
     #[test]
     fn test_get_num_stones_test05() {
-        assert_eq!(9, get_num_stones("input/2024/day11_test02.txt", 4));
+        assert_eq!(9, get_num_stones("input/2024/day11_test02.txt", 4).unwrap());
     }
 
     #[test]
     fn test_get_num_stones_test06() {
-        assert_eq!(13, get_num_stones("input/2024/day11_test02.txt", 5));
+        assert_eq!(
+            13,
+            get_num_stones("input/2024/day11_test02.txt", 5).unwrap()
+        );
     }
 
     #[test]
     fn test_get_num_stones_test07() {
-        assert_eq!(22, get_num_stones("input/2024/day11_test02.txt", 6));
+        assert_eq!(
+            22,
+            get_num_stones("input/2024/day11_test02.txt", 6).unwrap()
+        );
     }
 
     #[test]
     fn test_get_num_stones_test08() {
-        assert_eq!(55312, get_num_stones("input/2024/day11_test02.txt", 25));
+        assert_eq!(
+            55312,
+            get_num_stones("input/2024/day11_test02.txt", 25).unwrap()
+        );
     }
 
     #[test]
     fn test_get_num_stones() {
-        assert_eq!(187738, get_num_stones("input/2024/day11.txt", 25));
+        assert_eq!(187738, get_num_stones("input/2024/day11.txt", 25).unwrap());
     }
 
     #[test]
     fn test_get_num_stones_memoize_test01() {
-        assert_eq!(7, get_num_stones_memoize("input/2024/day11_test01.txt", 1));
+        assert_eq!(
+            7,
+            get_num_stones_memoize("input/2024/day11_test01.txt", 1).unwrap()
+        );
     }
 
     #[test]
     fn test_get_num_stones_memoize_test02() {
-        assert_eq!(3, get_num_stones_memoize("input/2024/day11_test02.txt", 1));
+        assert_eq!(
+            3,
+            get_num_stones_memoize("input/2024/day11_test02.txt", 1).unwrap()
+        );
     }
 
     #[test]
     fn test_get_num_stones_memoize_test03() {
-        assert_eq!(4, get_num_stones_memoize("input/2024/day11_test02.txt", 2));
+        assert_eq!(
+            4,
+            get_num_stones_memoize("input/2024/day11_test02.txt", 2).unwrap()
+        );
     }
 
     #[test]
     fn test_get_num_stones_memoize_test04() {
-        assert_eq!(5, get_num_stones_memoize("input/2024/day11_test02.txt", 3));
+        assert_eq!(
+            5,
+            get_num_stones_memoize("input/2024/day11_test02.txt", 3).unwrap()
+        );
     }
 
     #[test]
     fn test_get_num_stones_memoize_test05() {
-        assert_eq!(9, get_num_stones_memoize("input/2024/day11_test02.txt", 4));
+        assert_eq!(
+            9,
+            get_num_stones_memoize("input/2024/day11_test02.txt", 4).unwrap()
+        );
     }
 
     #[test]
     fn test_get_num_stones_memoize_test06() {
-        assert_eq!(13, get_num_stones_memoize("input/2024/day11_test02.txt", 5));
+        assert_eq!(
+            13,
+            get_num_stones_memoize("input/2024/day11_test02.txt", 5).unwrap()
+        );
     }
 
     #[test]
     fn test_get_num_stones_memoize_test07() {
-        assert_eq!(22, get_num_stones_memoize("input/2024/day11_test02.txt", 6));
+        assert_eq!(
+            22,
+            get_num_stones_memoize("input/2024/day11_test02.txt", 6).unwrap()
+        );
     }
 
     #[test]
     fn test_get_num_stones_memoize_test08() {
         assert_eq!(
             55312,
-            get_num_stones_memoize("input/2024/day11_test02.txt", 25)
+            get_num_stones_memoize("input/2024/day11_test02.txt", 25).unwrap()
         );
     }
 
     #[test]
     fn test_get_num_stones_memoize_25_blinks() {
-        assert_eq!(187738, get_num_stones_memoize("input/2024/day11.txt", 25));
+        assert_eq!(
+            187738,
+            get_num_stones_memoize("input/2024/day11.txt", 25).unwrap()
+        );
     }
 
     #[test]
     fn test_get_num_stones_memoize_75_blinks() {
         assert_eq!(
             223767210249237,
-            get_num_stones_memoize("input/2024/day11.txt", 75)
+            get_num_stones_memoize("input/2024/day11.txt", 75).unwrap()
         );
     }
 }
@@ -2,34 +2,22 @@
 
 use std::collections::{HashMap, HashSet};
 
-use crate::utils::get_lines;
+use log::debug;
+
+use crate::error::AocResult;
+use crate::solution::{Answer, Solution};
+use crate::utils::{Compass, Contents, Grid, Pos, PuzzleInput};
 
 struct Input {
-    plants: Vec<Vec<char>>,
+    plants: Grid<char>,
 }
 
-fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
-
-    let mut plants: Vec<Vec<char>> = Vec::new();
-
-    for line in lines {
-        let report: Vec<char> = line.chars().collect();
-        plants.push(report);
-    }
+fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let plants = Grid::parse(puzzle_input, Some)?;
 
-    Input { plants }
+    Ok(Input { plants })
 }
 
-/*fn print_plants(plants: &Vec<Vec<char>>) {
-    for row in plants {
-        for level in row {
-            print!("{}", level);
-        }
-        println!();
-    }
-}*/
-
 #[derive(Debug)]
 struct Region {
     area: usize,
@@ -49,12 +37,12 @@ impl Region {
 
 fn flood_fill(
     plant: char,
-    plant_pos: (usize, usize),
-    plants: &[Vec<char>],
+    plant_pos: Pos,
+    plants: &Grid<char>,
     regions: &mut HashMap<char, Vec<Region>>,
-    visited_plants: &mut HashSet<(usize, usize)>,
+    visited_plants: &mut HashSet<Pos>,
 ) {
-    let mut stack: Vec<(usize, usize)> = Vec::new();
+    let mut stack: Vec<Pos> = Vec::new();
 
     // Add current node to the stack
     stack.push(plant_pos);
@@ -63,77 +51,45 @@ fn flood_fill(
     let mut perimeter = 0;
     let mut corners = 0;
 
-    while let Some((row, col)) = stack.pop() {
+    while let Some(pos) = stack.pop() {
         // This makes sure we don't double count positions
-        if visited_plants.contains(&(row, col)) {
+        if visited_plants.contains(&pos) {
             continue;
         }
 
         // Is this 'Inside'?
-        if plants[row][col] == plant {
+        if plants[pos] == plant {
             // If so, increment the area, and mark as visited
             area += 1;
-            visited_plants.insert((row, col));
-
-            let n_dir = if row as i32 - 1 < 0 || plants[row - 1][col] != plant {
-                perimeter += 1;
-                None
-            } else {
-                stack.push((row - 1, col));
-                Some((row - 1, col))
-            };
-            let nw_dir =
-                if row as i32 - 1 < 0 || col as i32 - 1 < 0 || plants[row - 1][col - 1] != plant {
-                    None
-                } else {
-                    Some((row - 1, col - 1))
-                };
-            let w_dir = if col as i32 - 1 < 0 || plants[row][col - 1] != plant {
-                perimeter += 1;
-                None
-            } else {
-                stack.push((row, col - 1));
-                Some((row, col - 1))
-            };
-            let sw_dir = if row as i32 + 1 >= plants.len() as i32
-                || col as i32 - 1 < 0
-                || plants[row + 1][col - 1] != plant
-            {
-                None
-            } else {
-                Some((row + 1, col - 1))
-            };
-            let s_dir = if row as i32 + 1 >= plants.len() as i32 || plants[row + 1][col] != plant {
-                perimeter += 1;
-                None
-            } else {
-                stack.push((row + 1, col));
-                Some((row + 1, col))
-            };
-            let se_dir = if row as i32 + 1 >= plants.len() as i32
-                || col as i32 + 1 >= plants[0].len() as i32
-                || plants[row + 1][col + 1] != plant
-            {
-                None
-            } else {
-                Some((row + 1, col + 1))
-            };
-            let e_dir = if col as i32 + 1 >= plants[0].len() as i32 || plants[row][col + 1] != plant
-            {
-                perimeter += 1;
-                None
-            } else {
-                stack.push((row, col + 1));
-                Some((row, col + 1))
-            };
-            let ne_dir = if row as i32 - 1 < 0
-                || col as i32 + 1 >= plants[0].len() as i32
-                || plants[row - 1][col + 1] != plant
-            {
-                None
-            } else {
-                Some((row - 1, col + 1))
-            };
+            visited_plants.insert(pos);
+
+            // The directions whose neighbor is in the same region
+            let inside_dirs: Vec<Compass> = plants
+                .neighbors8(pos)
+                .filter(|&(_, next)| plants[next] == plant)
+                .map(|(dir, _)| dir)
+                .collect();
+            let inside = |dir: Compass| inside_dirs.contains(&dir);
+
+            for (_, next) in plants.neighbors4(pos) {
+                if plants[next] == plant {
+                    stack.push(next);
+                }
+            }
+
+            let n_dir = inside(Compass::North);
+            let ne_dir = inside(Compass::NorthEast);
+            let e_dir = inside(Compass::East);
+            let se_dir = inside(Compass::SouthEast);
+            let s_dir = inside(Compass::South);
+            let sw_dir = inside(Compass::SouthWest);
+            let w_dir = inside(Compass::West);
+            let nw_dir = inside(Compass::NorthWest);
+
+            perimeter += [n_dir, e_dir, s_dir, w_dir]
+                .into_iter()
+                .filter(|inside| !inside)
+                .count();
 
             // We want to check concave and convex corners
 
@@ -142,16 +98,16 @@ fn flood_fill(
             // ###<- This is a convex corner
             // ###
 
-            if n_dir.is_none() && e_dir.is_none() {
+            if !n_dir && !e_dir {
                 corners += 1;
             }
-            if s_dir.is_none() && e_dir.is_none() {
+            if !s_dir && !e_dir {
                 corners += 1;
             }
-            if s_dir.is_none() && w_dir.is_none() {
+            if !s_dir && !w_dir {
                 corners += 1;
             }
-            if n_dir.is_none() && w_dir.is_none() {
+            if !n_dir && !w_dir {
                 corners += 1;
             }
 
@@ -160,16 +116,16 @@ fn flood_fill(
             //                           ##.
             // This is a concave corner->###
 
-            if n_dir.is_some() && e_dir.is_some() && ne_dir.is_none() {
+            if n_dir && e_dir && !ne_dir {
                 corners += 1;
             }
-            if s_dir.is_some() && e_dir.is_some() && se_dir.is_none() {
+            if s_dir && e_dir && !se_dir {
                 corners += 1;
             }
-            if s_dir.is_some() && w_dir.is_some() && sw_dir.is_none() {
+            if s_dir && w_dir && !sw_dir {
                 corners += 1;
             }
-            if n_dir.is_some() && w_dir.is_some() && nw_dir.is_none() {
+            if n_dir && w_dir && !nw_dir {
                 corners += 1;
             }
         }
@@ -188,39 +144,31 @@ fn flood_fill(
     }
 }
 
-pub fn get_price_fencing_all_regions(input_file: &str, apply_discount: bool) -> usize {
-    let input = parse_input(input_file);
+pub fn get_price_fencing_all_regions(
+    puzzle_input: impl PuzzleInput,
+    apply_discount: bool,
+) -> AocResult<usize> {
+    let input = parse_input(puzzle_input)?;
 
-    //print_plants(&input.plants);
+    debug!("\n{}", input.plants);
 
-    let plant_pos_list: Vec<(char, (usize, usize))> = input
+    let plant_pos_list: Vec<(char, Pos)> = input
         .plants
         .iter()
-        .enumerate()
-        .flat_map(|(row_index, row)| {
-            row.iter()
-                .enumerate()
-                .map(move |(col_index, plant)| (*plant, (row_index, col_index)))
-        })
-        .collect::<Vec<(char, (usize, usize))>>();
+        .map(|(pos, plant)| (*plant, pos))
+        .collect();
 
     let mut regions: HashMap<char, Vec<Region>> = HashMap::new();
-    let mut visited_plants: HashSet<(usize, usize)> = HashSet::new();
-
-    for (plant, (row, col)) in plant_pos_list {
-        flood_fill(
-            plant,
-            (row, col),
-            &input.plants,
-            &mut regions,
-            &mut visited_plants,
-        );
+    let mut visited_plants: HashSet<Pos> = HashSet::new();
+
+    for (plant, pos) in plant_pos_list {
+        flood_fill(plant, pos, &input.plants, &mut regions, &mut visited_plants);
     }
 
     //println!("Regions: {:?}", regions);
 
     if apply_discount {
-        regions
+        Ok(regions
             .into_values()
             .map(|region| {
                 region
@@ -228,9 +176,9 @@ pub fn get_price_fencing_all_regions(input_file: &str, apply_discount: bool) ->
                     .map(|r| r.area * r.corners)
                     .sum::<usize>()
             })
-            .sum()
+            .sum())
     } else {
-        regions
+        Ok(regions
             .into_values()
             .map(|region| {
                 region
@@ -238,7 +186,24 @@ pub fn get_price_fencing_all_regions(input_file: &str, apply_discount: bool) ->
                     .map(|r| r.area * r.perimeter)
                     .sum::<usize>()
             })
-            .sum()
+            .sum())
+    }
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_price_fencing_all_regions(Contents(input), false)?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_price_fencing_all_regions(Contents(input), true)?.into())
     }
 }
 
@@ -250,7 +215,7 @@ mod tests {
     fn test_get_price_fencing_all_regions_test01() {
         assert_eq!(
             140,
-            get_price_fencing_all_regions("input/2024/day12_test01.txt", false)
+            get_price_fencing_all_regions("input/2024/day12_test01.txt", false).unwrap()
         );
     }
 
@@ -258,7 +223,7 @@ mod tests {
     fn test_get_price_fencing_all_regions_test02() {
         assert_eq!(
             772,
-            get_price_fencing_all_regions("input/2024/day12_test02.txt", false)
+            get_price_fencing_all_regions("input/2024/day12_test02.txt", false).unwrap()
         );
     }
 
@@ -266,7 +231,7 @@ mod tests {
     fn test_get_price_fencing_all_regions_test03() {
         assert_eq!(
             1930,
-            get_price_fencing_all_regions("input/2024/day12_test03.txt", false)
+            get_price_fencing_all_regions("input/2024/day12_test03.txt", false).unwrap()
         );
     }
 
@@ -274,7 +239,7 @@ mod tests {
     fn test_get_price_fencing_all_regions_test04() {
         assert_eq!(
             692,
-            get_price_fencing_all_regions("input/2024/day12_test04.txt", false)
+            get_price_fencing_all_regions("input/2024/day12_test04.txt", false).unwrap()
         );
     }
 
@@ -282,7 +247,7 @@ mod tests {
     fn test_get_price_fencing_all_regions_test05() {
         assert_eq!(
             1184,
-            get_price_fencing_all_regions("input/2024/day12_test05.txt", false)
+            get_price_fencing_all_regions("input/2024/day12_test05.txt", false).unwrap()
         );
     }
 
@@ -290,7 +255,7 @@ mod tests {
     fn test_get_price_fencing_all_regions() {
         assert_eq!(
             1363682,
-            get_price_fencing_all_regions("input/2024/day12.txt", false)
+            get_price_fencing_all_regions("input/2024/day12.txt", false).unwrap()
         );
     }
 
@@ -298,7 +263,7 @@ mod tests {
     fn test_get_price_fencing_all_regions_bulk_discount_test01() {
         assert_eq!(
             80,
-            get_price_fencing_all_regions("input/2024/day12_test01.txt", true)
+            get_price_fencing_all_regions("input/2024/day12_test01.txt", true).unwrap()
         );
     }
 
@@ -306,7 +271,7 @@ mod tests {
     fn test_get_price_fencing_all_regions_bulk_discount_test02() {
         assert_eq!(
             436,
-            get_price_fencing_all_regions("input/2024/day12_test02.txt", true)
+            get_price_fencing_all_regions("input/2024/day12_test02.txt", true).unwrap()
         );
     }
 
@@ -314,7 +279,7 @@ mod tests {
     fn test_get_price_fencing_all_regions_bulk_discount_test03() {
         assert_eq!(
             1206,
-            get_price_fencing_all_regions("input/2024/day12_test03.txt", true)
+            get_price_fencing_all_regions("input/2024/day12_test03.txt", true).unwrap()
         );
     }
 
@@ -322,7 +287,7 @@ mod tests {
     fn test_get_price_fencing_all_regions_bulk_discount_test04() {
         assert_eq!(
             236,
-            get_price_fencing_all_regions("input/2024/day12_test04.txt", true)
+            get_price_fencing_all_regions("input/2024/day12_test04.txt", true).unwrap()
         );
     }
 
@@ -330,7 +295,7 @@ mod tests {
     fn test_get_price_fencing_all_regions_bulk_discount_test05() {
         assert_eq!(
             368,
-            get_price_fencing_all_regions("input/2024/day12_test05.txt", true)
+            get_price_fencing_all_regions("input/2024/day12_test05.txt", true).unwrap()
         );
     }
 
@@ -338,7 +303,7 @@ mod tests {
     fn test_get_price_fencing_all_regions_bulk_discount_() {
         assert_eq!(
             787680,
-            get_price_fencing_all_regions("input/2024/day12.txt", true)
+            get_price_fencing_all_regions("input/2024/day12.txt", true).unwrap()
         );
     }
 }
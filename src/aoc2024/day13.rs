@@ -3,98 +3,87 @@
 use nalgebra::{Matrix2, Vector2};
 use regex::Regex;
 
-use crate::utils::get_lines;
-
-#[derive(Clone, Copy, Debug)]
-struct GamePosition {
-    x_right: usize,
-    y_forward: usize,
-}
+use crate::error::{AocError, AocResult, parse_token};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, Point, PuzzleInput, get_lines};
 
 #[derive(Debug)]
 struct Game {
-    button_a: GamePosition,
-    button_b: GamePosition,
-    prize: GamePosition,
+    button_a: Point<usize>,
+    button_b: Point<usize>,
+    prize: Point<usize>,
 }
 
 struct Input {
     games: Vec<Game>,
 }
 
-fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
+fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let lines = get_lines(puzzle_input)?;
 
     let mut games: Vec<Game> = Vec::new();
 
-    lines.split(|line| line.trim().is_empty()).for_each(|game| {
+    let mut first_line_idx = 0;
+    for game in lines.split(|line| line.trim().is_empty()) {
         if let [button_a, button_b, prize] = game {
             games.push(Game {
-                button_a: parse_button_a(button_a),
-                button_b: parse_button_b(button_b),
-                prize: parse_prize(prize),
+                button_a: parse_button_a(first_line_idx, button_a)?,
+                button_b: parse_button_b(first_line_idx + 1, button_b)?,
+                prize: parse_prize(first_line_idx + 2, prize)?,
             });
         }
-    });
-    Input { games }
+        first_line_idx += game.len() + 1;
+    }
+    Ok(Input { games })
 }
 
-fn parse_button_a(button_a: &str) -> GamePosition {
-    let re = Regex::new(r"Button A: X\+(\d+), Y\+(\d+)").unwrap();
-    let caps = re.captures(button_a).unwrap();
+fn parse_game_position(line_idx: usize, line: &str, re: &Regex) -> AocResult<Point<usize>> {
+    let caps = re.captures(line).ok_or_else(|| {
+        AocError::parse(
+            line_idx,
+            0,
+            format!("expected a line matching {:?}", re.as_str()),
+        )
+    })?;
 
-    let x = caps.get(1).unwrap().as_str().parse::<i32>().unwrap();
-    let y = caps.get(2).unwrap().as_str().parse::<i32>().unwrap();
+    let x: usize = parse_token(line_idx, line, caps.get(1).unwrap().as_str())?;
+    let y: usize = parse_token(line_idx, line, caps.get(2).unwrap().as_str())?;
 
-    GamePosition {
-        x_right: x as usize,
-        y_forward: y as usize,
-    }
+    Ok(Point::new(x, y))
 }
 
-fn parse_button_b(button_b: &str) -> GamePosition {
-    let re = Regex::new(r"Button B: X\+(\d+), Y\+(\d+)").unwrap();
-    let caps = re.captures(button_b).unwrap();
-
-    let x = caps.get(1).unwrap().as_str().parse::<i32>().unwrap();
-    let y = caps.get(2).unwrap().as_str().parse::<i32>().unwrap();
+fn parse_button_a(line_idx: usize, button_a: &str) -> AocResult<Point<usize>> {
+    let re = Regex::new(r"Button A: X\+(\d+), Y\+(\d+)").unwrap();
+    parse_game_position(line_idx, button_a, &re)
+}
 
-    GamePosition {
-        x_right: x as usize,
-        y_forward: y as usize,
-    }
+fn parse_button_b(line_idx: usize, button_b: &str) -> AocResult<Point<usize>> {
+    let re = Regex::new(r"Button B: X\+(\d+), Y\+(\d+)").unwrap();
+    parse_game_position(line_idx, button_b, &re)
 }
 
-fn parse_prize(prize: &str) -> GamePosition {
+fn parse_prize(line_idx: usize, prize: &str) -> AocResult<Point<usize>> {
     let re = Regex::new(r"Prize: X=(\d+), Y=(\d+)").unwrap();
-    let caps = re.captures(prize).unwrap();
-
-    let x = caps.get(1).unwrap().as_str().parse::<i32>().unwrap();
-    let y = caps.get(2).unwrap().as_str().parse::<i32>().unwrap();
-
-    GamePosition {
-        x_right: x as usize,
-        y_forward: y as usize,
-    }
+    parse_game_position(line_idx, prize, &re)
 }
 
-pub fn get_fewest_tokens(input_file: &str, pos_inc: usize) -> usize {
-    let input = parse_input(input_file);
+pub fn get_fewest_tokens(puzzle_input: impl PuzzleInput, pos_inc: usize) -> AocResult<usize> {
+    let input = parse_input(puzzle_input)?;
 
-    input
+    Ok(input
         .games
         .into_iter()
         .map(|game| {
             // Create a system of linear equations in the form Ax = b
             let a = Matrix2::new(
-                game.button_a.x_right as f64,
-                game.button_b.x_right as f64,
-                game.button_a.y_forward as f64,
-                game.button_b.y_forward as f64,
+                game.button_a.x as f64,
+                game.button_b.x as f64,
+                game.button_a.y as f64,
+                game.button_b.y as f64,
             );
             let b = Vector2::new(
-                game.prize.x_right as f64 + pos_inc as f64,
-                game.prize.y_forward as f64 + pos_inc as f64,
+                game.prize.x as f64 + pos_inc as f64,
+                game.prize.y as f64 + pos_inc as f64,
             );
 
             let mut prize = 0;
@@ -114,7 +103,24 @@ pub fn get_fewest_tokens(input_file: &str, pos_inc: usize) -> usize {
             }
             prize
         })
-        .sum()
+        .sum())
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_fewest_tokens(Contents(input), 0)?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_fewest_tokens(Contents(input), 10000000000000)?.into())
+    }
 }
 
 #[cfg(test)]
@@ -123,19 +129,22 @@ mod tests {
 
     #[test]
     fn test_get_fewest_tokens_test01() {
-        assert_eq!(480, get_fewest_tokens("input/2024/day13_test01.txt", 0));
+        assert_eq!(
+            480,
+            get_fewest_tokens("input/2024/day13_test01.txt", 0).unwrap()
+        );
     }
 
     #[test]
     fn test_get_fewest_tokens() {
-        assert_eq!(29517, get_fewest_tokens("input/2024/day13.txt", 0));
+        assert_eq!(29517, get_fewest_tokens("input/2024/day13.txt", 0).unwrap());
     }
 
     #[test]
     fn test_get_fewest_tokens_pos_inc_test01() {
         assert_eq!(
             875318608908,
-            get_fewest_tokens("input/2024/day13_test01.txt", 10000000000000)
+            get_fewest_tokens("input/2024/day13_test01.txt", 10000000000000).unwrap()
         );
     }
 
@@ -143,7 +152,7 @@ mod tests {
     fn test_get_fewest_token_pos_inc_() {
         assert_eq!(
             103570327981381,
-            get_fewest_tokens("input/2024/day13.txt", 10000000000000)
+            get_fewest_tokens("input/2024/day13.txt", 10000000000000).unwrap()
         );
     }
 }
@@ -2,102 +2,93 @@
 
 use std::collections::HashMap;
 
+use log::{Level, debug, log_enabled};
 use regex::Regex;
 
-use crate::utils::get_lines;
+use crate::error::{AocError, AocResult, parse_token};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, Point, PuzzleInput, get_lines};
 
 #[derive(Debug, Clone)]
 struct Robot {
-    pos: (i32, i32),
-    vel: (i32, i32),
+    pos: Point<i32>,
+    vel: Point<i32>,
+}
+
+impl Robot {
+    /// Where the robot is after `secs` seconds, wrapping around the edges of
+    /// the room.
+    fn pos_after(&self, secs: i32, width: i32, height: i32) -> Point<i32> {
+        let pos = self.pos + self.vel * secs;
+        Point::new(pos.x.rem_euclid(width), pos.y.rem_euclid(height))
+    }
 }
 
 struct Input {
     robots: Vec<Robot>,
 }
 
-fn parse_coordinates(input: &str) -> ((i32, i32), (i32, i32)) {
+fn parse_coordinates(line_idx: usize, input: &str) -> AocResult<(Point<i32>, Point<i32>)> {
     let re = Regex::new(r"p=(-?\d+),(-?\d+) v=(-?\d+),(-?\d+)").unwrap();
-    let caps = re.captures(input).unwrap();
+    let caps = re
+        .captures(input)
+        .ok_or_else(|| AocError::parse(line_idx, 0, "expected \"p=X,Y v=DX,DY\""))?;
 
-    let p1 = caps.get(1).unwrap().as_str().parse::<i32>().unwrap();
-    let p2 = caps.get(2).unwrap().as_str().parse::<i32>().unwrap();
-    let v1 = caps.get(3).unwrap().as_str().parse::<i32>().unwrap();
-    let v2 = caps.get(4).unwrap().as_str().parse::<i32>().unwrap();
+    let field = |i| parse_token::<i32>(line_idx, input, caps.get(i).unwrap().as_str());
+    let p1 = field(1)?;
+    let p2 = field(2)?;
+    let v1 = field(3)?;
+    let v2 = field(4)?;
 
-    ((p1, p2), (v1, v2))
+    Ok((Point::new(p1, p2), Point::new(v1, v2)))
 }
 
-fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
+fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let lines = get_lines(puzzle_input)?;
 
     let mut robots: Vec<Robot> = Vec::new();
 
-    for line in lines {
-        let (pos, vel) = parse_coordinates(&line);
+    for (line_idx, line) in lines.iter().enumerate() {
+        let (pos, vel) = parse_coordinates(line_idx, line)?;
         robots.push(Robot { pos, vel });
     }
 
-    Input { robots }
+    Ok(Input { robots })
 }
 
-fn print_robots(width: i32, height: i32, robot_map: &HashMap<(i32, i32), Vec<Robot>>) {
+fn print_robots(width: i32, height: i32, robot_map: &HashMap<Point<i32>, Vec<Robot>>) {
+    if !log_enabled!(Level::Debug) {
+        return;
+    }
+
     for row in 0..height {
-        for col in 0..width {
-            let mut found = false;
-            if let Some(robots) = robot_map.get(&(col, row)) {
-                print!("{}", robots.len());
-                found = true;
-            }
-            if !found {
-                print!(".");
-            }
-        }
-        println!();
+        let line: String = (0..width)
+            .map(|col| match robot_map.get(&Point::new(col, row)) {
+                Some(robots) => robots.len().to_string(),
+                None => ".".to_string(),
+            })
+            .collect();
+        debug!("{}", line);
     }
 }
 
-pub fn get_safety_factor(input_file: &str, width: i32, height: i32, num_secs: usize) -> usize {
-    let input = parse_input(input_file);
+pub fn get_safety_factor(
+    puzzle_input: impl PuzzleInput,
+    width: i32,
+    height: i32,
+    num_secs: usize,
+) -> AocResult<usize> {
+    let input = parse_input(puzzle_input)?;
 
     let mut robots = input.robots.clone();
 
-    for _ in 0..num_secs {
-        for robot in &mut robots {
-            let (old_x, old_y) = robot.pos;
-            let (dx, dy) = robot.vel;
-
-            let new_x = if old_x + dx < 0 {
-                dx + width + old_x
-            } else if old_x + dx >= width {
-                dx - width + old_x
-            } else {
-                old_x + dx
-            };
-            let new_y = if old_y + dy < 0 {
-                dy + height + old_y
-            } else if old_y + dy >= height {
-                dy - height + old_y
-            } else {
-                old_y + dy
-            };
-
-            robot.pos = (new_x, new_y);
-
-            /*println!(
-                "Secs: {}, old_x: {}, old_y: {}, dx: {}, dy: {}, new_x: {}, new_y: {}",
-                secs + 1,
-                old_x,
-                old_y,
-                dx,
-                dy,
-                new_x,
-                new_y
-            );*/
-        }
+    // The robots are back where they started every width * height seconds
+    let secs = (num_secs % (width * height) as usize) as i32;
+    for robot in &mut robots {
+        robot.pos = robot.pos_after(secs, width, height);
     }
 
-    let mut robot_map: HashMap<(i32, i32), Vec<Robot>> = HashMap::new();
+    let mut robot_map: HashMap<Point<i32>, Vec<Robot>> = HashMap::new();
     for robot in robots {
         robot_map.entry(robot.pos).or_default().push(robot);
     }
@@ -116,8 +107,7 @@ pub fn get_safety_factor(input_file: &str, width: i32, height: i32, num_secs: us
 
     for (pos, robots) in robot_map {
         for _ in robots {
-            let (x, y) = pos;
-            let (x, y) = (x as usize, y as usize);
+            let (x, y) = (pos.x as usize, pos.y as usize);
             if x < quad_width && y < quad_height {
                 ne_robots += 1;
             } else if x > quad_width && y < quad_height {
@@ -130,38 +120,25 @@ pub fn get_safety_factor(input_file: &str, width: i32, height: i32, num_secs: us
         }
     }
 
-    ne_robots * nw_robots * se_robots * sw_robots
+    Ok(ne_robots * nw_robots * se_robots * sw_robots)
 }
 
-pub fn check_xmas_tree(input_file: &str, width: i32, height: i32, max_secs: usize) -> usize {
-    let input = parse_input(input_file);
+pub fn check_xmas_tree(
+    puzzle_input: impl PuzzleInput,
+    width: i32,
+    height: i32,
+    max_secs: usize,
+) -> AocResult<usize> {
+    let input = parse_input(puzzle_input)?;
 
     let mut robots = input.robots.clone();
 
     for secs in 0..max_secs {
         for robot in &mut robots {
-            let (old_x, old_y) = robot.pos;
-            let (dx, dy) = robot.vel;
-
-            let new_x = if old_x + dx < 0 {
-                dx + width + old_x
-            } else if old_x + dx >= width {
-                dx - width + old_x
-            } else {
-                old_x + dx
-            };
-            let new_y = if old_y + dy < 0 {
-                dy + height + old_y
-            } else if old_y + dy >= height {
-                dy - height + old_y
-            } else {
-                old_y + dy
-            };
-
-            robot.pos = (new_x, new_y);
+            robot.pos = robot.pos_after(1, width, height);
         }
 
-        let mut robot_map: HashMap<(i32, i32), Vec<Robot>> = HashMap::new();
+        let mut robot_map: HashMap<Point<i32>, Vec<Robot>> = HashMap::new();
         for robot in &robots {
             robot_map.entry(robot.pos).or_default().push(robot.clone());
         }
@@ -172,11 +149,28 @@ pub fn check_xmas_tree(input_file: &str, width: i32, height: i32, max_secs: usiz
 
         if sum_single_entries == robots.len() {
             print_robots(width, height, &robot_map.clone());
-            return secs + 1;
+            return Ok(secs + 1);
         }
     }
 
-    0
+    Ok(0)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_safety_factor(Contents(input), 101, 103, 100)?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(check_xmas_tree(Contents(input), 101, 103, 10000)?.into())
+    }
 }
 
 #[cfg(test)]
@@ -187,7 +181,7 @@ mod tests {
     fn test_get_safety_factor_test01() {
         assert_eq!(
             0,
-            get_safety_factor("input/2024/day14_test01.txt", 11, 7, 0)
+            get_safety_factor("input/2024/day14_test01.txt", 11, 7, 0).unwrap()
         );
     }
 
@@ -195,7 +189,7 @@ mod tests {
     fn test_get_safety_factor_test02() {
         assert_eq!(
             0,
-            get_safety_factor("input/2024/day14_test02.txt", 11, 7, 0)
+            get_safety_factor("input/2024/day14_test02.txt", 11, 7, 0).unwrap()
         );
     }
 
@@ -203,7 +197,7 @@ mod tests {
     fn test_get_safety_factor_test03() {
         assert_eq!(
             0,
-            get_safety_factor("input/2024/day14_test02.txt", 11, 7, 1)
+            get_safety_factor("input/2024/day14_test02.txt", 11, 7, 1).unwrap()
         );
     }
 
@@ -211,7 +205,7 @@ mod tests {
     fn test_get_safety_factor_test04() {
         assert_eq!(
             0,
-            get_safety_factor("input/2024/day14_test02.txt", 11, 7, 2)
+            get_safety_factor("input/2024/day14_test02.txt", 11, 7, 2).unwrap()
         );
     }
 
@@ -219,7 +213,7 @@ mod tests {
     fn test_get_safety_factor_test05() {
         assert_eq!(
             0,
-            get_safety_factor("input/2024/day14_test02.txt", 11, 7, 3)
+            get_safety_factor("input/2024/day14_test02.txt", 11, 7, 3).unwrap()
         );
     }
 
@@ -227,7 +221,7 @@ mod tests {
     fn test_get_safety_factor_test06() {
         assert_eq!(
             0,
-            get_safety_factor("input/2024/day14_test02.txt", 11, 7, 4)
+            get_safety_factor("input/2024/day14_test02.txt", 11, 7, 4).unwrap()
         );
     }
 
@@ -235,7 +229,7 @@ mod tests {
     fn test_get_safety_factor_test07() {
         assert_eq!(
             0,
-            get_safety_factor("input/2024/day14_test02.txt", 11, 7, 5)
+            get_safety_factor("input/2024/day14_test02.txt", 11, 7, 5).unwrap()
         );
     }
 
@@ -243,7 +237,7 @@ mod tests {
     fn test_get_safety_factor_test08() {
         assert_eq!(
             0,
-            get_safety_factor("input/2024/day14_test03.txt", 11, 7, 0)
+            get_safety_factor("input/2024/day14_test03.txt", 11, 7, 0).unwrap()
         );
     }
 
@@ -251,7 +245,7 @@ mod tests {
     fn test_get_safety_factor_test09() {
         assert_eq!(
             0,
-            get_safety_factor("input/2024/day14_test03.txt", 11, 7, 1)
+            get_safety_factor("input/2024/day14_test03.txt", 11, 7, 1).unwrap()
         );
     }
 
@@ -259,7 +253,7 @@ mod tests {
     fn test_get_safety_factor_test10() {
         assert_eq!(
             0,
-            get_safety_factor("input/2024/day14_test03.txt", 11, 7, 2)
+            get_safety_factor("input/2024/day14_test03.txt", 11, 7, 2).unwrap()
         );
     }
 
@@ -267,7 +261,7 @@ mod tests {
     fn test_get_safety_factor_test11() {
         assert_eq!(
             0,
-            get_safety_factor("input/2024/day14_test03.txt", 11, 7, 3)
+            get_safety_factor("input/2024/day14_test03.txt", 11, 7, 3).unwrap()
         );
     }
 
@@ -275,7 +269,7 @@ mod tests {
     fn test_get_safety_factor_test12() {
         assert_eq!(
             0,
-            get_safety_factor("input/2024/day14_test03.txt", 11, 7, 4)
+            get_safety_factor("input/2024/day14_test03.txt", 11, 7, 4).unwrap()
         );
     }
 
@@ -283,7 +277,7 @@ mod tests {
     fn test_get_safety_factor_test13() {
         assert_eq!(
             0,
-            get_safety_factor("input/2024/day14_test03.txt", 11, 7, 5)
+            get_safety_factor("input/2024/day14_test03.txt", 11, 7, 5).unwrap()
         );
     }
 
@@ -291,7 +285,7 @@ mod tests {
     fn test_get_safety_factor_test14() {
         assert_eq!(
             12,
-            get_safety_factor("input/2024/day14_test01.txt", 11, 7, 100)
+            get_safety_factor("input/2024/day14_test01.txt", 11, 7, 100).unwrap()
         );
     }
 
@@ -299,7 +293,7 @@ mod tests {
     fn test_get_safety_factor() {
         assert_eq!(
             222901875,
-            get_safety_factor("input/2024/day14.txt", 101, 103, 100)
+            get_safety_factor("input/2024/day14.txt", 101, 103, 100).unwrap()
         );
     }
 
@@ -308,7 +302,7 @@ mod tests {
     fn test_check_xmas_tree() {
         assert_eq!(
             6243,
-            check_xmas_tree("input/2024/day14.txt", 101, 103, 10000)
+            check_xmas_tree("input/2024/day14.txt", 101, 103, 10000).unwrap()
         );
     }
 }
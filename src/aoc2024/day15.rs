@@ -1,48 +1,62 @@
 // https://adventofcode.com/2024/day/15
 
-use core::panic;
 use std::collections::{HashSet, VecDeque};
 
-use crate::utils::{MoveDir, get_lines};
+use crate::error::{AocError, AocResult};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, Direction, Grid, MoveDir, Pos, PuzzleInput, get_lines};
 
 #[derive(Debug, PartialEq, Eq, Hash, Default, Clone)]
 struct Move {
-    pos: (usize, usize),
+    pos: Pos,
     entry: char,
     dir: MoveDir,
 }
 
 #[derive(Debug)]
 struct Input {
-    warehouse: Vec<Vec<char>>,
+    warehouse: Grid<char>,
+    robot_pos: Pos,
     moves: Vec<MoveDir>,
 }
 
-fn parse_warehouse(warehouse_part: &[&str]) -> Vec<Vec<char>> {
-    warehouse_part
+/// The warehouse along with where its one robot starts.
+fn parse_warehouse(warehouse_part: &[&str]) -> AocResult<(Grid<char>, Pos)> {
+    let warehouse = Grid::from_lines(warehouse_part, |c| {
+        matches!(c, '.' | '#' | 'O' | '@').then_some(c)
+    })?;
+    let robots: Vec<Pos> = warehouse
         .iter()
-        .map(|line| line.chars().collect())
-        .collect()
+        .filter(|(_, entry)| **entry == '@')
+        .map(|(pos, _)| pos)
+        .collect();
+    match robots[..] {
+        [robot_pos] => Ok((warehouse, robot_pos)),
+        _ => Err(AocError::invalid(format!(
+            "expected one robot in the warehouse, found {}",
+            robots.len()
+        ))),
+    }
 }
 
-fn parse_moves(moves_part: &[&str]) -> Vec<MoveDir> {
+fn parse_moves(first_line_idx: usize, moves_part: &[&str]) -> AocResult<Vec<MoveDir>> {
     let mut moves: Vec<MoveDir> = vec![];
-    for move_line in moves_part {
-        move_line.chars().for_each(|c| {
+    for (line_idx, move_line) in (first_line_idx..).zip(moves_part) {
+        for (col_idx, c) in move_line.chars().enumerate() {
             match c {
                 '^' => moves.push(MoveDir::Up),
                 'v' => moves.push(MoveDir::Down),
                 '<' => moves.push(MoveDir::Left),
                 '>' => moves.push(MoveDir::Right),
-                _ => panic!("Unknown move"),
+                _ => return Err(AocError::unexpected_char(line_idx, col_idx, c)),
             };
-        });
+        }
     }
-    moves
+    Ok(moves)
 }
 
-fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
+fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let lines = get_lines(puzzle_input)?;
 
     let parts: Vec<_> = lines.split(|line| line.trim().is_empty()).collect();
     let warehouse_part: Vec<&str> = parts
@@ -54,162 +68,102 @@ fn parse_input(input_file: &str) -> Input {
         .map(|v| v.iter().map(|s| s.as_str()).collect())
         .unwrap_or(vec![]);
 
-    let warehouse = parse_warehouse(&warehouse_part);
+    let (warehouse, robot_pos) = parse_warehouse(&warehouse_part)?;
 
-    let moves = parse_moves(&moves_part);
+    let moves = parse_moves(warehouse_part.len() + 1, &moves_part)?;
 
-    Input { warehouse, moves }
-}
-
-/*fn print_warehouse(warehouse: &[Vec<char>]) {
-    for row in warehouse {
-        for entry in row {
-            print!("{}", entry);
-        }
-        println!();
-    }
-}*/
-
-fn get_next_move(move_pos: (usize, usize), move_dir: &MoveDir) -> (usize, usize) {
-    let (move_x, move_y) = move_pos;
-    match move_dir {
-        MoveDir::Up => (move_x, move_y - 1),
-        MoveDir::Down => (move_x, move_y + 1),
-        MoveDir::Left => (move_x - 1, move_y),
-        MoveDir::Right => (move_x + 1, move_y),
-    }
+    Ok(Input {
+        warehouse,
+        robot_pos,
+        moves,
+    })
 }
 
-fn perform_move(warehouse: &mut [Vec<char>], robot_pos: &mut (usize, usize), move_dir: &MoveDir) {
+fn perform_move(warehouse: &mut Grid<char>, robot_pos: &mut Pos, move_dir: &MoveDir) {
     //println!("MoveDir: {:?}", move_dir);
-    let (robot_x, robot_y) = robot_pos;
-    let mut maybe_next_move = Some(get_next_move((*robot_x, *robot_y), move_dir));
+    let dir = Direction::from(*move_dir);
+    let mut maybe_next_move = warehouse.neighbor(*robot_pos, dir);
     while let Some(next_move) = maybe_next_move {
-        let (next_x, next_y) = next_move;
-        match warehouse[next_y][next_x] {
+        match warehouse[next_move] {
             '.' => {
-                warehouse[*robot_y][*robot_x] = '.';
-                warehouse[next_y][next_x] = '@';
-                *robot_x = next_x;
-                *robot_y = next_y;
+                warehouse[*robot_pos] = '.';
+                warehouse[next_move] = '@';
+                *robot_pos = next_move;
                 maybe_next_move = None;
             }
-            'O' => {
-                let (peek_x, peek_y) = get_next_move((next_x, next_y), move_dir);
-                match warehouse[peek_y][peek_x] {
-                    '.' => {
-                        warehouse[next_y][next_x] = '.';
-                        warehouse[peek_y][peek_x] = 'O';
-                        maybe_next_move = Some(get_next_move((*robot_x, *robot_y), move_dir))
-                    }
-                    'O' => maybe_next_move = Some((peek_x, peek_y)),
-                    _ => maybe_next_move = None,
+            'O' => match warehouse.neighbor(next_move, dir) {
+                Some(peek_move) if warehouse[peek_move] == '.' => {
+                    warehouse[next_move] = '.';
+                    warehouse[peek_move] = 'O';
+                    maybe_next_move = warehouse.neighbor(*robot_pos, dir);
                 }
-            }
+                Some(peek_move) if warehouse[peek_move] == 'O' => maybe_next_move = Some(peek_move),
+                _ => maybe_next_move = None,
+            },
             _ => {
                 maybe_next_move = None;
             }
         }
     }
-
-    //print_warehouse(warehouse);
-    //println!();
 }
 
-fn get_robot_pos(warehouse: &[Vec<char>]) -> (usize, usize) {
-    for (y, row) in warehouse.iter().enumerate() {
-        for (x, entry) in row.iter().enumerate() {
-            if *entry == '@' {
-                return (x, y);
-            }
-        }
-    }
-    panic!("Robot not found in the warehouse");
-}
-
-pub fn get_sum_gps(input_file: &str) -> u32 {
-    let input = parse_input(input_file);
+pub fn get_sum_gps(puzzle_input: impl PuzzleInput) -> AocResult<u32> {
+    let input = parse_input(puzzle_input)?;
 
     let mut warehouse = input.warehouse.clone();
 
-    let mut robot_pos: (usize, usize) = get_robot_pos(warehouse.as_slice());
+    let mut robot_pos = input.robot_pos;
 
     /*println!("Initial robot pos: {:?}", robot_pos);
     println!("Initial state:");
-    print_warehouse(&warehouse);
-    println!();*/
+    println!("{}", warehouse);*/
 
     for move_dir in &input.moves {
         perform_move(&mut warehouse, &mut robot_pos, move_dir);
     }
 
-    warehouse.iter().enumerate().fold(0, |acc, (i, row)| {
-        acc + row
-            .iter()
-            .enumerate()
-            .fold(0, |acc, (j, entry)| match entry {
-                'O' => acc + (100 * i as u32 + j as u32),
-                _ => acc,
-            })
-    })
+    Ok(warehouse
+        .iter()
+        .fold(0, |acc, ((row, col), entry)| match entry {
+            'O' => acc + (100 * row as u32 + col as u32),
+            _ => acc,
+        }))
 }
 
 //
 // Part 2
 //
 
-fn widen_warehouse(warehouse: &[Vec<char>]) -> Vec<Vec<char>> {
-    warehouse.iter().fold(vec![], |mut acc, row| {
-        let mut new_row: Vec<char> = vec![];
-        for entry in row {
-            match entry {
-                '.' => {
-                    new_row.push('.');
-                    new_row.push('.');
-                }
-                '#' => {
-                    new_row.push('#');
-                    new_row.push('#');
-                }
-                'O' => {
-                    new_row.push('[');
-                    new_row.push(']');
-                }
-                '@' => {
-                    new_row.push('@');
-                    new_row.push('.');
-                }
-                _ => panic!("Unknown entry"),
-            }
-        }
-        acc.push(new_row);
-        acc
-    })
-}
-
-fn get_pot_move(move_pos: (usize, usize), move_dir: MoveDir) -> (usize, usize) {
-    let (move_x, move_y) = move_pos;
-    match move_dir {
-        MoveDir::Left => (move_x - 1, move_y),
-        MoveDir::Right => (move_x + 1, move_y),
-        MoveDir::Up => (move_x, move_y - 1),
-        MoveDir::Down => (move_x, move_y + 1),
-    }
+fn widen_warehouse(warehouse: &Grid<char>) -> AocResult<Grid<char>> {
+    let rows = warehouse
+        .rows()
+        .map(|row| {
+            row.iter()
+                .flat_map(|entry| match entry {
+                    '.' => ['.', '.'],
+                    '#' => ['#', '#'],
+                    'O' => ['[', ']'],
+                    '@' => ['@', '.'],
+                    _ => panic!("Unknown entry"),
+                })
+                .collect()
+        })
+        .collect();
+    Grid::from_rows(rows)
 }
 
 fn is_valid_pot_move(
-    warehouse: &[Vec<char>],
-    visited_pos: &mut HashSet<(usize, usize)>,
-    move_pos: &(usize, usize),
+    warehouse: &Grid<char>,
+    visited_pos: &mut HashSet<Pos>,
+    move_pos: &Pos,
 ) -> bool {
-    let (pos_x, pos_y) = move_pos;
-    if *pos_x >= warehouse[0].len() || *pos_y >= warehouse.len() {
+    let Some(&entry) = warehouse.get(*move_pos) else {
         /*println!(
             "Move to position {:?} is not valid: out of bounds",
             move_pos
         );*/
         return false;
-    }
+    };
     if visited_pos.contains(move_pos) {
         /*println!(
             "Move to position {:?} is not valid: already visited",
@@ -217,7 +171,6 @@ fn is_valid_pot_move(
         );*/
         return false;
     }
-    let entry = warehouse[*pos_y][*pos_x];
     let is_valid = matches!(entry, '@' | '[' | ']');
     if !is_valid {
         /*println!(
@@ -231,10 +184,10 @@ fn is_valid_pot_move(
 }
 
 fn build_moves_wider_bfs(
-    warehouse: &[Vec<char>],
-    visited_pos: &mut HashSet<(usize, usize)>,
+    warehouse: &Grid<char>,
+    visited_pos: &mut HashSet<Pos>,
     stored_moves: &mut Vec<Move>,
-    move_pos: (usize, usize),
+    move_pos: Pos,
     move_dir: MoveDir,
 ) {
     let mut queue = VecDeque::new();
@@ -243,8 +196,10 @@ fn build_moves_wider_bfs(
 
     while !queue.is_empty() {
         if let Some(move_pos) = queue.pop_front() {
-            let (move_x, move_y) = move_pos;
-            let entry = warehouse[move_y][move_x];
+            let entry = warehouse[move_pos];
+            let east = warehouse.neighbor(move_pos, Direction::E);
+            let west = warehouse.neighbor(move_pos, Direction::W);
+            let is_vertical = move_dir == MoveDir::Up || move_dir == MoveDir::Down;
 
             //println!("Storing {:?} move {:?}", move_dir, move_pos);
             let next_move = Move {
@@ -254,39 +209,25 @@ fn build_moves_wider_bfs(
             };
             stored_moves.push(next_move);
 
-            match entry {
-                '[' if warehouse[move_y][move_x + 1] == ']'
-                    && (move_dir == MoveDir::Up || move_dir == MoveDir::Down) =>
-                {
-                    let adj_move_pos = (move_x + 1, move_y);
-                    if is_valid_pot_move(warehouse, visited_pos, &adj_move_pos) {
-                        visited_pos.insert(adj_move_pos);
-                        queue.push_back(adj_move_pos);
-                    } else if let Some(_wall_pos) =
-                        found_wall(warehouse, stored_moves, adj_move_pos)
-                    {
-                        //println!("Found wall at {:?}", wall_pos);
-                        break;
-                    }
-                }
-                ']' if warehouse[move_y][move_x - 1] == '['
-                    && (move_dir == MoveDir::Up || move_dir == MoveDir::Down) =>
-                {
-                    let adj_move_pos = (move_x - 1, move_y);
-                    if is_valid_pot_move(warehouse, visited_pos, &adj_move_pos) {
-                        visited_pos.insert(adj_move_pos);
-                        queue.push_back(adj_move_pos);
-                    } else if let Some(_wall_pos) =
-                        found_wall(warehouse, stored_moves, adj_move_pos)
-                    {
-                        //println!("Found wall at {:?}", wall_pos);
-                        break;
-                    }
+            // The other half of a box moves with it when pushed vertically
+            let other_half = match (entry, east, west) {
+                ('[', Some(east), _) if warehouse[east] == ']' && is_vertical => Some(east),
+                (']', _, Some(west)) if warehouse[west] == '[' && is_vertical => Some(west),
+                _ => None,
+            };
+            if let Some(adj_move_pos) = other_half {
+                if is_valid_pot_move(warehouse, visited_pos, &adj_move_pos) {
+                    visited_pos.insert(adj_move_pos);
+                    queue.push_back(adj_move_pos);
+                } else if let Some(_wall_pos) = found_wall(warehouse, stored_moves, adj_move_pos) {
+                    //println!("Found wall at {:?}", wall_pos);
+                    break;
                 }
-                _ => (),
             }
 
-            let pot_move_pos = get_pot_move(move_pos, move_dir);
+            let Some(pot_move_pos) = warehouse.neighbor(move_pos, Direction::from(move_dir)) else {
+                continue;
+            };
             if is_valid_pot_move(warehouse, visited_pos, &pot_move_pos) {
                 visited_pos.insert(pot_move_pos);
                 queue.push_back(pot_move_pos);
@@ -298,14 +239,8 @@ fn build_moves_wider_bfs(
     }
 }
 
-fn found_wall(
-    warehouse: &[Vec<char>],
-    stored_moves: &mut Vec<Move>,
-    move_pos: (usize, usize),
-) -> Option<(usize, usize)> {
-    let (move_x, move_y) = move_pos;
-    let entry = warehouse[move_y][move_x];
-    if entry == '#' {
+fn found_wall(warehouse: &Grid<char>, stored_moves: &mut Vec<Move>, move_pos: Pos) -> Option<Pos> {
+    if warehouse.get(move_pos) == Some(&'#') {
         //println!("Found wall at {:?}", move_pos);
         stored_moves.clear();
         return Some(move_pos);
@@ -313,40 +248,37 @@ fn found_wall(
     None
 }
 
-fn make_moves(warehouse: &mut [Vec<char>], stored_moves: &[Move]) {
+fn make_moves(warehouse: &mut Grid<char>, stored_moves: &[Move]) {
     for m in stored_moves.iter().rev() {
-        let (from_x, from_y) = m.pos;
-        let (to_x, to_y) = get_pot_move(m.pos, m.dir);
+        let Some(to_pos) = warehouse.neighbor(m.pos, Direction::from(m.dir)) else {
+            continue;
+        };
 
-        let from_entry = warehouse[from_y][from_x];
-        let to_entry = warehouse[to_y][to_x];
+        let from_entry = warehouse[m.pos];
+        let to_entry = warehouse[to_pos];
 
         /*println!(
             "Moving {:?} from {:?} to {:?}, replacing {:?}",
-            from_entry,
-            (from_x, from_y),
-            (to_x, to_y),
-            to_entry
+            from_entry, m.pos, to_pos, to_entry
         );*/
 
-        warehouse[from_y][from_x] = to_entry;
-        warehouse[to_y][to_x] = from_entry;
+        warehouse[m.pos] = to_entry;
+        warehouse[to_pos] = from_entry;
     }
 }
 
-pub fn get_sum_gps_wider(input_file: &str) -> u32 {
-    let input = parse_input(input_file);
+pub fn get_sum_gps_wider(puzzle_input: impl PuzzleInput) -> AocResult<u32> {
+    let input = parse_input(puzzle_input)?;
 
-    let mut warehouse_wider = widen_warehouse(&input.warehouse);
+    let mut warehouse_wider = widen_warehouse(&input.warehouse)?;
 
-    //println!("Initial state:");
-    //print_warehouse(&warehouse_wider);
-    //println!();
+    // Widening puts the robot on the left half of its tile
+    let (robot_row, robot_col) = input.robot_pos;
+    let mut robot_pos = (robot_row, robot_col * 2);
 
     for move_dir in input.moves {
-        let mut visited_pos: HashSet<(usize, usize)> = HashSet::new();
+        let mut visited_pos: HashSet<Pos> = HashSet::new();
         let mut stored_moves = vec![];
-        let robot_pos: (usize, usize) = get_robot_pos(&warehouse_wider);
         build_moves_wider_bfs(
             &warehouse_wider,
             &mut visited_pos,
@@ -355,34 +287,55 @@ pub fn get_sum_gps_wider(input_file: &str) -> u32 {
             move_dir,
         );
         make_moves(&mut warehouse_wider, &stored_moves);
-        //print_warehouse(&warehouse_wider);
-        //println!();
-    }
-
-    warehouse_wider.iter().enumerate().fold(0, |acc, (y, row)| {
-        acc + row
-            .iter()
-            .enumerate()
-            .fold(0, |acc, (x, entry)| match entry {
-                '[' => {
-                    let rhs_box_half = row[x + 1];
-                    if rhs_box_half == ']' {
-                        acc + (100 * y as u32 + x as u32)
-                    } else {
-                        acc
-                    }
+
+        // Nothing moves at all when a wall is in the way
+        if !stored_moves.is_empty()
+            && let Some(next_pos) = warehouse_wider.neighbor(robot_pos, Direction::from(move_dir))
+        {
+            robot_pos = next_pos;
+        }
+    }
+
+    Ok(warehouse_wider
+        .iter()
+        .fold(0, |acc, ((row, col), entry)| match entry {
+            '[' => {
+                let rhs_box_half = warehouse_wider.get((row, col + 1));
+                if rhs_box_half == Some(&']') {
+                    acc + (100 * row as u32 + col as u32)
+                } else {
+                    acc
                 }
-                ']' => {
-                    let lhs_box_half = row[x - 1];
-                    if lhs_box_half != '[' {
-                        panic!("Invalid box half");
-                    } else {
-                        acc
-                    }
+            }
+            ']' => {
+                let lhs_box_half = col
+                    .checked_sub(1)
+                    .and_then(|lhs_col| warehouse_wider.get((row, lhs_col)));
+                if lhs_box_half != Some(&'[') {
+                    panic!("Invalid box half");
+                } else {
+                    acc
                 }
-                _ => acc,
-            })
-    })
+            }
+            _ => acc,
+        }))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_sum_gps(Contents(input))?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_sum_gps_wider(Contents(input))?.into())
+    }
 }
 
 #[cfg(test)]
@@ -391,53 +344,53 @@ mod tests {
 
     #[test]
     fn test_get_sum_gps_test01() {
-        assert_eq!(10092, get_sum_gps("input/2024/day15_test01.txt"));
+        assert_eq!(10092, get_sum_gps("input/2024/day15_test01.txt").unwrap());
     }
 
     #[test]
     fn test_get_sum_gps_test02() {
-        assert_eq!(2028, get_sum_gps("input/2024/day15_test02.txt"));
+        assert_eq!(2028, get_sum_gps("input/2024/day15_test02.txt").unwrap());
     }
 
     #[test]
     fn test_get_sum_gps_test03() {
         // Down
-        assert_eq!(1624, get_sum_gps("input/2024/day15_test03.txt"));
+        assert_eq!(1624, get_sum_gps("input/2024/day15_test03.txt").unwrap());
     }
 
     #[test]
     fn test_get_sum_gps_test04() {
         // Right
-        assert_eq!(1626, get_sum_gps("input/2024/day15_test04.txt"));
+        assert_eq!(1626, get_sum_gps("input/2024/day15_test04.txt").unwrap());
     }
 
     #[test]
     fn test_get_sum_gps_test05() {
         // Down
-        assert_eq!(2024, get_sum_gps("input/2024/day15_test05.txt"));
+        assert_eq!(2024, get_sum_gps("input/2024/day15_test05.txt").unwrap());
     }
 
     #[test]
     fn test_get_sum_gps_test06() {
         // Left
-        assert_eq!(1621, get_sum_gps("input/2024/day15_test06.txt"));
+        assert_eq!(1621, get_sum_gps("input/2024/day15_test06.txt").unwrap());
     }
 
     #[test]
     fn test_get_sum_gps_test07() {
         // Up
-        assert_eq!(1224, get_sum_gps("input/2024/day15_test07.txt"));
+        assert_eq!(1224, get_sum_gps("input/2024/day15_test07.txt").unwrap());
     }
 
     #[test]
     fn test_get_sum_gps_test08() {
         // Right
-        assert_eq!(1627, get_sum_gps("input/2024/day15_test08.txt"));
+        assert_eq!(1627, get_sum_gps("input/2024/day15_test08.txt").unwrap());
     }
 
     #[test]
     fn test_get_sum_gps() {
-        assert_eq!(1517819, get_sum_gps("input/2024/day15.txt"));
+        assert_eq!(1517819, get_sum_gps("input/2024/day15.txt").unwrap());
     }
 
     //
@@ -447,108 +400,165 @@ mod tests {
     #[test]
     fn test_get_sum_gps_wider_test01() {
         // From Part 1
-        assert_eq!(9021, get_sum_gps_wider("input/2024/day15_test01.txt"));
+        assert_eq!(
+            9021,
+            get_sum_gps_wider("input/2024/day15_test01.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_gps_wider_test02() {
         // Also from Part 1
-        assert_eq!(1751, get_sum_gps_wider("input/2024/day15_test02.txt"));
+        assert_eq!(
+            1751,
+            get_sum_gps_wider("input/2024/day15_test02.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_gps_wider_test03() {
         // Down, no boxes, blocked by wall
-        assert_eq!(1648, get_sum_gps_wider("input/2024/day15_test03.txt"));
+        assert_eq!(
+            1648,
+            get_sum_gps_wider("input/2024/day15_test03.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_gps_wider_test04() {
         // Right
-        assert_eq!(1652, get_sum_gps_wider("input/2024/day15_test04.txt"));
+        assert_eq!(
+            1652,
+            get_sum_gps_wider("input/2024/day15_test04.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_gps_wider_test05() {
         // Down
-        assert_eq!(2048, get_sum_gps_wider("input/2024/day15_test05.txt"));
+        assert_eq!(
+            2048,
+            get_sum_gps_wider("input/2024/day15_test05.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_gps_wider_test06() {
         // Left
-        assert_eq!(1642, get_sum_gps_wider("input/2024/day15_test06.txt"));
+        assert_eq!(
+            1642,
+            get_sum_gps_wider("input/2024/day15_test06.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_gps_wider_test07() {
         // Up
-        assert_eq!(1248, get_sum_gps_wider("input/2024/day15_test07.txt"));
+        assert_eq!(
+            1248,
+            get_sum_gps_wider("input/2024/day15_test07.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_gps_wider_test08() {
         // Right
-        assert_eq!(1654, get_sum_gps_wider("input/2024/day15_test08.txt"));
+        assert_eq!(
+            1654,
+            get_sum_gps_wider("input/2024/day15_test08.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_gps_wider_test09() {
         // Part 2 example
-        assert_eq!(618, get_sum_gps_wider("input/2024/day15_test09.txt"));
+        assert_eq!(
+            618,
+            get_sum_gps_wider("input/2024/day15_test09.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_gps_wider_test10() {
         // Test all directions
-        assert_eq!(9235, get_sum_gps_wider("input/2024/day15_test10.txt"));
+        assert_eq!(
+            9235,
+            get_sum_gps_wider("input/2024/day15_test10.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_gps_wider_test11() {
         // Left
-        assert_eq!(406, get_sum_gps_wider("input/2024/day15_test11.txt"));
+        assert_eq!(
+            406,
+            get_sum_gps_wider("input/2024/day15_test11.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_gps_wider_test12() {
         // Up, blocked by wall
-        assert_eq!(509, get_sum_gps_wider("input/2024/day15_test12.txt"));
+        assert_eq!(
+            509,
+            get_sum_gps_wider("input/2024/day15_test12.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_gps_wider_test13() {
         // Up, blocked by wall (alt)
-        assert_eq!(511, get_sum_gps_wider("input/2024/day15_test13.txt"));
+        assert_eq!(
+            511,
+            get_sum_gps_wider("input/2024/day15_test13.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_gps_wider_test14() {
         // Around and then up
-        assert_eq!(816, get_sum_gps_wider("input/2024/day15_test14.txt"));
+        assert_eq!(
+            816,
+            get_sum_gps_wider("input/2024/day15_test14.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_gps_wider_test15() {
         // Around and then up (alt)
-        assert_eq!(822, get_sum_gps_wider("input/2024/day15_test15.txt"));
+        assert_eq!(
+            822,
+            get_sum_gps_wider("input/2024/day15_test15.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_gps_wider_test16() {
         // Checks corners with wall
-        assert_eq!(1430, get_sum_gps_wider("input/2024/day15_test16.txt"));
+        assert_eq!(
+            1430,
+            get_sum_gps_wider("input/2024/day15_test16.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_gps_wider_test17() {
         // Checks corners with wall (alt)
-        assert_eq!(1216, get_sum_gps_wider("input/2024/day15_test17.txt"));
+        assert_eq!(
+            1216,
+            get_sum_gps_wider("input/2024/day15_test17.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_gps_wider() {
         // Our test input
-        assert_eq!(1538862, get_sum_gps_wider("input/2024/day15.txt"));
+        assert_eq!(1538862, get_sum_gps_wider("input/2024/day15.txt").unwrap());
+    }
+
+    #[test]
+    fn test_get_sum_gps_unknown_move() {
+        let err = get_sum_gps(Contents("#####\n#@.O#\n#####\n\n<>x<\n")).unwrap_err();
+        assert_eq!("<input>:5:3: unexpected character 'x'", err.to_string());
     }
 }
@@ -2,54 +2,33 @@
 
 use std::collections::{HashMap, HashSet};
 
+use log::debug;
 use petgraph::{
     Graph, algo,
     graph::{DiGraph, NodeIndex},
 };
 
-use crate::utils::{Direction, get_all_paths, get_lines};
+use crate::error::{AocError, AocResult};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, Direction, Grid, Pos, PuzzleInput, get_all_paths};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Move {
-    pos: (isize, isize),
+    pos: Pos,
     dir: Direction,
 }
 
 #[derive(Debug)]
 struct Input {
-    maze: HashMap<(isize, isize), char>,
+    maze: Grid<char>,
 }
 
-fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
+fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let maze = Grid::parse(puzzle_input, Some)?;
 
-    let mut maze: HashMap<(isize, isize), char> = HashMap::new();
-
-    for (y, line) in lines.into_iter().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            maze.insert((x as isize, y as isize), c);
-        }
-    }
-
-    Input { maze }
+    Ok(Input { maze })
 }
 
-/*fn print_maze(maze: &HashMap<(isize, isize), char>) {
-    let max_x = maze.keys().map(|(x, _)| *x).max().unwrap();
-    let max_y = maze.keys().map(|(_, y)| *y).max().unwrap();
-
-    for y in 0..=max_y {
-        for x in 0..=max_x {
-            if let Some(c) = maze.get(&(x, y)) {
-                print!("{}", c);
-            } else {
-                print!(" ");
-            }
-        }
-        println!();
-    }
-}*/
-
 fn build_graph(input: &Input) -> (Graph<Move, f64>, HashMap<Move, NodeIndex>) {
     // Create directed graph
     let mut graph = DiGraph::<Move, f64>::new();
@@ -57,37 +36,32 @@ fn build_graph(input: &Input) -> (Graph<Move, f64>, HashMap<Move, NodeIndex>) {
 
     // Add nodes and edges
     // First create all nodes
-    for (&(x, y), &c) in input.maze.iter() {
+    for (pos, &c) in input.maze.iter() {
         if c != '#' {
             for dir in Direction::all() {
-                let point = Move { pos: (x, y), dir };
+                let point = Move { pos, dir };
                 let node_idx = graph.add_node(point);
                 node_indices.insert(point, node_idx);
                 /*println!(
-                    "Added node at ({}, {}) dir {:?} -> idx {:?}",
-                    x, y, dir, node_idx
+                    "Added node at {:?} dir {:?} -> idx {:?}",
+                    pos, dir, node_idx
                 );*/
             }
         }
     }
 
     // Then add all edges in a separate pass
-    for (&(x, y), &c) in input.maze.iter() {
+    for (pos, &c) in input.maze.iter() {
         if c != '#' {
             for dir in Direction::all() {
-                let point = Move { pos: (x, y), dir };
+                let point = Move { pos, dir };
                 let node_idx = node_indices[&point];
 
                 // Forward movement
-                let (dx, dy) = dir.to_delta();
-                let (next_x, next_y) = (x + dx, y + dy);
-                if let Some(&next_c) = input.maze.get(&(next_x, next_y))
-                    && next_c != '#'
+                if let Some(next_pos) = input.maze.neighbor(pos, dir)
+                    && input.maze[next_pos] != '#'
                 {
-                    let next_move = Move {
-                        pos: (next_x, next_y),
-                        dir,
-                    };
+                    let next_move = Move { pos: next_pos, dir };
                     let next_idx = node_indices[&next_move];
                     graph.add_edge(node_idx, next_idx, 1.0);
                     /*println!(
@@ -99,11 +73,11 @@ fn build_graph(input: &Input) -> (Graph<Move, f64>, HashMap<Move, NodeIndex>) {
 
                 // Turn edges
                 let left_move = Move {
-                    pos: (x, y),
+                    pos,
                     dir: dir.turn_left(),
                 };
                 let right_move = Move {
-                    pos: (x, y),
+                    pos,
                     dir: dir.turn_right(),
                 };
 
@@ -124,29 +98,25 @@ fn build_graph(input: &Input) -> (Graph<Move, f64>, HashMap<Move, NodeIndex>) {
     (graph, node_indices)
 }
 
-pub fn get_lowest_score(input_file: &str) -> (usize, usize) {
-    let input = parse_input(input_file);
+pub fn get_lowest_score(puzzle_input: impl PuzzleInput) -> AocResult<(usize, usize)> {
+    let input = parse_input(puzzle_input)?;
 
-    //print_maze(&input.maze);
+    debug!("\n{}", input.maze);
 
     // Find start and end points
-    let (start_x, start_y) = input
+    let start_pos = input
         .maze
-        .iter()
-        .find(|(_, c)| **c == 'S')
-        .map(|(&pos, _)| pos)
-        .unwrap();
-    let (end_x, end_y) = input
+        .position(|&c| c == 'S')
+        .ok_or_else(|| AocError::invalid("no start tile in the maze"))?;
+    let end_pos = input
         .maze
-        .iter()
-        .find(|(_, c)| **c == 'E')
-        .map(|(&pos, _)| pos)
-        .unwrap();
+        .position(|&c| c == 'E')
+        .ok_or_else(|| AocError::invalid("no end tile in the maze"))?;
 
-    let mut tiles: HashSet<(isize, isize)> = HashSet::new();
+    let mut tiles: HashSet<Pos> = HashSet::new();
 
     let start_move = Move {
-        pos: (start_x, start_y),
+        pos: start_pos,
         dir: Direction::E,
     };
 
@@ -159,7 +129,7 @@ pub fn get_lowest_score(input_file: &str) -> (usize, usize) {
 
         for end_dir in Direction::all() {
             let end_move = Move {
-                pos: (end_x, end_y),
+                pos: end_pos,
                 dir: end_dir,
             };
 
@@ -180,7 +150,7 @@ pub fn get_lowest_score(input_file: &str) -> (usize, usize) {
 
         for end_dir in Direction::all() {
             let end_move = Move {
-                pos: (end_x, end_y),
+                pos: end_pos,
                 dir: end_dir,
             };
 
@@ -198,7 +168,24 @@ pub fn get_lowest_score(input_file: &str) -> (usize, usize) {
         }
     }
 
-    (min_length as usize, tiles.len())
+    Ok((min_length as usize, tiles.len()))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_lowest_score(Contents(input))?.0.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_lowest_score(Contents(input))?.1.into())
+    }
 }
 
 #[cfg(test)]
@@ -207,83 +194,125 @@ mod tests {
 
     #[test]
     fn test_get_lowest_score_test01() {
-        assert_eq!(7036, get_lowest_score("input/2024/day16_test01.txt").0);
+        assert_eq!(
+            7036,
+            get_lowest_score("input/2024/day16_test01.txt").unwrap().0
+        );
     }
 
     #[test]
     fn test_get_lowest_score_test02() {
-        assert_eq!(11048, get_lowest_score("input/2024/day16_test02.txt").0);
+        assert_eq!(
+            11048,
+            get_lowest_score("input/2024/day16_test02.txt").unwrap().0
+        );
     }
 
     #[test]
     fn test_get_lowest_score_test03() {
-        assert_eq!(21148, get_lowest_score("input/2024/day16_test03.txt").0);
+        assert_eq!(
+            21148,
+            get_lowest_score("input/2024/day16_test03.txt").unwrap().0
+        );
     }
 
     #[test]
     fn test_get_lowest_score_test04() {
-        assert_eq!(4013, get_lowest_score("input/2024/day16_test04.txt").0);
+        assert_eq!(
+            4013,
+            get_lowest_score("input/2024/day16_test04.txt").unwrap().0
+        );
     }
 
     #[ignore]
     #[test]
     fn test_get_lowest_score_test05() {
-        assert_eq!(21110, get_lowest_score("input/2024/day16_test05.txt").0);
+        assert_eq!(
+            21110,
+            get_lowest_score("input/2024/day16_test05.txt").unwrap().0
+        );
     }
 
     #[test]
     fn test_get_lowest_score_test06() {
-        assert_eq!(9029, get_lowest_score("input/2024/day16_test06.txt").0);
+        assert_eq!(
+            9029,
+            get_lowest_score("input/2024/day16_test06.txt").unwrap().0
+        );
     }
 
     #[test]
     fn test_get_lowest_score_test07() {
-        assert_eq!(4011, get_lowest_score("input/2024/day16_test07.txt").0);
+        assert_eq!(
+            4011,
+            get_lowest_score("input/2024/day16_test07.txt").unwrap().0
+        );
     }
 
     #[test]
     fn test_get_lowest_score() {
-        assert_eq!(79404, get_lowest_score("input/2024/day16.txt").0);
+        assert_eq!(79404, get_lowest_score("input/2024/day16.txt").unwrap().0);
     }
 
     #[test]
     fn test_get_num_tiles_test01() {
-        assert_eq!(45, get_lowest_score("input/2024/day16_test01.txt").1);
+        assert_eq!(
+            45,
+            get_lowest_score("input/2024/day16_test01.txt").unwrap().1
+        );
     }
 
     #[test]
     fn test_get_num_tiles_test02() {
-        assert_eq!(64, get_lowest_score("input/2024/day16_test02.txt").1);
+        assert_eq!(
+            64,
+            get_lowest_score("input/2024/day16_test02.txt").unwrap().1
+        );
     }
 
     #[test]
     fn test_get_num_tiles_test03() {
-        assert_eq!(149, get_lowest_score("input/2024/day16_test03.txt").1);
+        assert_eq!(
+            149,
+            get_lowest_score("input/2024/day16_test03.txt").unwrap().1
+        );
     }
 
     #[test]
     fn test_get_num_tiles_test04() {
-        assert_eq!(14, get_lowest_score("input/2024/day16_test04.txt").1);
+        assert_eq!(
+            14,
+            get_lowest_score("input/2024/day16_test04.txt").unwrap().1
+        );
     }
 
     #[ignore]
     #[test]
     fn test_get_num_tiles_test05() {
-        assert_eq!(264, get_lowest_score("input/2024/day16_test05.txt").1);
+        assert_eq!(
+            264,
+            get_lowest_score("input/2024/day16_test05.txt").unwrap().1
+        );
     }
 
     #[test]
     fn test_get_num_tiles_test06() {
-        assert_eq!(62, get_lowest_score("input/2024/day16_test06.txt").1);
+        assert_eq!(
+            62,
+            get_lowest_score("input/2024/day16_test06.txt").unwrap().1
+        );
     }
 
     #[test]
     fn test_get_num_tiles_test07() {
-        assert_eq!(17, get_lowest_score("input/2024/day16_test07.txt").1);
+        assert_eq!(
+            17,
+            get_lowest_score("input/2024/day16_test07.txt").unwrap().1
+        );
     }
 
     #[test]
     fn test_get_num_tiles() {
-        assert_eq!(451, get_lowest_score("input/2024/day16.txt").1);
+        assert_eq!(451, get_lowest_score("input/2024/day16.txt").unwrap().1);
     }
 }
@@ -2,7 +2,11 @@
 
 use std::{collections::HashSet, panic};
 
-use crate::utils::get_lines;
+use log::debug;
+
+use crate::error::{AocError, AocResult, parse_token};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput, get_lines};
 
 #[derive(Clone, Copy, Debug)]
 pub struct Registers {
@@ -26,33 +30,41 @@ struct Input {
     program: Vec<u64>,
 }
 
-fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
+fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let lines = get_lines(puzzle_input)?;
 
     let mut registers: Registers = Registers::new();
     let mut program = vec![];
 
-    for line in lines {
+    for (line_idx, line) in lines.iter().enumerate() {
         let mut parts = line.splitn(2, ":");
         if let (Some(part_a), Some(part_b)) = (parts.next(), parts.next()) {
             let parts: Vec<&str> = part_a.split_whitespace().collect();
             if let [_, reg] = parts.as_slice() {
                 match *reg {
-                    "A" => registers.reg_a = part_b.trim().parse().unwrap_or(0),
-                    "B" => registers.reg_b = part_b.trim().parse().unwrap_or(0),
-                    "C" => registers.reg_c = part_b.trim().parse().unwrap_or(0),
+                    "A" => registers.reg_a = parse_token(line_idx, line, part_b)?,
+                    "B" => registers.reg_b = parse_token(line_idx, line, part_b)?,
+                    "C" => registers.reg_c = parse_token(line_idx, line, part_b)?,
                     _ => {}
                 }
             } else {
                 program = part_b
                     .split(",")
-                    .map(|o| o.trim().parse().unwrap_or(0))
-                    .collect();
+                    .map(|o| match parse_token(line_idx, line, o)? {
+                        val @ 0..=7 => Ok(val),
+                        _ => Err(AocError::parse_token(
+                            line_idx,
+                            line,
+                            o,
+                            "not a 3-bit number",
+                        )),
+                    })
+                    .collect::<AocResult<_>>()?;
             }
         }
     }
 
-    Input { registers, program }
+    Ok(Input { registers, program })
 }
 
 fn get_combo_operand_val(registers: &Registers, combo: u64) -> Option<u64> {
@@ -85,7 +97,7 @@ fn run_program(registers: &mut Registers, program: &[u64]) -> (Registers, Vec<u6
                 let div_res = numer / denom;
                 registers.reg_a = div_res as u64;
 
-                //println!("adv, reg_a: {:?}", registers.reg_a);
+                //debug!("adv, reg_a: {:?}", registers.reg_a);
 
                 // increment ins_ptr by 2
                 ins_ptr += 2;
@@ -95,7 +107,7 @@ fn run_program(registers: &mut Registers, program: &[u64]) -> (Registers, Vec<u6
                 // bxl does bitwise xor of reg_b and the literal operand
                 registers.reg_b ^= *operand;
 
-                //println!("bxl, reg_b: {:?}", registers.reg_b);
+                //debug!("bxl, reg_b: {:?}", registers.reg_b);
 
                 // increment ins_ptr by 2
                 ins_ptr += 2;
@@ -105,7 +117,7 @@ fn run_program(registers: &mut Registers, program: &[u64]) -> (Registers, Vec<u6
                 // bst does combo operand modulo 8
                 registers.reg_b = get_combo_operand_val(registers, *operand).unwrap() % 8;
 
-                //println!("bst, reg_b: {:?}", registers.reg_b);
+                //debug!("bst, reg_b: {:?}", registers.reg_b);
 
                 // increment ins_ptr by 2
                 ins_ptr += 2;
@@ -116,9 +128,9 @@ fn run_program(registers: &mut Registers, program: &[u64]) -> (Registers, Vec<u6
                 // If reg_a is not 0, then it jumps ins_ptr to the value of the literal operand
                 if registers.reg_a != 0 {
                     ins_ptr = *operand as usize;
-                    //println!("jnz, ins_ptr: {:?}", ins_ptr);
+                    //debug!("jnz, ins_ptr: {:?}", ins_ptr);
                 } else {
-                    //println!("ignoring jnz, ins_ptr: {:?}", ins_ptr);
+                    //debug!("ignoring jnz, ins_ptr: {:?}", ins_ptr);
 
                     // increment ins_ptr by 2
                     ins_ptr += 2;
@@ -129,7 +141,7 @@ fn run_program(registers: &mut Registers, program: &[u64]) -> (Registers, Vec<u6
                 // bxc does bitwise xor of reg_b and reg_c
                 registers.reg_b ^= registers.reg_c;
 
-                //println!("bxc, reg_b: {:?}", registers.reg_b);
+                //debug!("bxc, reg_b: {:?}", registers.reg_b);
 
                 // increment ins_ptr by 2
                 ins_ptr += 2;
@@ -140,7 +152,7 @@ fn run_program(registers: &mut Registers, program: &[u64]) -> (Registers, Vec<u6
                 let out_val = get_combo_operand_val(registers, *operand).unwrap() % 8;
                 out_vals.push(out_val);
 
-                //println!("out, out_val: {:?}", out_val);
+                //debug!("out, out_val: {:?}", out_val);
 
                 // increment ins_ptr by 2
                 ins_ptr += 2;
@@ -151,7 +163,7 @@ fn run_program(registers: &mut Registers, program: &[u64]) -> (Registers, Vec<u6
                 registers.reg_b = registers.reg_a
                     / 2_u64.pow(get_combo_operand_val(registers, *operand).unwrap() as u32);
 
-                //println!("bdv, reg_b: {:?}", registers.reg_b);
+                //debug!("bdv, reg_b: {:?}", registers.reg_b);
 
                 // increment ins_ptr by 2
                 ins_ptr += 2;
@@ -162,7 +174,7 @@ fn run_program(registers: &mut Registers, program: &[u64]) -> (Registers, Vec<u6
                 registers.reg_c = registers.reg_a
                     / 2_u64.pow(get_combo_operand_val(registers, *operand).unwrap() as u32);
 
-                //println!("cdv, reg_c: {:?}", registers.reg_c);
+                //debug!("cdv, reg_c: {:?}", registers.reg_c);
 
                 // increment ins_ptr by 2
                 ins_ptr += 2;
@@ -184,22 +196,22 @@ fn out_vals_to_str(out_vals: &[u64]) -> String {
         .join(",")
 }
 
-pub fn get_joined_vals(input_file: &str) -> (Registers, String) {
-    let input = parse_input(input_file);
+pub fn get_joined_vals(puzzle_input: impl PuzzleInput) -> AocResult<(Registers, String)> {
+    let input = parse_input(puzzle_input)?;
 
     let mut registers = input.registers;
 
     let program = input.program;
 
-    println!("program {program:?}");
+    debug!("program {program:?}");
 
     let (out_registers, out_vals) = run_program(&mut registers, &program);
 
-    (out_registers, out_vals_to_str(&out_vals))
+    Ok((out_registers, out_vals_to_str(&out_vals)))
 }
 
-pub fn get_lowest_positive_reg_a(input_file: &str) -> u64 {
-    let input = parse_input(input_file);
+pub fn get_lowest_positive_reg_a(puzzle_input: impl PuzzleInput) -> AocResult<u64> {
+    let input = parse_input(puzzle_input)?;
 
     let mut poss_reg_a_vals: HashSet<u64> = HashSet::new();
 
@@ -211,7 +223,7 @@ pub fn get_lowest_positive_reg_a(input_file: &str) -> u64 {
         for poss_reg_a in &poss_reg_a_vals {
             for opt in 0..8_u64 {
                 let poss_reg_a = (poss_reg_a << 3) + opt;
-                //println!("poss_reg_a: {:#b}", poss_reg_a);
+                //debug!("poss_reg_a: {:#b}", poss_reg_a);
 
                 let mut registers = input.registers;
                 registers.reg_a = poss_reg_a;
@@ -219,8 +231,8 @@ pub fn get_lowest_positive_reg_a(input_file: &str) -> u64 {
                 registers.reg_c = 0;
 
                 let (_out_registers, out_vals) = run_program(&mut registers, &input.program);
-                //println!("out_registers: {:?}", out_registers);
-                //println!("out_vals: {:?}", out_vals);
+                //debug!("out_registers: {:?}", out_registers);
+                //debug!("out_vals: {:?}", out_vals);
 
                 if let Some(first_out_vals) = out_vals.first()
                     && num == first_out_vals
@@ -232,7 +244,24 @@ pub fn get_lowest_positive_reg_a(input_file: &str) -> u64 {
         poss_reg_a_vals = new_poss_reg_a_vals;
     }
 
-    *poss_reg_a_vals.iter().min().unwrap_or(&0)
+    Ok(*poss_reg_a_vals.iter().min().unwrap_or(&0))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_joined_vals(Contents(input))?.1.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_lowest_positive_reg_a(Contents(input))?.into())
+    }
 }
 
 #[cfg(test)]
@@ -241,44 +270,44 @@ mod tests {
 
     #[test]
     fn test_get_joined_vals_test01() {
-        let (_, joined_vals) = get_joined_vals("input/2024/day17_test01.txt");
+        let (_, joined_vals) = get_joined_vals("input/2024/day17_test01.txt").unwrap();
         assert_eq!("4,6,3,5,6,3,5,2,1,0", joined_vals);
     }
 
     #[test]
     fn test_get_joined_vals_test02() {
-        let (registers, _) = get_joined_vals("input/2024/day17_test02.txt");
+        let (registers, _) = get_joined_vals("input/2024/day17_test02.txt").unwrap();
         assert_eq!(1, registers.reg_b);
     }
 
     #[test]
     fn test_get_joined_vals_test03() {
-        let (_, joined_vals) = get_joined_vals("input/2024/day17_test03.txt");
+        let (_, joined_vals) = get_joined_vals("input/2024/day17_test03.txt").unwrap();
         assert_eq!("0,1,2", joined_vals);
     }
 
     #[test]
     fn test_get_joined_vals_test04() {
-        let (registers, joined_vals) = get_joined_vals("input/2024/day17_test04.txt");
+        let (registers, joined_vals) = get_joined_vals("input/2024/day17_test04.txt").unwrap();
         assert_eq!(0, registers.reg_a);
         assert_eq!("4,2,5,6,7,7,7,7,3,1,0", joined_vals);
     }
 
     #[test]
     fn test_get_joined_vals_test05() {
-        let (registers, _) = get_joined_vals("input/2024/day17_test05.txt");
+        let (registers, _) = get_joined_vals("input/2024/day17_test05.txt").unwrap();
         assert_eq!(26, registers.reg_b);
     }
 
     #[test]
     fn test_get_joined_vals_test06() {
-        let (registers, _) = get_joined_vals("input/2024/day17_test06.txt");
+        let (registers, _) = get_joined_vals("input/2024/day17_test06.txt").unwrap();
         assert_eq!(44354, registers.reg_b);
     }
 
     #[test]
     fn test_get_joined_vals() {
-        let (_, joined_vals) = get_joined_vals("input/2024/day17.txt");
+        let (_, joined_vals) = get_joined_vals("input/2024/day17.txt").unwrap();
         assert_eq!("2,3,4,7,5,7,3,0,7", joined_vals);
     }
 
@@ -286,7 +315,7 @@ mod tests {
     fn test_get_lowest_positive_reg_a_test01() {
         assert_eq!(
             117440,
-            get_lowest_positive_reg_a("input/2024/day17_test07.txt")
+            get_lowest_positive_reg_a("input/2024/day17_test07.txt").unwrap()
         );
     }
 
@@ -294,7 +323,7 @@ mod tests {
     fn test_get_lowest_positive_reg_a() {
         assert_eq!(
             190384609508367,
-            get_lowest_positive_reg_a("input/2024/day17.txt")
+            get_lowest_positive_reg_a("input/2024/day17.txt").unwrap()
         );
     }
 }
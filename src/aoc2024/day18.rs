@@ -7,62 +7,46 @@ use petgraph::{
     graph::{DiGraph, NodeIndex},
 };
 
-use crate::utils::{Direction, get_lines};
+use crate::error::{AocError, AocResult, parse_token};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, Grid, Point, Pos, PuzzleInput, get_lines};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Move {
-    pos: (isize, isize),
+    pos: Pos,
 }
 
 #[derive(Debug)]
 struct Input {
-    corrupt_mem: Vec<(isize, isize)>,
+    corrupt_mem: Vec<Point<usize>>,
 }
 
-fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
+fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let lines = get_lines(puzzle_input)?;
 
-    let mut corrupt_mem: Vec<(isize, isize)> = vec![];
+    let mut corrupt_mem: Vec<Point<usize>> = vec![];
 
-    for line in lines {
+    for (line_idx, line) in lines.iter().enumerate() {
         if let Some((x_str, y_str)) = line.split_once(',') {
-            let x = x_str.parse::<isize>().unwrap();
-            let y = y_str.parse::<isize>().unwrap();
-            corrupt_mem.push((x, y));
+            let x = parse_token(line_idx, line, x_str)?;
+            let y = parse_token(line_idx, line, y_str)?;
+            corrupt_mem.push(Point::new(x, y));
         }
     }
 
-    Input { corrupt_mem }
+    Ok(Input { corrupt_mem })
 }
 
-/*fn print_mem_map(mem_map: &HashMap<(isize, isize), char>) {
-    let max_x = mem_map.keys().map(|(x, _)| *x).max().unwrap();
-    let max_y = mem_map.keys().map(|(_, y)| *y).max().unwrap();
-
-    for y in 0..=max_y {
-        for x in 0..=max_x {
-            if let Some(c) = mem_map.get(&(x, y)) {
-                print!("{}", c);
-            } else {
-                print!(" ");
-            }
-        }
-        println!();
-    }
-}*/
-
-fn build_graph(
-    mem_map: &HashMap<(isize, isize), char>,
-) -> (Graph<Move, f64>, HashMap<Move, NodeIndex>) {
+fn build_graph(mem_map: &Grid<char>) -> (Graph<Move, f64>, HashMap<Move, NodeIndex>) {
     // Create directed graph
     let mut graph = DiGraph::<Move, f64>::new();
     let mut node_indices: HashMap<Move, NodeIndex> = HashMap::new();
 
     // Add nodes and edges
     // First create all nodes
-    for (&(x, y), &c) in mem_map.iter() {
+    for (pos, &c) in mem_map.iter() {
         if c != '#' {
-            let point = Move { pos: (x, y) };
+            let point = Move { pos };
             let node_idx = graph.add_node(point);
             node_indices.insert(point, node_idx);
             //println!("Added node {:?}", graph[node_idx]);
@@ -70,22 +54,13 @@ fn build_graph(
     }
 
     // Then add all edges in a separate pass
-    for (&(x, y), &c) in mem_map.iter() {
+    for (pos, &c) in mem_map.iter() {
         if c != '#' {
-            for dir in Direction::all() {
-                let point = Move { pos: (x, y) };
-                let node_idx = node_indices[&point];
-
+            let node_idx = node_indices[&Move { pos }];
+            for (_, next_pos) in mem_map.neighbors4(pos) {
                 // Forward movement
-                let (dx, dy) = dir.to_delta();
-                let (next_x, next_y) = (x + dx, y + dy);
-                if let Some(&next_c) = mem_map.get(&(next_x, next_y))
-                    && next_c != '#'
-                {
-                    let next_move = Move {
-                        pos: (next_x, next_y),
-                    };
-                    let next_idx = node_indices[&next_move];
+                if mem_map[next_pos] != '#' {
+                    let next_idx = node_indices[&Move { pos: next_pos }];
                     graph.add_edge(node_idx, next_idx, 1.0);
                     //println!("Added edge {:?} -> {:?}", graph[node_idx], graph[next_idx]);
                 }
@@ -96,112 +71,98 @@ fn build_graph(
 }
 
 fn build_mem_map(
-    corrupt_mem: &[(isize, isize)],
-    x_len: isize,
-    y_len: isize,
+    corrupt_mem: &[Point<usize>],
+    x_len: usize,
+    y_len: usize,
     bytes: usize,
-) -> HashMap<(isize, isize), char> {
-    let mut mem_map = HashMap::new();
-    for y in 0..y_len {
-        for x in 0..x_len {
-            mem_map.insert((x, y), '.');
-        }
-    }
-
-    for (count, (x, y)) in corrupt_mem.iter().enumerate() {
-        if count <= bytes {
-            mem_map.insert((*x, *y), '#');
-        } else {
-            break;
-        }
+) -> AocResult<Grid<char>> {
+    let mut mem_map = Grid::new(y_len, x_len, '.');
+
+    for byte_pos in corrupt_mem.iter().take(bytes + 1) {
+        let Some(cell) = byte_pos.to_pos().and_then(|pos| mem_map.get_mut(pos)) else {
+            return Err(AocError::invalid(format!(
+                "byte {byte_pos} is outside the {x_len}x{y_len} memory space"
+            )));
+        };
+        *cell = '#';
     }
 
-    mem_map
+    Ok(mem_map)
 }
 
-pub fn get_min_steps(input_file: &str, x_len: isize, y_len: isize, bytes: usize) -> usize {
-    let input = parse_input(input_file);
-
-    //println!("Corrupt mem: {:?}", input.corrupt_mem);
-
-    let mem_map: HashMap<(isize, isize), char> =
-        build_mem_map(&input.corrupt_mem, x_len, y_len, bytes);
-
-    //print_mem_map(&mem_map);
-
-    let (graph, node_indices) = build_graph(&mem_map);
+/// The length of the shortest path from the top left to the bottom right.
+fn find_exit(mem_map: &Grid<char>) -> Option<usize> {
+    let (graph, node_indices) = build_graph(mem_map);
 
     let start = Move { pos: (0, 0) };
-    let start_idx = node_indices[&start];
-
     let end = Move {
-        pos: (x_len - 1, y_len - 1),
+        pos: (mem_map.height() - 1, mem_map.width() - 1),
     };
-    let end_idx = node_indices[&end];
+    let start_idx = *node_indices.get(&start)?;
+    let end_idx = *node_indices.get(&end)?;
 
-    if let Some((distance, _path)) = algo::astar(
+    algo::astar(
         &graph,
         start_idx,
         |finish| finish == end_idx,
         |e| *e.weight() as usize,
         |_| 0,
-    ) {
-        /*println!(
-            "Found path from {:?} to {:?} with distance {}",
-            start, end, distance
-        );
-        for node in path {
-            println!("  {:?}", graph[node]);
-        }*/
-        return distance;
-    }
+    )
+    .map(|(distance, _path)| distance)
+}
 
-    panic!("did not find path")
+pub fn get_min_steps(
+    puzzle_input: impl PuzzleInput,
+    x_len: usize,
+    y_len: usize,
+    bytes: usize,
+) -> AocResult<usize> {
+    let input = parse_input(puzzle_input)?;
+
+    //println!("Corrupt mem: {:?}", input.corrupt_mem);
+
+    let mem_map = build_mem_map(&input.corrupt_mem, x_len, y_len, bytes)?;
+
+    find_exit(&mem_map).ok_or(AocError::invalid("no path to the exit"))
 }
 
 pub fn get_coords_first_byte_to_prevent_exit(
-    input_file: &str,
-    x_len: isize,
-    y_len: isize,
-) -> (isize, isize) {
-    let input = parse_input(input_file);
+    puzzle_input: impl PuzzleInput,
+    x_len: usize,
+    y_len: usize,
+) -> AocResult<(usize, usize)> {
+    let input = parse_input(puzzle_input)?;
 
     for (byte, byte_pos) in input.corrupt_mem.iter().enumerate() {
         //println!("Testing byte {:?} at index {:?}", byte_pos, byte);
 
-        let mem_map: HashMap<(isize, isize), char> =
-            build_mem_map(&input.corrupt_mem, x_len, y_len, byte);
+        let mem_map = build_mem_map(&input.corrupt_mem, x_len, y_len, byte)?;
 
-        //print_mem_map(&mem_map);
+        if find_exit(&mem_map).is_none() {
+            //println!("Found blocking byte {:?} at index {:?}", *byte_pos, byte);
+            return Ok((*byte_pos).into());
+        }
+    }
 
-        let (graph, node_indices) = build_graph(&mem_map);
+    Err(AocError::invalid("no byte blocks the exit"))
+}
 
-        let start = Move { pos: (0, 0) };
-        let start_idx = node_indices[&start];
+pub struct Solver;
 
-        let end = Move {
-            pos: (x_len - 1, y_len - 1),
-        };
-        let end_idx = node_indices[&end];
-
-        if let Some((_distance, _path)) = algo::astar(
-            &graph,
-            start_idx,
-            |finish| finish == end_idx,
-            |e| *e.weight() as usize,
-            |_| 0,
-        ) {
-            /*println!(
-                "Found path from {:?} to {:?} with distance {}",
-                start, end, distance
-            );*/
-        } else {
-            //println!("Found blocking byte {:?} at index {:?}", *byte_pos, byte);
-            return *byte_pos;
-        }
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
     }
 
-    panic!("did not find byte to prevent exit")
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_min_steps(Contents(input), 71, 71, 1023)?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        let (x, y) = get_coords_first_byte_to_prevent_exit(Contents(input), 71, 71)?;
+        Ok(format!("{x},{y}").into())
+    }
 }
 
 #[cfg(test)]
@@ -210,19 +171,25 @@ mod tests {
 
     #[test]
     fn test_get_min_steps_test01() {
-        assert_eq!(22, get_min_steps("input/2024/day18_test01.txt", 7, 7, 11));
+        assert_eq!(
+            22,
+            get_min_steps("input/2024/day18_test01.txt", 7, 7, 11).unwrap()
+        );
     }
 
     #[test]
     fn test_get_min_steps() {
-        assert_eq!(302, get_min_steps("input/2024/day18.txt", 71, 71, 1023));
+        assert_eq!(
+            302,
+            get_min_steps("input/2024/day18.txt", 71, 71, 1023).unwrap()
+        );
     }
 
     #[test]
     fn test_get_coords_first_byte_to_prevent_exit_test01() {
         assert_eq!(
             (6, 1),
-            get_coords_first_byte_to_prevent_exit("input/2024/day18_test01.txt", 7, 7)
+            get_coords_first_byte_to_prevent_exit("input/2024/day18_test01.txt", 7, 7).unwrap()
         );
     }
 
@@ -231,7 +198,7 @@ mod tests {
     fn test_get_coords_first_byte_to_prevent_exit() {
         assert_eq!(
             (24, 32),
-            get_coords_first_byte_to_prevent_exit("input/2024/day18.txt", 71, 71)
+            get_coords_first_byte_to_prevent_exit("input/2024/day18.txt", 71, 71).unwrap()
         );
     }
 }
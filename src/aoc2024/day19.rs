@@ -2,7 +2,9 @@
 
 use std::collections::HashMap;
 
-use crate::utils::get_lines;
+use crate::error::{AocError, AocResult};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput, get_lines};
 
 #[derive(Debug)]
 struct Input {
@@ -10,23 +12,22 @@ struct Input {
     designs: Vec<String>,
 }
 
-fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
+fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let lines = get_lines(puzzle_input)?;
 
-    let mut towels: Vec<String> = if let Some(first_line) = lines.first() {
-        first_line
-            .split(',')
-            .map(|s| s.trim().to_string())
-            .collect()
-    } else {
-        panic!("No towels found");
+    let Some(first_line) = lines.first() else {
+        return Err(AocError::invalid("no towels found"));
     };
+    let mut towels: Vec<String> = first_line
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .collect();
 
     towels.sort_by(|a, b| b.cmp(a));
 
     let designs = lines.iter().skip(2).map(|line| line.to_string()).collect();
 
-    Input { towels, designs }
+    Ok(Input { towels, designs })
 }
 
 fn poss_design_combos(
@@ -50,8 +51,8 @@ fn poss_design_combos(
     num_combos
 }
 
-pub fn get_poss_designs(input_file: &str) -> (usize, usize) {
-    let input = parse_input(input_file);
+pub fn get_poss_designs(puzzle_input: impl PuzzleInput) -> AocResult<(usize, usize)> {
+    let input = parse_input(puzzle_input)?;
 
     //println!("towels: {:?}", input.towels);
     //println!("designs: {:?}", input.designs);
@@ -67,7 +68,24 @@ pub fn get_poss_designs(input_file: &str) -> (usize, usize) {
         tot_num_combos += num_combos;
     }
 
-    (poss_designs, tot_num_combos)
+    Ok((poss_designs, tot_num_combos))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_poss_designs(Contents(input))?.0.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_poss_designs(Contents(input))?.1.into())
+    }
 }
 
 #[cfg(test)]
@@ -76,36 +94,54 @@ mod tests {
 
     #[test]
     fn test_get_poss_designs_test01() {
-        assert_eq!(6, get_poss_designs("input/2024/day19_test01.txt").0);
+        assert_eq!(
+            6,
+            get_poss_designs("input/2024/day19_test01.txt").unwrap().0
+        );
     }
 
     #[test]
     fn test_get_poss_designs_test02() {
-        assert_eq!(1, get_poss_designs("input/2024/day19_test02.txt").0);
+        assert_eq!(
+            1,
+            get_poss_designs("input/2024/day19_test02.txt").unwrap().0
+        );
     }
 
     #[test]
     fn test_get_poss_designs_test03() {
-        assert_eq!(0, get_poss_designs("input/2024/day19_test03.txt").0);
+        assert_eq!(
+            0,
+            get_poss_designs("input/2024/day19_test03.txt").unwrap().0
+        );
     }
 
     #[test]
     fn test_get_poss_designs_test04() {
-        assert_eq!(0, get_poss_designs("input/2024/day19_test04.txt").0);
+        assert_eq!(
+            0,
+            get_poss_designs("input/2024/day19_test04.txt").unwrap().0
+        );
     }
 
     #[test]
     fn test_get_poss_designs() {
-        assert_eq!(317, get_poss_designs("input/2024/day19.txt").0);
+        assert_eq!(317, get_poss_designs("input/2024/day19.txt").unwrap().0);
     }
 
     #[test]
     fn test_get_num_combos_test01() {
-        assert_eq!(16, get_poss_designs("input/2024/day19_test01.txt").1);
+        assert_eq!(
+            16,
+            get_poss_designs("input/2024/day19_test01.txt").unwrap().1
+        );
     }
 
     #[test]
     fn test_get_num_combos() {
-        assert_eq!(883443544805484, get_poss_designs("input/2024/day19.txt").1);
+        assert_eq!(
+            883443544805484,
+            get_poss_designs("input/2024/day19.txt").unwrap().1
+        );
     }
 }
@@ -10,57 +10,35 @@ use petgraph::{
     graph::{DiGraph, NodeIndex},
 };
 
-use crate::utils::{Direction, get_lines, manhattan_distance};
+use crate::error::{AocError, AocResult};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, Grid, Point, Pos, PuzzleInput};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Move {
-    pos: (usize, usize),
+    pos: Pos,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Cheat {
-    from: (usize, usize),
-    to: (usize, usize),
+    from: Point<usize>,
+    to: Point<usize>,
     distance: usize,
 }
 
 #[derive(Debug)]
 struct Input {
-    track: HashMap<(usize, usize), char>,
+    track: Grid<char>,
 }
 
-fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
+fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let track = Grid::parse(puzzle_input, Some)?;
 
-    let mut track: HashMap<(usize, usize), char> = HashMap::new();
-
-    for (y, line) in lines.into_iter().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            track.insert((x, y), c);
-        }
-    }
-
-    Input { track }
+    Ok(Input { track })
 }
 
-/*fn print_track(track: &HashMap<(usize, usize), char>) {
-    let max_x = track.keys().map(|(x, _)| *x).max().unwrap();
-    let max_y = track.keys().map(|(_, y)| *y).max().unwrap();
-
-    for y in 0..=max_y {
-        for x in 0..=max_x {
-            if let Some(c) = track.get(&(x, y)) {
-                print!("{}", c);
-            } else {
-                print!(" ");
-            }
-        }
-        println!();
-    }
-}*/
-
 fn build_graph(
-    track: &HashMap<(usize, usize), char>,
+    track: &Grid<char>,
     maybe_cheat: Option<Cheat>,
 ) -> (Graph<Move, f64>, HashMap<Move, NodeIndex>) {
     // Create directed graph
@@ -69,9 +47,9 @@ fn build_graph(
 
     // Add nodes and edges
     // First create all nodes
-    for (&(x, y), &c) in track.iter() {
+    for (pos, &c) in track.iter() {
         if c != '#' {
-            let point = Move { pos: (x, y) };
+            let point = Move { pos };
             let node_idx = graph.add_node(point);
             node_indices.insert(point, node_idx);
             //println!("Added node {:?}", graph[node_idx]);
@@ -79,22 +57,13 @@ fn build_graph(
     }
 
     // Then add all edges in a separate pass
-    for (&(x, y), &c) in track.iter() {
+    for (pos, &c) in track.iter() {
         if c != '#' {
-            for dir in Direction::all() {
-                let point = Move { pos: (x, y) };
-                let node_idx = node_indices[&point];
-
+            let node_idx = node_indices[&Move { pos }];
+            for (_, next_pos) in track.neighbors4(pos) {
                 // Forward movement
-                let (dx, dy) = dir.to_delta();
-                let (next_x, next_y) = (x as isize + dx, y as isize + dy);
-                if let Some(&next_c) = track.get(&(next_x as usize, next_y as usize))
-                    && next_c != '#'
-                {
-                    let next_move = Move {
-                        pos: (next_x as usize, next_y as usize),
-                    };
-                    let next_idx = node_indices[&next_move];
+                if track[next_pos] != '#' {
+                    let next_idx = node_indices[&Move { pos: next_pos }];
                     graph.add_edge(node_idx, next_idx, 1.0);
                     //println!("Added edge {:?} -> {:?}", graph[node_idx], graph[next_idx]);
                 }
@@ -102,29 +71,28 @@ fn build_graph(
         }
     }
 
-    if let Some(cheat) = maybe_cheat {
-        let from = Move { pos: cheat.from };
-        let to = Move { pos: cheat.to };
-        if let Some(from_idx) = node_indices.get(&from)
-            && let Some(to_idx) = node_indices.get(&to)
-        {
-            graph.add_edge(*from_idx, *to_idx, cheat.distance as f64);
-            /*println!(
-                "Added cheat edge {:?} -> {:?}",
-                graph[*from_idx], graph[*to_idx]
-            );*/
-        }
+    if let Some(cheat) = maybe_cheat
+        && let Some(from) = cheat.from.to_pos()
+        && let Some(to) = cheat.to.to_pos()
+        && let Some(from_idx) = node_indices.get(&Move { pos: from })
+        && let Some(to_idx) = node_indices.get(&Move { pos: to })
+    {
+        graph.add_edge(*from_idx, *to_idx, cheat.distance as f64);
+        /*println!(
+            "Added cheat edge {:?} -> {:?}",
+            graph[*from_idx], graph[*to_idx]
+        );*/
     }
 
     (graph, node_indices)
 }
 
 fn get_distance(
-    start: (usize, usize),
-    end: (usize, usize),
-    track: &HashMap<(usize, usize), char>,
+    start: Pos,
+    end: Pos,
+    track: &Grid<char>,
     maybe_cheat: Option<Cheat>,
-) -> Option<Vec<(usize, usize)>> {
+) -> Option<Vec<Point<usize>>> {
     let (graph, node_indices) = build_graph(track, maybe_cheat);
 
     let start_move = Move { pos: start };
@@ -142,10 +110,7 @@ fn get_distance(
     ) {
         let path_pos = path
             .iter()
-            .map(|idx| {
-                let node = &graph[*idx];
-                node.pos
-            })
+            .filter_map(|idx| Point::from_pos(graph[*idx].pos))
             .collect::<Vec<_>>();
 
         Some(path_pos)
@@ -154,47 +119,35 @@ fn get_distance(
     }
 }
 
-fn get_cheats(
-    track: &HashMap<(usize, usize), char>,
-    no_cheat_path: &[(usize, usize)],
-    max_distance: usize,
-) -> HashSet<(Cheat, usize)> {
+/// The cheats between points on the path, which runs over every open cell of
+/// the track, with how many steps each saves.
+fn get_cheats(no_cheat_path: &[Point<usize>], max_distance: usize) -> HashSet<(Cheat, usize)> {
     let mut cheats = HashSet::new();
-    let path_lens: HashMap<(usize, usize), usize> = no_cheat_path
+    let path_lens: HashMap<Point<usize>, usize> = no_cheat_path
         .iter()
         .rev()
         .enumerate()
         .map(|(i, &pos)| (pos, i))
         .collect();
-    for (track_x, track_y) in no_cheat_path.iter() {
-        let track_entry = track[&(*track_x, *track_y)];
-        for ((next_track_x, next_track_y), next_track_entry) in track.iter() {
-            if (track_x, track_y) != (next_track_x, next_track_y)
-                && track_entry != '#'
-                && *next_track_entry != '#'
-            {
-                let distance =
-                    manhattan_distance((*track_x, *track_y), (*next_track_x, *next_track_y));
+    for &track_pos in no_cheat_path {
+        for &next_track_pos in no_cheat_path {
+            if track_pos != next_track_pos {
+                let distance = track_pos.manhattan_distance(next_track_pos);
                 if distance <= max_distance {
-                    let curr_path_len = path_lens[&(*track_x, *track_y)];
-                    let rem_path_len = path_lens[&(*next_track_x, *next_track_y)];
+                    let curr_path_len = path_lens[&track_pos];
+                    let rem_path_len = path_lens[&next_track_pos];
 
                     if rem_path_len < curr_path_len {
                         let cheat_saving = curr_path_len - rem_path_len - distance;
                         if cheat_saving > 0 {
                             /*println!(
-                                "Adding cheat from {:?}:{:?} to {:?}:{:?} with distance/saving {}/{}",
-                                (*track_x, *track_y),
-                                track_entry,
-                                (*next_track_x, *next_track_y),
-                                next_track_entry,
-                                distance,
-                                cheat_saving
+                                "Adding cheat from {} to {} with distance/saving {}/{}",
+                                track_pos, next_track_pos, distance, cheat_saving
                             );*/
                             cheats.insert((
                                 Cheat {
-                                    from: (*track_x, *track_y),
-                                    to: (*next_track_x, *next_track_y),
+                                    from: track_pos,
+                                    to: next_track_pos,
                                     distance,
                                 },
                                 cheat_saving,
@@ -209,36 +162,30 @@ fn get_cheats(
 }
 
 pub fn get_num_cheats(
-    input_file: &str,
+    puzzle_input: impl PuzzleInput,
     required_savings: usize,
     is_at_least: bool,
     max_distance: usize,
-) -> usize {
-    let input = parse_input(input_file);
-
-    //print_track(&input.track);
+) -> AocResult<usize> {
+    let input = parse_input(puzzle_input)?;
 
     // Find start and end points
     let start = input
         .track
-        .iter()
-        .find(|(_, c)| **c == 'S')
-        .map(|(&pos, _)| pos)
-        .unwrap();
+        .position(|c| *c == 'S')
+        .ok_or_else(|| AocError::invalid("no start position on the track"))?;
     let end = input
         .track
-        .iter()
-        .find(|(_, c)| **c == 'E')
-        .map(|(&pos, _)| pos)
-        .unwrap();
+        .position(|c| *c == 'E')
+        .ok_or_else(|| AocError::invalid("no end position on the track"))?;
 
     let no_cheat_path = if let Some(distance) = get_distance(start, end, &input.track, None) {
         distance
     } else {
-        return 0;
+        return Ok(0);
     };
 
-    let cheat_savings = get_cheats(&input.track, &no_cheat_path, max_distance);
+    let cheat_savings = get_cheats(&no_cheat_path, max_distance);
 
     let filtered_cheat_savings = cheat_savings
         .iter()
@@ -251,7 +198,24 @@ pub fn get_num_cheats(
         })
         .collect::<Vec<_>>();
 
-    filtered_cheat_savings.len()
+    Ok(filtered_cheat_savings.len())
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_num_cheats(Contents(input), 100, true, 2)?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_num_cheats(Contents(input), 100, true, 20)?.into())
+    }
 }
 
 #[cfg(test)]
@@ -262,7 +226,7 @@ mod tests {
     fn test_get_num_cheats_test01() {
         assert_eq!(
             14,
-            get_num_cheats("input/2024/day20_test01.txt", 2, false, 2)
+            get_num_cheats("input/2024/day20_test01.txt", 2, false, 2).unwrap()
         );
     }
 
@@ -270,7 +234,7 @@ mod tests {
     fn test_get_num_cheats_test02() {
         assert_eq!(
             14,
-            get_num_cheats("input/2024/day20_test01.txt", 4, false, 2)
+            get_num_cheats("input/2024/day20_test01.txt", 4, false, 2).unwrap()
         );
     }
 
@@ -278,7 +242,7 @@ mod tests {
     fn test_get_num_cheats_test03() {
         assert_eq!(
             2,
-            get_num_cheats("input/2024/day20_test01.txt", 6, false, 2)
+            get_num_cheats("input/2024/day20_test01.txt", 6, false, 2).unwrap()
         );
     }
 
@@ -286,7 +250,7 @@ mod tests {
     fn test_get_num_cheats_test04() {
         assert_eq!(
             4,
-            get_num_cheats("input/2024/day20_test01.txt", 8, false, 2)
+            get_num_cheats("input/2024/day20_test01.txt", 8, false, 2).unwrap()
         );
     }
 
@@ -294,7 +258,7 @@ mod tests {
     fn test_get_num_cheats_test05() {
         assert_eq!(
             2,
-            get_num_cheats("input/2024/day20_test01.txt", 10, false, 2)
+            get_num_cheats("input/2024/day20_test01.txt", 10, false, 2).unwrap()
         );
     }
 
@@ -302,7 +266,7 @@ mod tests {
     fn test_get_num_cheats_test06() {
         assert_eq!(
             3,
-            get_num_cheats("input/2024/day20_test01.txt", 12, false, 2)
+            get_num_cheats("input/2024/day20_test01.txt", 12, false, 2).unwrap()
         );
     }
 
@@ -310,7 +274,7 @@ mod tests {
     fn test_get_num_cheats_test07() {
         assert_eq!(
             1,
-            get_num_cheats("input/2024/day20_test01.txt", 20, false, 2)
+            get_num_cheats("input/2024/day20_test01.txt", 20, false, 2).unwrap()
         );
     }
 
@@ -318,7 +282,7 @@ mod tests {
     fn test_get_num_cheats_test08() {
         assert_eq!(
             1,
-            get_num_cheats("input/2024/day20_test01.txt", 36, false, 2)
+            get_num_cheats("input/2024/day20_test01.txt", 36, false, 2).unwrap()
         );
     }
 
@@ -326,7 +290,7 @@ mod tests {
     fn test_get_num_cheats_test09() {
         assert_eq!(
             1,
-            get_num_cheats("input/2024/day20_test01.txt", 38, false, 2)
+            get_num_cheats("input/2024/day20_test01.txt", 38, false, 2).unwrap()
         );
     }
 
@@ -334,7 +298,7 @@ mod tests {
     fn test_get_num_cheats_test10() {
         assert_eq!(
             1,
-            get_num_cheats("input/2024/day20_test01.txt", 40, false, 2)
+            get_num_cheats("input/2024/day20_test01.txt", 40, false, 2).unwrap()
         );
     }
 
@@ -342,26 +306,32 @@ mod tests {
     fn test_get_num_cheats_test11() {
         assert_eq!(
             1,
-            get_num_cheats("input/2024/day20_test01.txt", 64, false, 2)
+            get_num_cheats("input/2024/day20_test01.txt", 64, false, 2).unwrap()
         );
     }
 
     #[test]
     fn test_get_num_cheats_test12() {
-        assert_eq!(2, get_num_cheats("input/2024/day20_test02.txt", 2, true, 2));
+        assert_eq!(
+            2,
+            get_num_cheats("input/2024/day20_test02.txt", 2, true, 2).unwrap()
+        );
     }
 
     #[ignore]
     #[test]
     fn test_get_num_cheats() {
-        assert_eq!(1399, get_num_cheats("input/2024/day20.txt", 100, true, 2));
+        assert_eq!(
+            1399,
+            get_num_cheats("input/2024/day20.txt", 100, true, 2).unwrap()
+        );
     }
 
     #[test]
     fn test_get_num_cheats_new_rules_test01() {
         assert_eq!(
             32,
-            get_num_cheats("input/2024/day20_test01.txt", 50, false, 20)
+            get_num_cheats("input/2024/day20_test01.txt", 50, false, 20).unwrap()
         );
     }
 
@@ -369,7 +339,7 @@ mod tests {
     fn test_get_num_cheats_new_rules_test02() {
         assert_eq!(
             31,
-            get_num_cheats("input/2024/day20_test01.txt", 52, false, 20)
+            get_num_cheats("input/2024/day20_test01.txt", 52, false, 20).unwrap()
         );
     }
 
@@ -377,7 +347,7 @@ mod tests {
     fn test_get_num_cheats_new_rules_test03() {
         assert_eq!(
             29,
-            get_num_cheats("input/2024/day20_test01.txt", 54, false, 20)
+            get_num_cheats("input/2024/day20_test01.txt", 54, false, 20).unwrap()
         );
     }
 
@@ -385,7 +355,7 @@ mod tests {
     fn test_get_num_cheats_new_rules_test04() {
         assert_eq!(
             39,
-            get_num_cheats("input/2024/day20_test01.txt", 56, false, 20)
+            get_num_cheats("input/2024/day20_test01.txt", 56, false, 20).unwrap()
         );
     }
 
@@ -393,7 +363,7 @@ mod tests {
     fn test_get_num_cheats_new_rules_test05() {
         assert_eq!(
             25,
-            get_num_cheats("input/2024/day20_test01.txt", 58, false, 20)
+            get_num_cheats("input/2024/day20_test01.txt", 58, false, 20).unwrap()
         );
     }
 
@@ -401,7 +371,7 @@ mod tests {
     fn test_get_num_cheats_new_rules_test06() {
         assert_eq!(
             23,
-            get_num_cheats("input/2024/day20_test01.txt", 60, false, 20)
+            get_num_cheats("input/2024/day20_test01.txt", 60, false, 20).unwrap()
         );
     }
 
@@ -409,7 +379,7 @@ mod tests {
     fn test_get_num_cheats_new_rules_test07() {
         assert_eq!(
             20,
-            get_num_cheats("input/2024/day20_test01.txt", 62, false, 20)
+            get_num_cheats("input/2024/day20_test01.txt", 62, false, 20).unwrap()
         );
     }
 
@@ -417,7 +387,7 @@ mod tests {
     fn test_get_num_cheats_new_rules_test08() {
         assert_eq!(
             19,
-            get_num_cheats("input/2024/day20_test01.txt", 64, false, 20)
+            get_num_cheats("input/2024/day20_test01.txt", 64, false, 20).unwrap()
         );
     }
 
@@ -425,7 +395,7 @@ mod tests {
     fn test_get_num_cheats_new_rules_test09() {
         assert_eq!(
             12,
-            get_num_cheats("input/2024/day20_test01.txt", 66, false, 20)
+            get_num_cheats("input/2024/day20_test01.txt", 66, false, 20).unwrap()
         );
     }
 
@@ -433,7 +403,7 @@ mod tests {
     fn test_get_num_cheats_new_rules_test10() {
         assert_eq!(
             14,
-            get_num_cheats("input/2024/day20_test01.txt", 68, false, 20)
+            get_num_cheats("input/2024/day20_test01.txt", 68, false, 20).unwrap()
         );
     }
 
@@ -441,7 +411,7 @@ mod tests {
     fn test_get_num_cheats_new_rules_test11() {
         assert_eq!(
             12,
-            get_num_cheats("input/2024/day20_test01.txt", 70, false, 20)
+            get_num_cheats("input/2024/day20_test01.txt", 70, false, 20).unwrap()
         );
     }
 
@@ -449,7 +419,7 @@ mod tests {
     fn test_get_num_cheats_new_rules_test12() {
         assert_eq!(
             22,
-            get_num_cheats("input/2024/day20_test01.txt", 72, false, 20)
+            get_num_cheats("input/2024/day20_test01.txt", 72, false, 20).unwrap()
         );
     }
 
@@ -457,7 +427,7 @@ mod tests {
     fn test_get_num_cheats_new_rules_test13() {
         assert_eq!(
             4,
-            get_num_cheats("input/2024/day20_test01.txt", 74, false, 20)
+            get_num_cheats("input/2024/day20_test01.txt", 74, false, 20).unwrap()
         );
     }
 
@@ -465,7 +435,7 @@ mod tests {
     fn test_get_num_cheats_new_rules_test14() {
         assert_eq!(
             3,
-            get_num_cheats("input/2024/day20_test01.txt", 76, false, 20)
+            get_num_cheats("input/2024/day20_test01.txt", 76, false, 20).unwrap()
         );
     }
 
@@ -474,7 +444,7 @@ mod tests {
     fn test_get_num_cheats_new_rules() {
         assert_eq!(
             994807,
-            get_num_cheats("input/2024/day20.txt", 100, true, 20)
+            get_num_cheats("input/2024/day20.txt", 100, true, 20).unwrap()
         );
     }
 }
@@ -8,7 +8,10 @@ use petgraph::{
     graph::{DiGraph, NodeIndex},
 };
 
-use crate::utils::{Direction, get_all_paths, get_lines};
+use crate::error::{AocError, AocResult};
+use crate::grid::{Grid, Pos};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, Direction, PuzzleInput, get_all_paths, get_lines};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum Button {
@@ -31,7 +34,7 @@ enum Button {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Move {
-    pos: (usize, usize),
+    pos: Pos,
     button: Button,
 }
 
@@ -44,8 +47,8 @@ struct GraphData {
     node_indices: HashMap<Move, NodeIndex>,
 }
 
-fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
+fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let lines = get_lines(puzzle_input)?;
 
     let mut codes = vec![];
 
@@ -53,53 +56,39 @@ fn parse_input(input_file: &str) -> Input {
         codes.push(line);
     }
 
-    Input { codes }
+    Ok(Input { codes })
 }
 
-fn build_num_keypad() -> HashMap<(usize, usize), Option<Button>> {
-    let mut num_keypad = HashMap::new();
-
-    // First column
-    num_keypad.insert((0, 0), Some(Button::Seven));
-    num_keypad.insert((0, 1), Some(Button::Four));
-    num_keypad.insert((0, 2), Some(Button::One));
-    num_keypad.insert((0, 3), None);
-
-    // Second column
-    num_keypad.insert((1, 0), Some(Button::Eight));
-    num_keypad.insert((1, 1), Some(Button::Five));
-    num_keypad.insert((1, 2), Some(Button::Two));
-    num_keypad.insert((1, 3), Some(Button::Zero));
-
-    // Third column
-    num_keypad.insert((2, 0), Some(Button::Nine));
-    num_keypad.insert((2, 1), Some(Button::Six));
-    num_keypad.insert((2, 2), Some(Button::Three));
-    num_keypad.insert((2, 3), Some(Button::Activate));
-
-    num_keypad
+fn build_num_keypad() -> Grid<Option<Button>> {
+    Grid::from_rows(vec![
+        vec![Some(Button::Seven), Some(Button::Eight), Some(Button::Nine)],
+        vec![Some(Button::Four), Some(Button::Five), Some(Button::Six)],
+        vec![Some(Button::One), Some(Button::Two), Some(Button::Three)],
+        vec![None, Some(Button::Zero), Some(Button::Activate)],
+    ])
+    .expect("keypad rows are the same width")
 }
 
-fn build_dir_keypad() -> HashMap<(usize, usize), Option<Button>> {
-    let mut dir_keypad = HashMap::new();
-
-    // First column
-    dir_keypad.insert((0, 0), None);
-    dir_keypad.insert((0, 1), Some(Button::Left));
-
-    // Second column
-    dir_keypad.insert((1, 0), Some(Button::Up));
-    dir_keypad.insert((1, 1), Some(Button::Down));
-
-    // Third column
-    dir_keypad.insert((2, 0), Some(Button::Activate));
-    dir_keypad.insert((2, 1), Some(Button::Right));
+fn build_dir_keypad() -> Grid<Option<Button>> {
+    Grid::from_rows(vec![
+        vec![None, Some(Button::Up), Some(Button::Activate)],
+        vec![Some(Button::Left), Some(Button::Down), Some(Button::Right)],
+    ])
+    .expect("keypad rows are the same width")
+}
 
-    dir_keypad
+/// The keypad's buttons with the position of each.
+fn keypad_moves(keypad: &Grid<Option<Button>>) -> impl Iterator<Item = Move> + '_ {
+    keypad.iter().filter_map(|(pos, &maybe_button)| {
+        Some(Move {
+            pos,
+            button: maybe_button?,
+        })
+    })
 }
 
 fn build_keypad_graph(
-    keypad: &HashMap<(usize, usize), Option<Button>>,
+    keypad: &Grid<Option<Button>>,
 ) -> (Graph<Move, f64>, HashMap<Move, NodeIndex>) {
     // Create directed graph
     let mut graph = DiGraph::<Move, f64>::new();
@@ -108,42 +97,22 @@ fn build_keypad_graph(
     // Add nodes and edges
 
     // First create all nodes
-    for (&(x, y), &maybe_button) in keypad.iter() {
-        if let Some(button) = maybe_button {
-            for _dir in Direction::all() {
-                let point = Move {
-                    pos: (x, y),
-                    button,
-                };
-                let node_idx = graph.add_node(point);
-                node_indices.insert(point, node_idx);
-            }
-        }
+    for point in keypad_moves(keypad) {
+        let node_idx = graph.add_node(point);
+        node_indices.insert(point, node_idx);
     }
 
     // Then add all edges in a separate pass
-    for (&(x, y), &maybe_button) in keypad.iter() {
-        if let Some(button) = maybe_button {
-            for dir in Direction::all() {
-                let point = Move {
-                    pos: (x, y),
-                    button,
+    for point in keypad_moves(keypad) {
+        let node_idx = node_indices[&point];
+        for (_, next_pos) in keypad.neighbors4(point.pos) {
+            if let Some(next_button) = keypad[next_pos] {
+                let next_move = Move {
+                    pos: next_pos,
+                    button: next_button,
                 };
-                let node_idx = node_indices[&point];
-
-                // Forward movement
-                let (dx, dy) = dir.to_delta();
-                let (next_x, next_y) = (x as isize + dx, y as isize + dy);
-                if let Some(&maybe_next_button) = keypad.get(&(next_x as usize, next_y as usize))
-                    && let Some(next_button) = maybe_next_button
-                {
-                    let next_move = Move {
-                        pos: (next_x as usize, next_y as usize),
-                        button: next_button,
-                    };
-                    let next_idx = node_indices[&next_move];
-                    graph.add_edge(node_idx, next_idx, 1.0);
-                }
+                let next_idx = node_indices[&next_move];
+                graph.add_edge(node_idx, next_idx, 1.0);
             }
         }
     }
@@ -181,15 +150,15 @@ fn get_shortest_paths_for_move(
 }
 
 fn get_move_direction(start: Move, end: Move) -> Option<Direction> {
-    let (start_x, start_y) = start.pos;
-    let (end_x, end_y) = end.pos;
+    let (start_row, start_col) = start.pos;
+    let (end_row, end_col) = end.pos;
     if start == end {
         None
-    } else if end_x > start_x {
+    } else if end_col > start_col {
         Some(Direction::E)
-    } else if end_x < start_x {
+    } else if end_col < start_col {
         Some(Direction::W)
-    } else if end_y > start_y {
+    } else if end_row > start_row {
         Some(Direction::S)
     } else {
         Some(Direction::N)
@@ -217,34 +186,21 @@ fn get_dir_path_from_keypad_path(keypad_path: &[Move]) -> Vec<Button> {
 }
 
 fn build_move_cache(
-    keypad: &HashMap<(usize, usize), Option<Button>>,
+    keypad: &Grid<Option<Button>>,
     keypad_graph_data: &GraphData,
 ) -> HashMap<(Move, Move), Vec<Vec<Button>>> {
     let mut move_cache = HashMap::new();
 
-    for (&(x1, y1), &maybe_button1) in keypad.iter() {
-        if let Some(button1) = maybe_button1 {
-            for (&(x2, y2), &maybe_button2) in keypad.iter() {
-                if let Some(button2) = maybe_button2 {
-                    let start_move = Move {
-                        pos: (x1, y1),
-                        button: button1,
-                    };
-                    let end_move = Move {
-                        pos: (x2, y2),
-                        button: button2,
-                    };
-                    let paths =
-                        get_shortest_paths_for_move(&start_move, &end_move, keypad_graph_data);
-
-                    let dir_paths: Vec<Vec<Button>> = paths
-                        .iter()
-                        .map(|path| get_dir_path_from_keypad_path(path))
-                        .collect();
-
-                    move_cache.insert((start_move, end_move), dir_paths);
-                }
-            }
+    for start_move in keypad_moves(keypad) {
+        for end_move in keypad_moves(keypad) {
+            let paths = get_shortest_paths_for_move(&start_move, &end_move, keypad_graph_data);
+
+            let dir_paths: Vec<Vec<Button>> = paths
+                .iter()
+                .map(|path| get_dir_path_from_keypad_path(path))
+                .collect();
+
+            move_cache.insert((start_move, end_move), dir_paths);
         }
     }
 
@@ -298,24 +254,22 @@ fn build_keypad_seq(
     result_path
 }
 
-fn get_buttons_for_code(code: &str) -> Vec<Button> {
+fn get_buttons_for_code(line_idx: usize, code: &str) -> AocResult<Vec<Button>> {
     code.chars()
-        .map(|c| {
-            match c {
-                '0' => Some(Button::Zero),
-                '1' => Some(Button::One),
-                '2' => Some(Button::Two),
-                '3' => Some(Button::Three),
-                '4' => Some(Button::Four),
-                '5' => Some(Button::Five),
-                '6' => Some(Button::Six),
-                '7' => Some(Button::Seven),
-                '8' => Some(Button::Eight),
-                '9' => Some(Button::Nine),
-                'A' => Some(Button::Activate),
-                _ => panic!("Invalid button"),
-            }
-            .unwrap()
+        .enumerate()
+        .map(|(col_idx, c)| match c {
+            '0' => Ok(Button::Zero),
+            '1' => Ok(Button::One),
+            '2' => Ok(Button::Two),
+            '3' => Ok(Button::Three),
+            '4' => Ok(Button::Four),
+            '5' => Ok(Button::Five),
+            '6' => Ok(Button::Six),
+            '7' => Ok(Button::Seven),
+            '8' => Ok(Button::Eight),
+            '9' => Ok(Button::Nine),
+            'A' => Ok(Button::Activate),
+            _ => Err(AocError::unexpected_char(line_idx, col_idx, c)),
         })
         .collect()
 }
@@ -365,11 +319,11 @@ fn get_shortest_seq_len(
     total
 }
 
-pub fn get_sum_complexity(input_file: &str, depth: usize) -> usize {
-    let input = parse_input(input_file);
+pub fn get_sum_complexity(puzzle_input: impl PuzzleInput, depth: usize) -> AocResult<usize> {
+    let input = parse_input(puzzle_input)?;
 
-    let num_keypad: HashMap<(usize, usize), Option<Button>> = build_num_keypad();
-    let dir_keypad: HashMap<(usize, usize), Option<Button>> = build_dir_keypad();
+    let num_keypad = build_num_keypad();
+    let dir_keypad = build_dir_keypad();
 
     let (num_keypad_graph, num_keypad_node_indices) = build_keypad_graph(&num_keypad);
     let (dir_keypad_graph, dir_keypad_node_indices) = build_keypad_graph(&dir_keypad);
@@ -400,16 +354,40 @@ pub fn get_sum_complexity(input_file: &str, depth: usize) -> usize {
 
     let mut sum_complexity = 0;
 
-    for code in input.codes {
-        let buttons = get_buttons_for_code(&code);
+    for (line_idx, code) in input.codes.iter().enumerate() {
+        let buttons = get_buttons_for_code(line_idx, code)?;
+        let Some(num_str) = code.strip_suffix('A') else {
+            return Err(AocError::parse(
+                line_idx,
+                code.len(),
+                "expected the code to end with 'A'",
+            ));
+        };
         let shortest_seq_len =
             get_shortest_seq_len(&buttons, depth, &keypad_path_cache, &mut move_cache);
-        if let Ok(num_part) = code[0..code.len() - 1].parse::<usize>() {
+        if let Ok(num_part) = num_str.parse::<usize>() {
             sum_complexity += shortest_seq_len * num_part;
         }
     }
 
-    sum_complexity
+    Ok(sum_complexity)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_sum_complexity(Contents(input), 3)?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_sum_complexity(Contents(input), 26)?.into())
+    }
 }
 
 #[cfg(test)]
@@ -418,54 +396,81 @@ mod tests {
 
     #[test]
     fn test_get_sum_complexity_test01() {
-        assert_eq!(126384, get_sum_complexity("input/2024/day21_test01.txt", 3));
+        assert_eq!(
+            126384,
+            get_sum_complexity("input/2024/day21_test01.txt", 3).unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_complexity_test02() {
-        assert_eq!(1972, get_sum_complexity("input/2024/day21_test02.txt", 3));
+        assert_eq!(
+            1972,
+            get_sum_complexity("input/2024/day21_test02.txt", 3).unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_complexity_test03() {
-        assert_eq!(58800, get_sum_complexity("input/2024/day21_test03.txt", 3));
+        assert_eq!(
+            58800,
+            get_sum_complexity("input/2024/day21_test03.txt", 3).unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_complexity_test04() {
-        assert_eq!(12172, get_sum_complexity("input/2024/day21_test04.txt", 3));
+        assert_eq!(
+            12172,
+            get_sum_complexity("input/2024/day21_test04.txt", 3).unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_complexity_test05() {
-        assert_eq!(29184, get_sum_complexity("input/2024/day21_test05.txt", 3));
+        assert_eq!(
+            29184,
+            get_sum_complexity("input/2024/day21_test05.txt", 3).unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_complexity_test06() {
-        assert_eq!(24256, get_sum_complexity("input/2024/day21_test06.txt", 3));
+        assert_eq!(
+            24256,
+            get_sum_complexity("input/2024/day21_test06.txt", 3).unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_complexity_test07() {
-        assert_eq!(0, get_sum_complexity("input/2024/day21_test07.txt", 3));
+        assert_eq!(
+            0,
+            get_sum_complexity("input/2024/day21_test07.txt", 3).unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_complexity_test08() {
-        assert_eq!(9990, get_sum_complexity("input/2024/day21_test08.txt", 3));
+        assert_eq!(
+            9990,
+            get_sum_complexity("input/2024/day21_test08.txt", 3).unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_complexity() {
-        assert_eq!(184180, get_sum_complexity("input/2024/day21.txt", 3));
+        assert_eq!(
+            184180,
+            get_sum_complexity("input/2024/day21.txt", 3).unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_complexity_part_two_test01() {
         assert_eq!(
             154115708116294,
-            get_sum_complexity("input/2024/day21_test01.txt", 26)
+            get_sum_complexity("input/2024/day21_test01.txt", 26).unwrap()
         );
     }
 
@@ -473,7 +478,7 @@ mod tests {
     fn test_get_sum_complexity_part_two_test02() {
         assert_eq!(
             2379451789590,
-            get_sum_complexity("input/2024/day21_test02.txt", 26)
+            get_sum_complexity("input/2024/day21_test02.txt", 26).unwrap()
         );
     }
 
@@ -481,7 +486,7 @@ mod tests {
     fn test_get_sum_complexity_part_two_test03() {
         assert_eq!(
             70797185862200,
-            get_sum_complexity("input/2024/day21_test03.txt", 26)
+            get_sum_complexity("input/2024/day21_test03.txt", 26).unwrap()
         );
     }
 
@@ -489,7 +494,7 @@ mod tests {
     fn test_get_sum_complexity_part_two_test04() {
         assert_eq!(
             14543936021812,
-            get_sum_complexity("input/2024/day21_test04.txt", 26)
+            get_sum_complexity("input/2024/day21_test04.txt", 26).unwrap()
         );
     }
 
@@ -497,7 +502,7 @@ mod tests {
     fn test_get_sum_complexity_part_two_test05() {
         assert_eq!(
             36838581189648,
-            get_sum_complexity("input/2024/day21_test05.txt", 26)
+            get_sum_complexity("input/2024/day21_test05.txt", 26).unwrap()
         );
     }
 
@@ -505,20 +510,23 @@ mod tests {
     fn test_get_sum_complexity_part_two_test06() {
         assert_eq!(
             29556553253044,
-            get_sum_complexity("input/2024/day21_test06.txt", 26)
+            get_sum_complexity("input/2024/day21_test06.txt", 26).unwrap()
         );
     }
 
     #[test]
     fn test_get_sum_complexity_part_two_test07() {
-        assert_eq!(0, get_sum_complexity("input/2024/day21_test07.txt", 26));
+        assert_eq!(
+            0,
+            get_sum_complexity("input/2024/day21_test07.txt", 26).unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_complexity_part_two_test08() {
         assert_eq!(
             11835830901420,
-            get_sum_complexity("input/2024/day21_test08.txt", 26)
+            get_sum_complexity("input/2024/day21_test08.txt", 26).unwrap()
         );
     }
 
@@ -526,7 +534,7 @@ mod tests {
     fn test_get_sum_complexity_part_two() {
         assert_eq!(
             231309103124520,
-            get_sum_complexity("input/2024/day21.txt", 26)
+            get_sum_complexity("input/2024/day21.txt", 26).unwrap()
         );
     }
 }
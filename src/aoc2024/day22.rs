@@ -2,22 +2,24 @@
 
 use std::collections::{HashMap, HashSet};
 
-use crate::utils::get_lines;
+use crate::error::{AocError, AocResult, parse_token};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput, get_lines};
 
 struct Input {
     init_sec_nums: Vec<usize>,
 }
 
-fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
+fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let lines = get_lines(puzzle_input)?;
 
     let mut init_sec_nums = Vec::new();
 
-    for line in lines {
-        init_sec_nums.push(line.parse::<usize>().unwrap());
+    for (line_idx, line) in lines.iter().enumerate() {
+        init_sec_nums.push(parse_token(line_idx, line, line)?);
     }
 
-    Input { init_sec_nums }
+    Ok(Input { init_sec_nums })
 }
 
 fn get_next_sec_num(sec_num: usize) -> usize {
@@ -32,8 +34,8 @@ fn get_next_sec_num(sec_num: usize) -> usize {
     next_sec_num % 16777216
 }
 
-pub fn get_sum_sec_nums(input_file: &str, level: usize) -> usize {
-    let input = parse_input(input_file);
+pub fn get_sum_sec_nums(puzzle_input: impl PuzzleInput, level: usize) -> AocResult<usize> {
+    let input = parse_input(puzzle_input)?;
 
     let mut sum_sec_num = 0usize;
 
@@ -42,7 +44,7 @@ pub fn get_sum_sec_nums(input_file: &str, level: usize) -> usize {
         sum_sec_num += sec_nums.last().unwrap();
     }
 
-    sum_sec_num
+    Ok(sum_sec_num)
 }
 
 pub fn get_all_sec_nums(sec_num: usize, level: usize) -> Vec<usize> {
@@ -113,8 +115,8 @@ fn get_banana_price_seqs_with_prices(
     banana_price_seqs_with_prices
 }
 
-pub fn get_max_bananas(input_file: &str, level: usize) -> usize {
-    let input = parse_input(input_file);
+pub fn get_max_bananas(puzzle_input: impl PuzzleInput, level: usize) -> AocResult<usize> {
+    let input = parse_input(puzzle_input)?;
 
     let mut all_banana_price_seqs: HashSet<(isize, isize, isize, isize)> = HashSet::new();
     let mut banana_price_seqs_with_prices_by_sec_num: HashMap<usize, Vec<BananaPriceSeqWithPrice>> =
@@ -147,7 +149,27 @@ pub fn get_max_bananas(input_file: &str, level: usize) -> usize {
         max_banana_prices.push(max_banana_price_for_seq);
     }
 
-    *max_banana_prices.iter().max().unwrap()
+    max_banana_prices
+        .into_iter()
+        .max()
+        .ok_or(AocError::invalid("no secret numbers in the input"))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_sum_sec_nums(Contents(input), 2000)?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_max_bananas(Contents(input), 2000)?.into())
+    }
 }
 
 #[cfg(test)]
@@ -158,20 +180,23 @@ mod tests {
     fn test_get_sum_sec_nums_test01() {
         assert_eq!(
             37327623,
-            get_sum_sec_nums("input/2024/day22_test01.txt", 2000)
+            get_sum_sec_nums("input/2024/day22_test01.txt", 2000).unwrap()
         );
     }
 
     #[test]
     fn test_get_sum_sec_nums_test02() {
-        assert_eq!(5908254, get_sum_sec_nums("input/2024/day22_test02.txt", 10));
+        assert_eq!(
+            5908254,
+            get_sum_sec_nums("input/2024/day22_test02.txt", 10).unwrap()
+        );
     }
 
     #[test]
     fn test_get_sum_sec_nums_test03() {
         assert_eq!(
             18183557,
-            get_sum_sec_nums("input/2024/day22_test04.txt", 2000)
+            get_sum_sec_nums("input/2024/day22_test04.txt", 2000).unwrap()
         );
     }
 
@@ -179,13 +204,16 @@ mod tests {
     fn test_get_sum_sec_nums_test04() {
         assert_eq!(
             8876699,
-            get_sum_sec_nums("input/2024/day22_test05.txt", 2000)
+            get_sum_sec_nums("input/2024/day22_test05.txt", 2000).unwrap()
         );
     }
 
     #[test]
     fn test_get_sum_sec_nums() {
-        assert_eq!(15608699004, get_sum_sec_nums("input/2024/day22.txt", 2000));
+        assert_eq!(
+            15608699004,
+            get_sum_sec_nums("input/2024/day22.txt", 2000).unwrap()
+        );
     }
 
     #[test]
@@ -220,32 +248,47 @@ mod tests {
 
     #[test]
     fn test_get_max_bananas_test01() {
-        assert_eq!(6, get_max_bananas("input/2024/day22_test02.txt", 9));
+        assert_eq!(
+            6,
+            get_max_bananas("input/2024/day22_test02.txt", 9).unwrap()
+        );
     }
 
     #[test]
     fn test_get_max_bananas_test02() {
-        assert_eq!(23, get_max_bananas("input/2024/day22_test03.txt", 2000));
+        assert_eq!(
+            23,
+            get_max_bananas("input/2024/day22_test03.txt", 2000).unwrap()
+        );
     }
 
     #[test]
     fn test_get_max_bananas_test03() {
-        assert_eq!(27, get_max_bananas("input/2024/day22_test04.txt", 2000));
+        assert_eq!(
+            27,
+            get_max_bananas("input/2024/day22_test04.txt", 2000).unwrap()
+        );
     }
 
     #[test]
     fn test_get_max_bananas_test04() {
-        assert_eq!(27, get_max_bananas("input/2024/day22_test05.txt", 2000));
+        assert_eq!(
+            27,
+            get_max_bananas("input/2024/day22_test05.txt", 2000).unwrap()
+        );
     }
 
     #[test]
     fn test_get_max_bananas_test05() {
-        assert_eq!(12, get_max_bananas("input/2024/day22_test06.txt", 9));
+        assert_eq!(
+            12,
+            get_max_bananas("input/2024/day22_test06.txt", 9).unwrap()
+        );
     }
 
     #[ignore]
     #[test]
     fn test_get_max_bananas() {
-        assert_eq!(1791, get_max_bananas("input/2024/day22.txt", 2000));
+        assert_eq!(1791, get_max_bananas("input/2024/day22.txt", 2000).unwrap());
     }
 }
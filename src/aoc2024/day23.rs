@@ -7,23 +7,25 @@ use petgraph::{
     graph::{NodeIndex, UnGraph},
 };
 
-use crate::utils::get_lines;
+use crate::error::{AocResult, require};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput, get_lines};
 
 struct Input {
     conn_pairs: Vec<(String, String)>,
 }
 
-fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
+fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let lines = get_lines(puzzle_input)?;
 
     let mut conn_pairs: Vec<(String, String)> = vec![];
 
-    for line in lines {
-        let parts: Vec<&str> = line.split("-").collect();
-        conn_pairs.push((parts[0].to_string(), parts[1].to_string()));
+    for (line_idx, line) in lines.iter().enumerate() {
+        let (node1, node2) = require(line.split_once('-'), line_idx, "expected a-b")?;
+        conn_pairs.push((node1.to_string(), node2.to_string()));
     }
 
-    Input { conn_pairs }
+    Ok(Input { conn_pairs })
 }
 
 fn build_conn_graph(
@@ -57,8 +59,8 @@ fn build_conn_graph(
     (graph, node_indices)
 }
 
-pub fn get_num_conn_start_t_brute_force(input_file: &str) -> usize {
-    let input = parse_input(input_file);
+pub fn get_num_conn_start_t_brute_force(puzzle_input: impl PuzzleInput) -> AocResult<usize> {
+    let input = parse_input(puzzle_input)?;
 
     let (graph, node_indices) = build_conn_graph(&input.conn_pairs);
 
@@ -124,7 +126,7 @@ pub fn get_num_conn_start_t_brute_force(input_file: &str) -> usize {
         }
     }
 
-    conn_comp_tuples.len()
+    Ok(conn_comp_tuples.len())
 }
 
 fn bron_kerbosch<F>(graph: &UnGraph<String, ()>, mut callback: F)
@@ -172,8 +174,8 @@ fn bron_kerbosch_recursive<F>(
     }
 }
 
-pub fn get_password(input_file: &str) -> String {
-    let input = parse_input(input_file);
+pub fn get_password(puzzle_input: impl PuzzleInput) -> AocResult<String> {
+    let input = parse_input(puzzle_input)?;
 
     let (graph, _node_indices) = build_conn_graph(&input.conn_pairs);
 
@@ -192,7 +194,24 @@ pub fn get_password(input_file: &str) -> String {
 
     password_vec.sort();
 
-    password_vec.join(",")
+    Ok(password_vec.join(","))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_num_conn_start_t_brute_force(Contents(input))?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_password(Contents(input))?.into())
+    }
 }
 
 #[cfg(test)]
@@ -203,7 +222,7 @@ mod tests {
     fn test_get_num_conn_start_t_test01() {
         assert_eq!(
             7,
-            get_num_conn_start_t_brute_force("input/2024/day23_test01.txt")
+            get_num_conn_start_t_brute_force("input/2024/day23_test01.txt").unwrap()
         );
     }
 
@@ -212,20 +231,23 @@ mod tests {
     fn test_get_num_conn_start_t() {
         assert_eq!(
             1327,
-            get_num_conn_start_t_brute_force("input/2024/day23.txt")
+            get_num_conn_start_t_brute_force("input/2024/day23.txt").unwrap()
         );
     }
 
     #[test]
     fn test_get_password_test01() {
-        assert_eq!("co,de,ka,ta", get_password("input/2024/day23_test01.txt"));
+        assert_eq!(
+            "co,de,ka,ta",
+            get_password("input/2024/day23_test01.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_password() {
         assert_eq!(
             "df,kg,la,mp,pb,qh,sk,th,vn,ww,xp,yp,zk",
-            get_password("input/2024/day23.txt")
+            get_password("input/2024/day23.txt").unwrap()
         );
     }
 }
@@ -1,13 +1,16 @@
 // https://adventofcode.com/2024/day/24
 
 use itertools::Itertools;
+use log::debug;
 use std::{
     collections::{HashMap, HashSet},
     iter::zip,
     vec,
 };
 
-use crate::utils::get_lines;
+use crate::error::{AocError, AocResult, parse_token};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput, get_lines};
 
 struct Input {
     init_wires: HashMap<String, bool>,
@@ -49,14 +52,14 @@ pub struct GateCalc {
     output_wire_val: Option<bool>,
 }
 
-fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
+fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let lines = get_lines(puzzle_input)?;
 
     let mut init_wires = HashMap::new();
     let mut gates = Vec::new();
     let mut parsing_gates = false;
 
-    for line in lines {
+    for (line_idx, line) in lines.iter().enumerate() {
         if line.trim().is_empty() {
             parsing_gates = true;
             continue;
@@ -69,7 +72,14 @@ fn parse_input(input_file: &str) -> Input {
                     "AND" => Operation::And,
                     "OR" => Operation::Or,
                     "XOR" => Operation::Xor,
-                    _ => panic!("Unknown operation"),
+                    _ => {
+                        return Err(AocError::parse_token(
+                            line_idx,
+                            line,
+                            op,
+                            format!("unknown operation {op:?}"),
+                        ));
+                    }
                 };
                 let gate = Gate {
                     input_wire1: input1.to_string(),
@@ -80,21 +90,19 @@ fn parse_input(input_file: &str) -> Input {
                 if !gates.contains(&gate) {
                     gates.push(gate.clone());
                 } else {
-                    panic!("Duplicate gate found: {gate:?}");
+                    return Err(AocError::parse(line_idx, 0, "duplicate gate"));
                 }
             }
         } else {
             let parts: Vec<&str> = line.split(':').collect();
             if let [wire, value] = &parts[..] {
-                init_wires.insert(
-                    wire.trim().to_string(),
-                    value.trim().parse::<usize>().unwrap() != 0,
-                );
+                let value: usize = parse_token(line_idx, line, value)?;
+                init_wires.insert(wire.trim().to_string(), value != 0);
             }
         }
     }
 
-    Input { init_wires, gates }
+    Ok(Input { init_wires, gates })
 }
 
 fn get_gate_result(gate: &Gate, wire1: bool, wire2: bool) -> bool {
@@ -137,9 +145,9 @@ fn get_gate_calcs(input: &Input) -> HashMap<String, GateCalc> {
         })
         .collect();
 
-    println!("POST INIT GATE CALCS");
+    debug!("POST INIT GATE CALCS");
     for (_output_wire, gate_calc) in gate_calcs.iter_mut() {
-        println!(
+        debug!(
             "Gate: {:?}, Input1: {:?}, Input2: {:?}, Output: {:?}",
             gate_calc.gate,
             gate_calc.input_wire1_val,
@@ -193,14 +201,14 @@ fn get_z_output_vals(gate_calcs: &HashMap<String, GateCalc>) -> Vec<bool> {
         .collect::<Vec<_>>()
 }
 
-pub fn get_z_decimal_num(input_file: &str) -> usize {
-    let input = parse_input(input_file);
+pub fn get_z_decimal_num(puzzle_input: impl PuzzleInput) -> AocResult<usize> {
+    let input = parse_input(puzzle_input)?;
 
     let gate_calcs = get_gate_calcs(&input);
 
-    println!("POST UPDATE GATE CALCS");
+    debug!("POST UPDATE GATE CALCS");
     for gate_calc in gate_calcs.values() {
-        println!(
+        debug!(
             "Gate: {:?}, Input1: {:?}, Input2: {:?}, Output: {:?}",
             gate_calc.gate,
             gate_calc.input_wire1_val,
@@ -211,9 +219,9 @@ pub fn get_z_decimal_num(input_file: &str) -> usize {
 
     let z_output_vals = get_z_output_vals(&gate_calcs);
 
-    println!("Z output vals: {z_output_vals:?}");
+    debug!("Z output vals: {z_output_vals:?}");
 
-    bin_to_dec(&z_output_vals)
+    Ok(bin_to_dec(&z_output_vals))
 }
 
 pub fn full_adder(a: bool, b: bool, c_in: bool) -> (bool, bool) {
@@ -247,11 +255,11 @@ pub fn ripple_adder(a_bits: &[bool], b_bits: &[bool]) -> (Vec<bool>, Vec<bool>)
     (sums, c_outs)
 }
 
-pub fn get_swapped_wires(input_file: &str) -> String {
-    let input = parse_input(input_file);
+pub fn get_swapped_wires(puzzle_input: impl PuzzleInput) -> AocResult<String> {
+    let input = parse_input(puzzle_input)?;
 
     let num_bits = input.init_wires.len() / 2;
-    println!("num_bits: {num_bits:?}");
+    debug!("num_bits: {num_bits:?}");
 
     let mut a_bits = vec![];
     let mut b_bits = vec![];
@@ -268,15 +276,15 @@ pub fn get_swapped_wires(input_file: &str) -> String {
         }
     }
 
-    println!("a_bits: {a_bits:?}");
-    println!("b_bits: {b_bits:?}");
+    debug!("a_bits: {a_bits:?}");
+    debug!("b_bits: {b_bits:?}");
 
     let (sums, c_outs) = ripple_adder(&a_bits, &b_bits);
 
-    println!("sums: {sums:?}");
-    println!("c_outs: {c_outs:?}");
+    debug!("sums: {sums:?}");
+    debug!("c_outs: {c_outs:?}");
 
-    swap_wires(&input.gates.clone()).to_string()
+    Ok(swap_wires(&input.gates.clone()).to_string())
 }
 
 fn swap_wires(gates: &[Gate]) -> String {
@@ -290,7 +298,7 @@ fn swap_wires(gates: &[Gate]) -> String {
     }
 
     for initial_invalid_gate in initial_invalid_gates.clone() {
-        println!("Initial invalid gate: {initial_invalid_gate:?}");
+        debug!("Initial invalid gate: {initial_invalid_gate:?}");
     }
 
     let output_wires = initial_invalid_gates
@@ -300,7 +308,7 @@ fn swap_wires(gates: &[Gate]) -> String {
         .collect::<Vec<_>>()
         .join(","); // Join the sorted output wires with commas
 
-    println!("Output wires: {output_wires:?}");
+    debug!("Output wires: {output_wires:?}");
 
     output_wires // Return the comma-separated string
 }
@@ -388,23 +396,46 @@ fn has_swapped_wires(gates: &[Gate]) -> Result<Vec<Gate>, Vec<Gate>> {
     }
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_z_decimal_num(Contents(input))?.into())
+    }
+
+    fn part_two(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_swapped_wires(Contents(input))?.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_get_z_decimal_num_test01() {
-        assert_eq!(4, get_z_decimal_num("input/2024/day24_test01.txt"));
+        assert_eq!(4, get_z_decimal_num("input/2024/day24_test01.txt").unwrap());
     }
 
     #[test]
     fn test_get_z_decimal_num_test02() {
-        assert_eq!(2024, get_z_decimal_num("input/2024/day24_test02.txt"));
+        assert_eq!(
+            2024,
+            get_z_decimal_num("input/2024/day24_test02.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_z_decimal_num() {
-        assert_eq!(65635066541798, get_z_decimal_num("input/2024/day24.txt"));
+        assert_eq!(
+            65635066541798,
+            get_z_decimal_num("input/2024/day24.txt").unwrap()
+        );
     }
 
     #[test]
@@ -432,7 +463,14 @@ mod tests {
     fn test_get_swapped_wires() {
         assert_eq!(
             "dgr,dtv,fgc,mtj,vvm,z12,z29,z37",
-            get_swapped_wires("input/2024/day24.txt")
+            get_swapped_wires("input/2024/day24.txt").unwrap()
         );
     }
+
+    #[test]
+    fn test_get_z_decimal_num_unknown_operation() {
+        let err =
+            get_z_decimal_num(Contents("x00: 1\ny00: 0\n\nx00 NAND y00 -> z00\n")).unwrap_err();
+        assert_eq!("<input>:4:5: unknown operation \"NAND\"", err.to_string());
+    }
 }
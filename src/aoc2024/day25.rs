@@ -2,7 +2,9 @@
 
 use std::vec;
 
-use crate::utils::get_lines;
+use crate::error::{AocError, AocResult};
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, Grid, PuzzleInput, get_lines};
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 enum LockPin {
@@ -11,101 +13,73 @@ enum LockPin {
 }
 
 struct Input {
-    schematics: Vec<Vec<Vec<LockPin>>>,
+    schematics: Vec<Grid<LockPin>>,
 }
 
-fn parse_input(input_file: &str) -> Input {
-    let lines = get_lines(input_file);
+fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let lines = get_lines(puzzle_input)?;
 
     let mut schematics = vec![]; // Declare schematics as mutable
+    let mut schematic = vec![];
 
-    lines
-        .split(|line| line.trim().is_empty())
-        .for_each(|schematic_str| {
-            let schematic: Vec<Vec<LockPin>> = schematic_str
-                .iter()
-                .map(|line| {
-                    line.chars()
-                        .map(|c| match c {
-                            '.' => LockPin::Empty,
-                            '#' => LockPin::Filled,
-                            _ => panic!("Unexpected character: {c}"),
-                        })
-                        .collect()
-                })
-                .collect();
-
-            schematics.push(schematic);
-        });
-
-    Input { schematics }
+    for (line_idx, line) in lines.iter().enumerate() {
+        if line.trim().is_empty() {
+            if !schematic.is_empty() {
+                schematics.push(Grid::from_rows(std::mem::take(&mut schematic))?);
+            }
+            continue;
+        }
+        if line.len() != 5 {
+            return Err(AocError::parse(line_idx, 0, "expected 5 pins per row"));
+        }
+        let row = line
+            .chars()
+            .enumerate()
+            .map(|(col_idx, c)| match c {
+                '.' => Ok(LockPin::Empty),
+                '#' => Ok(LockPin::Filled),
+                _ => Err(AocError::unexpected_char(line_idx, col_idx, c)),
+            })
+            .collect::<AocResult<Vec<LockPin>>>()?;
+        schematic.push(row);
+    }
+    if !schematic.is_empty() {
+        schematics.push(Grid::from_rows(schematic)?);
+    }
+
+    Ok(Input { schematics })
 }
 
-pub fn get_unique_lock_key_pairs(input_file: &str) -> u32 {
-    let input = parse_input(input_file);
+pub fn get_unique_lock_key_pairs(puzzle_input: impl PuzzleInput) -> AocResult<u32> {
+    let input = parse_input(puzzle_input)?;
 
-    let mut locks: Vec<Vec<[LockPin; 5]>> = vec![];
-    let mut keys: Vec<Vec<[LockPin; 5]>> = vec![];
+    let mut lock_pin_heights: Vec<Vec<usize>> = vec![];
+    let mut key_pin_heights: Vec<Vec<usize>> = vec![];
 
     for schematic in input.schematics {
-        if let Some(first_line) = schematic.first()
+        // Each column holds one pin, and its filled row at the base doesn't
+        // count towards the height
+        let pin_heights = schematic
+            .columns()
+            .map(|col| {
+                col.filter(|&&pin| pin == LockPin::Filled)
+                    .count()
+                    .saturating_sub(1)
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(first_line) = schematic.rows().next()
             && first_line.iter().all(|&pin| pin == LockPin::Filled)
         {
-            locks.push(
-                schematic
-                    .iter()
-                    .map(|line| {
-                        line.as_slice()
-                            .try_into()
-                            .expect("Each line must have exactly 5 elements")
-                    })
-                    .collect(),
-            );
+            lock_pin_heights.push(pin_heights.clone());
         }
-        if let Some(last_line) = schematic.last()
+        if let Some(last_line) = schematic.rows().last()
             && last_line.iter().all(|&pin| pin == LockPin::Filled)
         {
-            keys.push(
-                schematic
-                    .iter()
-                    .map(|line| {
-                        line.as_slice()
-                            .try_into()
-                            .expect("Each line must have exactly 5 elements")
-                    })
-                    .collect(),
-            );
+            key_pin_heights.push(pin_heights);
         }
     }
 
-    let lock_pin_heights = locks
-        .iter()
-        .map(|lock| {
-            (0..5) // Iterate over columns
-                .map(|col| {
-                    lock.iter()
-                        .filter(|line| line[col] == LockPin::Filled)
-                        .count()
-                        - 1
-                }) // Subtract 1 from each height
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>();
-
-    let key_pin_heights = keys
-        .iter()
-        .map(|key| {
-            (0..5) // Iterate over columns
-                .map(|col| {
-                    key.iter()
-                        .filter(|line| line[col] == LockPin::Filled)
-                        .count()
-                        - 1
-                }) // Subtract 1 from each height
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>();
-
     let mut unique_lock_key_pairs = 0;
 
     for lock_pin_height in lock_pin_heights {
@@ -122,7 +96,20 @@ pub fn get_unique_lock_key_pairs(input_file: &str) -> u32 {
         }
     }
 
-    unique_lock_key_pairs
+    Ok(unique_lock_key_pairs)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_unique_lock_key_pairs(Contents(input))?.into())
+    }
 }
 
 #[cfg(test)]
@@ -131,16 +118,25 @@ mod tests {
 
     #[test]
     fn test_get_unique_lock_key_pairs_test01() {
-        assert_eq!(3, get_unique_lock_key_pairs("input/2024/day25_test01.txt"));
+        assert_eq!(
+            3,
+            get_unique_lock_key_pairs("input/2024/day25_test01.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_unique_lock_key_pairs_test02() {
-        assert_eq!(0, get_unique_lock_key_pairs("input/2024/day25_test02.txt"));
+        assert_eq!(
+            0,
+            get_unique_lock_key_pairs("input/2024/day25_test02.txt").unwrap()
+        );
     }
 
     #[test]
     fn test_get_unique_lock_key_pairs() {
-        assert_eq!(3525, get_unique_lock_key_pairs("input/2024/day25.txt"));
+        assert_eq!(
+            3525,
+            get_unique_lock_key_pairs("input/2024/day25.txt").unwrap()
+        );
     }
 }
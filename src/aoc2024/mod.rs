@@ -23,3 +23,33 @@ pub mod day22;
 pub mod day23;
 pub mod day24;
 pub mod day25;
+
+use crate::solution::YearSolutions;
+
+pub const SOLUTIONS: YearSolutions = &[
+    (1, &day01::Solver),
+    (2, &day02::Solver),
+    (3, &day03::Solver),
+    (4, &day04::Solver),
+    (5, &day05::Solver),
+    (6, &day06::Solver),
+    (7, &day07::Solver),
+    (8, &day08::Solver),
+    (9, &day09::Solver),
+    (10, &day10::Solver),
+    (11, &day11::Solver),
+    (12, &day12::Solver),
+    (13, &day13::Solver),
+    (14, &day14::Solver),
+    (15, &day15::Solver),
+    (16, &day16::Solver),
+    (17, &day17::Solver),
+    (18, &day18::Solver),
+    (19, &day19::Solver),
+    (20, &day20::Solver),
+    (21, &day21::Solver),
+    (22, &day22::Solver),
+    (23, &day23::Solver),
+    (24, &day24::Solver),
+    (25, &day25::Solver),
+];
@@ -11,3 +11,52 @@ pub mod utils;
 
 #[path = "./utils/intcode.rs"]
 pub mod intcode;
+
+#[path = "./utils/solution.rs"]
+pub mod solution;
+
+#[path = "./utils/error.rs"]
+pub mod error;
+
+#[path = "./utils/answers.rs"]
+pub mod answers;
+
+#[path = "./utils/bench.rs"]
+pub mod bench;
+
+#[path = "./utils/provider.rs"]
+pub mod provider;
+
+#[path = "./utils/submit.rs"]
+pub mod submit;
+
+#[path = "./utils/scaffold.rs"]
+pub mod scaffold;
+
+#[path = "./utils/disassembler.rs"]
+pub mod disassembler;
+
+#[path = "./utils/assembler.rs"]
+pub mod assembler;
+
+#[path = "./utils/debugger.rs"]
+pub mod debugger;
+
+#[path = "./utils/cluster.rs"]
+pub mod cluster;
+
+#[path = "./utils/ascii.rs"]
+pub mod ascii;
+
+#[path = "./utils/robot.rs"]
+pub mod robot;
+
+#[path = "./utils/grid.rs"]
+pub mod grid;
+
+#[path = "./utils/point.rs"]
+pub mod point;
+
+#[cfg(test)]
+#[path = "./utils/differential.rs"]
+mod differential;
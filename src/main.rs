@@ -0,0 +1,535 @@
+use std::{
+    env, fs,
+    io::{self, Read},
+    path::Path,
+    process,
+    time::Duration,
+};
+
+use aoc::answers::{
+    CheckStatus, DEFAULT_MANIFEST, DEFAULT_SLOW_THRESHOLD, check_answer, parse_known_answers,
+};
+use aoc::ascii::{AsciiMachine, interact, script_lines};
+use aoc::bench::{
+    BenchResult, DEFAULT_RUNS, bench_part, change_from_baseline, parse_baseline, save_baseline,
+};
+use aoc::debugger::{Debugger, repl};
+use aoc::disassembler::disassemble;
+use aoc::error::AocError;
+use aoc::intcode::{IntcodeMachine, parse_intcode_input};
+use aoc::provider::{DEFAULT_INPUT_DIR, InputProvider};
+use aoc::scaffold::{DEFAULT_SRC_DIR, create_day};
+use aoc::solution::{Answer, all_solutions, get_solution};
+use aoc::submit::{Submission, Submitter, Verdict};
+use aoc::utils::Contents;
+
+const USAGE: &str =
+    "Usage: aoc --year <YEAR> --day <DAY> --part <PART> [--input <PATH|->] [--submit]
+       aoc --check-all [--manifest <PATH>] [--slow-ms <MS>]
+       aoc --bench [--year <YEAR>] [--day <DAY>] [--part <PART>] [--runs <N>]
+                   [--save <PATH>] [--baseline <PATH>]
+       aoc --new --year <YEAR> --day <DAY>
+       aoc --disassemble <PATH|->
+       aoc --debug <PATH>
+       aoc --ascii <PATH> [--script <PATH>]
+       aoc --list";
+
+#[derive(Debug)]
+struct Args {
+    year: u32,
+    day: u32,
+    part: u32,
+    input_file: Option<String>,
+    submit: bool,
+}
+
+fn parse_args(args: &[String]) -> Result<Args, String> {
+    let mut year = None;
+    let mut day = None;
+    let mut part = None;
+    let mut input_file = None;
+    let mut submit = false;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "--submit" {
+            submit = true;
+            continue;
+        }
+        let value = iter
+            .next()
+            .ok_or_else(|| format!("Missing value for {arg}"))?;
+        match arg.as_str() {
+            "--year" | "-y" => year = Some(parse_num(arg, value)?),
+            "--day" | "-d" => day = Some(parse_num(arg, value)?),
+            "--part" | "-p" => part = Some(parse_num(arg, value)?),
+            "--input" | "-i" => input_file = Some(value.clone()),
+            _ => return Err(format!("Unknown argument {arg}")),
+        }
+    }
+
+    Ok(Args {
+        year: year.ok_or("Missing --year")?,
+        day: day.ok_or("Missing --day")?,
+        part: part.ok_or("Missing --part")?,
+        input_file,
+        submit,
+    })
+}
+
+#[derive(Debug)]
+struct CheckArgs {
+    manifest: String,
+    slow_threshold: Duration,
+}
+
+fn parse_check_args(args: &[String]) -> Result<CheckArgs, String> {
+    let mut manifest = DEFAULT_MANIFEST.to_string();
+    let mut slow_threshold = DEFAULT_SLOW_THRESHOLD;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let value = iter
+            .next()
+            .ok_or_else(|| format!("Missing value for {arg}"))?;
+        match arg.as_str() {
+            "--manifest" | "-m" => manifest = value.clone(),
+            "--slow-ms" => slow_threshold = Duration::from_millis(parse_num(arg, value)?.into()),
+            _ => return Err(format!("Unknown argument {arg}")),
+        }
+    }
+
+    Ok(CheckArgs {
+        manifest,
+        slow_threshold,
+    })
+}
+
+#[derive(Debug, Default)]
+struct BenchArgs {
+    year: Option<u32>,
+    day: Option<u32>,
+    part: Option<u32>,
+    runs: Option<usize>,
+    save: Option<String>,
+    baseline: Option<String>,
+}
+
+fn parse_bench_args(args: &[String]) -> Result<BenchArgs, String> {
+    let mut bench_args = BenchArgs::default();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let value = iter
+            .next()
+            .ok_or_else(|| format!("Missing value for {arg}"))?;
+        match arg.as_str() {
+            "--year" | "-y" => bench_args.year = Some(parse_num(arg, value)?),
+            "--day" | "-d" => bench_args.day = Some(parse_num(arg, value)?),
+            "--part" | "-p" => bench_args.part = Some(parse_num(arg, value)?),
+            "--runs" | "-n" => bench_args.runs = Some(parse_num(arg, value)? as usize),
+            "--save" => bench_args.save = Some(value.clone()),
+            "--baseline" => bench_args.baseline = Some(value.clone()),
+            _ => return Err(format!("Unknown argument {arg}")),
+        }
+    }
+
+    Ok(bench_args)
+}
+
+#[derive(Debug)]
+struct NewArgs {
+    year: u32,
+    day: u32,
+}
+
+fn parse_new_args(args: &[String]) -> Result<NewArgs, String> {
+    let mut year = None;
+    let mut day = None;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let value = iter
+            .next()
+            .ok_or_else(|| format!("Missing value for {arg}"))?;
+        match arg.as_str() {
+            "--year" | "-y" => year = Some(parse_num(arg, value)?),
+            "--day" | "-d" => day = Some(parse_num(arg, value)?),
+            _ => return Err(format!("Unknown argument {arg}")),
+        }
+    }
+
+    Ok(NewArgs {
+        year: year.ok_or("Missing --year")?,
+        day: day.ok_or("Missing --day")?,
+    })
+}
+
+fn parse_num(arg: &str, value: &str) -> Result<u32, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Invalid value for {arg}: {value}"))
+}
+
+/// Reads the puzzle input from `input_file`, or from stdin when it is `-`.
+fn read_input(input_file: &str) -> io::Result<String> {
+    if input_file == "-" {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input)?;
+        Ok(input)
+    } else {
+        fs::read_to_string(input_file)
+    }
+}
+
+/// Submits the answer to the puzzle server, returning whether it was correct.
+fn submit(args: &Args, answer: &Answer) -> bool {
+    let submission =
+        Submitter::from_env().submit(args.year, args.day, args.part, &answer.to_string());
+    match submission {
+        Ok(Submission::Sent(verdict)) => {
+            eprintln!("Submitted: {verdict}");
+            verdict == Verdict::Correct
+        }
+        Ok(Submission::Known(verdict @ Verdict::Wait(_))) => {
+            eprintln!("Not submitted, the server asked to {verdict}");
+            false
+        }
+        Ok(Submission::Known(verdict)) => {
+            eprintln!("Not submitted, a previous answer shows this is {verdict}");
+            false
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            false
+        }
+    }
+}
+
+/// Reads an Intcode program, reporting why it couldn't be.
+fn read_intcode(input_file: &str) -> Option<Vec<isize>> {
+    let input = match read_input(input_file) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("couldn't read {input_file}: {err}");
+            return None;
+        }
+    };
+    parse_intcode_input(Contents(&input))
+        .map_err(|err| eprintln!("{}", err.in_file(input_file)))
+        .ok()
+}
+
+/// Prints the listing of an Intcode program, returning whether it could be read.
+fn print_disassembly(input_file: &str) -> bool {
+    let Some(intcode) = read_intcode(input_file) else {
+        return false;
+    };
+    print!("{}", disassemble(&intcode));
+    true
+}
+
+/// Steps through an Intcode program with commands read from stdin, returning
+/// whether it could be read.
+fn debug_program(input_file: &str) -> bool {
+    let Some(intcode) = read_intcode(input_file) else {
+        return false;
+    };
+    let mut debugger = Debugger::new(IntcodeMachine::new(intcode));
+    if let Err(err) = repl(&mut debugger, io::stdin().lock(), io::stdout()) {
+        eprintln!("{err}");
+        return false;
+    }
+    true
+}
+
+/// Runs an ASCII Intcode program from the terminal, first feeding it the
+/// lines of `script` if given, returning whether it ran without a fault.
+fn run_ascii(input_file: &str, script: Option<&str>) -> bool {
+    let Some(intcode) = read_intcode(input_file) else {
+        return false;
+    };
+    let mut machine = AsciiMachine::new(IntcodeMachine::new(intcode));
+    if let Some(script) = script {
+        let contents = match fs::read_to_string(script) {
+            Ok(contents) => contents,
+            Err(err) => {
+                eprintln!("couldn't read {script}: {err}");
+                return false;
+            }
+        };
+        for line in script_lines(&contents) {
+            if let Err(err) = machine.push_line(line) {
+                eprintln!("{script}: {err}");
+                return false;
+            }
+        }
+    }
+    if let Err(err) = interact(&mut machine, io::stdin().lock(), io::stdout()) {
+        eprintln!("{err}");
+        return false;
+    }
+    true
+}
+
+/// Checks every known answer in the manifest, returning whether they all passed.
+fn check_all(args: &CheckArgs) -> bool {
+    let known_answers = match parse_known_answers(args.manifest.as_str()) {
+        Ok(known_answers) => known_answers,
+        Err(err) => {
+            eprintln!("{}", err.in_file(&args.manifest));
+            return false;
+        }
+    };
+
+    let (mut passed, mut slow, mut failed) = (0, 0, 0);
+    for known in &known_answers {
+        let result = check_answer(known, args.slow_threshold);
+        let name = format!("{} day {:02} part {}", known.year, known.day, known.part);
+        let elapsed = result.elapsed;
+        match result.status {
+            CheckStatus::Pass => {
+                passed += 1;
+                println!("pass {name} ({elapsed:.2?})");
+            }
+            CheckStatus::Slow => {
+                slow += 1;
+                println!("slow {name} ({elapsed:.2?})");
+            }
+            CheckStatus::Fail(answer) => {
+                failed += 1;
+                println!(
+                    "FAIL {name} ({elapsed:.2?}): expected {}, got {answer}",
+                    known.answer
+                );
+            }
+            CheckStatus::Error(err) => {
+                failed += 1;
+                println!("FAIL {name} ({elapsed:.2?}): {err}");
+            }
+        }
+    }
+
+    println!("{passed} passed, {slow} slow, {failed} failed");
+    failed == 0
+}
+
+/// Benchmarks every matching part, returning whether they all ran successfully.
+fn bench_all(args: &BenchArgs) -> bool {
+    let baseline = match args.baseline.as_deref().map(parse_baseline).transpose() {
+        Ok(baseline) => baseline,
+        Err(err) => {
+            eprintln!("{err}");
+            return false;
+        }
+    };
+    let runs = args.runs.unwrap_or(DEFAULT_RUNS);
+
+    // Only inputs already on disk are benchmarked, so that a plain --bench
+    // never goes to the puzzle server
+    let provider = InputProvider::from_env();
+
+    let mut ok = true;
+    let mut skipped = 0;
+    let mut results: Vec<BenchResult> = vec![];
+    for (year, day, _) in all_solutions() {
+        if args.year.is_some_and(|y| y != year) || args.day.is_some_and(|d| d != day) {
+            continue;
+        }
+        for part in [1, 2] {
+            if args.part.is_some_and(|p| p != part) {
+                continue;
+            }
+            let name = format!("{year} day {day:02} part {part}");
+            let input_path = provider.input_path(year, day);
+            if !input_path.exists() {
+                skipped += 1;
+                continue;
+            }
+            let input_file = input_path.display().to_string();
+            let result = fs::read_to_string(&input_path)
+                .map_err(|source| AocError::Io {
+                    file: input_file.clone(),
+                    source,
+                })
+                .and_then(|input| {
+                    bench_part(year, day, part, &input, runs)
+                        .map_err(|err| err.in_file(&input_file))
+                });
+            let result = match result {
+                Ok(Some(result)) => result,
+                Ok(None) => continue,
+                Err(err) => {
+                    ok = false;
+                    println!("{name}  {err}");
+                    continue;
+                }
+            };
+
+            let change = baseline
+                .as_ref()
+                .and_then(|baseline| change_from_baseline(&result, baseline))
+                .map(|change| format!("  {:+.1}%", change * 100.0))
+                .unwrap_or_default();
+            println!(
+                "{name}  parse ~{:.2?}  solve ~{:.2?}  total {:.2?} (min {:.2?}, max {:.2?}){change}",
+                result.parse.median,
+                result.solve.median,
+                result.total.median,
+                result.total.min,
+                result.total.max,
+            );
+            results.push(result);
+        }
+    }
+
+    if skipped > 0 {
+        println!("Skipped {skipped} parts without a cached input");
+    }
+
+    if let Some(path) = &args.save
+        && let Err(err) = save_baseline(path, &results)
+    {
+        eprintln!("{err}");
+        return false;
+    }
+    ok
+}
+
+/// Reports a command line error along with the usage and exits.
+fn usage_error(err: &str) -> ! {
+    eprintln!("{err}");
+    eprintln!("{USAGE}");
+    process::exit(2);
+}
+
+fn main() {
+    // Debug views are silent unless switched on per module, e.g.
+    // RUST_LOG=aoc::aoc2019::day07=debug or RUST_LOG=aoc::intcode=trace
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("off")).init();
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "--list" || arg == "-l") {
+        for (year, day, _) in all_solutions() {
+            println!("{year} day {day:02}");
+        }
+        return;
+    }
+
+    if args.first().is_some_and(|arg| arg == "--check-all") {
+        let args = parse_check_args(&args[1..]).unwrap_or_else(|err| usage_error(&err));
+        if !check_all(&args) {
+            process::exit(1);
+        }
+        return;
+    }
+
+    if args.first().is_some_and(|arg| arg == "--bench") {
+        let args = parse_bench_args(&args[1..]).unwrap_or_else(|err| usage_error(&err));
+        if !bench_all(&args) {
+            process::exit(1);
+        }
+        return;
+    }
+
+    if args.first().is_some_and(|arg| arg == "--disassemble") {
+        let [_, input_file] = &args[..] else {
+            usage_error("Expected --disassemble <PATH|->");
+        };
+        if !print_disassembly(input_file) {
+            process::exit(1);
+        }
+        return;
+    }
+
+    if args.first().is_some_and(|arg| arg == "--ascii") {
+        let (input_file, script) = match &args[..] {
+            [_, input_file] => (input_file, None),
+            [_, input_file, flag, script] if flag == "--script" => (input_file, Some(script)),
+            _ => usage_error("Expected --ascii <PATH> [--script <PATH>]"),
+        };
+        if !run_ascii(input_file, script.map(String::as_str)) {
+            process::exit(1);
+        }
+        return;
+    }
+
+    if args.first().is_some_and(|arg| arg == "--debug") {
+        let [_, input_file] = &args[..] else {
+            usage_error("Expected --debug <PATH>");
+        };
+        if !debug_program(input_file) {
+            process::exit(1);
+        }
+        return;
+    }
+
+    if args.first().is_some_and(|arg| arg == "--new") {
+        let args = parse_new_args(&args[1..]).unwrap_or_else(|err| usage_error(&err));
+        let src_dir = Path::new(DEFAULT_SRC_DIR);
+        match create_day(src_dir, Path::new(DEFAULT_INPUT_DIR), args.year, args.day) {
+            Ok(changed) => {
+                for path in changed {
+                    println!("{}", path.display());
+                }
+            }
+            Err(err) => {
+                eprintln!("{err}");
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    let args = parse_args(&args).unwrap_or_else(|err| usage_error(&err));
+
+    // Without an explicit input, use the cached one, fetching it if needed
+    let (input_file, input) = match args.input_file.clone() {
+        Some(input_file) => {
+            let input =
+                read_input(&input_file).map_err(|err| format!("couldn't read {input_file}: {err}"));
+            (input_file, input)
+        }
+        None => {
+            let provider = InputProvider::from_env();
+            let input_file = provider.input_path(args.year, args.day);
+            let input = provider
+                .get_input(args.year, args.day)
+                .map_err(|err| err.to_string());
+            (input_file.display().to_string(), input)
+        }
+    };
+    let input = input.unwrap_or_else(|err| {
+        eprintln!("{err}");
+        process::exit(1);
+    });
+
+    let answer = get_solution(args.year, args.day).map_or(Ok(Answer::Unsolved), |solution| {
+        solution.solve(args.part, &input)
+    });
+
+    match answer {
+        Err(err) => {
+            let name = if input_file == "-" {
+                "<stdin>"
+            } else {
+                &input_file
+            };
+            eprintln!("{}", err.in_file(name));
+            process::exit(1);
+        }
+        Ok(Answer::Unsolved) => {
+            eprintln!(
+                "No solution for year {} day {} part {}",
+                args.year, args.day, args.part
+            );
+            process::exit(1);
+        }
+        Ok(answer) => {
+            println!("{answer}");
+            if args.submit && !submit(&args, &answer) {
+                process::exit(1);
+            }
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use std::{
+    panic::{self, AssertUnwindSafe},
+    time::{Duration, Instant},
+};
+
+use csv::ReaderBuilder;
+
+use crate::error::{AocError, AocResult, parse_token};
+use crate::solution::{Answer, get_solution};
+use crate::utils::PuzzleInput;
+
+/// The manifest of known answers, relative to the repository root.
+pub const DEFAULT_MANIFEST: &str = "input/answers.csv";
+
+/// Parts that take longer than this to solve are reported as slow.
+pub const DEFAULT_SLOW_THRESHOLD: Duration = Duration::from_secs(1);
+
+/// The expected answer for one part of a day, solved against `input`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KnownAnswer {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub input: String,
+    pub answer: String,
+}
+
+#[derive(Debug)]
+pub enum CheckStatus {
+    Pass,
+    /// The answer was right but took longer than the slow threshold.
+    Slow,
+    Fail(Answer),
+    Error(AocError),
+}
+
+#[derive(Debug)]
+pub struct CheckResult {
+    pub status: CheckStatus,
+    pub elapsed: Duration,
+}
+
+/// Reads a manifest with a `year,day,part,input,answer` header. Answers
+/// containing commas or newlines are quoted, and lines starting with `#` are
+/// skipped.
+pub fn parse_known_answers(puzzle_input: impl PuzzleInput) -> AocResult<Vec<KnownAnswer>> {
+    let contents = puzzle_input.read_to_string()?;
+
+    let mut reader = ReaderBuilder::new()
+        .comment(Some(b'#'))
+        .from_reader(contents.as_bytes());
+
+    let mut known_answers = vec![];
+    for record in reader.records() {
+        let record = record.map_err(|err| {
+            let line_idx = err.position().map_or(0, |pos| pos.line() as usize - 1);
+            AocError::parse(line_idx, 0, err.to_string())
+        })?;
+        let line_idx = record.position().map_or(0, |pos| pos.line() as usize - 1);
+        let fields: Vec<&str> = record.iter().collect();
+        let [year, day, part, input, answer] = fields[..] else {
+            return Err(AocError::parse(
+                line_idx,
+                0,
+                "expected year,day,part,input,answer",
+            ));
+        };
+        known_answers.push(KnownAnswer {
+            year: parse_token(line_idx, year, year)?,
+            day: parse_token(line_idx, day, day)?,
+            part: parse_token(line_idx, part, part)?,
+            input: input.to_string(),
+            answer: answer.to_string(),
+        });
+    }
+
+    Ok(known_answers)
+}
+
+/// Solves `known` against its input and compares the result with the expected answer.
+pub fn check_answer(known: &KnownAnswer, slow_threshold: Duration) -> CheckResult {
+    let start = Instant::now();
+    let answer = known.input.as_str().read_to_string().and_then(|input| {
+        let solution = get_solution(known.year, known.day)
+            .ok_or_else(|| AocError::invalid("no solution registered"))?;
+        // A panicking day shouldn't stop the remaining days from being checked
+        panic::catch_unwind(AssertUnwindSafe(|| solution.solve(known.part, &input)))
+            .unwrap_or_else(|_| Err(AocError::invalid("panicked")))
+            .map_err(|err| err.in_file(&known.input))
+    });
+    let elapsed = start.elapsed();
+
+    let status = match answer {
+        Err(err) => CheckStatus::Error(err),
+        Ok(answer) if answer.to_string() != known.answer => CheckStatus::Fail(answer),
+        Ok(_) if elapsed > slow_threshold => CheckStatus::Slow,
+        Ok(_) => CheckStatus::Pass,
+    };
+
+    CheckResult { status, elapsed }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::utils::Contents;
+
+    #[test]
+    fn test_parse_known_answers() {
+        let manifest = "year,day,part,input,answer\n\
+                        2024,1,1,input/2024/day01.txt,1151792\n\
+                        # 2024,6,2,input/2024/day06.txt,1976\n\
+                        2024,17,1,input/2024/day17.txt,\"2,3,4,7,5,7,3,0,7\"\n";
+        let known_answers = parse_known_answers(Contents(manifest)).unwrap();
+        assert_eq!(2, known_answers.len());
+        assert_eq!(
+            KnownAnswer {
+                year: 2024,
+                day: 17,
+                part: 1,
+                input: "input/2024/day17.txt".to_string(),
+                answer: "2,3,4,7,5,7,3,0,7".to_string(),
+            },
+            known_answers[1]
+        );
+    }
+
+    #[test]
+    fn test_parse_known_answers_bad_day() {
+        let manifest = "year,day,part,input,answer\n2024,x,1,input/2024/day01.txt,1\n";
+        let err = parse_known_answers(Contents(manifest)).unwrap_err();
+        assert_eq!("<input>:2:1: couldn't parse \"x\" as u32", err.to_string());
+    }
+
+    #[test]
+    fn test_manifest_refers_to_solutions_and_inputs() {
+        for known in parse_known_answers(DEFAULT_MANIFEST).unwrap() {
+            assert!(get_solution(known.year, known.day).is_some(), "{known:?}");
+            assert!(Path::new(&known.input).exists(), "{known:?}");
+        }
+    }
+
+    #[test]
+    fn test_check_answer() {
+        let mut known = KnownAnswer {
+            year: 2024,
+            day: 1,
+            part: 1,
+            input: "input/2024/day01.txt".to_string(),
+            answer: "1151792".to_string(),
+        };
+        let result = check_answer(&known, Duration::MAX);
+        assert!(matches!(result.status, CheckStatus::Pass));
+
+        known.answer = "42".to_string();
+        let result = check_answer(&known, Duration::MAX);
+        assert!(matches!(
+            result.status,
+            CheckStatus::Fail(Answer::Num(1151792))
+        ));
+
+        known.input = "input/2024/day99.txt".to_string();
+        let result = check_answer(&known, Duration::MAX);
+        assert!(matches!(
+            result.status,
+            CheckStatus::Error(AocError::Io { .. })
+        ));
+    }
+}
@@ -0,0 +1,189 @@
+use std::io::{BufRead, Write};
+
+use crate::error::{AocError, AocResult};
+use crate::intcode::IntcodeMachine;
+
+/// What an ASCII program printed, with any values too large to be characters,
+/// such as a final answer, set apart.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AsciiOutput {
+    pub text: String,
+    pub values: Vec<isize>,
+}
+
+impl AsciiOutput {
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.text.lines()
+    }
+}
+
+/// Encodes `text` as one input value per character.
+pub fn encode(text: &str) -> AocResult<Vec<isize>> {
+    text.chars()
+        .map(|c| {
+            if c.is_ascii() {
+                Ok(c as isize)
+            } else {
+                Err(AocError::invalid(format!(
+                    "{c:?} is not an ASCII character"
+                )))
+            }
+        })
+        .collect()
+}
+
+/// Decodes outputs into text, keeping values outside the ASCII range apart.
+pub fn decode(outputs: &[isize]) -> AsciiOutput {
+    let mut decoded = AsciiOutput::default();
+    for &output in outputs {
+        match u8::try_from(output) {
+            Ok(byte) if byte.is_ascii() => decoded.text.push(byte as char),
+            _ => decoded.values.push(output),
+        }
+    }
+    decoded
+}
+
+/// An Intcode machine driven by lines of text rather than numbers.
+#[derive(Clone, Debug)]
+pub struct AsciiMachine {
+    machine: IntcodeMachine,
+}
+
+impl AsciiMachine {
+    pub fn new(machine: IntcodeMachine) -> Self {
+        AsciiMachine { machine }
+    }
+
+    pub fn machine(&self) -> &IntcodeMachine {
+        &self.machine
+    }
+
+    pub fn into_machine(self) -> IntcodeMachine {
+        self.machine
+    }
+
+    /// Queues `line` followed by a newline.
+    pub fn push_line(&mut self, line: &str) -> AocResult<()> {
+        self.push_text(line)?;
+        self.machine.push_input('\n' as isize);
+        Ok(())
+    }
+
+    /// Queues `text` as it is.
+    pub fn push_text(&mut self, text: &str) -> AocResult<()> {
+        self.machine.push_inputs(encode(text)?);
+        Ok(())
+    }
+
+    /// Runs until the program halts or waits for more input, decoding what
+    /// it printed.
+    pub fn run(&mut self) -> AocResult<AsciiOutput> {
+        Ok(decode(&self.machine.run_until_blocked()?))
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.machine.is_halted()
+    }
+}
+
+/// Runs the program, writing what it prints to `output` and feeding it a line
+/// from `input` whenever it waits for one, until it halts or `input` runs
+/// out. Returns the values that weren't characters, which are also written
+/// on lines of their own.
+pub fn interact(
+    machine: &mut AsciiMachine,
+    input: impl BufRead,
+    mut output: impl Write,
+) -> AocResult<Vec<isize>> {
+    let terminal_error = |source| AocError::Io {
+        file: "<terminal>".to_string(),
+        source,
+    };
+    let mut values = vec![];
+    let mut lines = input.lines();
+    loop {
+        let printed = machine.run()?;
+        write!(output, "{}", printed.text).map_err(terminal_error)?;
+        for value in &printed.values {
+            writeln!(output, "{value}").map_err(terminal_error)?;
+        }
+        output.flush().map_err(terminal_error)?;
+        values.extend(printed.values);
+
+        if machine.is_halted() {
+            return Ok(values);
+        }
+        match lines.next() {
+            Some(line) => machine.push_line(&line.map_err(terminal_error)?)?,
+            None => return Ok(values),
+        }
+    }
+}
+
+/// Reads lines to feed a program from a script file, skipping blank lines and
+/// those starting with `#`.
+pub fn script_lines(script: &str) -> Vec<&str> {
+    script
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assembler::assemble;
+
+    /// Prompts with "?", then prints 1000 plus the length of the line it reads.
+    const COUNTER: &str = "\
+                OUT #63
+                OUT #10
+        loop:   IN [c]
+                EQ [c], #10, [t]
+                JNZ [t], #done
+                ADD [n], #1, [n]
+                JZ #0, #loop
+        done:   ADD [n], #1000, [n]
+                OUT [n]
+                HLT
+        c:      DATA 0
+        t:      DATA 0
+        n:      DATA 0";
+
+    fn counter() -> AsciiMachine {
+        AsciiMachine::new(IntcodeMachine::new(assemble(COUNTER).unwrap()))
+    }
+
+    #[test]
+    fn test_encode_and_decode() {
+        assert_eq!(vec![72, 105, 10], encode("Hi\n").unwrap());
+        assert!(encode("é").is_err());
+
+        let decoded = decode(&[72, 105, 10, 33, 10, 1234, -1]);
+        assert_eq!(vec!["Hi", "!"], decoded.lines().collect::<Vec<_>>());
+        assert_eq!(vec![1234, -1], decoded.values);
+    }
+
+    #[test]
+    fn test_run_and_push_line() {
+        let mut machine = counter();
+        let prompt = machine.run().unwrap();
+        assert_eq!("?\n", prompt.text);
+        assert!(!machine.is_halted());
+
+        machine.push_line("hello").unwrap();
+        assert_eq!(vec![1005], machine.run().unwrap().values);
+        assert!(machine.is_halted());
+    }
+
+    #[test]
+    fn test_interact() {
+        let mut machine = counter();
+        let mut output = vec![];
+        let values = interact(&mut machine, "hi there\nunused\n".as_bytes(), &mut output).unwrap();
+        assert_eq!(vec![1008], values);
+        assert_eq!("?\n1008\n", String::from_utf8(output).unwrap());
+    }
+}
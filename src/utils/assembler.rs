@@ -0,0 +1,267 @@
+use std::collections::HashMap;
+
+use crate::error::{AocError, AocResult, column_of};
+use crate::intcode::{Mode, Opcode};
+
+/// A word of the program, which may name a label that is only defined later.
+enum Word<'a> {
+    Value(isize),
+    Label {
+        name: &'a str,
+        line_idx: usize,
+        col_idx: usize,
+    },
+}
+
+/// Assembles a program written in the syntax of the disassembler's listings.
+///
+/// Each line holds an optional `label:`, an instruction such as
+/// `ADD [12], #5, rb[3]` or `DATA 1, 2, 3`, and an optional `; comment`.
+/// Parameters are `[a]` in position, `#v` in immediate and `rb[o]` in relative
+/// mode, and a label can stand in for any number. Mnemonics are those of
+/// [`Opcode::mnemonic`], in any case.
+pub fn assemble(source: &str) -> AocResult<Vec<isize>> {
+    let mut words = vec![];
+    let mut labels: HashMap<&str, usize> = HashMap::new();
+
+    for (line_idx, line) in source.lines().enumerate() {
+        let mut statement = line.split(';').next().unwrap_or_default().trim();
+        if let Some((name, rest)) = statement.split_once(':') {
+            let name = name.trim();
+            if !is_identifier(name) {
+                return Err(AocError::parse_token(
+                    line_idx,
+                    line,
+                    name,
+                    format!("invalid label {name:?}"),
+                ));
+            }
+            if labels.insert(name, words.len()).is_some() {
+                return Err(AocError::parse_token(
+                    line_idx,
+                    line,
+                    name,
+                    format!("label {name:?} is already defined"),
+                ));
+            }
+            statement = rest.trim();
+        }
+        if statement.is_empty() {
+            continue;
+        }
+
+        let (mnemonic, operands) = statement
+            .split_once(char::is_whitespace)
+            .unwrap_or((statement, ""));
+        let operands: Vec<&str> = if operands.trim().is_empty() {
+            vec![]
+        } else {
+            operands.split(',').map(str::trim).collect()
+        };
+
+        if mnemonic.eq_ignore_ascii_case("DATA") {
+            if operands.is_empty() {
+                return Err(AocError::parse_token(
+                    line_idx,
+                    line,
+                    mnemonic,
+                    "DATA needs at least one value",
+                ));
+            }
+            for operand in operands {
+                words.push(parse_value(line_idx, line, operand)?);
+            }
+            continue;
+        }
+
+        let opcode = Opcode::from_mnemonic(mnemonic).ok_or_else(|| {
+            AocError::parse_token(
+                line_idx,
+                line,
+                mnemonic,
+                format!("unknown mnemonic {mnemonic:?}"),
+            )
+        })?;
+        if operands.len() != opcode.param_count() {
+            return Err(AocError::parse_token(
+                line_idx,
+                line,
+                mnemonic,
+                format!(
+                    "{} takes {} parameters, found {}",
+                    opcode.mnemonic(),
+                    opcode.param_count(),
+                    operands.len()
+                ),
+            ));
+        }
+
+        let mut instruction = opcode as isize;
+        let mut params = Vec::with_capacity(operands.len());
+        for (idx, operand) in operands.into_iter().enumerate() {
+            let (mode, word) = parse_operand(line_idx, line, operand)?;
+            instruction += mode as isize * 10isize.pow(idx as u32 + 2);
+            params.push(word);
+        }
+        words.push(Word::Value(instruction));
+        words.extend(params);
+    }
+
+    words
+        .into_iter()
+        .map(|word| match word {
+            Word::Value(value) => Ok(value),
+            Word::Label {
+                name,
+                line_idx,
+                col_idx,
+            } => labels
+                .get(name)
+                .map(|&address| address as isize)
+                .ok_or_else(|| {
+                    AocError::parse(line_idx, col_idx, format!("undefined label {name:?}"))
+                }),
+        })
+        .collect()
+}
+
+fn is_identifier(token: &str) -> bool {
+    let mut chars = token.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn parse_value<'a>(line_idx: usize, line: &str, token: &'a str) -> AocResult<Word<'a>> {
+    if let Ok(value) = token.parse() {
+        Ok(Word::Value(value))
+    } else if is_identifier(token) {
+        Ok(Word::Label {
+            name: token,
+            line_idx,
+            col_idx: column_of(line, token),
+        })
+    } else {
+        Err(AocError::parse_token(
+            line_idx,
+            line,
+            token,
+            format!("expected a number or label, found {token:?}"),
+        ))
+    }
+}
+
+fn parse_operand<'a>(line_idx: usize, line: &str, operand: &'a str) -> AocResult<(Mode, Word<'a>)> {
+    let (mode, value) = if let Some(value) = operand.strip_prefix('#') {
+        (Mode::Immediate, value)
+    } else if let Some(value) = operand
+        .strip_prefix("rb[")
+        .and_then(|v| v.strip_suffix(']'))
+    {
+        (Mode::Relative, value)
+    } else if let Some(value) = operand.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+        (Mode::Position, value)
+    } else {
+        return Err(AocError::parse_token(
+            line_idx,
+            line,
+            operand,
+            format!("expected [address], #value or rb[offset], found {operand:?}"),
+        ));
+    };
+    Ok((mode, parse_value(line_idx, line, value.trim())?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::disassembler::disassemble;
+    use crate::intcode::parse_intcode_input;
+
+    #[test]
+    fn test_assemble() {
+        let source = "\
+            ; Outputs a countdown from 3 to 1
+            loop:   OUT [n]
+                    add [n], #-1, [n]
+                    JNZ [n], #loop
+                    HLT
+            n:      DATA 3";
+        assert_eq!(
+            vec![4, 10, 1001, 10, -1, 10, 1005, 10, 0, 99, 3],
+            assemble(source).unwrap()
+        );
+        assert_eq!(
+            vec![109, -1, 204, 1, 99],
+            assemble("ARB #-1\nOUT rb[1]\nHLT").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_assemble_relative_mode_programs() {
+        // The relative base programs pinned in the 2019 day 9 tests
+        for (source, intcode) in [
+            ("ARB #-1\nOUT [1]\nHLT", vec![109, -1, 4, 1, 99]),
+            ("ARB #-1\nOUT #1\nHLT", vec![109, -1, 104, 1, 99]),
+            (
+                "ARB #1\nARB [2]\nOUT rb[-6]\nHLT",
+                vec![109, 1, 9, 2, 204, -6, 99],
+            ),
+            (
+                "ARB #1\nARB #9\nOUT rb[-6]\nHLT",
+                vec![109, 1, 109, 9, 204, -6, 99],
+            ),
+            (
+                "ARB #1\nARB rb[-1]\nOUT rb[-106]\nHLT",
+                vec![109, 1, 209, -1, 204, -106, 99],
+            ),
+            (
+                "ARB #1\nIN [3]\nOUT rb[2]\nHLT",
+                vec![109, 1, 3, 3, 204, 2, 99],
+            ),
+            (
+                "ARB #1\nIN rb[2]\nOUT rb[2]\nHLT",
+                vec![109, 1, 203, 2, 204, 2, 99],
+            ),
+        ] {
+            assert_eq!(intcode, assemble(source).unwrap(), "{source:?}");
+        }
+    }
+
+    #[test]
+    fn test_assemble_round_trips_listings() {
+        for path in [
+            "input/2019/day05.txt",
+            "input/2019/day09.txt",
+            "input/2019/day09_test01.txt",
+            "input/2019/day11.txt",
+        ] {
+            let intcode = parse_intcode_input(path).unwrap();
+            let listing = disassemble(&intcode).to_string();
+            assert_eq!(intcode, assemble(&listing).unwrap(), "{path}");
+        }
+    }
+
+    #[test]
+    fn test_assemble_errors() {
+        let message = |source| assemble(source).unwrap_err().to_string();
+        assert_eq!("<input>:2:1: unknown mnemonic \"NOP\"", message("HLT\nNOP"));
+        assert_eq!(
+            "<input>:1:1: ADD takes 3 parameters, found 2",
+            message("ADD #1, #2")
+        );
+        assert_eq!(
+            "<input>:1:6: expected [address], #value or rb[offset], found \"5\"",
+            message("OUT  5")
+        );
+        assert_eq!(
+            "<input>:1:10: undefined label \"end\"",
+            message("JNZ #1, #end")
+        );
+        assert_eq!(
+            "<input>:2:1: label \"a\" is already defined",
+            message("a: HLT\na: HLT")
+        );
+    }
+}
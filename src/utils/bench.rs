@@ -0,0 +1,236 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    time::{Duration, Instant},
+};
+
+use csv::{ReaderBuilder, Writer};
+
+use crate::error::{AocError, AocResult, parse_token};
+use crate::solution::{Answer, get_solution};
+use crate::utils::PuzzleInput;
+
+pub const DEFAULT_RUNS: usize = 10;
+
+/// The spread of a set of timed runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timings {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Timings {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        Timings {
+            min: sorted.first().copied().unwrap_or_default(),
+            median: sorted.get(sorted.len() / 2).copied().unwrap_or_default(),
+            max: sorted.last().copied().unwrap_or_default(),
+        }
+    }
+}
+
+/// Timings for one part of a day. `total` is the measured time to get the
+/// answer. Solutions parse the input as part of solving, so `parse` and
+/// `solve` are only an estimate of how `total` splits between the two, taken
+/// from a separate parse of the same input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchResult {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub parse: Timings,
+    pub solve: Timings,
+    pub total: Timings,
+}
+
+/// Solves `part` of the given day against `input` `runs` times, after a
+/// warm-up run. Returns `None` when the day doesn't solve that part.
+pub fn bench_part(
+    year: u32,
+    day: u32,
+    part: u32,
+    input: &str,
+    runs: usize,
+) -> AocResult<Option<BenchResult>> {
+    let solution =
+        get_solution(year, day).ok_or_else(|| AocError::invalid("no solution registered"))?;
+
+    if solution.solve(part, input)? == Answer::Unsolved {
+        return Ok(None);
+    }
+
+    let mut parse_samples = Vec::with_capacity(runs);
+    let mut solve_samples = Vec::with_capacity(runs);
+    let mut total_samples = Vec::with_capacity(runs);
+
+    for _ in 0..runs.max(1) {
+        // Solving parses the input itself and can't be timed in parts, so a
+        // parse of the same warm input right after it stands in for that share
+        let start = Instant::now();
+        solution.solve(part, input)?;
+        let solved = Instant::now();
+        solution.parse(input)?;
+        let parsed = Instant::now();
+
+        let total_elapsed = solved - start;
+        let parse_elapsed = (parsed - solved).min(total_elapsed);
+        parse_samples.push(parse_elapsed);
+        solve_samples.push(total_elapsed - parse_elapsed);
+        total_samples.push(total_elapsed);
+    }
+
+    Ok(Some(BenchResult {
+        year,
+        day,
+        part,
+        parse: Timings::from_samples(&parse_samples),
+        solve: Timings::from_samples(&solve_samples),
+        total: Timings::from_samples(&total_samples),
+    }))
+}
+
+/// Median total time of each `(year, day, part)` from a saved benchmark run.
+pub type Baseline = HashMap<(u32, u32, u32), Duration>;
+
+const BASELINE_HEADER: [&str; 6] = ["year", "day", "part", "min_ns", "median_ns", "max_ns"];
+
+pub fn save_baseline(path: &str, results: &[BenchResult]) -> AocResult<()> {
+    let to_error = |message: String| AocError::invalid(message).in_file(path);
+
+    let file = File::create(path).map_err(|source| AocError::Io {
+        file: path.to_string(),
+        source,
+    })?;
+    let mut writer = Writer::from_writer(file);
+    writer
+        .write_record(BASELINE_HEADER)
+        .map_err(|err| to_error(err.to_string()))?;
+    for result in results {
+        let total = result.total;
+        writer
+            .write_record([
+                result.year.to_string(),
+                result.day.to_string(),
+                result.part.to_string(),
+                total.min.as_nanos().to_string(),
+                total.median.as_nanos().to_string(),
+                total.max.as_nanos().to_string(),
+            ])
+            .map_err(|err| to_error(err.to_string()))?;
+    }
+    writer.flush().map_err(|source| AocError::Io {
+        file: path.to_string(),
+        source,
+    })
+}
+
+pub fn parse_baseline(puzzle_input: impl PuzzleInput) -> AocResult<Baseline> {
+    let contents = puzzle_input.read_to_string()?;
+
+    let mut reader = ReaderBuilder::new().from_reader(contents.as_bytes());
+
+    let mut baseline = Baseline::new();
+    for record in reader.records() {
+        let record = record.map_err(|err| {
+            let line_idx = err.position().map_or(0, |pos| pos.line() as usize - 1);
+            AocError::parse(line_idx, 0, err.to_string())
+        })?;
+        let line_idx = record.position().map_or(0, |pos| pos.line() as usize - 1);
+        let fields: Vec<&str> = record.iter().collect();
+        let [year, day, part, _, median_ns, _] = fields[..] else {
+            return Err(AocError::parse(
+                line_idx,
+                0,
+                format!("expected {}", BASELINE_HEADER.join(",")),
+            ));
+        };
+        baseline.insert(
+            (
+                parse_token(line_idx, year, year)?,
+                parse_token(line_idx, day, day)?,
+                parse_token(line_idx, part, part)?,
+            ),
+            Duration::from_nanos(parse_token(line_idx, median_ns, median_ns)?),
+        );
+    }
+
+    Ok(baseline)
+}
+
+/// The relative change of `result`'s median total time against the baseline,
+/// e.g. `-0.25` when it got a quarter faster.
+pub fn change_from_baseline(result: &BenchResult, baseline: &Baseline) -> Option<f64> {
+    let before = baseline.get(&(result.year, result.day, result.part))?;
+    if before.is_zero() {
+        return None;
+    }
+    Some(result.total.median.as_secs_f64() / before.as_secs_f64() - 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::Contents;
+
+    #[test]
+    fn test_timings_from_samples() {
+        let samples = [3, 1, 4, 1, 5].map(Duration::from_millis);
+        assert_eq!(
+            Timings {
+                min: Duration::from_millis(1),
+                median: Duration::from_millis(3),
+                max: Duration::from_millis(5),
+            },
+            Timings::from_samples(&samples)
+        );
+    }
+
+    #[test]
+    fn test_bench_part() {
+        let result = bench_part(2024, 1, 1, "3   4\n4   3\n", 3)
+            .unwrap()
+            .unwrap();
+        assert_eq!((2024, 1, 1), (result.year, result.day, result.part));
+        assert!(result.total.min <= result.total.median);
+        assert!(result.total.median <= result.total.max);
+        assert!(result.parse.median <= result.total.median);
+
+        // The last day only has one part
+        assert_eq!(None, bench_part(2024, 25, 2, "", 3).unwrap());
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let millis = |ms| Timings::from_samples(&[Duration::from_millis(ms)]);
+        let result = BenchResult {
+            year: 2024,
+            day: 1,
+            part: 2,
+            parse: millis(1),
+            solve: millis(3),
+            total: millis(4),
+        };
+        let path = std::env::temp_dir().join("aoc_test_baseline_round_trip.csv");
+        let path = path.to_str().unwrap();
+        save_baseline(path, &[result]).unwrap();
+
+        let baseline = parse_baseline(path).unwrap();
+        assert_eq!(Some(&Duration::from_millis(4)), baseline.get(&(2024, 1, 2)));
+
+        let faster = BenchResult {
+            total: millis(3),
+            ..result
+        };
+        assert_eq!(Some(-0.25), change_from_baseline(&faster, &baseline));
+    }
+
+    #[test]
+    fn test_parse_baseline_bad_median() {
+        let baseline = "year,day,part,min_ns,median_ns,max_ns\n2024,1,1,10,x,30\n";
+        let err = parse_baseline(Contents(baseline)).unwrap_err();
+        assert_eq!("<input>:2:1: couldn't parse \"x\" as u64", err.to_string());
+    }
+}
@@ -0,0 +1,306 @@
+use std::collections::{HashMap, VecDeque};
+
+use log::{debug, trace};
+
+use crate::error::{AocError, AocResult};
+use crate::intcode::{IntcodeMachine, Status};
+
+/// Where a machine's outputs go.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Route {
+    /// Every output is sent to each of the named channels, which covers
+    /// chains, rings and broadcasts.
+    To(Vec<String>),
+    /// Outputs are grouped into packets of `size` words, the first naming the
+    /// address the rest are sent to. See [`Cluster::bind_address`].
+    Packets { size: usize },
+    /// Outputs are dropped.
+    Nowhere,
+}
+
+impl Route {
+    pub fn to(channel: &str) -> Self {
+        Route::To(vec![channel.to_string()])
+    }
+}
+
+/// Why [`Cluster::run`] stopped.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ClusterStatus {
+    /// Every machine has halted.
+    Halted,
+    /// A whole round passed with every inbox empty and no machine sending
+    /// anything, while machines were being fed the idle input.
+    Idle,
+    /// The listed machines are all waiting on empty inboxes, and the rest have
+    /// halted, so nothing can happen until something is sent to them.
+    Deadlocked { waiting: Vec<usize> },
+}
+
+#[derive(Clone, Debug)]
+struct Node {
+    machine: IntcodeMachine,
+    inbox: String,
+    route: Route,
+    /// The words of a packet that hasn't been fully output yet.
+    packet: Vec<isize>,
+}
+
+/// Intcode machines connected by named channels, run round-robin.
+///
+/// Each machine reads from one inbox channel and sends its outputs along its
+/// [`Route`]. Channels that no machine reads from collect outputs for the
+/// caller, and anything can be sent to a channel between runs.
+#[derive(Clone, Debug, Default)]
+pub struct Cluster {
+    nodes: Vec<Node>,
+    channels: HashMap<String, VecDeque<isize>>,
+    addresses: HashMap<isize, String>,
+    idle_input: Option<isize>,
+}
+
+impl Cluster {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds `value` to a machine that needs an input when its inbox is
+    /// empty, once per turn, instead of leaving it waiting.
+    pub fn with_idle_input(mut self, value: isize) -> Self {
+        self.idle_input = Some(value);
+        self
+    }
+
+    /// Adds a machine reading from `inbox`, returning its index.
+    pub fn add_machine(&mut self, machine: IntcodeMachine, inbox: &str, route: Route) -> usize {
+        self.channels.entry(inbox.to_string()).or_default();
+        self.nodes.push(Node {
+            machine,
+            inbox: inbox.to_string(),
+            route,
+            packet: vec![],
+        });
+        self.nodes.len() - 1
+    }
+
+    /// Delivers packets sent to `address` to `channel`.
+    pub fn bind_address(&mut self, address: isize, channel: &str) {
+        self.addresses.insert(address, channel.to_string());
+    }
+
+    pub fn machine(&self, idx: usize) -> &IntcodeMachine {
+        &self.nodes[idx].machine
+    }
+
+    pub fn send(&mut self, channel: &str, value: isize) {
+        self.channels
+            .entry(channel.to_string())
+            .or_default()
+            .push_back(value);
+    }
+
+    pub fn send_all(&mut self, channel: &str, values: impl IntoIterator<Item = isize>) {
+        self.channels
+            .entry(channel.to_string())
+            .or_default()
+            .extend(values);
+    }
+
+    /// Takes everything waiting in `channel`, oldest first.
+    pub fn drain(&mut self, channel: &str) -> Vec<isize> {
+        self.channels
+            .get_mut(channel)
+            .map(|queue| queue.drain(..).collect())
+            .unwrap_or_default()
+    }
+
+    /// Gives each machine a turn in order, running it until it halts or
+    /// waits on an empty inbox, until every machine halts or the cluster
+    /// stops making progress.
+    pub fn run(&mut self) -> AocResult<ClusterStatus> {
+        loop {
+            let mut progressed = false;
+            let mut waiting = vec![];
+            for idx in 0..self.nodes.len() {
+                if self.nodes[idx].machine.is_halted() {
+                    continue;
+                }
+                progressed |= self.take_turn(idx)?;
+                if !self.nodes[idx].machine.is_halted() {
+                    waiting.push(idx);
+                }
+            }
+
+            if waiting.is_empty() {
+                return Ok(ClusterStatus::Halted);
+            }
+            if !progressed {
+                debug!("Cluster stalled with machines {:?} waiting", waiting);
+                return Ok(match self.idle_input {
+                    Some(_) => ClusterStatus::Idle,
+                    None => ClusterStatus::Deadlocked { waiting },
+                });
+            }
+        }
+    }
+
+    /// Runs machine `idx` until it halts or needs an input it doesn't have,
+    /// returning whether it received, sent or halted.
+    fn take_turn(&mut self, idx: usize) -> AocResult<bool> {
+        let node = &mut self.nodes[idx];
+        let inbox = self.channels.entry(node.inbox.clone()).or_default();
+        let mut progressed = !inbox.is_empty();
+        node.machine.push_inputs(inbox.drain(..));
+
+        let mut polled = false;
+        loop {
+            match self.nodes[idx].machine.run()? {
+                Status::Output(output) => {
+                    trace!("Machine {} output {}", idx, output);
+                    self.route(idx, output)?;
+                    progressed = true;
+                }
+                Status::Halted => return Ok(true),
+                Status::NeedsInput => match self.idle_input {
+                    Some(idle_input) if !polled => {
+                        self.nodes[idx].machine.push_input(idle_input);
+                        polled = true;
+                    }
+                    _ => return Ok(progressed),
+                },
+            }
+        }
+    }
+
+    fn route(&mut self, idx: usize, output: isize) -> AocResult<()> {
+        let node = &mut self.nodes[idx];
+        match &node.route {
+            Route::To(channels) => {
+                for channel in channels {
+                    self.channels
+                        .entry(channel.clone())
+                        .or_default()
+                        .push_back(output);
+                }
+            }
+            Route::Packets { size } => {
+                node.packet.push(output);
+                if node.packet.len() == *size {
+                    let address = node.packet[0];
+                    let channel = self.addresses.get(&address).ok_or_else(|| {
+                        AocError::invalid(format!(
+                            "machine {idx} sent a packet to unknown address {address}"
+                        ))
+                    })?;
+                    debug!("Machine {} sent {:?}", idx, node.packet);
+                    self.channels
+                        .entry(channel.clone())
+                        .or_default()
+                        .extend(node.packet.drain(..).skip(1));
+                }
+            }
+            Route::Nowhere => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assembler::assemble;
+
+    fn machine(source: &str) -> IntcodeMachine {
+        IntcodeMachine::new(assemble(source).unwrap())
+    }
+
+    /// Outputs double each input, forever.
+    const DOUBLER: &str = "\
+        loop:   IN [x]
+                MUL [x], #2, [x]
+                OUT [x]
+                JZ #0, #loop
+        x:      DATA 0";
+
+    #[test]
+    fn test_broadcast_and_chain() {
+        let mut cluster = Cluster::new();
+        cluster.add_machine(
+            machine(DOUBLER),
+            "in",
+            Route::To(vec!["a".into(), "b".into()]),
+        );
+        cluster.add_machine(machine(DOUBLER), "a", Route::to("a_out"));
+        cluster.add_machine(machine(DOUBLER), "b", Route::to("c"));
+        cluster.add_machine(machine(DOUBLER), "c", Route::to("c_out"));
+        cluster.send_all("in", [1, 2]);
+
+        assert_eq!(
+            ClusterStatus::Deadlocked {
+                waiting: vec![0, 1, 2, 3]
+            },
+            cluster.run().unwrap()
+        );
+        assert_eq!(vec![4, 8], cluster.drain("a_out"));
+        assert_eq!(vec![8, 16], cluster.drain("c_out"));
+    }
+
+    #[test]
+    fn test_halted_and_deadlocked() {
+        let mut cluster = Cluster::new();
+        cluster.add_machine(machine("IN [3]\nHLT\nDATA 0"), "in", Route::Nowhere);
+        cluster.send("in", 1);
+        assert_eq!(ClusterStatus::Halted, cluster.run().unwrap());
+
+        // Each waits for the other to go first
+        let mut cluster = Cluster::new();
+        cluster.add_machine(machine(DOUBLER), "a", Route::to("b"));
+        cluster.add_machine(machine(DOUBLER), "b", Route::to("a"));
+        assert_eq!(
+            ClusterStatus::Deadlocked {
+                waiting: vec![0, 1]
+            },
+            cluster.run().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_packets_until_idle() {
+        // Sends (1, 7, 8), then polls for input forever
+        let sender = "\
+                    OUT #1
+                    OUT #7
+                    OUT #8
+            idle:   IN [v]
+                    JZ #0, #idle
+            v:      DATA 0";
+        // Forwards each (x, y) it receives to address 255
+        let forwarder = "\
+            loop:   IN [x]
+                    EQ [x], #-1, [t]
+                    JNZ [t], #loop
+                    IN [y]
+                    OUT #255
+                    OUT [x]
+                    OUT [y]
+                    JZ #0, #loop
+            x:      DATA 0
+            y:      DATA 0
+            t:      DATA 0";
+
+        let mut cluster = Cluster::new().with_idle_input(-1);
+        for (address, source) in [sender, forwarder].into_iter().enumerate() {
+            let inbox = address.to_string();
+            cluster.add_machine(machine(source), &inbox, Route::Packets { size: 3 });
+            cluster.bind_address(address as isize, &inbox);
+        }
+        cluster.bind_address(255, "nat");
+
+        assert_eq!(ClusterStatus::Idle, cluster.run().unwrap());
+        assert_eq!(vec![7, 8], cluster.drain("nat"));
+
+        cluster.send_all("1", [3, 4]);
+        assert_eq!(ClusterStatus::Idle, cluster.run().unwrap());
+        assert_eq!(vec![3, 4], cluster.drain("nat"));
+    }
+}
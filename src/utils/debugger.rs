@@ -0,0 +1,555 @@
+use std::{
+    collections::{BTreeSet, VecDeque},
+    fmt,
+    io::{self, BufRead, Write},
+};
+
+use crate::disassembler::{Instruction, decode};
+use crate::error::AocResult;
+use crate::intcode::{IntcodeMachine, Mode, Status};
+
+/// How many executed instructions the trace keeps unless told otherwise.
+pub const DEFAULT_TRACE_LEN: usize = 32;
+
+/// An instruction the machine executed, with the values it saw.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TraceEntry {
+    pub address: usize,
+    /// The instruction word as it was before executing.
+    pub word: isize,
+    /// The decoded instruction, or `None` for one the disassembler can't
+    /// decode, such as an extension opcode.
+    pub instruction: Option<Instruction>,
+    /// The values of the parameters it read, in order, when it decoded.
+    pub reads: Vec<isize>,
+    /// The address it wrote its result to, and the result.
+    pub write: Option<(usize, isize)>,
+}
+
+impl fmt::Display for TraceEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reads: Vec<String> = self.reads.iter().map(isize::to_string).collect();
+        let text = match self.write {
+            Some((address, value)) => format!("{} -> [{address}] = {value}", reads.join(", ")),
+            None => reads.join(", "),
+        };
+        let instruction = match &self.instruction {
+            Some(instruction) => instruction.to_string(),
+            None => format!("OP {}", self.word),
+        };
+        write!(
+            f,
+            "{:>6}  {:<28} {}",
+            self.address,
+            instruction,
+            text.trim()
+        )
+    }
+}
+
+/// Why the debugger handed control back.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Event {
+    /// The next instruction to execute is at a breakpoint.
+    Breakpoint(usize),
+    /// An instruction wrote `value` to the watched `address`.
+    Watchpoint { address: usize, value: isize },
+    /// The machine halted, needs an input or produced an output.
+    Status(Status),
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Event::Breakpoint(address) => write!(f, "breakpoint at {address}"),
+            Event::Watchpoint { address, value } => write!(f, "watchpoint: [{address}] = {value}"),
+            Event::Status(Status::Halted) => write!(f, "halted"),
+            Event::Status(Status::NeedsInput) => write!(f, "waiting for input"),
+            Event::Status(Status::Output(output)) => write!(f, "output {output}"),
+        }
+    }
+}
+
+/// Runs an [`IntcodeMachine`] under breakpoints and watchpoints, keeping a
+/// trace of the most recently executed instructions.
+#[derive(Clone, Debug)]
+pub struct Debugger {
+    machine: IntcodeMachine,
+    breakpoints: BTreeSet<usize>,
+    watchpoints: BTreeSet<usize>,
+    trace: VecDeque<TraceEntry>,
+    trace_len: usize,
+    /// The breakpoint last stopped at, which doesn't stop a run from it again.
+    stopped_at: Option<usize>,
+}
+
+impl Debugger {
+    pub fn new(machine: IntcodeMachine) -> Self {
+        Debugger {
+            machine,
+            breakpoints: BTreeSet::new(),
+            watchpoints: BTreeSet::new(),
+            trace: VecDeque::with_capacity(DEFAULT_TRACE_LEN),
+            trace_len: DEFAULT_TRACE_LEN,
+            stopped_at: None,
+        }
+    }
+
+    /// Keeps the last `trace_len` executed instructions instead of the default.
+    pub fn with_trace_len(mut self, trace_len: usize) -> Self {
+        self.trace_len = trace_len;
+        while self.trace.len() > trace_len {
+            self.trace.pop_front();
+        }
+        self
+    }
+
+    pub fn machine(&self) -> &IntcodeMachine {
+        &self.machine
+    }
+
+    /// The machine being debugged, e.g. to queue inputs.
+    pub fn machine_mut(&mut self) -> &mut IntcodeMachine {
+        &mut self.machine
+    }
+
+    pub fn into_machine(self) -> IntcodeMachine {
+        self.machine
+    }
+
+    /// Stops before the instruction at `address` runs, returning whether the
+    /// breakpoint is new.
+    pub fn add_breakpoint(&mut self, address: usize) -> bool {
+        self.breakpoints.insert(address)
+    }
+
+    pub fn remove_breakpoint(&mut self, address: usize) -> bool {
+        self.breakpoints.remove(&address)
+    }
+
+    pub fn breakpoints(&self) -> impl Iterator<Item = usize> + '_ {
+        self.breakpoints.iter().copied()
+    }
+
+    /// Stops after any instruction writes to `address`, returning whether the
+    /// watchpoint is new.
+    pub fn add_watchpoint(&mut self, address: usize) -> bool {
+        self.watchpoints.insert(address)
+    }
+
+    pub fn remove_watchpoint(&mut self, address: usize) -> bool {
+        self.watchpoints.remove(&address)
+    }
+
+    pub fn watchpoints(&self) -> impl Iterator<Item = usize> + '_ {
+        self.watchpoints.iter().copied()
+    }
+
+    /// The most recently executed instructions, oldest first.
+    pub fn trace(&self) -> impl Iterator<Item = &TraceEntry> {
+        self.trace.iter()
+    }
+
+    /// Executes a single instruction, returning an event if it hit a
+    /// watchpoint or the machine stopped.
+    pub fn step(&mut self) -> AocResult<Option<Event>> {
+        let address = self.machine.prog_counter();
+        let word = self.peek(Some(address));
+        let instruction = decode(self.machine.memory(), address);
+        let reads = instruction.as_ref().map_or(vec![], |instruction| {
+            let destination = instruction.opcode.destination();
+            (0..instruction.params.len())
+                .filter(|&idx| Some(idx) != destination)
+                .map(|idx| self.value_of(instruction.params[idx]))
+                .collect()
+        });
+
+        let status = self.machine.step()?;
+        if status == Some(Status::NeedsInput) {
+            return Ok(Some(Event::Status(Status::NeedsInput)));
+        }
+        self.stopped_at = None;
+
+        let mut event = status.map(Event::Status);
+        // The machine reports where it wrote, which covers instructions that
+        // don't decode as well
+        let write = self
+            .machine
+            .last_write()
+            .map(|address| (address, self.machine.memory()[address]));
+        if let Some((address, value)) = write
+            && self.watchpoints.contains(&address)
+        {
+            event = Some(Event::Watchpoint { address, value });
+        }
+        self.record(TraceEntry {
+            address,
+            word,
+            instruction,
+            reads,
+            write,
+        });
+        Ok(event)
+    }
+
+    /// Runs until the machine stops, hits a watchpoint or reaches a
+    /// breakpoint. Running again from the breakpoint it stopped at moves past it.
+    pub fn run(&mut self) -> AocResult<Event> {
+        loop {
+            let prog_counter = self.machine.prog_counter();
+            if self.breakpoints.contains(&prog_counter) && self.stopped_at != Some(prog_counter) {
+                self.stopped_at = Some(prog_counter);
+                return Ok(Event::Breakpoint(prog_counter));
+            }
+            if let Some(event) = self.step()? {
+                return Ok(event);
+            }
+        }
+    }
+
+    fn record(&mut self, entry: TraceEntry) {
+        if self.trace_len == 0 {
+            return;
+        }
+        if self.trace.len() == self.trace_len {
+            self.trace.pop_front();
+        }
+        self.trace.push_back(entry);
+    }
+
+    fn address_of(&self, (mode, value): (Mode, isize)) -> Option<usize> {
+        let address = match mode {
+            Mode::Position => value,
+            Mode::Relative => self.machine.relative_base().checked_add(value)?,
+            Mode::Immediate => return None,
+        };
+        usize::try_from(address).ok()
+    }
+
+    fn value_of(&self, (mode, value): (Mode, isize)) -> isize {
+        match mode {
+            Mode::Immediate => value,
+            _ => self.peek(self.address_of((mode, value))),
+        }
+    }
+
+    fn peek(&self, address: Option<usize>) -> isize {
+        address
+            .and_then(|address| self.machine.memory().get(address))
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+const HELP: &str = "\
+step [N]        execute N instructions, 1 by default
+continue        run until a breakpoint, watchpoint, output or halt
+break ADDR      stop before the instruction at ADDR runs
+delete ADDR     remove a breakpoint
+watch ADDR      stop after an instruction writes to ADDR
+unwatch ADDR    remove a watchpoint
+input VALUE...  queue inputs for the program
+print ADDR [N]  show N memory cells from ADDR
+list [N]        disassemble N instructions from the program counter
+trace           show the most recently executed instructions
+regs            show the program counter and relative base
+quit            leave the debugger
+An empty line repeats the last command, and commands can be shortened to
+their first letter.";
+
+const PROMPT: &str = "(intcode) ";
+
+/// Reads debugger commands from `input` until it ends or says `quit`,
+/// writing what they show to `output`.
+pub fn repl(
+    debugger: &mut Debugger,
+    input: impl BufRead,
+    mut output: impl Write,
+) -> io::Result<()> {
+    let mut last = String::new();
+    write!(output, "{PROMPT}")?;
+    output.flush()?;
+    for line in input.lines() {
+        let line = line?;
+        let command = match line.trim() {
+            "" => last.clone(),
+            command => command.to_string(),
+        };
+        if !run_command(debugger, &command, &mut output)? {
+            return Ok(());
+        }
+        last = command;
+        write!(output, "{PROMPT}")?;
+        output.flush()?;
+    }
+    writeln!(output)
+}
+
+/// Runs one REPL command, returning whether to carry on.
+fn run_command(
+    debugger: &mut Debugger,
+    command: &str,
+    output: &mut impl Write,
+) -> io::Result<bool> {
+    let mut words = command.split_whitespace();
+    let Some(name) = words.next() else {
+        return Ok(true);
+    };
+    let args: Vec<&str> = words.collect();
+    let numbers: Result<Vec<isize>, String> = args
+        .iter()
+        .map(|arg| {
+            arg.parse()
+                .map_err(|_| format!("expected a number, found {arg:?}"))
+        })
+        .collect();
+    let numbers = match numbers {
+        Ok(numbers) => numbers,
+        Err(err) => {
+            writeln!(output, "{err}")?;
+            return Ok(true);
+        }
+    };
+    let count = |default: usize| numbers.first().map_or(default, |&n| n.max(0) as usize);
+    let address = || match numbers[..] {
+        [address] if address >= 0 => Ok(address as usize),
+        _ => Err(format!("{name} takes one address")),
+    };
+
+    match name {
+        "s" | "step" => {
+            for _ in 0..count(1) {
+                let result = debugger.step();
+                if let Some(entry) = debugger.trace().last().filter(|_| result.is_ok()) {
+                    writeln!(output, "{entry}")?;
+                }
+                match result {
+                    Ok(None) => continue,
+                    Ok(Some(event)) => writeln!(output, "{event}")?,
+                    Err(err) => writeln!(output, "{err}")?,
+                }
+                break;
+            }
+        }
+        "c" | "continue" => match debugger.run() {
+            Ok(event) => writeln!(output, "{event}")?,
+            Err(err) => writeln!(output, "{err}")?,
+        },
+        "b" | "break" | "d" | "delete" | "w" | "watch" | "u" | "unwatch" => match address() {
+            Ok(address) => {
+                let changed = match name {
+                    "b" | "break" => debugger.add_breakpoint(address),
+                    "d" | "delete" => debugger.remove_breakpoint(address),
+                    "w" | "watch" => debugger.add_watchpoint(address),
+                    _ => debugger.remove_watchpoint(address),
+                };
+                if !changed {
+                    writeln!(output, "nothing changed at {address}")?;
+                }
+            }
+            Err(err) => writeln!(output, "{err}")?,
+        },
+        "i" | "input" => debugger.machine_mut().push_inputs(numbers),
+        "p" | "print" => match numbers[..] {
+            [start, ..] if start >= 0 => {
+                let len = numbers.get(1).map_or(1, |&n| n.max(0) as usize);
+                // Cells past the end of memory are all zero, so show only the first
+                let start = start as usize;
+                let end = start
+                    .saturating_add(len)
+                    .min(debugger.machine().memory().len().max(start + 1));
+                for address in start..end {
+                    let value = debugger.peek(Some(address));
+                    writeln!(output, "[{address}] = {value}")?;
+                }
+            }
+            _ => writeln!(output, "print takes an address and an optional count")?,
+        },
+        "l" | "list" => {
+            let mut address = debugger.machine().prog_counter();
+            for _ in 0..count(5) {
+                let marker = if address == debugger.machine().prog_counter() {
+                    "=>"
+                } else if debugger.breakpoints.contains(&address) {
+                    " *"
+                } else {
+                    "  "
+                };
+                match decode(debugger.machine().memory(), address) {
+                    Some(instruction) => {
+                        writeln!(output, "{marker}{address:>6}  {instruction}")?;
+                        address += instruction.size();
+                    }
+                    None => {
+                        let value = debugger.peek(Some(address));
+                        writeln!(output, "{marker}{address:>6}  DATA {value}")?;
+                        address += 1;
+                    }
+                }
+            }
+        }
+        "t" | "trace" => {
+            for entry in debugger.trace() {
+                writeln!(output, "{entry}")?;
+            }
+        }
+        "r" | "regs" => {
+            let machine = debugger.machine();
+            writeln!(
+                output,
+                "pc {}  rb {}",
+                machine.prog_counter(),
+                machine.relative_base()
+            )?;
+        }
+        "q" | "quit" => return Ok(false),
+        "h" | "help" => writeln!(output, "{HELP}")?,
+        _ => writeln!(output, "unknown command {name:?}, try help")?,
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assembler::assemble;
+    use crate::error::AocError;
+    use crate::intcode::{Extension, InstructionSet};
+
+    fn countdown() -> IntcodeMachine {
+        let source = "\
+            loop:   OUT [n]
+                    ADD [n], #-1, [n]
+                    JNZ [n], #loop
+                    HLT
+            n:      DATA 3";
+        IntcodeMachine::new(assemble(source).unwrap())
+    }
+
+    #[test]
+    fn test_breakpoints_and_watchpoints() {
+        let mut debugger = Debugger::new(countdown());
+        debugger.add_breakpoint(6);
+        assert_eq!(Event::Status(Status::Output(3)), debugger.run().unwrap());
+        assert_eq!(Event::Breakpoint(6), debugger.run().unwrap());
+        assert_eq!(Event::Status(Status::Output(2)), debugger.run().unwrap());
+
+        debugger.remove_breakpoint(6);
+        debugger.add_watchpoint(10);
+        assert_eq!(
+            Event::Watchpoint {
+                address: 10,
+                value: 1
+            },
+            debugger.run().unwrap()
+        );
+        assert_eq!(Event::Status(Status::Output(1)), debugger.run().unwrap());
+    }
+
+    #[test]
+    fn test_trace_keeps_recent_instructions() {
+        let mut debugger = Debugger::new(countdown()).with_trace_len(2);
+        debugger.step().unwrap();
+        debugger.step().unwrap();
+        assert_eq!(
+            "     2  ADD [10], #-1, [10]          3, -1 -> [10] = 2",
+            debugger.trace().last().unwrap().to_string()
+        );
+
+        while debugger.run().unwrap() != Event::Status(Status::Halted) {}
+        let trace: Vec<&TraceEntry> = debugger.trace().collect();
+        assert_eq!(2, trace.len());
+        assert_eq!((6, vec![0, 0]), (trace[0].address, trace[0].reads.clone()));
+        assert_eq!(9, trace[1].address);
+    }
+
+    #[test]
+    fn test_watchpoints_see_writes_that_dont_decode() {
+        // An ADD with a stray mode digit, which the VM runs but the
+        // disassembler takes for data
+        let mut debugger = Debugger::new(IntcodeMachine::new(vec![100001, 5, 5, 6, 99, 7, 0]));
+        debugger.add_watchpoint(6);
+        assert_eq!(
+            Event::Watchpoint {
+                address: 6,
+                value: 14
+            },
+            debugger.run().unwrap()
+        );
+        assert_eq!(
+            "     0  OP 100001                    -> [6] = 14",
+            debugger.trace().last().unwrap().to_string()
+        );
+
+        let instruction_set = InstructionSet::default()
+            .with_extension(
+                20,
+                Extension::new("SET", 1, |execution| {
+                    execution.write(1, 42)?;
+                    Ok(None)
+                }),
+            )
+            .unwrap();
+        let machine = IntcodeMachine::new(vec![20, 3, 99, 0]).with_instruction_set(instruction_set);
+        let mut debugger = Debugger::new(machine);
+        debugger.add_watchpoint(3);
+        assert_eq!(
+            Event::Watchpoint {
+                address: 3,
+                value: 42
+            },
+            debugger.run().unwrap()
+        );
+        assert_eq!(None, debugger.trace().last().unwrap().instruction);
+    }
+
+    #[test]
+    fn test_overflowing_relative_address_is_a_fault() {
+        let source = format!("ARB #1\nOUT rb[{}]\nHLT", isize::MAX);
+        let mut debugger = Debugger::new(IntcodeMachine::new(assemble(&source).unwrap()));
+        debugger.step().unwrap();
+        assert!(matches!(
+            debugger.step(),
+            Err(AocError::Intcode {
+                prog_counter: 2,
+                address: None,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_repl() {
+        let mut debugger = Debugger::new(countdown());
+        let mut output = vec![];
+        repl(
+            &mut debugger,
+            "break 2\nc\nc\n\nprint 10\nbogus\nq\n".as_bytes(),
+            &mut output,
+        )
+        .unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(
+            "(intcode) (intcode) output 3\n\
+             (intcode) breakpoint at 2\n\
+             (intcode) output 2\n\
+             (intcode) [10] = 2\n\
+             (intcode) unknown command \"bogus\", try help\n\
+             (intcode) ",
+            output
+        );
+    }
+
+    #[test]
+    fn test_print_clamps_to_memory() {
+        let mut debugger = Debugger::new(countdown());
+        let mut output = vec![];
+        let command = format!("print 9 {}\nprint 20 {}\n", isize::MAX, isize::MAX);
+        repl(&mut debugger, command.as_bytes(), &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(
+            "(intcode) [9] = 99\n[10] = 3\n(intcode) [20] = 0\n(intcode) \n",
+            output
+        );
+    }
+}
@@ -0,0 +1,304 @@
+// Differential tests running random well-formed programs on both the
+// `IntcodeMachine` and a deliberately simple reference interpreter.
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::error::AocError;
+use crate::intcode::{IntcodeMachine, MEMORY_LIMIT, Mode, Opcode, Status};
+
+/// Programs are padded with data to this many words, and most addresses they
+/// use fall within it.
+const PROGRAM_LEN: usize = 48;
+
+/// Instructions executed before a run is cut short, as jumps can loop forever.
+const STEP_BUDGET: usize = 300;
+
+/// SplitMix64, enough randomness for generating programs reproducibly.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    /// A value in `lo..hi`.
+    fn range(&mut self, lo: isize, hi: isize) -> isize {
+        lo + self.below((hi - lo) as usize) as isize
+    }
+
+    fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.below(items.len())]
+    }
+}
+
+/// Builds a program of random instructions ending in `HLT`, then data.
+/// Destinations use `modes` without `Immediate`, other parameters use `modes`
+/// with it, so that relative writes, say, can be exercised on their own.
+fn generate(rng: &mut Rng, modes: &[Mode]) -> Vec<isize> {
+    let destination_modes: Vec<Mode> = modes
+        .iter()
+        .copied()
+        .filter(|&mode| mode != Mode::Immediate)
+        .collect();
+    let source_modes: Vec<Mode> = modes.iter().copied().chain([Mode::Immediate]).collect();
+
+    let mut program = vec![];
+    for _ in 0..rng.range(1, 12) {
+        let opcode = rng.pick(&Opcode::ALL[..9]);
+        let mut instruction = opcode as isize;
+        let mut params = vec![];
+        for idx in 0..opcode.param_count() {
+            let mode = if opcode.destination() == Some(idx) {
+                rng.pick(&destination_modes)
+            } else {
+                rng.pick(&source_modes)
+            };
+            instruction += mode as isize * 10isize.pow(idx as u32 + 2);
+            params.push(match mode {
+                Mode::Position => rng.range(0, PROGRAM_LEN as isize),
+                Mode::Immediate => rng.range(-4, PROGRAM_LEN as isize),
+                Mode::Relative => rng.range(-8, 16),
+            });
+        }
+        program.push(instruction);
+        program.extend(params);
+    }
+    program.push(Opcode::Halt as isize);
+    while program.len() < PROGRAM_LEN {
+        program.push(rng.range(-3, 20));
+    }
+    program
+}
+
+/// How a run ended.
+#[derive(Debug, PartialEq, Eq)]
+enum End {
+    Halted,
+    NeedsInput,
+    /// The program faulted on the instruction at this address.
+    Fault(usize),
+    OutOfSteps,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Outcome {
+    end: End,
+    outputs: Vec<isize>,
+    /// Memory up to its last non-zero word.
+    memory: Vec<isize>,
+    prog_counter: usize,
+    relative_base: isize,
+}
+
+fn trim(mut memory: Vec<isize>) -> Vec<isize> {
+    while memory.last() == Some(&0) {
+        memory.pop();
+    }
+    memory
+}
+
+fn run_machine(program: &[isize], inputs: &[isize]) -> Outcome {
+    let mut machine = IntcodeMachine::new(program.to_vec());
+    machine.push_inputs(inputs.iter().copied());
+    let mut outputs = vec![];
+    let mut end = End::OutOfSteps;
+    for _ in 0..STEP_BUDGET {
+        match machine.step() {
+            Ok(None) => {}
+            Ok(Some(Status::Output(output))) => outputs.push(output),
+            Ok(Some(Status::Halted)) => end = End::Halted,
+            Ok(Some(Status::NeedsInput)) => end = End::NeedsInput,
+            Err(AocError::Intcode { prog_counter, .. }) => end = End::Fault(prog_counter),
+            Err(err) => panic!("unexpected error {err}"),
+        }
+        if end != End::OutOfSteps {
+            break;
+        }
+    }
+    Outcome {
+        end,
+        outputs,
+        memory: trim(machine.memory().to_vec()),
+        prog_counter: machine.prog_counter(),
+        relative_base: machine.relative_base(),
+    }
+}
+
+/// The Intcode rules written out as plainly as possible, with sparse memory.
+struct Reference {
+    memory: HashMap<isize, isize>,
+    pc: isize,
+    rb: isize,
+    inputs: VecDeque<isize>,
+    outputs: Vec<isize>,
+}
+
+/// Why the reference stopped executing.
+enum Stop {
+    Halted,
+    NeedsInput,
+    Fault,
+}
+
+impl Reference {
+    fn load(&self, address: isize) -> Result<isize, Stop> {
+        if address < 0 || address >= MEMORY_LIMIT as isize {
+            return Err(Stop::Fault);
+        }
+        Ok(self.memory.get(&address).copied().unwrap_or(0))
+    }
+
+    fn mode(&self, param: u32) -> Result<isize, Stop> {
+        let mode = self.load(self.pc)? / 10isize.pow(param + 1) % 10;
+        if (0..=2).contains(&mode) {
+            Ok(mode)
+        } else {
+            Err(Stop::Fault)
+        }
+    }
+
+    fn address(&self, param: u32) -> Result<isize, Stop> {
+        let raw = self.load(self.pc + param as isize)?;
+        match self.mode(param)? {
+            0 => Ok(raw),
+            2 => raw.checked_add(self.rb).ok_or(Stop::Fault),
+            _ => Err(Stop::Fault),
+        }
+    }
+
+    fn value(&self, param: u32) -> Result<isize, Stop> {
+        if self.mode(param)? == 1 {
+            self.load(self.pc + param as isize)
+        } else {
+            self.load(self.address(param)?)
+        }
+    }
+
+    fn store(&mut self, param: u32, value: isize) -> Result<(), Stop> {
+        let address = self.address(param)?;
+        self.load(address)?;
+        self.memory.insert(address, value);
+        Ok(())
+    }
+
+    fn step(&mut self) -> Result<(), Stop> {
+        match self.load(self.pc)? % 100 {
+            1 => {
+                let sum = self.value(1)?.checked_add(self.value(2)?);
+                self.store(3, sum.ok_or(Stop::Fault)?)?;
+                self.pc += 4;
+            }
+            2 => {
+                let product = self.value(1)?.checked_mul(self.value(2)?);
+                self.store(3, product.ok_or(Stop::Fault)?)?;
+                self.pc += 4;
+            }
+            3 => {
+                let input = self.inputs.pop_front().ok_or(Stop::NeedsInput)?;
+                self.store(1, input)?;
+                self.pc += 2;
+            }
+            4 => {
+                self.outputs.push(self.value(1)?);
+                self.pc += 2;
+            }
+            opcode @ (5 | 6) => {
+                if (self.value(1)? != 0) == (opcode == 5) {
+                    let target = self.value(2)?;
+                    self.load(target)?;
+                    self.pc = target;
+                } else {
+                    self.pc += 3;
+                }
+            }
+            opcode @ (7 | 8) => {
+                let (lhs, rhs) = (self.value(1)?, self.value(2)?);
+                let result = if opcode == 7 { lhs < rhs } else { lhs == rhs };
+                self.store(3, result as isize)?;
+                self.pc += 4;
+            }
+            9 => {
+                self.rb = self.rb.checked_add(self.value(1)?).ok_or(Stop::Fault)?;
+                self.pc += 2;
+            }
+            99 => return Err(Stop::Halted),
+            _ => return Err(Stop::Fault),
+        }
+        Ok(())
+    }
+}
+
+fn run_reference(program: &[isize], inputs: &[isize]) -> Outcome {
+    let mut reference = Reference {
+        memory: (0..).zip(program.iter().copied()).collect(),
+        pc: 0,
+        rb: 0,
+        inputs: inputs.iter().copied().collect(),
+        outputs: vec![],
+    };
+    let mut end = End::OutOfSteps;
+    for _ in 0..STEP_BUDGET {
+        if let Err(stop) = reference.step() {
+            end = match stop {
+                Stop::Halted => End::Halted,
+                Stop::NeedsInput => End::NeedsInput,
+                Stop::Fault => End::Fault(reference.pc as usize),
+            };
+            break;
+        }
+    }
+
+    let len = reference.memory.keys().max().map_or(0, |&max| max + 1);
+    let memory = (0..len).map(|address| reference.memory.get(&address).copied().unwrap_or(0));
+    Outcome {
+        end,
+        outputs: reference.outputs,
+        memory: trim(memory.collect()),
+        prog_counter: reference.pc as usize,
+        relative_base: reference.rb,
+    }
+}
+
+/// Runs `cases` random programs on both interpreters, panicking on the first
+/// that they disagree about.
+fn check_against_reference(seed: u64, cases: usize, modes: &[Mode]) {
+    let mut rng = Rng(seed);
+    for case in 0..cases {
+        let program = generate(&mut rng, modes);
+        let inputs: Vec<isize> = (0..rng.below(4)).map(|_| rng.range(-5, 50)).collect();
+        assert_eq!(
+            run_reference(&program, &inputs),
+            run_machine(&program, &inputs),
+            "case {case}: program {program:?} with inputs {inputs:?}"
+        );
+    }
+}
+
+#[test]
+fn test_random_programs_match_reference() {
+    check_against_reference(2019, 2000, &[Mode::Position, Mode::Relative]);
+}
+
+#[test]
+fn test_random_relative_programs_match_reference() {
+    check_against_reference(9, 1000, &[Mode::Relative]);
+}
+
+#[test]
+fn test_generated_programs_run() {
+    // Guards against a generator that only makes programs which fault at once
+    let mut rng = Rng(7);
+    let ends: Vec<End> = (0..200)
+        .map(|_| run_machine(&generate(&mut rng, &[Mode::Position]), &[1, 2, 3]).end)
+        .collect();
+    assert!(ends.iter().filter(|&end| *end == End::Halted).count() > 20);
+    assert!(ends.iter().any(|end| matches!(end, End::Fault(_))));
+}
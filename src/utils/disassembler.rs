@@ -0,0 +1,317 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+use crate::intcode::{Mode, Opcode, split_instruction};
+
+/// Data words are listed this many to a line.
+const DATA_PER_LINE: usize = 8;
+
+/// A decoded instruction and its parameters.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Instruction {
+    pub opcode: Opcode,
+    pub params: Vec<(Mode, isize)>,
+}
+
+impl Instruction {
+    /// The number of words the instruction takes up.
+    pub fn size(&self) -> usize {
+        1 + self.params.len()
+    }
+
+    /// Where the instruction jumps to, if it's a jump with an immediate target.
+    pub fn jump_target(&self) -> Option<usize> {
+        match (self.opcode, self.params.get(1)) {
+            (Opcode::JumpIfTrue | Opcode::JumpIfFalse, Some(&(Mode::Immediate, target)))
+                if target >= 0 =>
+            {
+                Some(target as usize)
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether execution can carry on to the next instruction.
+    fn falls_through(&self) -> bool {
+        match (self.opcode, self.params.first()) {
+            (Opcode::Halt, _) => false,
+            (Opcode::JumpIfTrue, Some(&(Mode::Immediate, cond))) => cond == 0,
+            (Opcode::JumpIfFalse, Some(&(Mode::Immediate, cond))) => cond != 0,
+            _ => true,
+        }
+    }
+
+    /// The value an `ADD` or `MUL` of two immediates stores, which is often an
+    /// address to return to later through an indirect jump.
+    fn constant_result(&self) -> Option<isize> {
+        match self.params[..] {
+            [(Mode::Immediate, lhs), (Mode::Immediate, rhs), _] => match self.opcode {
+                Opcode::Add => lhs.checked_add(rhs),
+                Opcode::Multiply => lhs.checked_mul(rhs),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Decodes the instruction at `address`, or `None` if it isn't a valid one.
+pub fn decode(intcode: &[isize], address: usize) -> Option<Instruction> {
+    let value = *intcode.get(address)?;
+    let (code, modes) = split_instruction(value);
+    let opcode = Opcode::try_from(code).ok()?;
+    // Mode digits beyond the opcode's parameters mean the word is data
+    if value / 10_isize.pow(2 + opcode.param_count() as u32) != 0 {
+        return None;
+    }
+
+    let mut params = Vec::with_capacity(opcode.param_count());
+    for (offset, &mode) in modes.iter().take(opcode.param_count()).enumerate() {
+        let mode = Mode::try_from(mode).ok()?;
+        params.push((mode, *intcode.get(address + 1 + offset)?));
+    }
+    Some(Instruction { opcode, params })
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Item {
+    Code(Instruction),
+    /// Words that no path through the program executes.
+    Data(Vec<isize>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Line {
+    pub address: usize,
+    /// Whether some jump lands here, directly or through a stored address.
+    pub is_target: bool,
+    pub item: Item,
+}
+
+/// A program split into code and data, rendered one line per instruction.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Listing {
+    pub lines: Vec<Line>,
+}
+
+/// The label given to a jump target.
+pub fn label(address: usize) -> String {
+    format!("L{address}")
+}
+
+/// Disassembles a program, following control flow from address 0 to tell code
+/// from data. Jumps through memory can't be followed, so values stored by an
+/// `ADD` or `MUL` of two immediates are also tried as entry points.
+/// Instructions that the program patches before running them, as in 2019 day
+/// 5, don't decode, so everything only reachable through them is listed as data.
+pub fn disassemble(intcode: &[isize]) -> Listing {
+    let mut code: BTreeMap<usize, Instruction> = BTreeMap::new();
+    let mut covered = vec![false; intcode.len()];
+    let mut targets = BTreeSet::new();
+
+    let mut entries = vec![0];
+    loop {
+        while let Some(entry) = entries.pop() {
+            let mut address = entry;
+            while let Some(instruction) = decode(intcode, address) {
+                let span = address..address + instruction.size();
+                if covered[span.clone()].iter().any(|&c| c) {
+                    break;
+                }
+                covered[span].fill(true);
+
+                if let Some(target) = instruction.jump_target() {
+                    targets.insert(target);
+                    entries.push(target);
+                }
+                let falls_through = instruction.falls_through();
+                let len = instruction.size();
+                code.insert(address, instruction);
+                if !falls_through {
+                    break;
+                }
+                address += len;
+            }
+        }
+
+        // Retry stored constants that land on unexplored code
+        let pointers: Vec<usize> = code
+            .values()
+            .filter_map(Instruction::constant_result)
+            .filter_map(|value| usize::try_from(value).ok())
+            .filter(|&address| {
+                address < intcode.len() && !covered[address] && decode(intcode, address).is_some()
+            })
+            .collect();
+        if pointers.is_empty() {
+            break;
+        }
+        targets.extend(&pointers);
+        entries = pointers;
+    }
+
+    let mut lines = vec![];
+    let mut address = 0;
+    while address < intcode.len() {
+        if let Some(instruction) = code.remove(&address) {
+            let len = instruction.size();
+            lines.push(Line {
+                address,
+                is_target: targets.contains(&address),
+                item: Item::Code(instruction),
+            });
+            address += len;
+        } else {
+            let end = (address..intcode.len())
+                .find(|&a| code.contains_key(&a))
+                .unwrap_or(intcode.len());
+            for chunk_start in (address..end).step_by(DATA_PER_LINE) {
+                let chunk_end = (chunk_start + DATA_PER_LINE).min(end);
+                lines.push(Line {
+                    address: chunk_start,
+                    is_target: false,
+                    item: Item::Data(intcode[chunk_start..chunk_end].to_vec()),
+                });
+            }
+            address = end;
+        }
+    }
+
+    Listing { lines }
+}
+
+fn format_param(mode: Mode, value: isize) -> String {
+    match mode {
+        Mode::Position => format!("[{value}]"),
+        Mode::Immediate => format!("#{value}"),
+        Mode::Relative => format!("rb[{value}]"),
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.opcode.mnemonic())?;
+        for (idx, &(mode, value)) in self.params.iter().enumerate() {
+            let separator = if idx == 0 { " " } else { ", " };
+            write!(f, "{separator}{}", format_param(mode, value))?;
+        }
+        Ok(())
+    }
+}
+
+/// Renders an item, naming the targets of jumps to labelled addresses. Jumps
+/// into data or the middle of an instruction keep their number.
+fn format_item(item: &Item, labelled: &BTreeSet<usize>) -> String {
+    match item {
+        Item::Code(instruction) => {
+            let target = instruction.jump_target().filter(|t| labelled.contains(t));
+            let Some(target) = target else {
+                return instruction.to_string();
+            };
+            let params: Vec<String> = instruction
+                .params
+                .iter()
+                .enumerate()
+                .map(|(idx, &(mode, value))| match idx {
+                    1 => format!("#{}", label(target)),
+                    _ => format_param(mode, value),
+                })
+                .collect();
+            format!("{} {}", instruction.opcode.mnemonic(), params.join(", "))
+        }
+        Item::Data(values) => {
+            let values: Vec<String> = values.iter().map(isize::to_string).collect();
+            format!("DATA {}", values.join(", "))
+        }
+    }
+}
+
+impl fmt::Display for Listing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let labelled: BTreeSet<usize> = self
+            .lines
+            .iter()
+            .filter(|line| line.is_target)
+            .map(|line| line.address)
+            .collect();
+        for line in &self.lines {
+            let label = if line.is_target {
+                format!("{}:", label(line.address))
+            } else {
+                String::new()
+            };
+            let text = format_item(&line.item, &labelled);
+            writeln!(f, "{label:<8}{text:<32} ; {}", line.address)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode::parse_intcode_input;
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(
+            Some(Instruction {
+                opcode: Opcode::Add,
+                params: vec![
+                    (Mode::Position, 12),
+                    (Mode::Immediate, 5),
+                    (Mode::Relative, 3)
+                ],
+            }),
+            decode(&[21001, 12, 5, 3], 0)
+        );
+        assert_eq!(None, decode(&[21001, 12, 5], 0));
+        assert_eq!(None, decode(&[42], 0));
+        assert_eq!(None, decode(&[304], 0));
+    }
+
+    #[test]
+    fn test_disassemble() {
+        // Jumps over a data word, outputs [9] twice, then halts
+        let intcode = [1105, 1, 4, 42, 4, 9, 1005, 9, 4, 99];
+        assert_eq!(
+            "        JNZ #1, #L4                      ; 0\n\
+             \x20       DATA 42                          ; 3\n\
+             L4:     OUT [9]                          ; 4\n\
+             \x20       JNZ [9], #L4                     ; 6\n\
+             \x20       HLT                              ; 9\n",
+            disassemble(&intcode).to_string()
+        );
+    }
+
+    #[test]
+    fn test_disassemble_follows_stored_return_address() {
+        // Stores the return address 7 in rb[0], calls 10, which returns through rb[0]
+        let intcode = [21101, 7, 0, 0, 1105, 1, 10, 99, 0, 0, 2105, 1, 0];
+        let listing = disassemble(&intcode);
+        let addresses: Vec<usize> = listing
+            .lines
+            .iter()
+            .filter(|line| matches!(line.item, Item::Code(_)))
+            .map(|line| line.address)
+            .collect();
+        assert_eq!(vec![0, 4, 7, 10], addresses);
+    }
+
+    #[test]
+    fn test_disassemble_covers_every_word() {
+        let intcode = parse_intcode_input("input/2019/day09.txt").unwrap();
+        let listing = disassemble(&intcode);
+        let len: usize = listing
+            .lines
+            .iter()
+            .map(|line| match &line.item {
+                Item::Code(instruction) => instruction.size(),
+                Item::Data(values) => values.len(),
+            })
+            .sum();
+        assert_eq!(intcode.len(), len);
+    }
+}
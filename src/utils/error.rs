@@ -0,0 +1,219 @@
+use std::{error::Error, fmt, io, str::FromStr};
+
+pub type AocResult<T> = Result<T, AocError>;
+
+#[derive(Debug)]
+pub enum AocError {
+    /// The input could not be read at all.
+    Io { file: String, source: io::Error },
+    /// A specific line and column of the input could not be understood.
+    Parse {
+        file: Option<String>,
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// The input was readable but is not a valid puzzle, e.g. a missing section.
+    Invalid {
+        file: Option<String>,
+        message: String,
+    },
+    /// The puzzle server could not be reached or refused the request.
+    Fetch { url: String, message: String },
+    /// An API or command was used wrongly, independent of any input, e.g. by
+    /// registering a clashing Intcode opcode.
+    Usage { message: String },
+    /// An Intcode program faulted, e.g. by addressing memory out of range.
+    /// `address` is the memory address it faulted on, if any. It is signed as
+    /// programs can compute negative addresses.
+    Intcode {
+        prog_counter: usize,
+        address: Option<isize>,
+        message: String,
+    },
+}
+
+impl AocError {
+    /// A parse error at the 0-based `line_idx` and `col_idx` of the input.
+    pub fn parse(line_idx: usize, col_idx: usize, message: impl Into<String>) -> Self {
+        AocError::Parse {
+            file: None,
+            line: line_idx + 1,
+            column: col_idx + 1,
+            message: message.into(),
+        }
+    }
+
+    /// A parse error for `token`, a slice of `line` found at the 0-based `line_idx`.
+    pub fn parse_token(
+        line_idx: usize,
+        line: &str,
+        token: &str,
+        message: impl Into<String>,
+    ) -> Self {
+        AocError::parse(line_idx, column_of(line, token), message)
+    }
+
+    /// A parse error for the unexpected character `c` at the 0-based `line_idx` and `col_idx`.
+    pub fn unexpected_char(line_idx: usize, col_idx: usize, c: char) -> Self {
+        AocError::parse(line_idx, col_idx, format!("unexpected character {c:?}"))
+    }
+
+    pub fn usage(message: impl Into<String>) -> Self {
+        AocError::Usage {
+            message: message.into(),
+        }
+    }
+
+    pub fn invalid(message: impl Into<String>) -> Self {
+        AocError::Invalid {
+            file: None,
+            message: message.into(),
+        }
+    }
+
+    /// Places a parse error from a parser that only saw a single line at the
+    /// 0-based `line_idx` of the whole input.
+    pub fn on_line(self, line_idx: usize) -> Self {
+        match self {
+            AocError::Parse {
+                file,
+                column,
+                message,
+                ..
+            } => AocError::Parse {
+                file,
+                line: line_idx + 1,
+                column,
+                message,
+            },
+            err => err,
+        }
+    }
+
+    /// Attaches the name of the input file, unless the error already names one.
+    pub fn in_file(self, name: &str) -> Self {
+        match self {
+            AocError::Parse {
+                file: None,
+                line,
+                column,
+                message,
+            } => AocError::Parse {
+                file: Some(name.to_string()),
+                line,
+                column,
+                message,
+            },
+            AocError::Invalid {
+                file: None,
+                message,
+            } => AocError::Invalid {
+                file: Some(name.to_string()),
+                message,
+            },
+            err => err,
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AocError::Io { file, source } => write!(f, "couldn't read {file}: {source}"),
+            AocError::Parse {
+                file,
+                line,
+                column,
+                message,
+            } => {
+                let file = file.as_deref().unwrap_or("<input>");
+                write!(f, "{file}:{line}:{column}: {message}")
+            }
+            AocError::Invalid { file, message } => {
+                let file = file.as_deref().unwrap_or("<input>");
+                write!(f, "{file}: {message}")
+            }
+            AocError::Fetch { url, message } => write!(f, "couldn't fetch {url}: {message}"),
+            AocError::Usage { message } => write!(f, "{message}"),
+            AocError::Intcode {
+                prog_counter,
+                message,
+                ..
+            } => write!(f, "intcode fault at {prog_counter}: {message}"),
+        }
+    }
+}
+
+impl Error for AocError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AocError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// The 0-based column at which `token` starts within `line`, or 0 if it is not a slice of it.
+pub fn column_of(line: &str, token: &str) -> usize {
+    let line_start = line.as_ptr() as usize;
+    let token_start = token.as_ptr() as usize;
+    if token_start >= line_start && token_start + token.len() <= line_start + line.len() {
+        line[..token_start - line_start].chars().count()
+    } else {
+        0
+    }
+}
+
+/// Parses `token`, a slice of `line` found at the 0-based `line_idx`.
+pub fn parse_token<T: FromStr>(line_idx: usize, line: &str, token: &str) -> AocResult<T> {
+    token.trim().parse().map_err(|_| {
+        AocError::parse_token(
+            line_idx,
+            line,
+            token,
+            format!(
+                "couldn't parse {:?} as {}",
+                token,
+                std::any::type_name::<T>()
+            ),
+        )
+    })
+}
+
+/// Unwraps `value`, reporting `message` at the start of `line_idx` when it is missing.
+pub fn require<T>(value: Option<T>, line_idx: usize, message: &str) -> AocResult<T> {
+    value.ok_or_else(|| AocError::parse(line_idx, 0, message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column_of() {
+        let line = "p=0,4 v=3,-3";
+        let token = line.split(' ').nth(1).unwrap();
+        assert_eq!(6, column_of(line, token));
+        assert_eq!(0, column_of(line, "v=3"));
+    }
+
+    #[test]
+    fn test_parse_token_error() {
+        let line = "190: 10 x9";
+        let token = line.split(' ').nth(2).unwrap();
+        let err = parse_token::<u64>(2, line, token).unwrap_err();
+        assert_eq!(
+            "day07.txt:3:9: couldn't parse \"x9\" as u64",
+            err.in_file("day07.txt").to_string()
+        );
+    }
+
+    #[test]
+    fn test_in_file_keeps_existing_file() {
+        let err = AocError::invalid("no robot")
+            .in_file("a.txt")
+            .in_file("b.txt");
+        assert_eq!("a.txt: no robot", err.to_string());
+    }
+}
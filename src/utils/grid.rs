@@ -0,0 +1,380 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::error::{AocError, AocResult};
+use crate::utils::{Compass, Direction, PuzzleInput, get_lines};
+
+/// A cell's `(row, col)`, with rows counted from the top so that moving
+/// [`Direction::S`] adds one to the row.
+pub type Pos = (usize, usize);
+
+/// A rectangular grid of cells, stored row by row.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// A `height` by `width` grid with every cell set to `cell`.
+    pub fn new(height: usize, width: usize, cell: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: vec![cell; height * width],
+            width,
+            height,
+        }
+    }
+
+    /// A grid of the given rows, which must all be as long as the first.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> AocResult<Self> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+        for (row_idx, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(AocError::invalid(format!(
+                    "row {row_idx} has {} cells, expected {width}",
+                    row.len()
+                )));
+            }
+            cells.extend(row);
+        }
+        Ok(Grid {
+            cells,
+            width,
+            height,
+        })
+    }
+
+    /// Parses one row per line, mapping each character to a cell, or to
+    /// `None` if it isn't allowed. Trailing blank lines are ignored.
+    pub fn from_lines<S: AsRef<str>>(
+        lines: &[S],
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> AocResult<Self> {
+        let lines = match lines.iter().rposition(|line| !line.as_ref().is_empty()) {
+            Some(last) => &lines[..=last],
+            None => return Err(AocError::invalid("empty grid")),
+        };
+        let width = lines[0].as_ref().chars().count();
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for (line_idx, line) in lines.iter().enumerate() {
+            let line = line.as_ref();
+            let mut row_width = 0;
+            for (col_idx, c) in line.chars().enumerate() {
+                cells.push(cell(c).ok_or_else(|| AocError::unexpected_char(line_idx, col_idx, c))?);
+                row_width += 1;
+            }
+            if row_width != width {
+                return Err(AocError::parse(
+                    line_idx,
+                    row_width.min(width),
+                    format!("expected {width} cells per row, found {row_width}"),
+                ));
+            }
+        }
+        Ok(Grid {
+            cells,
+            width,
+            height: lines.len(),
+        })
+    }
+
+    /// Reads the whole input as a grid, see [`Grid::from_lines`].
+    pub fn parse(
+        puzzle_input: impl PuzzleInput,
+        cell: impl FnMut(char) -> Option<T>,
+    ) -> AocResult<Self> {
+        Self::from_lines(&get_lines(puzzle_input)?, cell)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (row, col): Pos) -> bool {
+        row < self.height && col < self.width
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.0 * self.width + pos.1])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.contains(pos)
+            .then(|| &mut self.cells[pos.0 * self.width + pos.1])
+    }
+
+    /// The position `(dx, dy)` away from `pos`, if it is on the grid.
+    pub fn offset(&self, (row, col): Pos, (dx, dy): (isize, isize)) -> Option<Pos> {
+        let pos = (row.checked_add_signed(dy)?, col.checked_add_signed(dx)?);
+        self.contains(pos).then_some(pos)
+    }
+
+    /// The position one step from `pos` in `dir`, if it is on the grid.
+    pub fn neighbor(&self, pos: Pos, dir: Direction) -> Option<Pos> {
+        self.offset(pos, dir.to_delta())
+    }
+
+    /// The orthogonal neighbors of `pos` on the grid, clockwise from north.
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = (Direction, Pos)> + '_ {
+        [Direction::N, Direction::E, Direction::S, Direction::W]
+            .into_iter()
+            .filter_map(move |dir| Some((dir, self.neighbor(pos, dir)?)))
+    }
+
+    /// The orthogonal and diagonal neighbors of `pos` on the grid, clockwise
+    /// from north.
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = (Compass, Pos)> + '_ {
+        Compass::all().filter_map(move |dir| Some((dir, self.offset(pos, dir.to_delta())?)))
+    }
+
+    /// Every position in reading order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    /// Every cell with its position, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The first position in reading order whose cell matches.
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(pos, _)| pos)
+    }
+
+    /// The cells from `start` in `dir` up to the edge of the grid, starting
+    /// with `start` itself.
+    pub fn line(&self, start: Pos, dir: Compass) -> impl Iterator<Item = &T> {
+        let delta = dir.to_delta();
+        std::iter::successors(self.contains(start).then_some(start), move |&pos| {
+            self.offset(pos, delta)
+        })
+        .map(|pos| &self[pos])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|col| self.line((0, col), Compass::South))
+    }
+
+    /// The lines running south-east, starting with the one in the top right
+    /// corner and ending with the one in the bottom left.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let top = (0..self.width).rev().map(|col| (0, col));
+        let left = (1..self.edge_height()).map(|row| (row, 0));
+        top.chain(left)
+            .map(|start| self.line(start, Compass::SouthEast))
+    }
+
+    /// The lines running south-west, starting with the one in the top left
+    /// corner and ending with the one in the bottom right.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let top = (0..self.width).map(|col| (0, col));
+        let right = (1..self.edge_height()).map(|row| (row, self.width - 1));
+        top.chain(right)
+            .map(|start| self.line(start, Compass::SouthWest))
+    }
+
+    /// The height of the left and right edges, which a grid without columns
+    /// doesn't have.
+    fn edge_height(&self) -> usize {
+        if self.width == 0 { 0 } else { self.height }
+    }
+
+    /// A grid of the same size with each cell mapped by `f`.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// The grid mirrored along its main diagonal, so rows become columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rearranged(self.width, self.height, |(row, col)| (col, row))
+    }
+
+    /// The grid turned a quarter clockwise.
+    pub fn rotate_right(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rearranged(self.width, self.height, |(row, col)| {
+            (self.height - 1 - col, row)
+        })
+    }
+
+    /// The grid turned a quarter anticlockwise.
+    pub fn rotate_left(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rearranged(self.width, self.height, |(row, col)| {
+            (col, self.width - 1 - row)
+        })
+    }
+
+    /// A `height` by `width` grid whose cell at each position is copied from
+    /// the one at `source(position)`.
+    fn rearranged(&self, height: usize, width: usize, source: impl Fn(Pos) -> Pos) -> Self
+    where
+        T: Clone,
+    {
+        let positions = (0..height).flat_map(|row| (0..width).map(move |col| (row, col)));
+        Grid {
+            cells: positions.map(|pos| self[source(pos)].clone()).collect(),
+            width,
+            height,
+        }
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        let (height, width) = (self.height, self.width);
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside the {height}x{width} grid"))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (height, width) = (self.height, self.width);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside the {height}x{width} grid"))
+    }
+}
+
+/// Draws each row on a line of its own.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::Contents;
+
+    fn digits(rows: &[&str]) -> Grid<u32> {
+        Grid::from_lines(rows, |c| c.to_digit(10)).unwrap()
+    }
+
+    fn collect<'a>(lines: impl Iterator<Item = impl Iterator<Item = &'a u32>>) -> Vec<Vec<u32>> {
+        lines.map(|line| line.copied().collect()).collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::parse(Contents("#.#\n..#\n\n"), Some).unwrap();
+        assert_eq!((2, 3), (grid.height(), grid.width()));
+        assert_eq!(Some(&'.'), grid.get((1, 1)));
+        assert_eq!(None, grid.get((0, 3)));
+        assert_eq!('#', grid[(1, 2)]);
+        assert_eq!(Some((0, 1)), grid.position(|&c| c == '.'));
+        assert_eq!("#.#\n..#\n", grid.to_string());
+
+        let err = Grid::from_lines(&["12", "3x"], |c| c.to_digit(10)).unwrap_err();
+        assert_eq!("<input>:2:2: unexpected character 'x'", err.to_string());
+        let err = Grid::from_lines(&["12", "3"], |c| c.to_digit(10)).unwrap_err();
+        assert_eq!(
+            "<input>:2:2: expected 2 cells per row, found 1",
+            err.to_string()
+        );
+        assert!(Grid::from_lines(&[""], Some).is_err());
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = digits(&["123", "456"]);
+        assert_eq!(
+            vec![(Direction::E, (0, 1)), (Direction::S, (1, 0))],
+            grid.neighbors4((0, 0)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![
+                (Compass::North, (0, 1)),
+                (Compass::NorthEast, (0, 2)),
+                (Compass::East, (1, 2)),
+                (Compass::West, (1, 0)),
+                (Compass::NorthWest, (0, 0)),
+            ],
+            grid.neighbors8((1, 1)).collect::<Vec<_>>()
+        );
+        assert_eq!(None, grid.neighbor((1, 2), Direction::E));
+        assert_eq!(Some((0, 2)), grid.offset((1, 0), (2, -1)));
+    }
+
+    #[test]
+    fn test_lines() {
+        let grid = digits(&["123", "456"]);
+        assert_eq!(
+            vec![&[1, 2, 3][..], &[4, 5, 6]],
+            grid.rows().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![vec![1, 4], vec![2, 5], vec![3, 6]],
+            collect(grid.columns())
+        );
+        assert_eq!(
+            vec![vec![3], vec![2, 6], vec![1, 5], vec![4]],
+            collect(grid.diagonals())
+        );
+        assert_eq!(
+            vec![vec![1], vec![2, 4], vec![3, 5], vec![6]],
+            collect(grid.anti_diagonals())
+        );
+        assert_eq!(
+            vec![&6, &2],
+            grid.line((1, 2), Compass::NorthWest).collect::<Vec<_>>()
+        );
+
+        let empty = Grid::new(2, 0, 0);
+        assert_eq!(0, empty.columns().count());
+        assert_eq!(0, empty.diagonals().count());
+        assert_eq!(0, empty.anti_diagonals().count());
+    }
+
+    #[test]
+    fn test_transpose_and_rotate() {
+        let grid = digits(&["123", "456"]);
+        assert_eq!(digits(&["14", "25", "36"]), grid.transpose());
+        assert_eq!(digits(&["41", "52", "63"]), grid.rotate_right());
+        assert_eq!(digits(&["36", "25", "14"]), grid.rotate_left());
+        assert_eq!(grid, grid.rotate_right().rotate_left());
+        assert_eq!(
+            grid.rotate_left(),
+            grid.rotate_right().rotate_right().rotate_right()
+        );
+    }
+}
@@ -1,4 +1,14 @@
-use crate::utils::{int_to_instruction, int_to_modes};
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt, fs,
+    path::Path,
+    sync::Arc,
+};
+
+use log::{debug, trace};
+
+use crate::error::{AocError, AocResult, parse_token};
+use crate::utils::PuzzleInput;
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Opcode {
@@ -34,6 +44,62 @@ impl TryFrom<isize> for Opcode {
     }
 }
 
+impl Opcode {
+    pub const ALL: [Opcode; 10] = [
+        Opcode::Add,
+        Opcode::Multiply,
+        Opcode::Store,
+        Opcode::Load,
+        Opcode::JumpIfTrue,
+        Opcode::JumpIfFalse,
+        Opcode::LessThan,
+        Opcode::Equals,
+        Opcode::RelativeBaseOffset,
+        Opcode::Halt,
+    ];
+
+    /// The number of parameters that follow the instruction.
+    pub fn param_count(self) -> usize {
+        match self {
+            Opcode::Add | Opcode::Multiply | Opcode::LessThan | Opcode::Equals => 3,
+            Opcode::JumpIfTrue | Opcode::JumpIfFalse => 2,
+            Opcode::Store | Opcode::Load | Opcode::RelativeBaseOffset => 1,
+            Opcode::Halt => 0,
+        }
+    }
+
+    /// The index of the parameter the instruction writes its result to.
+    pub fn destination(self) -> Option<usize> {
+        match self {
+            Opcode::Add | Opcode::Multiply | Opcode::LessThan | Opcode::Equals => Some(2),
+            Opcode::Store => Some(0),
+            _ => None,
+        }
+    }
+
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            Opcode::Add => "ADD",
+            Opcode::Multiply => "MUL",
+            Opcode::Store => "IN",
+            Opcode::Load => "OUT",
+            Opcode::JumpIfTrue => "JNZ",
+            Opcode::JumpIfFalse => "JZ",
+            Opcode::LessThan => "LT",
+            Opcode::Equals => "EQ",
+            Opcode::RelativeBaseOffset => "ARB",
+            Opcode::Halt => "HLT",
+        }
+    }
+
+    /// The opcode written as `mnemonic`, ignoring case.
+    pub fn from_mnemonic(mnemonic: &str) -> Option<Opcode> {
+        Opcode::ALL
+            .into_iter()
+            .find(|opcode| opcode.mnemonic().eq_ignore_ascii_case(mnemonic))
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Mode {
     Position = 0isize,
@@ -54,326 +120,851 @@ impl TryFrom<isize> for Mode {
     }
 }
 
-pub fn parse_intcode_input(input_file: &str) -> Vec<isize> {
-    let input = std::fs::read_to_string(input_file).expect("Failed to read file");
-    input
-        .trim()
-        .split(',')
-        .map(|s| s.trim().parse::<isize>().unwrap())
-        .collect()
-}
-
-pub fn run_intcode<'a>(
-    intcode: &'a mut [isize],
-    prog_counter: &mut usize,
-    relative_base: &mut isize,
-    inputs: &mut Vec<isize>,
-    outputs: &mut Vec<isize>,
-) -> &'a [isize] {
-    let instruction = int_to_instruction(intcode[*prog_counter]);
-    let modes = int_to_modes(intcode[*prog_counter]);
-    match Opcode::try_from(instruction) {
-        Ok(Opcode::Add) => {
-            println!("Add at position {}", *prog_counter);
-            calc_add(intcode, &modes, *prog_counter, *relative_base);
-            *prog_counter += 4;
-            run_intcode(intcode, prog_counter, relative_base, inputs, outputs)
-        }
-        Ok(Opcode::Multiply) => {
-            println!("Multiply at position {}", *prog_counter);
-            calc_multiply(intcode, &modes, *prog_counter, *relative_base);
-            *prog_counter += 4;
-            run_intcode(intcode, prog_counter, relative_base, inputs, outputs)
-        }
-        Ok(Opcode::Store) => {
-            if let Some(input) = inputs.pop() {
-                println!("Store at position {}, input: {:?}", *prog_counter, input);
-                calc_store(intcode, &modes, *prog_counter, *relative_base, input);
-                *prog_counter += 2;
-                run_intcode(intcode, prog_counter, relative_base, inputs, outputs)
-            } else {
-                println!(
-                    "No input provided for Store operation at position: {}",
-                    *prog_counter
-                );
-                intcode
-            }
-        }
-        Ok(Opcode::Load) => {
-            println!("Load at position {}", *prog_counter);
-            let output = calc_load(intcode, &modes, *prog_counter, *relative_base);
-            outputs.push(output);
-            *prog_counter += 2;
-            run_intcode(intcode, prog_counter, relative_base, inputs, outputs)
-        }
-        Ok(Opcode::JumpIfTrue) => {
-            let maybe_jump_counter =
-                calc_jump_if_true(intcode, &modes, *prog_counter, *relative_base);
-            *prog_counter = if let Some(jump_counter) = maybe_jump_counter {
-                jump_counter
-            } else {
-                *prog_counter + 3
-            };
-            println!("JumpIfTrue at position {}", *prog_counter);
-            run_intcode(intcode, prog_counter, relative_base, inputs, outputs)
-        }
-        Ok(Opcode::JumpIfFalse) => {
-            let maybe_jump_counter =
-                calc_jump_if_false(intcode, &modes, *prog_counter, *relative_base);
-            *prog_counter = if let Some(jump_counter) = maybe_jump_counter {
-                jump_counter
-            } else {
-                *prog_counter + 3
-            };
-            println!("JumpIfFalse at position {}", *prog_counter);
-            run_intcode(intcode, prog_counter, relative_base, inputs, outputs)
+pub fn parse_intcode_input(puzzle_input: impl PuzzleInput) -> AocResult<Vec<isize>> {
+    let input = puzzle_input.read_to_string()?;
+    let mut intcode = vec![];
+    for (line_idx, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
         }
-        Ok(Opcode::LessThan) => {
-            println!("LessThan at position {}", *prog_counter);
-            calc_less_than(intcode, &modes, *prog_counter, *relative_base);
-            *prog_counter += 4;
-            run_intcode(intcode, prog_counter, relative_base, inputs, outputs)
+        for token in line.trim_end().trim_end_matches(',').split(',') {
+            intcode.push(parse_token(line_idx, line, token)?);
         }
-        Ok(Opcode::Equals) => {
-            println!("Equals at position {}", *prog_counter);
-            calc_equals(intcode, &modes, *prog_counter, *relative_base);
-            *prog_counter += 4;
-            run_intcode(intcode, prog_counter, relative_base, inputs, outputs)
-        }
-        Ok(Opcode::RelativeBaseOffset) => {
-            let relative_base_offset =
-                calc_relative_base_offset(intcode, &modes, *prog_counter, *relative_base);
-            *relative_base += relative_base_offset;
-            println!(
-                "RelativeBaseOffset at position {}, new relative base {}",
-                *prog_counter, *relative_base
-            );
-            *prog_counter += 2;
-            run_intcode(intcode, prog_counter, relative_base, inputs, outputs)
-        }
-        Ok(Opcode::Halt) => {
-            println!("Halt at position {}", *prog_counter);
-            intcode
-        }
-        Err(_) => panic!("Unexpected Opcode {}", intcode[*prog_counter]),
     }
+    Ok(intcode)
 }
 
-pub fn calc_add(intcode: &mut [isize], modes: &[isize], prog_counter: usize, relative_base: isize) {
-    let (operand_lhs, operand_rhs, dest_param, dest_mode) =
-        get_two_operands_and_dest(intcode, modes, prog_counter, relative_base);
-    write_parameter(
-        intcode,
-        dest_param,
-        dest_mode,
-        relative_base,
-        operand_lhs + operand_rhs,
-    );
+/// Why [`IntcodeMachine::run`] stopped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    Halted,
+    /// Waiting on a `Store` with no input left; push one and run again.
+    NeedsInput,
+    Output(isize),
 }
 
-pub fn calc_multiply(
-    intcode: &mut [isize],
-    modes: &[isize],
-    prog_counter: usize,
-    relative_base: isize,
-) {
-    let (operand_lhs, operand_rhs, dest_param, dest_mode) =
-        get_two_operands_and_dest(intcode, modes, prog_counter, relative_base);
-    write_parameter(
-        intcode,
-        dest_param,
-        dest_mode,
-        relative_base,
-        operand_lhs * operand_rhs,
-    );
+/// What an extension instruction does, given access to its parameters. It
+/// returns a status to stop the machine with, like a built-in `OUT` or `HLT`.
+/// After `NeedsInput` the instruction runs again once there is input.
+pub type Execute = dyn Fn(&mut Execution<'_>) -> AocResult<Option<Status>> + Send + Sync;
+
+/// A custom instruction added to an [`InstructionSet`].
+#[derive(Clone)]
+pub struct Extension {
+    pub mnemonic: &'static str,
+    pub param_count: usize,
+    pub execute: Arc<Execute>,
 }
 
-pub fn calc_store(
-    intcode: &mut [isize],
-    modes: &[isize],
-    prog_counter: usize,
-    relative_base: isize,
-    input: isize,
-) {
-    let params = get_parameters(intcode, prog_counter, 1);
-    let param_1 = params[0];
-    let mode_1 = get_mode(*modes.first().unwrap_or(&0));
-    // write input into the destination parameter (param_1) according to its mode
-    write_parameter(intcode, param_1, mode_1, relative_base, input);
+impl Extension {
+    pub fn new(
+        mnemonic: &'static str,
+        param_count: usize,
+        execute: impl Fn(&mut Execution<'_>) -> AocResult<Option<Status>> + Send + Sync + 'static,
+    ) -> Self {
+        Extension {
+            mnemonic,
+            param_count,
+            execute: Arc::new(execute),
+        }
+    }
 }
 
-pub fn calc_load(
-    intcode: &mut [isize],
-    modes: &[isize],
-    prog_counter: usize,
-    relative_base: isize,
-) -> isize {
-    let params = get_parameters(intcode, prog_counter, 1);
-    let param_1 = params[0];
+/// Extensions are the same when they share their handler, as closures can't
+/// be compared.
+impl PartialEq for Extension {
+    fn eq(&self, other: &Self) -> bool {
+        self.mnemonic == other.mnemonic
+            && self.param_count == other.param_count
+            && Arc::ptr_eq(&self.execute, &other.execute)
+    }
+}
+
+impl Eq for Extension {}
 
-    let mode = get_mode(*modes.first().unwrap_or(&0));
-    get_parameter_value(intcode, param_1, mode, relative_base)
+/// The opcodes a machine accepts: a selection of the built-in ones plus any
+/// extensions. Anything else faults when the machine reaches it.
+#[derive(Clone, PartialEq, Eq)]
+pub struct InstructionSet {
+    /// Bit `n` is set when built-in opcode `n` is enabled.
+    builtins: u128,
+    extensions: HashMap<isize, Extension>,
 }
 
-pub fn calc_jump_if_true(
-    intcode: &mut [isize],
-    modes: &[isize],
-    prog_counter: usize,
-    relative_base: isize,
-) -> Option<usize> {
-    let params = get_parameters(intcode, prog_counter, 2);
-    let (param_1, param_2) = (params[0], params[1]);
-
-    let mode_1 = get_mode(*modes.first().unwrap_or(&0));
-    let operand_1 = get_parameter_value(intcode, param_1, mode_1, relative_base);
-    let mode_2 = get_mode(*modes.get(1).unwrap_or(&0));
-    let operand_2 = get_parameter_value(intcode, param_2, mode_2, relative_base);
-    if operand_1 != 0 {
-        Some(operand_2 as usize)
-    } else {
-        None
+impl Default for InstructionSet {
+    /// Every built-in opcode and no extensions.
+    fn default() -> Self {
+        Self::only(&Opcode::ALL)
     }
 }
 
-pub fn calc_jump_if_false(
-    intcode: &mut [isize],
-    modes: &[isize],
-    prog_counter: usize,
-    relative_base: isize,
-) -> Option<usize> {
-    let params = get_parameters(intcode, prog_counter, 2);
-    let (param_1, param_2) = (params[0], params[1]);
-
-    let mode_1 = get_mode(*modes.first().unwrap_or(&0));
-    let operand_1 = get_parameter_value(intcode, param_1, mode_1, relative_base);
-    let mode_2 = get_mode(*modes.get(1).unwrap_or(&0));
-    let operand_2 = get_parameter_value(intcode, param_2, mode_2, relative_base);
-    if operand_1 == 0 {
-        Some(operand_2 as usize)
-    } else {
-        None
+impl InstructionSet {
+    /// Just the given built-in opcodes, e.g. the three from 2019 day 2.
+    pub fn only(opcodes: &[Opcode]) -> Self {
+        InstructionSet {
+            builtins: opcodes
+                .iter()
+                .fold(0, |bits, &opcode| bits | 1 << opcode as isize),
+            extensions: HashMap::new(),
+        }
+    }
+
+    /// Adds an extension under an opcode that no built-in or other extension
+    /// uses. Fails if the opcode is taken or out of range, or the extension has
+    /// more than three parameters.
+    pub fn with_extension(mut self, opcode: isize, extension: Extension) -> AocResult<Self> {
+        if !(1..100).contains(&opcode)
+            || Opcode::try_from(opcode).is_ok()
+            || self.extensions.contains_key(&opcode)
+        {
+            return Err(AocError::usage(format!(
+                "opcode {opcode} isn't free for {}",
+                extension.mnemonic
+            )));
+        }
+        if extension.param_count > 3 {
+            return Err(AocError::usage(format!(
+                "{} has more than three parameters",
+                extension.mnemonic
+            )));
+        }
+        self.extensions.insert(opcode, extension);
+        Ok(self)
+    }
+
+    fn allows(&self, opcode: Opcode) -> bool {
+        self.builtins & 1 << opcode as isize != 0
     }
 }
 
-pub fn calc_less_than(
-    intcode: &mut [isize],
-    modes: &[isize],
-    prog_counter: usize,
-    relative_base: isize,
-) {
-    let (operand_lhs, operand_rhs, dest_param, dest_mode) =
-        get_two_operands_and_dest(intcode, modes, prog_counter, relative_base);
-    let result = if operand_lhs < operand_rhs { 1 } else { 0 };
-    write_parameter(intcode, dest_param, dest_mode, relative_base, result);
+impl fmt::Debug for InstructionSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let builtins: Vec<&str> = Opcode::ALL
+            .into_iter()
+            .filter(|&opcode| self.allows(opcode))
+            .map(Opcode::mnemonic)
+            .collect();
+        let mut extensions: Vec<(isize, &str)> = self
+            .extensions
+            .iter()
+            .map(|(&opcode, extension)| (opcode, extension.mnemonic))
+            .collect();
+        extensions.sort_unstable();
+        f.debug_struct("InstructionSet")
+            .field("builtins", &builtins)
+            .field("extensions", &extensions)
+            .finish()
+    }
 }
 
-pub fn calc_equals(
-    intcode: &mut [isize],
-    modes: &[isize],
-    prog_counter: usize,
-    relative_base: isize,
-) {
-    let (operand_lhs, operand_rhs, dest_param, dest_mode) =
-        get_two_operands_and_dest(intcode, modes, prog_counter, relative_base);
-    let result = if operand_lhs == operand_rhs { 1 } else { 0 };
-    write_parameter(intcode, dest_param, dest_mode, relative_base, result);
+/// An extension instruction in the middle of executing, through which it
+/// reads and writes its parameters.
+pub struct Execution<'a> {
+    machine: &'a mut IntcodeMachine,
+    modes: [isize; 3],
+    param_count: usize,
+    jump: Option<usize>,
 }
 
-pub fn calc_relative_base_offset(
-    intcode: &mut [isize],
-    modes: &[isize],
+impl Execution<'_> {
+    /// The value of the `offset`th parameter, counting from 1, resolved
+    /// according to its mode.
+    pub fn read(&self, offset: usize) -> AocResult<isize> {
+        self.check_offset(offset)?;
+        self.machine.operand(&self.modes, offset)
+    }
+
+    /// Writes `value` to where the `offset`th parameter points.
+    pub fn write(&mut self, offset: usize, value: isize) -> AocResult<()> {
+        self.check_offset(offset)?;
+        self.machine.write(&self.modes, offset, value)
+    }
+
+    /// Continues at `target` instead of the next instruction.
+    pub fn jump(&mut self, target: isize) -> AocResult<()> {
+        self.jump = Some(self.machine.check_address(target)?);
+        Ok(())
+    }
+
+    /// Takes the next queued input, if there is one.
+    pub fn pop_input(&mut self) -> Option<isize> {
+        self.machine.inputs.pop_front()
+    }
+
+    pub fn relative_base(&self) -> isize {
+        self.machine.relative_base
+    }
+
+    fn check_offset(&self, offset: usize) -> AocResult<()> {
+        if !(1..=self.param_count).contains(&offset) {
+            return Err(self
+                .machine
+                .fault(format!("extension has no parameter {offset}")));
+        }
+        Ok(())
+    }
+}
+
+/// Programs can address this many words of memory before a read or write is
+/// reported as out of range.
+pub const MEMORY_LIMIT: usize = 1 << 24;
+
+/// An Intcode computer owning its memory and registers. Memory grows as the
+/// program writes past its end, and reads past the end see zeroes.
+///
+/// Memory is shared with clones and snapshots until one of them writes to it,
+/// so forking a machine at a decision point is cheap.
+#[derive(Clone, Debug)]
+pub struct IntcodeMachine {
+    memory: Arc<Vec<isize>>,
     prog_counter: usize,
     relative_base: isize,
-) -> isize {
-    let params = get_parameters(intcode, prog_counter, 1);
-    let param_1 = params[0];
-    let mode = get_mode(*modes.first().unwrap_or(&0));
-    get_parameter_value(intcode, param_1, mode, relative_base)
+    inputs: VecDeque<isize>,
+    instruction_set: Arc<InstructionSet>,
+    /// The address the last executed instruction wrote to.
+    last_write: Option<usize>,
+    /// Whether an extension halted the program, which `is_halted` can't tell
+    /// from the instruction alone.
+    halted_by_extension: bool,
 }
 
-// Helper function to extract mode
-fn get_mode(mode_val: isize) -> Mode {
-    match Mode::try_from(mode_val) {
-        Ok(mode) => mode,
-        Err(_) => panic!("Unexpected Mode {}", mode_val),
+impl IntcodeMachine {
+    pub fn new(intcode: Vec<isize>) -> Self {
+        IntcodeMachine {
+            memory: Arc::new(intcode),
+            prog_counter: 0,
+            relative_base: 0,
+            inputs: VecDeque::new(),
+            instruction_set: Arc::default(),
+            last_write: None,
+            halted_by_extension: false,
+        }
     }
-}
 
-// Helper function to extract parameters from intcode at given offsets
-fn get_parameters(intcode: &[isize], prog_counter: usize, count: usize) -> Vec<isize> {
-    (1..=count)
-        .map(|offset| intcode[prog_counter + offset])
-        .collect()
-}
+    /// Runs programs with `instruction_set` instead of the built-in opcodes.
+    pub fn with_instruction_set(mut self, instruction_set: InstructionSet) -> Self {
+        self.instruction_set = Arc::new(instruction_set);
+        self
+    }
 
-// Helper function to resolve parameter value based on mode
-fn get_parameter_value(intcode: &[isize], param: isize, mode: Mode, relative_base: isize) -> isize {
-    match mode {
-        Mode::Position => {
-            let index = param as usize;
-            if let Some(param_val) = intcode.get(index) {
-                println!("Position mode. Index {}. Param Val {}", index, param_val);
-                *param_val
-            } else {
-                panic!("Requesting index {} larger than memory!", index);
+    pub fn memory(&self) -> &[isize] {
+        &self.memory
+    }
+
+    pub fn prog_counter(&self) -> usize {
+        self.prog_counter
+    }
+
+    pub fn relative_base(&self) -> isize {
+        self.relative_base
+    }
+
+    /// The address the last executed instruction wrote to, if it wrote to
+    /// memory at all.
+    pub fn last_write(&self) -> Option<usize> {
+        self.last_write
+    }
+
+    /// Queues an input, to be read after any already queued.
+    pub fn push_input(&mut self, input: isize) {
+        self.inputs.push_back(input);
+    }
+
+    /// Queues several inputs, to be read in order.
+    pub fn push_inputs(&mut self, inputs: impl IntoIterator<Item = isize>) {
+        self.inputs.extend(inputs);
+    }
+
+    /// Captures the complete state of the machine, including queued inputs.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            memory: Arc::clone(&self.memory),
+            prog_counter: self.prog_counter,
+            relative_base: self.relative_base,
+            inputs: self.inputs.clone(),
+            instruction_set: Arc::clone(&self.instruction_set),
+        }
+    }
+
+    /// Puts the machine back into the state captured by `snapshot`.
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        self.memory = Arc::clone(&snapshot.memory);
+        self.prog_counter = snapshot.prog_counter;
+        self.relative_base = snapshot.relative_base;
+        self.inputs = snapshot.inputs.clone();
+        self.instruction_set = Arc::clone(&snapshot.instruction_set);
+        self.last_write = None;
+        self.halted_by_extension = false;
+    }
+
+    pub fn is_halted(&self) -> bool {
+        if self.halted_by_extension {
+            return true;
+        }
+        let instruction = self.memory.get(self.prog_counter).copied().unwrap_or(0);
+        Opcode::try_from(split_instruction(instruction).0) == Ok(Opcode::Halt)
+    }
+
+    /// Runs until the program halts, needs an input it doesn't have, or
+    /// produces an output.
+    pub fn run(&mut self) -> AocResult<Status> {
+        loop {
+            if let Some(status) = self.step()? {
+                return Ok(status);
             }
         }
-        Mode::Immediate => {
-            println!("Immediate mode. Param Val {}", param);
-            param
+    }
+
+    /// Runs until the program halts or needs an input, collecting its outputs.
+    pub fn run_until_blocked(&mut self) -> AocResult<Vec<isize>> {
+        let mut outputs = vec![];
+        while let Status::Output(output) = self.run()? {
+            outputs.push(output);
         }
-        Mode::Relative => {
-            let index = param + relative_base;
-            if let Some(param_val) = intcode.get(index as usize) {
-                println!("Relative mode. Index {}. Param Val {}", index, param_val);
-                *param_val
-            } else {
-                panic!("Requesting index {} larger than memory!", index as usize);
+        Ok(outputs)
+    }
+
+    /// Executes a single instruction, returning `None` if the program carries on.
+    pub fn step(&mut self) -> AocResult<Option<Status>> {
+        let prog_counter = self.prog_counter;
+        self.last_write = None;
+        let instruction = self.read(prog_counter as isize)?;
+        let (code, modes) = split_instruction(instruction);
+        let opcode = match Opcode::try_from(code) {
+            Ok(opcode) if self.instruction_set.allows(opcode) => opcode,
+            _ => return self.execute_extension(code, modes),
+        };
+        match opcode {
+            Opcode::Add => {
+                trace!("Add at position {}", prog_counter);
+                let result = self
+                    .operand(&modes, 1)?
+                    .checked_add(self.operand(&modes, 2)?);
+                let result = self.no_overflow(result)?;
+                self.write(&modes, 3, result)?;
+                self.prog_counter += 4;
             }
+            Opcode::Multiply => {
+                trace!("Multiply at position {}", prog_counter);
+                let result = self
+                    .operand(&modes, 1)?
+                    .checked_mul(self.operand(&modes, 2)?);
+                let result = self.no_overflow(result)?;
+                self.write(&modes, 3, result)?;
+                self.prog_counter += 4;
+            }
+            Opcode::Store => {
+                let Some(input) = self.inputs.pop_front() else {
+                    debug!(
+                        "No input provided for Store operation at position: {}",
+                        prog_counter
+                    );
+                    return Ok(Some(Status::NeedsInput));
+                };
+                trace!("Store at position {}, input: {:?}", prog_counter, input);
+                self.write(&modes, 1, input)?;
+                self.prog_counter += 2;
+            }
+            Opcode::Load => {
+                trace!("Load at position {}", prog_counter);
+                let output = self.operand(&modes, 1)?;
+                self.prog_counter += 2;
+                return Ok(Some(Status::Output(output)));
+            }
+            Opcode::JumpIfTrue => {
+                let jump = self.operand(&modes, 1)? != 0;
+                self.jump_if(jump, &modes)?;
+                trace!("JumpIfTrue at position {}", self.prog_counter);
+            }
+            Opcode::JumpIfFalse => {
+                let jump = self.operand(&modes, 1)? == 0;
+                self.jump_if(jump, &modes)?;
+                trace!("JumpIfFalse at position {}", self.prog_counter);
+            }
+            Opcode::LessThan => {
+                trace!("LessThan at position {}", prog_counter);
+                let result = self.operand(&modes, 1)? < self.operand(&modes, 2)?;
+                self.write(&modes, 3, result as isize)?;
+                self.prog_counter += 4;
+            }
+            Opcode::Equals => {
+                trace!("Equals at position {}", prog_counter);
+                let result = self.operand(&modes, 1)? == self.operand(&modes, 2)?;
+                self.write(&modes, 3, result as isize)?;
+                self.prog_counter += 4;
+            }
+            Opcode::RelativeBaseOffset => {
+                let relative_base = self.relative_base.checked_add(self.operand(&modes, 1)?);
+                self.relative_base = self.no_overflow(relative_base)?;
+                trace!(
+                    "RelativeBaseOffset at position {}, new relative base {}",
+                    prog_counter, self.relative_base
+                );
+                self.prog_counter += 2;
+            }
+            Opcode::Halt => {
+                trace!("Halt at position {}", prog_counter);
+                return Ok(Some(Status::Halted));
+            }
+        }
+        Ok(None)
+    }
+
+    fn execute_extension(&mut self, code: isize, modes: [isize; 3]) -> AocResult<Option<Status>> {
+        let Some(extension) = self.instruction_set.extensions.get(&code).cloned() else {
+            return Err(self.fault(format!("unknown opcode {code}")));
+        };
+        trace!("{} at position {}", extension.mnemonic, self.prog_counter);
+        let mut execution = Execution {
+            machine: self,
+            modes,
+            param_count: extension.param_count,
+            jump: None,
+        };
+        let status = (extension.execute)(&mut execution)?;
+        let jump = execution.jump;
+        // Like the built-ins, waiting for input or halting leaves the program
+        // counter on the instruction
+        if matches!(status, Some(Status::NeedsInput | Status::Halted)) {
+            self.halted_by_extension = status == Some(Status::Halted);
+            return Ok(status);
+        }
+        self.prog_counter = jump.unwrap_or(self.prog_counter + 1 + extension.param_count);
+        Ok(status)
+    }
+
+    fn fault(&self, message: String) -> AocError {
+        AocError::Intcode {
+            prog_counter: self.prog_counter,
+            address: None,
+            message,
+        }
+    }
+
+    fn no_overflow(&self, result: Option<isize>) -> AocResult<isize> {
+        result.ok_or_else(|| self.fault("arithmetic overflow".to_string()))
+    }
+
+    fn check_address(&self, address: isize) -> AocResult<usize> {
+        if address < 0 || address as usize >= MEMORY_LIMIT {
+            return Err(AocError::Intcode {
+                prog_counter: self.prog_counter,
+                address: Some(address),
+                message: format!("address {address} is out of range"),
+            });
+        }
+        Ok(address as usize)
+    }
+
+    fn read(&self, address: isize) -> AocResult<isize> {
+        let index = self.check_address(address)?;
+        Ok(self.memory.get(index).copied().unwrap_or(0))
+    }
+
+    fn mode(&self, modes: &[isize; 3], offset: usize) -> AocResult<Mode> {
+        let mode = modes[offset - 1];
+        Mode::try_from(mode)
+            .map_err(|_| self.fault(format!("parameter {offset} has unknown mode {mode}")))
+    }
+
+    /// The raw value of the current instruction's `offset`th parameter.
+    fn parameter(&self, offset: usize) -> AocResult<isize> {
+        self.read((self.prog_counter + offset) as isize)
+    }
+
+    /// The address the current instruction's `offset`th parameter refers to.
+    fn parameter_address(&self, modes: &[isize; 3], offset: usize) -> AocResult<isize> {
+        let param = self.parameter(offset)?;
+        match self.mode(modes, offset)? {
+            Mode::Position => Ok(param),
+            Mode::Relative => self.no_overflow(param.checked_add(self.relative_base)),
+            Mode::Immediate => Err(self.fault(format!(
+                "parameter {offset} is a destination in immediate mode"
+            ))),
         }
     }
+
+    /// The value of the current instruction's `offset`th parameter, resolved
+    /// according to its mode.
+    fn operand(&self, modes: &[isize; 3], offset: usize) -> AocResult<isize> {
+        if self.mode(modes, offset)? == Mode::Immediate {
+            let param = self.parameter(offset)?;
+            trace!("Immediate mode. Param Val {}", param);
+            return Ok(param);
+        }
+        let address = self.parameter_address(modes, offset)?;
+        let value = self.read(address)?;
+        trace!("Index {}. Param Val {}", address, value);
+        Ok(value)
+    }
+
+    /// Writes `value` to where the current instruction's `offset`th parameter
+    /// points, growing memory to fit.
+    fn write(&mut self, modes: &[isize; 3], offset: usize, value: isize) -> AocResult<()> {
+        let address = self.parameter_address(modes, offset)?;
+        let index = self.check_address(address)?;
+        let memory = Arc::make_mut(&mut self.memory);
+        if index >= memory.len() {
+            memory.resize(index + 1, 0);
+        }
+        memory[index] = value;
+        self.last_write = Some(index);
+        Ok(())
+    }
+
+    fn jump_if(&mut self, jump: bool, modes: &[isize; 3]) -> AocResult<()> {
+        if !jump {
+            self.prog_counter += 3;
+            return Ok(());
+        }
+        let target = self.operand(modes, 2)?;
+        self.prog_counter = self.check_address(target)?;
+        Ok(())
+    }
 }
 
-// Helper to read two operands and the destination parameter (and its mode)
-fn get_two_operands_and_dest(
-    intcode: &[isize],
-    modes: &[isize],
+/// The complete state of an [`IntcodeMachine`], which can be restored later or
+/// saved to disk to resume a long run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Snapshot {
+    memory: Arc<Vec<isize>>,
     prog_counter: usize,
     relative_base: isize,
-) -> (isize, isize, isize, Mode) {
-    let params = get_parameters(intcode, prog_counter, 3);
-    let (param_1, param_2, param_3) = (params[0], params[1], params[2]);
-
-    let mode_lhs = get_mode(*modes.first().unwrap_or(&0));
-    let operand_lhs = get_parameter_value(intcode, param_1, mode_lhs, relative_base);
-    let mode_rhs = get_mode(*modes.get(1).unwrap_or(&0));
-    let operand_rhs = get_parameter_value(intcode, param_2, mode_rhs, relative_base);
-    let dest_mode = get_mode(*modes.get(2).unwrap_or(&0));
-    (operand_lhs, operand_rhs, param_3, dest_mode)
+    inputs: VecDeque<isize>,
+    instruction_set: Arc<InstructionSet>,
 }
 
-// Helper to write a value into a parameter respecting its mode
-fn write_parameter(
-    intcode: &mut [isize],
-    param: isize,
-    mode: Mode,
-    relative_base: isize,
-    value: isize,
-) {
-    match mode {
-        Mode::Position => {
-            if param >= 0 {
-                intcode[param as usize] = value;
-            }
+impl From<Snapshot> for IntcodeMachine {
+    fn from(snapshot: Snapshot) -> Self {
+        IntcodeMachine {
+            memory: snapshot.memory,
+            prog_counter: snapshot.prog_counter,
+            relative_base: snapshot.relative_base,
+            inputs: snapshot.inputs,
+            instruction_set: snapshot.instruction_set,
+            last_write: None,
+            halted_by_extension: false,
         }
-        Mode::Immediate => {
-            // immediate mode should not be used as a destination; ignore
+    }
+}
+
+impl Snapshot {
+    /// Writes the snapshot to `path`, to be read back with [`parse_snapshot`].
+    /// Extensions can't be saved, so only the state of the machine is.
+    pub fn save(&self, path: &Path) -> AocResult<()> {
+        let io_error = |source| AocError::Io {
+            file: path.display().to_string(),
+            source,
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(io_error)?;
         }
-        Mode::Relative => {
-            let index = param + relative_base;
-            if index >= 0 {
-                intcode[index as usize] = value;
+        fs::write(path, self.to_string()).map_err(io_error)
+    }
+}
+
+fn join(values: impl IntoIterator<Item = isize>) -> String {
+    let values: Vec<String> = values.into_iter().map(|v| v.to_string()).collect();
+    values.join(",")
+}
+
+impl fmt::Display for Snapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "prog_counter: {}", self.prog_counter)?;
+        writeln!(f, "relative_base: {}", self.relative_base)?;
+        writeln!(f, "inputs: {}", join(self.inputs.iter().copied()))?;
+        writeln!(f, "memory: {}", join(self.memory.iter().copied()))
+    }
+}
+
+/// Parses a snapshot written by [`Snapshot::save`], one `key: value` line for
+/// each register, the queued inputs and memory. It restores with the built-in
+/// opcodes, so set any other instruction set on the restored machine.
+pub fn parse_snapshot(puzzle_input: impl PuzzleInput) -> AocResult<Snapshot> {
+    let input = puzzle_input.read_to_string()?;
+    let mut prog_counter = None;
+    let mut relative_base = None;
+    let mut inputs = None;
+    let mut memory = None;
+    for (line_idx, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            return Err(AocError::parse(line_idx, 0, "expected \"key: value\""));
+        };
+        let values = || -> AocResult<Vec<isize>> {
+            if value.trim().is_empty() {
+                return Ok(vec![]);
+            }
+            value
+                .split(',')
+                .map(|token| parse_token(line_idx, line, token))
+                .collect()
+        };
+        match key.trim() {
+            "prog_counter" => prog_counter = Some(parse_token(line_idx, line, value)?),
+            "relative_base" => relative_base = Some(parse_token(line_idx, line, value)?),
+            "inputs" => inputs = Some(values()?.into()),
+            "memory" => memory = Some(Arc::new(values()?)),
+            key => {
+                return Err(AocError::parse_token(
+                    line_idx,
+                    line,
+                    key,
+                    format!("unknown key {key:?}"),
+                ));
             }
         }
     }
+
+    let missing = |key: &str| AocError::invalid(format!("snapshot is missing {key}"));
+    Ok(Snapshot {
+        memory: memory.ok_or_else(|| missing("memory"))?,
+        prog_counter: prog_counter.ok_or_else(|| missing("prog_counter"))?,
+        relative_base: relative_base.ok_or_else(|| missing("relative_base"))?,
+        inputs: inputs.ok_or_else(|| missing("inputs"))?,
+        instruction_set: Arc::default(),
+    })
+}
+
+/// Splits an instruction into its opcode and the modes of its parameters
+/// arithmetically, as this runs for every instruction executed.
+pub(crate) fn split_instruction(instruction: isize) -> (isize, [isize; 3]) {
+    let modes = [
+        instruction / 100 % 10,
+        instruction / 1000 % 10,
+        instruction / 10000 % 10,
+    ];
+    (instruction % 100, modes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_statuses() {
+        // Echoes one input, then halts
+        let mut machine = IntcodeMachine::new(vec![3, 9, 4, 9, 104, 7, 99, 0, 0, 0]);
+        assert_eq!(Status::NeedsInput, machine.run().unwrap());
+        assert_eq!(0, machine.prog_counter());
+
+        machine.push_input(42);
+        assert_eq!(Status::Output(42), machine.run().unwrap());
+        assert_eq!(Status::Output(7), machine.run().unwrap());
+        assert_eq!(Status::Halted, machine.run().unwrap());
+        assert!(machine.is_halted());
+        assert_eq!(Status::Halted, machine.run().unwrap());
+    }
+
+    #[test]
+    fn test_split_instruction() {
+        assert_eq!((2, [0, 1, 0]), split_instruction(1002));
+        assert_eq!((1, [2, 0, 1]), split_instruction(10201));
+        assert_eq!((99, [0, 0, 0]), split_instruction(99));
+    }
+
+    #[test]
+    fn test_inputs_are_read_in_order() {
+        // Outputs its two inputs in the order they were read
+        let mut machine = IntcodeMachine::new(vec![3, 11, 3, 12, 4, 11, 4, 12, 99, 0, 0, 0, 0]);
+        machine.push_inputs([1, 2]);
+        assert_eq!(vec![1, 2], machine.run_until_blocked().unwrap());
+    }
+
+    #[test]
+    fn test_run_long_loop() {
+        // Counts memory[14] up to 100000 without growing the stack
+        let intcode = vec![
+            1001, 14, 1, 14, 1007, 14, 100000, 15, 1005, 15, 0, 4, 14, 99, 0, 0,
+        ];
+        let mut machine = IntcodeMachine::new(intcode);
+        assert_eq!(vec![100000], machine.run_until_blocked().unwrap());
+    }
+
+    #[test]
+    fn test_memory_grows_on_demand() {
+        // Writes 7 to address 1000 and reads it back
+        let mut machine = IntcodeMachine::new(vec![1101, 3, 4, 1000, 4, 1000, 4, 2000, 99]);
+        assert_eq!(vec![7, 0], machine.run_until_blocked().unwrap());
+        assert_eq!(1001, machine.memory().len());
+    }
+
+    #[test]
+    fn test_snapshot_and_restore() {
+        // Outputs double its input
+        let mut machine = IntcodeMachine::new(vec![3, 9, 1002, 9, 2, 9, 4, 9, 99, 0]);
+        let snapshot = machine.snapshot();
+        assert!(Arc::ptr_eq(&machine.memory, &snapshot.memory));
+
+        machine.push_input(5);
+        assert_eq!(vec![10], machine.run_until_blocked().unwrap());
+        assert!(!Arc::ptr_eq(&machine.memory, &snapshot.memory));
+        assert_eq!(0, snapshot.memory[9]);
+
+        machine.restore(&snapshot);
+        machine.push_input(21);
+        assert_eq!(vec![42], machine.run_until_blocked().unwrap());
+
+        let mut fork = IntcodeMachine::from(snapshot);
+        fork.push_input(1);
+        assert_eq!(vec![2], fork.run_until_blocked().unwrap());
+    }
+
+    #[test]
+    fn test_snapshot_round_trips_through_disk() {
+        let mut machine = IntcodeMachine::new(vec![109, 3, 3, 9, 99]);
+        machine.push_inputs([-4, 7]);
+        machine.step().unwrap();
+        machine.step().unwrap();
+        let snapshot = machine.snapshot();
+        assert_eq!(
+            "prog_counter: 4\nrelative_base: 3\ninputs: 7\nmemory: 109,3,3,9,99,0,0,0,0,-4\n",
+            snapshot.to_string()
+        );
+
+        let path = std::env::temp_dir().join(format!("aoc_snapshot_{}.txt", std::process::id()));
+        snapshot.save(&path).unwrap();
+        assert_eq!(snapshot, parse_snapshot(&path).unwrap());
+        std::fs::remove_file(&path).unwrap();
+
+        let err = parse_snapshot(crate::utils::Contents("prog_counter: 4\nregs: 1")).unwrap_err();
+        assert_eq!("<input>:2:1: unknown key \"regs\"", err.to_string());
+    }
+
+    #[test]
+    fn test_unknown_opcodes_and_modes_are_faults() {
+        let mut machine = IntcodeMachine::new(vec![104, 1, 42, 99]);
+        assert_eq!(Status::Output(1), machine.run().unwrap());
+        let err = machine.run().unwrap_err();
+        assert_eq!("intcode fault at 2: unknown opcode 42", err.to_string());
+
+        let mut machine = IntcodeMachine::new(vec![301, 0, 0, 0, 99]);
+        let err = machine.run().unwrap_err();
+        assert_eq!(
+            "intcode fault at 0: parameter 1 has unknown mode 3",
+            err.to_string()
+        );
+
+        let instruction_set = InstructionSet::only(&[Opcode::Add, Opcode::Multiply, Opcode::Halt]);
+        let mut machine =
+            IntcodeMachine::new(vec![1, 0, 0, 0, 3, 0, 99]).with_instruction_set(instruction_set);
+        let err = machine.run().unwrap_err();
+        assert_eq!("intcode fault at 4: unknown opcode 3", err.to_string());
+    }
+
+    #[test]
+    fn test_extension_opcode_clash() {
+        let nop = || Extension::new("NOP", 0, |_| Ok(None));
+        let err = InstructionSet::default()
+            .with_extension(1, nop())
+            .unwrap_err();
+        assert!(matches!(err, AocError::Usage { .. }));
+        assert_eq!("opcode 1 isn't free for NOP", err.to_string());
+
+        let instruction_set = InstructionSet::default().with_extension(20, nop()).unwrap();
+        assert!(instruction_set.clone().with_extension(20, nop()).is_err());
+        assert!(instruction_set.clone().with_extension(100, nop()).is_err());
+        assert!(
+            instruction_set
+                .with_extension(21, Extension::new("WIDE", 4, |_| Ok(None)))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_halting_extension_stays_halted() {
+        let instruction_set = InstructionSet::default()
+            .with_extension(50, Extension::new("STOP", 0, |_| Ok(Some(Status::Halted))))
+            .unwrap();
+        let mut machine =
+            IntcodeMachine::new(vec![104, 1, 50, 104, 2, 99]).with_instruction_set(instruction_set);
+        assert!(!machine.is_halted());
+        assert_eq!(vec![1], machine.run_until_blocked().unwrap());
+        assert!(machine.is_halted());
+        assert_eq!(2, machine.prog_counter());
+        assert_eq!(Status::Halted, machine.run().unwrap());
+        assert_eq!(2, machine.prog_counter());
+    }
+
+    #[test]
+    fn test_extension_opcodes() {
+        let port = Arc::new(std::sync::Mutex::new(vec![]));
+        let sent = Arc::clone(&port);
+        let instruction_set = InstructionSet::default()
+            .with_extension(
+                20,
+                Extension::new("SEND", 1, move |execution| {
+                    sent.lock().unwrap().push(execution.read(1)?);
+                    Ok(None)
+                }),
+            )
+            .unwrap()
+            .with_extension(
+                21,
+                Extension::new("SUM", 2, |execution| {
+                    Ok(Some(Status::Output(
+                        execution.read(1)? + execution.read(2)?,
+                    )))
+                }),
+            )
+            .unwrap();
+
+        // Sends 5 to the port, outputs 2 + 3, then jumps over a bad opcode
+        let intcode = vec![120, 5, 1121, 2, 3, 1105, 1, 9, 42, 99];
+        let mut machine = IntcodeMachine::new(intcode).with_instruction_set(instruction_set);
+        let snapshot = machine.snapshot();
+        assert_eq!(vec![5], machine.run_until_blocked().unwrap());
+        assert!(machine.is_halted());
+        assert_eq!(vec![5], *port.lock().unwrap());
+
+        // Restoring keeps the extensions
+        machine.restore(&snapshot);
+        assert_eq!(vec![5], machine.run_until_blocked().unwrap());
+        let mut fork = IntcodeMachine::from(snapshot);
+        assert_eq!(vec![5], fork.run_until_blocked().unwrap());
+        assert_eq!(vec![5, 5, 5], *port.lock().unwrap());
+    }
+
+    #[test]
+    fn test_overflow_is_a_fault() {
+        let mut machine = IntcodeMachine::new(vec![1102, isize::MAX, 2, 0, 99]);
+        let err = machine.run().unwrap_err();
+        assert!(matches!(err, AocError::Intcode { address: None, .. }));
+        assert_eq!("intcode fault at 0: arithmetic overflow", err.to_string());
+    }
+
+    #[test]
+    fn test_negative_address() {
+        // Adjusts the relative base to -5, then writes relative to it
+        let mut machine = IntcodeMachine::new(vec![109, -5, 21101, 1, 1, 2, 99]);
+        let err = machine.run_until_blocked().unwrap_err();
+        assert!(matches!(
+            err,
+            AocError::Intcode {
+                prog_counter: 2,
+                address: Some(-3),
+                ..
+            }
+        ));
+        assert_eq!(
+            "intcode fault at 2: address -3 is out of range",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_address_beyond_limit() {
+        let address = MEMORY_LIMIT as isize;
+        let mut machine = IntcodeMachine::new(vec![4, address, 99]);
+        assert!(matches!(
+            machine.run_until_blocked().unwrap_err(),
+            AocError::Intcode {
+                prog_counter: 0,
+                address: Some(faulted),
+                ..
+            } if faulted == address
+        ));
+    }
 }
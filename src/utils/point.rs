@@ -0,0 +1,369 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::utils::{Compass, Direction, Pos};
+
+/// An integer type usable as a point coordinate.
+pub trait Coord:
+    Copy + Ord + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + fmt::Debug
+{
+    const ZERO: Self;
+
+    fn as_f64(self) -> f64;
+
+    /// The coordinate moved by `delta`, unless that leaves the type's range.
+    fn checked_add_signed(self, delta: isize) -> Option<Self>;
+
+    fn to_usize(self) -> Option<usize>;
+
+    fn from_usize(value: usize) -> Option<Self>;
+}
+
+macro_rules! impl_coord {
+    ($($t:ty),*) => {
+        $(
+            impl Coord for $t {
+                const ZERO: Self = 0;
+
+                fn as_f64(self) -> f64 {
+                    self as f64
+                }
+
+                fn checked_add_signed(self, delta: isize) -> Option<Self> {
+                    let moved = i128::try_from(self).ok()? + delta as i128;
+                    Self::try_from(moved).ok()
+                }
+
+                fn to_usize(self) -> Option<usize> {
+                    usize::try_from(self).ok()
+                }
+
+                fn from_usize(value: usize) -> Option<Self> {
+                    Self::try_from(value).ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_coord!(i32, i64, isize, u32, u64, usize);
+
+/// The difference between `a` and `b` as a non-negative amount, which works
+/// for unsigned coordinates too.
+fn abs_diff<T: Coord>(a: T, b: T) -> T {
+    if a > b { a - b } else { b - a }
+}
+
+/// Expands to `$t`, for spelling out a tuple with one `$t` per coordinate.
+macro_rules! per_coord {
+    ($coord:ident, $t:ty) => {
+        $t
+    };
+}
+
+/// Declares a point type with the given coordinates, along with everything
+/// that doesn't depend on how many there are.
+macro_rules! point {
+    ($(#[$meta:meta])* $name:ident { $($coord:ident),+ }, $tuple:ty) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub struct $name<T> {
+            $(pub $coord: T,)+
+        }
+
+        impl<T: Coord> $name<T> {
+            pub const ORIGIN: Self = $name { $($coord: T::ZERO,)+ };
+
+            pub fn new($($coord: T),+) -> Self {
+                $name { $($coord,)+ }
+            }
+
+            /// The point `delta` away, unless that leaves the coordinate
+            /// type's range, e.g. going below zero with `usize`.
+            pub fn offset(self, ($($coord),+): ($(per_coord!($coord, isize)),+)) -> Option<Self> {
+                Some($name { $($coord: self.$coord.checked_add_signed($coord)?,)+ })
+            }
+
+            /// The points one step away along each axis, leaving out any
+            /// that fall outside the coordinate type's range.
+            pub fn neighbors(self) -> impl Iterator<Item = Self> {
+                let mut neighbors = Vec::new();
+                $(
+                    for delta in [-1, 1] {
+                        if let Some($coord) = self.$coord.checked_add_signed(delta) {
+                            neighbors.push($name { $coord, ..self });
+                        }
+                    }
+                )+
+                neighbors.into_iter()
+            }
+
+            /// The coordinates as indices in declaration order, if none is
+            /// negative or too large.
+            pub fn to_indices(self) -> Option<($(per_coord!($coord, usize)),+)> {
+                Some(($(self.$coord.to_usize()?),+))
+            }
+
+            /// The point at the given indices, if they fit the coordinate
+            /// type.
+            pub fn from_indices(($($coord),+): ($(per_coord!($coord, usize)),+)) -> Option<Self> {
+                Some($name { $($coord: T::from_usize($coord)?,)+ })
+            }
+
+            /// The number of orthogonal steps between the points.
+            pub fn manhattan_distance(self, other: Self) -> T {
+                T::ZERO $(+ abs_diff(self.$coord, other.$coord))+
+            }
+
+            /// The number of steps between the points when diagonal steps
+            /// are allowed too.
+            pub fn chebyshev_distance(self, other: Self) -> T {
+                T::ZERO $(.max(abs_diff(self.$coord, other.$coord)))+
+            }
+
+            /// The straight-line distance between the points.
+            pub fn euclidean_distance(self, other: Self) -> f64 {
+                (0.0 $(+ abs_diff(self.$coord, other.$coord).as_f64().powi(2))+).sqrt()
+            }
+        }
+
+        impl<T: Coord> Add for $name<T> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                $name { $($coord: self.$coord + rhs.$coord,)+ }
+            }
+        }
+
+        impl<T: Coord> Sub for $name<T> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                $name { $($coord: self.$coord - rhs.$coord,)+ }
+            }
+        }
+
+        /// Scales every coordinate.
+        impl<T: Coord> Mul<T> for $name<T> {
+            type Output = Self;
+
+            fn mul(self, rhs: T) -> Self {
+                $name { $($coord: self.$coord * rhs,)+ }
+            }
+        }
+
+        impl<T: Coord + Neg<Output = T>> Neg for $name<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                $name { $($coord: -self.$coord,)+ }
+            }
+        }
+
+        impl<T: Coord> AddAssign for $name<T> {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<T: Coord> SubAssign for $name<T> {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl<T> From<$tuple> for $name<T> {
+            fn from(($($coord),+): $tuple) -> Self {
+                $name { $($coord,)+ }
+            }
+        }
+
+        impl<T> From<$name<T>> for $tuple {
+            fn from(point: $name<T>) -> Self {
+                ($(point.$coord),+)
+            }
+        }
+
+        impl<T: fmt::Display> fmt::Display for $name<T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let coords = [$(self.$coord.to_string()),+];
+                write!(f, "({})", coords.join(", "))
+            }
+        }
+    };
+}
+
+point!(
+    /// A point or vector on a plane, with `y` growing southwards as in
+    /// [`Direction::to_delta`].
+    Point { x, y },
+    (T, T)
+);
+
+point!(
+    /// A point or vector in three dimensions.
+    Point3 { x, y, z },
+    (T, T, T)
+);
+
+point!(
+    /// A point or vector in four dimensions.
+    Point4 { x, y, z, w },
+    (T, T, T, T)
+);
+
+impl<T: Coord> Point<T> {
+    /// The point one step away in `dir`, see [`Point::offset`].
+    pub fn neighbor(self, dir: Direction) -> Option<Self> {
+        self.offset(dir.to_delta())
+    }
+
+    /// The point one step away in `dir`, see [`Point::offset`].
+    pub fn compass_neighbor(self, dir: Compass) -> Option<Self> {
+        self.offset(dir.to_delta())
+    }
+
+    /// The grid position `(row, col)` of the point, if neither coordinate
+    /// is negative or too large.
+    pub fn to_pos(self) -> Option<Pos> {
+        Some((self.y.to_usize()?, self.x.to_usize()?))
+    }
+
+    /// The point at grid position `(row, col)`, if it fits the coordinate
+    /// type.
+    pub fn from_pos((row, col): Pos) -> Option<Self> {
+        Some(Point {
+            x: T::from_usize(col)?,
+            y: T::from_usize(row)?,
+        })
+    }
+}
+
+impl<T: Coord + Neg<Output = T>> Point<T> {
+    /// The vector turned a quarter anticlockwise about the origin as seen
+    /// on screen, so east turns north.
+    pub fn rotate_left(self) -> Self {
+        Point {
+            x: self.y,
+            y: -self.x,
+        }
+    }
+
+    /// The vector turned a quarter clockwise about the origin as seen on
+    /// screen, so east turns south.
+    pub fn rotate_right(self) -> Self {
+        Point {
+            x: -self.y,
+            y: self.x,
+        }
+    }
+}
+
+/// The vector of one step in the direction.
+impl From<Direction> for Point<isize> {
+    fn from(dir: Direction) -> Self {
+        Point::from(dir.to_delta())
+    }
+}
+
+/// The vector of one step in the direction.
+impl From<Compass> for Point<isize> {
+    fn from(dir: Compass) -> Self {
+        Point::from(dir.to_delta())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ops_and_distances() {
+        let a = Point::new(1isize, -2);
+        let b = Point::new(4isize, 2);
+        assert_eq!(Point::new(5, 0), a + b);
+        assert_eq!(Point::new(3, 4), b - a);
+        assert_eq!(Point::new(-2, 4), -a * 2);
+        assert_eq!(7, a.manhattan_distance(b));
+        assert_eq!(4, a.chebyshev_distance(b));
+        assert_eq!(5.0, a.euclidean_distance(b));
+
+        // Unsigned points work out distances without underflowing
+        let c = Point::new(3usize, 0);
+        assert_eq!(5, c.manhattan_distance(Point::new(0, 2)));
+
+        let mut d = Point4::new(1i32, 2, 3, 4);
+        d -= Point4::ORIGIN + Point4::new(1, 1, 1, 1);
+        assert_eq!(Point4::new(0, 1, 2, 3), d);
+        assert_eq!(6, d.manhattan_distance(Point4::ORIGIN));
+        assert_eq!(
+            3,
+            Point3::new(0u64, 0, 0).chebyshev_distance(Point3::new(1, 3, 2))
+        );
+        assert_eq!("(0, 1, 2, 3)", d.to_string());
+    }
+
+    #[test]
+    fn test_directions_and_rotations() {
+        let origin = Point::<isize>::ORIGIN;
+        assert_eq!(Point::new(0, -1), origin + Direction::N.into());
+        assert_eq!(
+            Some(Point::new(-1, 1)),
+            origin.compass_neighbor(Compass::SouthWest)
+        );
+        assert_eq!(None, Point::new(0usize, 5).neighbor(Direction::W));
+
+        let east = Point::from(Direction::E);
+        assert_eq!(Point::from(Direction::N), east.rotate_left());
+        assert_eq!(Point::from(Direction::S), east.rotate_right());
+        assert_eq!(
+            east,
+            east.rotate_right()
+                .rotate_right()
+                .rotate_right()
+                .rotate_right()
+        );
+    }
+
+    #[test]
+    fn test_grid_positions() {
+        assert_eq!(Some((2, 1)), Point::new(1isize, 2).to_pos());
+        assert_eq!(None, Point::new(-1isize, 2).to_pos());
+        assert_eq!(Some(Point::new(1i32, 2)), Point::from_pos((2, 1)));
+        assert_eq!(None, Point::<u32>::from_pos((usize::MAX, 0)));
+    }
+
+    #[test]
+    fn test_higher_dimension_steps() {
+        let a = Point3::new(1i64, 0, -1);
+        assert_eq!(Some(Point3::new(0, 2, -1)), a.offset((-1, 2, 0)));
+        assert_eq!(
+            vec![
+                Point3::new(0, 0, -1),
+                Point3::new(2, 0, -1),
+                Point3::new(1, -1, -1),
+                Point3::new(1, 1, -1),
+                Point3::new(1, 0, -2),
+                Point3::new(1, 0, 0),
+            ],
+            a.neighbors().collect::<Vec<_>>()
+        );
+        assert_eq!(None, Point3::new(0usize, 1, 1).offset((-1, 0, 0)));
+
+        // Unsigned points have no neighbors below zero
+        let b = Point4::new(0u32, 3, 0, 0);
+        assert_eq!(5, b.neighbors().count());
+        assert!(b.neighbors().all(|n| n.manhattan_distance(b) == 1));
+    }
+
+    #[test]
+    fn test_higher_dimension_indices() {
+        assert_eq!(Some((1, 2, 3)), Point3::new(1i32, 2, 3).to_indices());
+        assert_eq!(None, Point3::new(1i32, -2, 3).to_indices());
+        assert_eq!(
+            Some(Point4::new(1u32, 2, 3, 4)),
+            Point4::from_indices((1, 2, 3, 4))
+        );
+        assert_eq!(None, Point4::<u32>::from_indices((0, 0, usize::MAX, 0)));
+    }
+}
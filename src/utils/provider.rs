@@ -0,0 +1,257 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use ureq::Agent;
+
+use crate::error::{AocError, AocResult};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const DEFAULT_INPUT_DIR: &str = "input";
+
+/// Environment variable holding the adventofcode.com `session` cookie.
+pub const SESSION_ENV: &str = "AOC_SESSION";
+/// Environment variable overriding [`DEFAULT_BASE_URL`], e.g. for a local stand-in server.
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+
+const USER_AGENT: &str = concat!("aoc (", env!("CARGO_PKG_REPOSITORY"), ")");
+
+/// Resolves a day's puzzle input to a file under `input/<year>/dayNN.txt`,
+/// fetching and caching it from the puzzle server when it isn't there yet.
+#[derive(Debug, Clone)]
+pub struct InputProvider {
+    input_dir: PathBuf,
+    base_url: String,
+    session: Option<String>,
+}
+
+impl InputProvider {
+    pub fn new(input_dir: impl Into<PathBuf>) -> Self {
+        InputProvider {
+            input_dir: input_dir.into(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            session: None,
+        }
+    }
+
+    /// A provider for the repository's `input` directory, configured from
+    /// [`SESSION_ENV`] and [`BASE_URL_ENV`].
+    pub fn from_env() -> Self {
+        let mut provider = InputProvider::new(DEFAULT_INPUT_DIR);
+        if let Ok(base_url) = env::var(BASE_URL_ENV) {
+            provider = provider.with_base_url(&base_url);
+        }
+        if let Ok(session) = env::var(SESSION_ENV) {
+            provider = provider.with_session(session.trim());
+        }
+        provider
+    }
+
+    pub fn with_base_url(self, base_url: &str) -> Self {
+        InputProvider {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            ..self
+        }
+    }
+
+    pub fn with_session(self, session: &str) -> Self {
+        InputProvider {
+            session: Some(session.to_string()),
+            ..self
+        }
+    }
+
+    /// The cached input file for a day, shared by both of its parts.
+    pub fn input_path(&self, year: u32, day: u32) -> PathBuf {
+        self.input_dir
+            .join(year.to_string())
+            .join(format!("day{day:02}.txt"))
+    }
+
+    /// The input for a day, fetched into the cache if it's missing.
+    pub fn get_input(&self, year: u32, day: u32) -> AocResult<String> {
+        let path = self.input_path(year, day);
+        if path.exists() {
+            return fs::read_to_string(&path).map_err(|source| io_error(&path, source));
+        }
+
+        let input = self.fetch(year, day)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|source| io_error(dir, source))?;
+        }
+        fs::write(&path, &input).map_err(|source| io_error(&path, source))?;
+        Ok(input)
+    }
+
+    /// Downloads the input for a day from the puzzle server, bypassing the cache.
+    pub fn fetch(&self, year: u32, day: u32) -> AocResult<String> {
+        let url = format!("{}/{year}/day/{day}/input", self.base_url);
+        let fetch_error = |message: String| AocError::Fetch {
+            url: url.clone(),
+            message,
+        };
+
+        let session = self
+            .session
+            .as_deref()
+            .ok_or_else(|| fetch_error(format!("no session token, set {SESSION_ENV}")))?;
+
+        let mut response = agent()
+            .get(&url)
+            .header("Cookie", &format!("session={session}"))
+            .call()
+            .map_err(|err| fetch_error(err.to_string()))?;
+
+        if !response.status().is_success() {
+            return Err(fetch_error(format!(
+                "server responded {}",
+                response.status()
+            )));
+        }
+        response
+            .body_mut()
+            .read_to_string()
+            .map_err(|err| fetch_error(err.to_string()))
+    }
+}
+
+/// An HTTP client for the puzzle server that leaves error statuses to the caller.
+pub(crate) fn agent() -> Agent {
+    Agent::config_builder()
+        .user_agent(USER_AGENT)
+        .http_status_as_error(false)
+        .build()
+        .into()
+}
+
+fn io_error(path: &Path, source: std::io::Error) -> AocError {
+    AocError::Io {
+        file: path.display().to_string(),
+        source,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::fake_server::serve_once;
+
+    fn temp_input_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc_{name}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_input_path() {
+        let provider = InputProvider::new("input");
+        assert_eq!(
+            Path::new("input/2024/day16.txt"),
+            provider.input_path(2024, 16)
+        );
+        assert_eq!(
+            Path::new("input/2023/day06.txt"),
+            provider.input_path(2023, 6)
+        );
+    }
+
+    #[test]
+    fn test_get_input_fetches_and_caches() {
+        let (base_url, server) = serve_once("200 OK", "3   4\n4   3\n");
+        let input_dir = temp_input_dir("fetches_and_caches");
+        let provider = InputProvider::new(&input_dir)
+            .with_base_url(&base_url)
+            .with_session("abc123");
+
+        assert_eq!("3   4\n4   3\n", provider.get_input(2024, 1).unwrap());
+        let request = server.join().unwrap();
+        assert_eq!("GET /2024/day/1/input HTTP/1.1", request.line);
+        assert_eq!("session=abc123", request.cookie);
+
+        // The server is gone, so this can only come from the cache
+        assert_eq!("3   4\n4   3\n", provider.get_input(2024, 1).unwrap());
+        assert!(input_dir.join("2024/day01.txt").exists());
+
+        fs::remove_dir_all(&input_dir).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_rejected() {
+        let (base_url, server) = serve_once("400 Bad Request", "Puzzle inputs differ by user.");
+        let provider = InputProvider::new(temp_input_dir("fetch_rejected"))
+            .with_base_url(&base_url)
+            .with_session("expired");
+
+        let err = provider.fetch(2024, 1).unwrap_err();
+        server.join().unwrap();
+        assert_eq!(
+            format!("couldn't fetch {base_url}/2024/day/1/input: server responded 400 Bad Request"),
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_fetch_without_session() {
+        let provider = InputProvider::new(temp_input_dir("fetch_without_session"))
+            .with_base_url("http://127.0.0.1:9");
+        assert!(matches!(
+            provider.get_input(2024, 1),
+            Err(AocError::Fetch { .. })
+        ));
+    }
+}
+
+/// A stand-in for the puzzle server that answers a single request.
+#[cfg(test)]
+pub(crate) mod fake_server {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    #[derive(Debug, Default)]
+    pub(crate) struct Request {
+        pub line: String,
+        pub cookie: String,
+        pub body: String,
+    }
+
+    /// Serves one request with `status` and `body`, returning the base URL to
+    /// send it to and a handle yielding the request that was received.
+    pub(crate) fn serve_once(status: &str, body: &str) -> (String, JoinHandle<Request>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let response = format!(
+            "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request = Request::default();
+            reader.read_line(&mut request.line).unwrap();
+            request.line = request.line.trim().to_string();
+            let mut content_length = 0;
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                let Some((name, value)) = header.split_once(':') else {
+                    break;
+                };
+                if name.eq_ignore_ascii_case("cookie") {
+                    request.cookie = value.trim().to_string();
+                } else if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            request.body = String::from_utf8(body).unwrap();
+            stream.write_all(response.as_bytes()).unwrap();
+            request
+        });
+        (base_url, handle)
+    }
+}
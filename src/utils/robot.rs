@@ -0,0 +1,193 @@
+use std::collections::{HashMap, HashSet};
+
+use log::trace;
+
+use crate::error::AocResult;
+use crate::intcode::{IntcodeMachine, Status};
+use crate::utils::{Direction, Point};
+
+/// A robot on an unbounded grid, facing one of the compass directions, with
+/// `y` growing southwards as in [`Direction::to_delta`]. It remembers every
+/// cell it has been on and what it left in each cell it marked.
+#[derive(Clone, Debug)]
+pub struct Robot<C> {
+    position: Point<isize>,
+    direction: Direction,
+    cells: HashMap<Point<isize>, C>,
+    visited: HashSet<Point<isize>>,
+}
+
+impl<C> Robot<C> {
+    /// A robot at the origin facing `direction`.
+    pub fn new(direction: Direction) -> Self {
+        Robot {
+            position: Point::ORIGIN,
+            direction,
+            cells: HashMap::new(),
+            visited: HashSet::from([Point::ORIGIN]),
+        }
+    }
+
+    pub fn position(&self) -> Point<isize> {
+        self.position
+    }
+
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// What the robot left in the cell it's on, if anything.
+    pub fn cell(&self) -> Option<&C> {
+        self.cells.get(&self.position)
+    }
+
+    pub fn paint(&mut self, cell: C) {
+        self.cells.insert(self.position, cell);
+    }
+
+    pub fn turn_left(&mut self) {
+        self.direction = self.direction.turn_left();
+    }
+
+    pub fn turn_right(&mut self) {
+        self.direction = self.direction.turn_right();
+    }
+
+    pub fn face(&mut self, direction: Direction) {
+        self.direction = direction;
+    }
+
+    /// Moves one cell the way the robot is facing.
+    pub fn forward(&mut self) {
+        self.position += self.direction.into();
+        self.visited.insert(self.position);
+    }
+
+    /// Every cell the robot marked, with what it left there.
+    pub fn painted(&self) -> &HashMap<Point<isize>, C> {
+        &self.cells
+    }
+
+    pub fn visited(&self) -> &HashSet<Point<isize>> {
+        &self.visited
+    }
+
+    /// Draws every cell in the box around those visited or marked, one row
+    /// per line from north to south.
+    pub fn render(&self, draw: impl Fn(Point<isize>, Option<&C>) -> char) -> String {
+        let cells = self.visited.iter().chain(self.cells.keys());
+        let (min_x, max_x) = cells
+            .clone()
+            .map(|point| point.x)
+            .fold((isize::MAX, isize::MIN), |(lo, hi), x| {
+                (lo.min(x), hi.max(x))
+            });
+        let (min_y, max_y) = cells
+            .map(|point| point.y)
+            .fold((isize::MAX, isize::MIN), |(lo, hi), y| {
+                (lo.min(y), hi.max(y))
+            });
+
+        let mut rendered = String::new();
+        for y in min_y..=max_y {
+            for x in min_x..=max_x {
+                let point = Point::new(x, y);
+                rendered.push(draw(point, self.cells.get(&point)));
+            }
+            rendered.push('\n');
+        }
+        rendered
+    }
+}
+
+/// How a robot program senses the grid and what its outputs make the robot do.
+pub trait Protocol {
+    /// What the robot can leave in a cell, such as a panel color.
+    type Cell;
+
+    /// How many outputs make up one action.
+    const OUTPUTS: usize;
+
+    /// The inputs to give the program when it asks, or `None` to stop it.
+    fn sense(&mut self, robot: &Robot<Self::Cell>) -> Option<Vec<isize>>;
+
+    /// Carries out one action, e.g. painting and moving the robot.
+    fn act(&mut self, robot: &mut Robot<Self::Cell>, outputs: &[isize]) -> AocResult<()>;
+}
+
+/// Runs the program as the robot's brain until it halts, or until the
+/// protocol has nothing more to sense.
+pub fn drive<P: Protocol>(
+    machine: &mut IntcodeMachine,
+    protocol: &mut P,
+    robot: &mut Robot<P::Cell>,
+) -> AocResult<()> {
+    let mut outputs = Vec::with_capacity(P::OUTPUTS);
+    loop {
+        match machine.run()? {
+            Status::Output(output) => {
+                outputs.push(output);
+                if outputs.len() == P::OUTPUTS {
+                    trace!("Robot at {} acting on {:?}", robot.position, outputs);
+                    protocol.act(robot, &outputs)?;
+                    outputs.clear();
+                }
+            }
+            Status::NeedsInput => match protocol.sense(robot) {
+                Some(inputs) => machine.push_inputs(inputs),
+                None => return Ok(()),
+            },
+            Status::Halted => return Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assembler::assemble;
+
+    /// Senses whether its cell is marked, and acts on a step count by marking
+    /// the cell and stepping that far forward, then turning right.
+    struct Walker {
+        sensed: Vec<bool>,
+    }
+
+    impl Protocol for Walker {
+        type Cell = ();
+        const OUTPUTS: usize = 1;
+
+        fn sense(&mut self, robot: &Robot<()>) -> Option<Vec<isize>> {
+            self.sensed.push(robot.cell().is_some());
+            (self.sensed.len() < 4).then(|| vec![0])
+        }
+
+        fn act(&mut self, robot: &mut Robot<()>, outputs: &[isize]) -> AocResult<()> {
+            robot.paint(());
+            for _ in 0..outputs[0] {
+                robot.forward();
+            }
+            robot.turn_right();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_drive() {
+        // Walks 2 steps after each input, forever
+        let intcode = assemble("loop: IN [v]\nOUT #2\nJZ #0, #loop\nv: DATA 0").unwrap();
+        let mut robot = Robot::new(Direction::N);
+        let mut walker = Walker { sensed: vec![] };
+        drive(&mut IntcodeMachine::new(intcode), &mut walker, &mut robot).unwrap();
+
+        assert_eq!(vec![false, false, false, false], walker.sensed);
+        assert_eq!(Point::new(2, 0), robot.position());
+        assert_eq!(Direction::W, robot.direction());
+        assert_eq!(3, robot.painted().len());
+        assert_eq!(7, robot.visited().len());
+        assert_eq!(
+            "#.#\n...\n#..\n",
+            robot.render(|_, cell| if cell.is_some() { '#' } else { '.' })
+        );
+    }
+}
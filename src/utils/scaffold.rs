@@ -0,0 +1,409 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use regex::Regex;
+
+use crate::error::{AocError, AocResult};
+
+/// The source tree, relative to the repository root.
+pub const DEFAULT_SRC_DIR: &str = "src";
+
+const DAY_TEMPLATE: &str = r#"// https://adventofcode.com/{year}/day/{day}
+
+use crate::error::AocResult;
+use crate::solution::{Answer, Solution};
+use crate::utils::{Contents, PuzzleInput, get_lines};
+
+struct Input {
+    lines: Vec<String>,
+}
+
+fn parse_input(puzzle_input: impl PuzzleInput) -> AocResult<Input> {
+    let lines = get_lines(puzzle_input)?;
+
+    Ok(Input { lines })
+}
+
+pub fn get_part_one(puzzle_input: impl PuzzleInput) -> AocResult<usize> {
+    let input = parse_input(puzzle_input)?;
+
+    Ok(input.lines.len())
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> AocResult<()> {
+        parse_input(Contents(input))?;
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer> {
+        Ok(get_part_one(Contents(input))?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_part_one_test01() {
+        assert_eq!(0, get_part_one("input/{year}/day{dd}_test01.txt").unwrap());
+    }
+}
+"#;
+
+/// The source of a new day module, solving nothing yet beyond counting lines.
+pub fn render_day(year: u32, day: u32) -> String {
+    DAY_TEMPLATE
+        .replace("{year}", &year.to_string())
+        .replace("{day}", &day.to_string())
+        .replace("{dd}", &format!("{day:02}"))
+}
+
+/// The `mod.rs` of a year declaring `days` and registering their solvers, laid
+/// out the way rustfmt would.
+pub fn render_year_module(days: &[u32]) -> String {
+    let mut days = days.to_vec();
+    days.sort();
+    days.dedup();
+
+    let mut module = String::new();
+    for day in &days {
+        module += &format!("pub mod day{day:02};\n");
+    }
+    module += "\nuse crate::solution::YearSolutions;\n\n";
+    module + &render_solutions(&days)
+}
+
+/// The `SOLUTIONS` declaration registering the solvers of the sorted `days`.
+fn render_solutions(days: &[u32]) -> String {
+    let entries: Vec<String> = days
+        .iter()
+        .map(|day| format!("({day}, &day{day:02}::Solver)"))
+        .collect();
+    let declaration = "pub const SOLUTIONS: YearSolutions = ";
+    let inline = format!("&[{}]", entries.join(", "));
+    // rustfmt keeps short arrays on one line
+    if inline.len() <= 61 && declaration.len() + inline.len() < 100 {
+        format!("{declaration}{inline};\n")
+    } else {
+        let mut solutions = format!("{declaration}&[\n");
+        for entry in entries {
+            solutions += &format!("    {entry},\n");
+        }
+        solutions + "];\n"
+    }
+}
+
+/// Declares `day` in an existing year `mod.rs` and registers its solver,
+/// leaving everything else in the module as it was. Returns `None` when there
+/// is nowhere to put either.
+fn register_day(module: &str, day: u32) -> Option<String> {
+    lazy_static! {
+        static ref RE_DAY_MOD: Regex = Regex::new(r"^pub mod day(\d{2});$").unwrap();
+        static ref RE_DAY_SOLVER: Regex =
+            Regex::new(r"^    \((\d+), &day\d{2}::Solver\),$").unwrap();
+        static ref RE_INLINE_SOLUTIONS: Regex =
+            Regex::new(r"(?m)^pub const SOLUTIONS: YearSolutions = &\[(.*)\];\n?").unwrap();
+        static ref RE_INLINE_SOLVER: Regex = Regex::new(r"\((\d+), &day\d{2}::Solver\)").unwrap();
+    }
+
+    let module = insert_sorted(module, &RE_DAY_MOD, day, &format!("pub mod day{day:02};"))?;
+    if let Some(module) = insert_sorted(
+        &module,
+        &RE_DAY_SOLVER,
+        day,
+        &format!("    ({day}, &day{day:02}::Solver),"),
+    ) {
+        return Some(module);
+    }
+
+    // Short lists are kept on one line, which may not stay short
+    let inline = RE_INLINE_SOLUTIONS.captures(&module)?;
+    let mut days: Vec<u32> = RE_INLINE_SOLVER
+        .captures_iter(&inline[1])
+        .filter_map(|caps| caps[1].parse().ok())
+        .collect();
+    if days.contains(&day) {
+        return None;
+    }
+    days.push(day);
+    days.sort();
+    Some(module.replacen(&inline[0], &render_solutions(&days), 1))
+}
+
+/// Inserts `new_line` among the lines matching `re`, keeping them ordered by
+/// the number captured by its first group. Returns `None` when there are no
+/// such lines, or one with `key` is already present.
+fn insert_sorted(contents: &str, re: &Regex, key: u32, new_line: &str) -> Option<String> {
+    let mut lines: Vec<&str> = contents.lines().collect();
+    let keyed: Vec<(usize, u32)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, line)| Some((idx, re.captures(line)?[1].parse().ok()?)))
+        .collect();
+    if keyed.is_empty() || keyed.iter().any(|&(_, k)| k == key) {
+        return None;
+    }
+
+    let idx = keyed
+        .iter()
+        .find(|&&(_, k)| k > key)
+        .map_or(keyed.last().unwrap().0 + 1, |&(idx, _)| idx);
+    lines.insert(idx, new_line);
+    Some(lines.join("\n") + "\n")
+}
+
+fn read(path: &Path) -> AocResult<String> {
+    fs::read_to_string(path).map_err(|source| io_error(path, source))
+}
+
+fn write(path: &Path, contents: &str) -> AocResult<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|source| io_error(dir, source))?;
+    }
+    fs::write(path, contents).map_err(|source| io_error(path, source))
+}
+
+fn io_error(path: &Path, source: std::io::Error) -> AocError {
+    AocError::Io {
+        file: path.display().to_string(),
+        source,
+    }
+}
+
+fn unregistered(path: &Path, what: &str) -> AocError {
+    AocError::invalid(format!("couldn't find where to register the {what}"))
+        .in_file(&path.display().to_string())
+}
+
+/// Declares a new year in `lib.rs` and registers its solutions in `solution.rs`.
+fn register_year(src_dir: &Path, year: u32) -> AocResult<Vec<PathBuf>> {
+    lazy_static! {
+        static ref RE_YEAR_MOD: Regex = Regex::new(r"^pub mod aoc(\d{4});$").unwrap();
+        static ref RE_YEAR_SOLUTIONS: Regex =
+            Regex::new(r"^    \((\d{4}), aoc\d{4}::SOLUTIONS\),$").unwrap();
+        static ref RE_YEAR_USE: Regex = Regex::new(r"use crate::\{(aoc\d{4}(, )?)+\};").unwrap();
+    }
+
+    let lib_path = src_dir.join("lib.rs");
+    let lib = read(&lib_path)?;
+    let lib = insert_sorted(&lib, &RE_YEAR_MOD, year, &format!("pub mod aoc{year};"))
+        .ok_or_else(|| unregistered(&lib_path, "year module"))?;
+
+    let solution_path = src_dir.join("utils/solution.rs");
+    let solution = read(&solution_path)?;
+    let solution = insert_sorted(
+        &solution,
+        &RE_YEAR_SOLUTIONS,
+        year,
+        &format!("    ({year}, aoc{year}::SOLUTIONS),"),
+    )
+    .ok_or_else(|| unregistered(&solution_path, "year's solutions"))?;
+    let years: Vec<String> = lib
+        .lines()
+        .filter_map(|line| RE_YEAR_MOD.captures(line))
+        .map(|caps| format!("aoc{}", &caps[1]))
+        .collect();
+    if !RE_YEAR_USE.is_match(&solution) {
+        return Err(unregistered(&solution_path, "year's import"));
+    }
+    let solution = RE_YEAR_USE
+        .replace(&solution, format!("use crate::{{{}}};", years.join(", ")))
+        .to_string();
+
+    write(&lib_path, &lib)?;
+    write(&solution_path, &solution)?;
+    Ok(vec![lib_path, solution_path])
+}
+
+/// Creates the module for a new day from the template, registers it with its
+/// year (declaring the year first if it's new), and creates empty input and
+/// example files for it. Returns the files created or changed.
+pub fn create_day(
+    src_dir: &Path,
+    input_dir: &Path,
+    year: u32,
+    day: u32,
+) -> AocResult<Vec<PathBuf>> {
+    if !(2015..=9999).contains(&year) || !(1..=25).contains(&day) {
+        return Err(AocError::usage(format!(
+            "there's no puzzle for year {year} day {day}"
+        )));
+    }
+
+    let year_dir = src_dir.join(format!("aoc{year}"));
+    let day_path = year_dir.join(format!("day{day:02}.rs"));
+    if day_path.exists() {
+        return Err(
+            AocError::invalid("day already exists").in_file(&day_path.display().to_string())
+        );
+    }
+
+    let mut changed = vec![];
+    let mod_path = year_dir.join("mod.rs");
+    let module = if mod_path.exists() {
+        register_day(&read(&mod_path)?, day).ok_or_else(|| unregistered(&mod_path, "day"))?
+    } else {
+        changed.extend(register_year(src_dir, year)?);
+        render_year_module(&[day])
+    };
+
+    write(&day_path, &render_day(year, day))?;
+    changed.push(day_path);
+    write(&mod_path, &module)?;
+    changed.push(mod_path);
+
+    let input_year_dir = input_dir.join(year.to_string());
+    for name in [
+        format!("day{day:02}.txt"),
+        format!("day{day:02}_test01.txt"),
+    ] {
+        let path = input_year_dir.join(name);
+        // Keep an input that was already fetched
+        if !path.exists() {
+            write(&path, "")?;
+            changed.push(path);
+        }
+    }
+
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    fn temp_tree(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc_{name}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        write(
+            &dir.join("src/lib.rs"),
+            "pub mod aoc2019;\npub mod aoc2024;\n\npub mod utils;\n",
+        )
+        .unwrap();
+        write(
+            &dir.join("src/utils/solution.rs"),
+            "use crate::{aoc2019, aoc2024};\n\n\
+             pub const SOLUTIONS: &[(u32, YearSolutions)] = &[\n    \
+             (2019, aoc2019::SOLUTIONS),\n    (2024, aoc2024::SOLUTIONS),\n];\n",
+        )
+        .unwrap();
+        write(
+            &dir.join("src/aoc2024/mod.rs"),
+            &render_year_module(&[1, 3]),
+        )
+        .unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_render_year_module() {
+        assert_eq!(
+            "pub mod day01;\npub mod day02;\n\n\
+             use crate::solution::YearSolutions;\n\n\
+             pub const SOLUTIONS: YearSolutions = &[(1, &day01::Solver), (2, &day02::Solver)];\n",
+            render_year_module(&[2, 1])
+        );
+        assert!(render_year_module(&[1, 2, 3]).ends_with(
+            "= &[\n    (1, &day01::Solver),\n    (2, &day02::Solver),\n    (3, &day03::Solver),\n];\n"
+        ));
+    }
+
+    #[test]
+    fn test_render_year_module_matches_tree() {
+        let module = read(Path::new("src/aoc2024/mod.rs")).unwrap();
+        assert_eq!(module, render_year_module(&(1..=25).collect::<Vec<_>>()));
+    }
+
+    #[test]
+    fn test_create_day_in_existing_year() {
+        let dir = temp_tree("create_day_in_existing_year");
+        let (src_dir, input_dir) = (dir.join("src"), dir.join("input"));
+
+        let changed = create_day(&src_dir, &input_dir, 2024, 2).unwrap();
+        assert_eq!(4, changed.len());
+        assert_eq!(
+            render_year_module(&[1, 2, 3]),
+            read(&src_dir.join("aoc2024/mod.rs")).unwrap()
+        );
+        assert_eq!(
+            render_day(2024, 2),
+            read(&src_dir.join("aoc2024/day02.rs")).unwrap()
+        );
+        assert_eq!("", read(&input_dir.join("2024/day02_test01.txt")).unwrap());
+
+        assert!(create_day(&src_dir, &input_dir, 2024, 2).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_create_day_keeps_the_rest_of_the_year_module() {
+        let dir = temp_tree("create_day_keeps_the_rest_of_the_year_module");
+        let (src_dir, input_dir) = (dir.join("src"), dir.join("input"));
+        let module = |days: &str, solvers: &str| {
+            format!(
+                "// Days 1 to 5\n{days}\nmod common;\n\n\
+                 use crate::solution::YearSolutions;\n\n\
+                 pub const SOLUTIONS: YearSolutions = &[\n{solvers}];\n\n\
+                 #[cfg(test)]\nmod tests {{}}\n"
+            )
+        };
+        write(
+            &src_dir.join("aoc2024/mod.rs"),
+            &module(
+                "pub mod day01;\npub mod day05;\n",
+                "    (1, &day01::Solver),\n",
+            ),
+        )
+        .unwrap();
+
+        create_day(&src_dir, &input_dir, 2024, 3).unwrap();
+        assert_eq!(
+            module(
+                "pub mod day01;\npub mod day03;\npub mod day05;\n",
+                "    (1, &day01::Solver),\n    (3, &day03::Solver),\n"
+            ),
+            read(&src_dir.join("aoc2024/mod.rs")).unwrap()
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_create_day_in_new_year() {
+        let dir = temp_tree("create_day_in_new_year");
+        let (src_dir, input_dir) = (dir.join("src"), dir.join("input"));
+
+        create_day(&src_dir, &input_dir, 2022, 7).unwrap();
+        assert_eq!(
+            "pub mod aoc2019;\npub mod aoc2022;\npub mod aoc2024;\n\npub mod utils;\n",
+            read(&src_dir.join("lib.rs")).unwrap()
+        );
+        assert_eq!(
+            "use crate::{aoc2019, aoc2022, aoc2024};\n\n\
+             pub const SOLUTIONS: &[(u32, YearSolutions)] = &[\n    \
+             (2019, aoc2019::SOLUTIONS),\n    (2022, aoc2022::SOLUTIONS),\n    \
+             (2024, aoc2024::SOLUTIONS),\n];\n",
+            read(&src_dir.join("utils/solution.rs")).unwrap()
+        );
+        assert_eq!(
+            render_year_module(&[7]),
+            read(&src_dir.join("aoc2022/mod.rs")).unwrap()
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_create_day_out_of_range() {
+        let dir = temp_tree("create_day_out_of_range");
+        let err = create_day(&dir.join("src"), &dir.join("input"), 2024, 26).unwrap_err();
+        assert_eq!("there's no puzzle for year 2024 day 26", err.to_string());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,188 @@
+use std::fmt;
+
+use crate::error::AocResult;
+use crate::{aoc2019, aoc2022, aoc2023, aoc2024};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Num(i128),
+    Text(String),
+    Unsolved,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Num(num) => write!(f, "{num}"),
+            Answer::Text(text) => write!(f, "{text}"),
+            Answer::Unsolved => write!(f, "unsolved"),
+        }
+    }
+}
+
+macro_rules! impl_answer_from_num {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(num: $t) -> Self {
+                    Answer::Num(num as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_answer_from_num!(u32, u64, usize, i32, i64, isize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+impl<T> From<Option<T>> for Answer
+where
+    T: Into<Answer>,
+{
+    fn from(maybe_answer: Option<T>) -> Self {
+        maybe_answer.map_or(Answer::Unsolved, Into::into)
+    }
+}
+
+/// A single day's puzzle, solved from the contents of its input.
+///
+/// Malformed input is reported as an [`AocError`](crate::error::AocError)
+/// pointing at the offending line rather than a panic.
+pub trait Solution {
+    /// Parses the input without solving it, so benchmarks can time parsing on
+    /// its own. Days that parse as they go keep the default and report all of
+    /// their time as solving.
+    fn parse(&self, _input: &str) -> AocResult<()> {
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> AocResult<Answer>;
+
+    fn part_two(&self, _input: &str) -> AocResult<Answer> {
+        Ok(Answer::Unsolved)
+    }
+
+    fn solve(&self, part: u32, input: &str) -> AocResult<Answer> {
+        match part {
+            1 => self.part_one(input),
+            2 => self.part_two(input),
+            _ => Ok(Answer::Unsolved),
+        }
+    }
+}
+
+/// Solutions for a single year, keyed by day.
+pub type YearSolutions = &'static [(u32, &'static dyn Solution)];
+
+pub const SOLUTIONS: &[(u32, YearSolutions)] = &[
+    (2019, aoc2019::SOLUTIONS),
+    (2022, aoc2022::SOLUTIONS),
+    (2023, aoc2023::SOLUTIONS),
+    (2024, aoc2024::SOLUTIONS),
+];
+
+pub fn all_solutions() -> impl Iterator<Item = (u32, u32, &'static dyn Solution)> {
+    SOLUTIONS.iter().flat_map(|&(year, days)| {
+        days.iter()
+            .map(move |&(day, solution)| (year, day, solution))
+    })
+}
+
+pub fn get_solution(year: u32, day: u32) -> Option<&'static dyn Solution> {
+    all_solutions()
+        .find(|&(y, d, _)| y == year && d == day)
+        .map(|(_, _, solution)| solution)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_is_sorted_and_unique() {
+        let keys: Vec<_> = all_solutions().map(|(year, day, _)| (year, day)).collect();
+        let mut sorted = keys.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(sorted, keys);
+    }
+
+    #[test]
+    fn test_registry_covers_every_day_module() {
+        let re_day = regex::Regex::new(r"(?m)^pub mod day(\d{2});$").unwrap();
+        let mut years = vec![];
+        for entry in std::fs::read_dir("src").unwrap() {
+            let name = entry.unwrap().file_name().into_string().unwrap();
+            if let Some(year) = name.strip_prefix("aoc") {
+                years.push(year.parse::<u32>().unwrap());
+            }
+        }
+        years.sort();
+        assert_eq!(
+            years,
+            SOLUTIONS.iter().map(|&(year, _)| year).collect::<Vec<_>>()
+        );
+
+        for &(year, days) in SOLUTIONS {
+            let module = std::fs::read_to_string(format!("src/aoc{year}/mod.rs")).unwrap();
+            let declared: Vec<u32> = re_day
+                .captures_iter(&module)
+                .map(|caps| caps[1].parse().unwrap())
+                .collect();
+            let registered: Vec<u32> = days.iter().map(|&(day, _)| day).collect();
+            assert_eq!(
+                declared, registered,
+                "days declared in src/aoc{year}/mod.rs"
+            );
+        }
+    }
+
+    #[test]
+    fn test_get_solution() {
+        assert!(get_solution(2024, 16).is_some());
+        assert!(get_solution(2022, 25).is_none());
+        let input = std::fs::read_to_string("input/2024/day01.txt").unwrap();
+        assert_eq!(
+            Answer::Num(21790168),
+            get_solution(2024, 1).unwrap().part_two(&input).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_solve_reports_bad_input() {
+        let err = get_solution(2024, 1)
+            .unwrap()
+            .solve(1, "3   4\n4   x\n")
+            .unwrap_err();
+        assert_eq!("<input>:2:5: couldn't parse \"x\" as u32", err.to_string());
+    }
+
+    #[test]
+    fn test_solve_empty_input_does_not_panic() {
+        for (_, _, solution) in all_solutions() {
+            for input in ["", "\n"] {
+                for part in [1, 2] {
+                    let _ = solution.solve(part, input);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_answer_display() {
+        assert_eq!("42", Answer::from(42u64).to_string());
+        assert_eq!("abc", Answer::from("abc").to_string());
+        assert_eq!(Answer::Unsolved, Answer::from(None::<u32>));
+    }
+}
@@ -0,0 +1,490 @@
+use std::{
+    env, fmt, fs,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use csv::{ReaderBuilder, Writer};
+use regex::Regex;
+
+use crate::error::{AocError, AocResult, parse_token};
+use crate::provider::{BASE_URL_ENV, DEFAULT_BASE_URL, SESSION_ENV, agent};
+use crate::utils::PuzzleInput;
+
+/// The ledger of rejected answers, relative to the repository root.
+pub const DEFAULT_LEDGER: &str = "input/rejected.csv";
+
+/// The puzzle server's response to a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint as to which way.
+    Wrong,
+    /// Submitted too soon after the last answer.
+    Wait(Duration),
+    /// The part has already been solved, or isn't unlocked yet.
+    WrongLevel,
+    Unknown(String),
+}
+
+impl Verdict {
+    pub fn is_rejection(&self) -> bool {
+        matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::Wait(wait) => write!(f, "wait {}s before submitting again", wait.as_secs()),
+            Verdict::WrongLevel => write!(f, "already solved or not unlocked yet"),
+            Verdict::Unknown(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+/// What happened to a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Submission {
+    Sent(Verdict),
+    /// Not sent, as the ledger shows it would be rejected.
+    Known(Verdict),
+}
+
+/// Reads the verdict out of the HTML page returned from `url` for a submitted
+/// answer.
+pub fn parse_verdict(url: &str, html: &str) -> AocResult<Verdict> {
+    lazy_static! {
+        static ref RE_ARTICLE: Regex = Regex::new(r"(?s)<article>(?P<text>.*?)</article>").unwrap();
+        static ref RE_TAG: Regex = Regex::new(r"<[^>]*>").unwrap();
+        static ref RE_WAIT: Regex =
+            Regex::new(r"You have (?:(?P<mins>\d+)m )?(?P<secs>\d+)s left to wait").unwrap();
+    }
+    let text = RE_ARTICLE
+        .captures(html)
+        .map_or(html, |caps| caps.name("text").unwrap().as_str());
+    let text = RE_TAG.replace_all(text, "");
+    let parse_secs = |digits: &str| {
+        digits.parse::<u64>().map_err(|err| AocError::Fetch {
+            url: url.to_string(),
+            message: format!("bad wait time {digits:?}: {err}"),
+        })
+    };
+
+    Ok(if text.contains("That's the right answer") {
+        Verdict::Correct
+    } else if text.contains("That's not the right answer") {
+        if text.contains("too high") {
+            Verdict::TooHigh
+        } else if text.contains("too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        }
+    } else if text.contains("You gave an answer too recently") {
+        let wait = match RE_WAIT.captures(&text) {
+            Some(caps) => {
+                let mins = caps
+                    .name("mins")
+                    .map_or(Ok(0), |m| parse_secs(m.as_str()))?;
+                let secs = parse_secs(&caps["secs"])?;
+                mins.saturating_mul(60).saturating_add(secs)
+            }
+            None => 0,
+        };
+        Verdict::Wait(Duration::from_secs(wait))
+    } else if text.contains("You don't seem to be solving the right level") {
+        Verdict::WrongLevel
+    } else {
+        Verdict::Unknown(text.split_whitespace().collect::<Vec<_>>().join(" "))
+    })
+}
+
+/// An answer the puzzle server rejected, or told to wait before submitting
+/// again.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rejection {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+impl Rejection {
+    /// Whether `answer` is bound to be rejected for the same reason, either by
+    /// being identical or by lying beyond a too high or too low bound.
+    fn rules_out(&self, answer: &str) -> bool {
+        if self.answer == answer {
+            return true;
+        }
+        match (answer.parse::<i128>(), self.answer.parse::<i128>()) {
+            (Ok(answer), Ok(bound)) => match self.verdict {
+                Verdict::TooHigh => answer >= bound,
+                Verdict::TooLow => answer <= bound,
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+/// Reads a ledger with a `year,day,part,answer,verdict` header. A wait is
+/// recorded as `wait until <unix seconds>`, and is left out once that time has
+/// passed.
+pub fn parse_ledger(puzzle_input: impl PuzzleInput) -> AocResult<Vec<Rejection>> {
+    let contents = puzzle_input.read_to_string()?;
+
+    let mut reader = ReaderBuilder::new().from_reader(contents.as_bytes());
+
+    let mut rejections = vec![];
+    for record in reader.records() {
+        let record = record.map_err(|err| {
+            let line_idx = err.position().map_or(0, |pos| pos.line() as usize - 1);
+            AocError::parse(line_idx, 0, err.to_string())
+        })?;
+        let line_idx = record.position().map_or(0, |pos| pos.line() as usize - 1);
+        let fields: Vec<&str> = record.iter().collect();
+        let [year, day, part, answer, verdict] = fields[..] else {
+            return Err(AocError::parse(
+                line_idx,
+                0,
+                "expected year,day,part,answer,verdict",
+            ));
+        };
+        let verdict = match verdict {
+            "too high" => Verdict::TooHigh,
+            "too low" => Verdict::TooLow,
+            "wrong" => Verdict::Wrong,
+            _ if verdict.starts_with("wait until ") => {
+                let until = verdict.trim_start_matches("wait until ");
+                let until =
+                    UNIX_EPOCH + Duration::from_secs(parse_token(line_idx, verdict, until)?);
+                match until.duration_since(SystemTime::now()) {
+                    Ok(wait) => Verdict::Wait(wait),
+                    Err(_) => continue,
+                }
+            }
+            _ => {
+                return Err(AocError::parse(
+                    line_idx,
+                    0,
+                    format!("unknown verdict {verdict:?}"),
+                ));
+            }
+        };
+        rejections.push(Rejection {
+            year: parse_token(line_idx, year, year)?,
+            day: parse_token(line_idx, day, day)?,
+            part: parse_token(line_idx, part, part)?,
+            answer: answer.to_string(),
+            verdict,
+        });
+    }
+
+    Ok(rejections)
+}
+
+/// Posts answers to the puzzle server, keeping a ledger of the rejected ones
+/// so that they are never sent twice.
+#[derive(Debug, Clone)]
+pub struct Submitter {
+    ledger: PathBuf,
+    base_url: String,
+    session: Option<String>,
+}
+
+impl Submitter {
+    pub fn new(ledger: impl Into<PathBuf>) -> Self {
+        Submitter {
+            ledger: ledger.into(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            session: None,
+        }
+    }
+
+    /// A submitter for the repository's ledger, configured from
+    /// [`SESSION_ENV`] and [`BASE_URL_ENV`].
+    pub fn from_env() -> Self {
+        let mut submitter = Submitter::new(DEFAULT_LEDGER);
+        if let Ok(base_url) = env::var(BASE_URL_ENV) {
+            submitter = submitter.with_base_url(&base_url);
+        }
+        if let Ok(session) = env::var(SESSION_ENV) {
+            submitter = submitter.with_session(session.trim());
+        }
+        submitter
+    }
+
+    pub fn with_base_url(self, base_url: &str) -> Self {
+        Submitter {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            ..self
+        }
+    }
+
+    pub fn with_session(self, session: &str) -> Self {
+        Submitter {
+            session: Some(session.to_string()),
+            ..self
+        }
+    }
+
+    pub fn rejections(&self) -> AocResult<Vec<Rejection>> {
+        if !self.ledger.exists() {
+            return Ok(vec![]);
+        }
+        parse_ledger(self.ledger.as_path())
+            .map_err(|err| err.in_file(&self.ledger.display().to_string()))
+    }
+
+    /// Submits `answer` for one part of a day, unless the ledger already rules
+    /// it out or shows the server asked to wait. The server limits the whole
+    /// account, so a wait from any puzzle holds back every submission.
+    pub fn submit(&self, year: u32, day: u32, part: u32, answer: &str) -> AocResult<Submission> {
+        let mut rejections = self.rejections()?;
+        let wait = rejections
+            .iter()
+            .filter(|r| matches!(r.verdict, Verdict::Wait(_)))
+            .max_by_key(|r| match r.verdict {
+                Verdict::Wait(wait) => wait,
+                _ => Duration::ZERO,
+            });
+        if let Some(rejection) = wait.or_else(|| {
+            rejections
+                .iter()
+                .filter(|r| (r.year, r.day, r.part) == (year, day, part))
+                .find(|r| r.rules_out(answer))
+        }) {
+            return Ok(Submission::Known(rejection.verdict.clone()));
+        }
+
+        let verdict = self.post(year, day, part, answer)?;
+        if verdict.is_rejection() || matches!(verdict, Verdict::Wait(_)) {
+            rejections.push(Rejection {
+                year,
+                day,
+                part,
+                answer: answer.to_string(),
+                verdict: verdict.clone(),
+            });
+            self.save_rejections(&rejections)?;
+        }
+        Ok(Submission::Sent(verdict))
+    }
+
+    fn post(&self, year: u32, day: u32, part: u32, answer: &str) -> AocResult<Verdict> {
+        let url = format!("{}/{year}/day/{day}/answer", self.base_url);
+        let fetch_error = |message: String| AocError::Fetch {
+            url: url.clone(),
+            message,
+        };
+
+        let session = self
+            .session
+            .as_deref()
+            .ok_or_else(|| fetch_error(format!("no session token, set {SESSION_ENV}")))?;
+
+        let mut response = agent()
+            .post(&url)
+            .header("Cookie", &format!("session={session}"))
+            .send_form([("level", part.to_string().as_str()), ("answer", answer)])
+            .map_err(|err| fetch_error(err.to_string()))?;
+
+        if !response.status().is_success() {
+            return Err(fetch_error(format!(
+                "server responded {}",
+                response.status()
+            )));
+        }
+        let html = response
+            .body_mut()
+            .read_to_string()
+            .map_err(|err| fetch_error(err.to_string()))?;
+        parse_verdict(&url, &html)
+    }
+
+    fn save_rejections(&self, rejections: &[Rejection]) -> AocResult<()> {
+        let ledger = self.ledger.display().to_string();
+        let io_error = |source| AocError::Io {
+            file: ledger.clone(),
+            source,
+        };
+        let csv_error = |err: csv::Error| AocError::invalid(err.to_string()).in_file(&ledger);
+
+        if let Some(dir) = self
+            .ledger
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+        {
+            fs::create_dir_all(dir).map_err(io_error)?;
+        }
+        let mut writer = Writer::from_path(&self.ledger).map_err(csv_error)?;
+        writer
+            .write_record(["year", "day", "part", "answer", "verdict"])
+            .map_err(csv_error)?;
+        for rejection in rejections {
+            writer
+                .write_record([
+                    rejection.year.to_string(),
+                    rejection.day.to_string(),
+                    rejection.part.to_string(),
+                    rejection.answer.clone(),
+                    ledger_verdict(&rejection.verdict),
+                ])
+                .map_err(csv_error)?;
+        }
+        writer.flush().map_err(io_error)
+    }
+}
+
+/// The verdict as written to the ledger, with a wait turned into the time it
+/// is over so that it still holds when the ledger is read back later.
+fn ledger_verdict(verdict: &Verdict) -> String {
+    match verdict {
+        Verdict::Wait(wait) => {
+            let until = SystemTime::now() + *wait;
+            let secs = until.duration_since(UNIX_EPOCH).unwrap_or_default();
+            // Round up so the wait is never cut short
+            let secs = secs.as_secs() + u64::from(secs.subsec_nanos() > 0);
+            format!("wait until {secs}")
+        }
+        verdict => verdict.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::fake_server::serve_once;
+    use crate::utils::Contents;
+
+    const TOO_HIGH: &str = "<main><article><p>That's not the right answer; your answer is too \
+        high.  If you're stuck, make sure you're using the full input data. Please wait one \
+        minute before trying again. <a href=\"/2024/day/1\">[Return to Day 1]</a></p></article></main>";
+
+    const TOO_RECENT: &str = "<article><p>You gave an answer too recently; you have to wait \
+        after submitting an answer before trying again.  You have 4m 34s left to wait.</p></article>";
+
+    const URL: &str = "https://adventofcode.com/2024/day/1/answer";
+
+    fn temp_ledger(name: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("aoc_{name}_{}.csv", std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn test_parse_verdict() {
+        assert_eq!(Verdict::TooHigh, parse_verdict(URL, TOO_HIGH).unwrap());
+        assert_eq!(
+            Verdict::Correct,
+            parse_verdict(
+                URL,
+                "<article><p>That's the right answer!  You are one gold star closer.</p></article>"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            Verdict::Wait(Duration::from_secs(274)),
+            parse_verdict(URL, TOO_RECENT).unwrap()
+        );
+        assert_eq!(
+            Verdict::WrongLevel,
+            parse_verdict(
+                URL,
+                "<article><p>You don't seem to be solving the right level.</p></article>"
+            )
+            .unwrap()
+        );
+        assert!(matches!(
+            parse_verdict(
+                URL,
+                "<article><p>You gave an answer too recently.  You have \
+                 99999999999999999999s left to wait.</p></article>"
+            ),
+            Err(AocError::Fetch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_ledger() {
+        let ledger = "year,day,part,answer,verdict\n2024,1,1,42,too low\n2024,1,2,\"1,2\",wrong\n";
+        let rejections = parse_ledger(Contents(ledger)).unwrap();
+        assert_eq!(Verdict::TooLow, rejections[0].verdict);
+        assert_eq!("1,2", rejections[1].answer);
+
+        let ledger = "year,day,part,answer,verdict\n2024,1,1,42,wait until 0\n\
+            2024,1,2,43,wait until 99999999999\n";
+        let rejections = parse_ledger(Contents(ledger)).unwrap();
+        assert_eq!(1, rejections.len());
+        assert!(matches!(rejections[0].verdict, Verdict::Wait(_)));
+    }
+
+    #[test]
+    fn test_submit_records_rejection() {
+        let (base_url, server) = serve_once("200 OK", TOO_HIGH);
+        let ledger = temp_ledger("submit_records_rejection");
+        let submitter = Submitter::new(&ledger)
+            .with_base_url(&base_url)
+            .with_session("abc123");
+
+        assert_eq!(
+            Submission::Sent(Verdict::TooHigh),
+            submitter.submit(2024, 1, 2, "1000").unwrap()
+        );
+        let request = server.join().unwrap();
+        assert_eq!("POST /2024/day/1/answer HTTP/1.1", request.line);
+        assert_eq!("session=abc123", request.cookie);
+        assert_eq!("level=2&answer=1000", request.body);
+
+        // The server is gone, so these can only be answered from the ledger
+        assert_eq!(
+            Submission::Known(Verdict::TooHigh),
+            submitter.submit(2024, 1, 2, "1000").unwrap()
+        );
+        assert_eq!(
+            Submission::Known(Verdict::TooHigh),
+            submitter.submit(2024, 1, 2, "1234").unwrap()
+        );
+        assert!(submitter.submit(2024, 1, 2, "999").is_err());
+
+        fs::remove_file(&ledger).unwrap();
+    }
+
+    #[test]
+    fn test_submit_records_wait() {
+        let (base_url, server) = serve_once("200 OK", TOO_RECENT);
+        let ledger = temp_ledger("submit_records_wait");
+        let submitter = Submitter::new(&ledger)
+            .with_base_url(&base_url)
+            .with_session("abc123");
+
+        assert_eq!(
+            Submission::Sent(Verdict::Wait(Duration::from_secs(274))),
+            submitter.submit(2024, 1, 2, "1000").unwrap()
+        );
+        server.join().unwrap();
+
+        // The server is gone, so any answer within the wait is held back locally
+        let Submission::Known(Verdict::Wait(wait)) = submitter.submit(2024, 1, 2, "1234").unwrap()
+        else {
+            panic!("expected the wait to be known");
+        };
+        assert!(wait <= Duration::from_secs(275));
+
+        // The wait covers the whole account, not just the puzzle that got it
+        assert!(matches!(
+            submitter.submit(2023, 5, 1, "1234").unwrap(),
+            Submission::Known(Verdict::Wait(_))
+        ));
+
+        fs::remove_file(&ledger).unwrap();
+    }
+}
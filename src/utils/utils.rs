@@ -1,32 +1,122 @@
 use std::{
     collections::HashMap,
-    error::Error,
     fs::File,
-    io::{BufRead, BufReader},
-    path::Path,
+    io::{self, BufRead, BufReader, Read},
+    path::{Path, PathBuf},
 };
 
 use petgraph::graph::NodeIndex;
 
-pub fn get_lines(input_file: &str) -> Vec<String> {
-    let path = Path::new(input_file);
-    let display = path.display();
-
-    let file = match File::open(input_file) {
-        // The `description` method of `io::Error` returns a string that describes the error
-        Err(why) => panic!(
-            "couldn't open {}: {}",
-            display,
-            <dyn Error>::to_string(&why)
-        ),
-        Ok(file) => file,
-    };
-
-    let reader = BufReader::new(file);
-    reader
-        .lines()
-        .map(|l| l.expect("Could not parse line"))
-        .collect()
+use crate::error::{AocError, AocResult};
+
+pub use crate::grid::{Grid, Pos};
+pub use crate::point::{Coord, Point, Point3, Point4};
+
+/// A source of puzzle input: a file path, in-memory contents or any reader.
+///
+/// Plain `&str` and `String` values are treated as file paths, so wrap
+/// in-memory contents in [`Contents`] and readers such as stdin in [`FromReader`].
+pub trait PuzzleInput {
+    fn read_lines(self) -> AocResult<Vec<String>>;
+
+    fn read_to_string(self) -> AocResult<String>
+    where
+        Self: Sized,
+    {
+        Ok(self.read_lines()?.join("\n"))
+    }
+}
+
+/// Puzzle input held in memory, e.g. an example embedded in a test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Contents<'a>(pub &'a str);
+
+/// Puzzle input read from any [`Read`] implementor, e.g. stdin.
+#[derive(Debug)]
+pub struct FromReader<R: Read>(pub R);
+
+fn io_error(path: &Path, source: io::Error) -> AocError {
+    AocError::Io {
+        file: path.display().to_string(),
+        source,
+    }
+}
+
+impl PuzzleInput for &Path {
+    fn read_lines(self) -> AocResult<Vec<String>> {
+        let file = File::open(self).map_err(|why| io_error(self, why))?;
+        FromReader(file)
+            .read_lines()
+            .map_err(|err| err.in_file(&self.display().to_string()))
+    }
+
+    fn read_to_string(self) -> AocResult<String> {
+        std::fs::read_to_string(self).map_err(|why| io_error(self, why))
+    }
+}
+
+impl PuzzleInput for &PathBuf {
+    fn read_lines(self) -> AocResult<Vec<String>> {
+        self.as_path().read_lines()
+    }
+
+    fn read_to_string(self) -> AocResult<String> {
+        self.as_path().read_to_string()
+    }
+}
+
+impl PuzzleInput for &str {
+    fn read_lines(self) -> AocResult<Vec<String>> {
+        Path::new(self).read_lines()
+    }
+
+    fn read_to_string(self) -> AocResult<String> {
+        Path::new(self).read_to_string()
+    }
+}
+
+impl PuzzleInput for &String {
+    fn read_lines(self) -> AocResult<Vec<String>> {
+        self.as_str().read_lines()
+    }
+
+    fn read_to_string(self) -> AocResult<String> {
+        self.as_str().read_to_string()
+    }
+}
+
+impl PuzzleInput for Contents<'_> {
+    fn read_lines(self) -> AocResult<Vec<String>> {
+        Ok(self.0.lines().map(String::from).collect())
+    }
+
+    fn read_to_string(self) -> AocResult<String> {
+        Ok(self.0.to_string())
+    }
+}
+
+impl<R: Read> PuzzleInput for FromReader<R> {
+    fn read_lines(self) -> AocResult<Vec<String>> {
+        BufReader::new(self.0)
+            .lines()
+            .enumerate()
+            .map(|(line_idx, line)| {
+                line.map_err(|why| AocError::parse(line_idx, 0, why.to_string()))
+            })
+            .collect()
+    }
+
+    fn read_to_string(mut self) -> AocResult<String> {
+        let mut contents = String::new();
+        self.0
+            .read_to_string(&mut contents)
+            .map_err(|why| AocError::invalid(why.to_string()))?;
+        Ok(contents)
+    }
+}
+
+pub fn get_lines(puzzle_input: impl PuzzleInput) -> AocResult<Vec<String>> {
+    puzzle_input.read_lines()
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Default)]
@@ -53,6 +143,18 @@ impl TryFrom<u8> for MoveDir {
     }
 }
 
+/// Up is north, as y grows southwards in [`Direction::to_delta`].
+impl From<MoveDir> for Direction {
+    fn from(dir: MoveDir) -> Self {
+        match dir {
+            MoveDir::Up => Direction::N,
+            MoveDir::Down => Direction::S,
+            MoveDir::Left => Direction::W,
+            MoveDir::Right => Direction::E,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Ord, PartialOrd)]
 pub enum Direction {
     N,
@@ -133,6 +235,38 @@ pub enum Compass {
     NorthWest,
 }
 
+impl Compass {
+    /// The `(dx, dy)` of one step, with `y` growing southwards as in
+    /// [`Direction::to_delta`].
+    pub fn to_delta(self) -> (isize, isize) {
+        match self {
+            Compass::North => (0, -1),
+            Compass::NorthEast => (1, -1),
+            Compass::East => (1, 0),
+            Compass::SouthEast => (1, 1),
+            Compass::South => (0, 1),
+            Compass::SouthWest => (-1, 1),
+            Compass::West => (-1, 0),
+            Compass::NorthWest => (-1, -1),
+        }
+    }
+
+    /// Every point of the compass, clockwise from north.
+    pub fn all() -> impl Iterator<Item = Compass> {
+        [
+            Compass::North,
+            Compass::NorthEast,
+            Compass::East,
+            Compass::SouthEast,
+            Compass::South,
+            Compass::SouthWest,
+            Compass::West,
+            Compass::NorthWest,
+        ]
+        .into_iter()
+    }
+}
+
 pub struct ListNode<T> {
     pub val: T,
     pub next: Option<Box<ListNode<T>>>,
@@ -288,18 +422,6 @@ pub fn get_all_paths<T>(
     all_paths
 }
 
-pub fn manhattan_distance(from: (usize, usize), to: (usize, usize)) -> usize {
-    let (from_x, from_y) = from;
-    let (to_x, to_y) = to;
-    ((from_x as isize - to_x as isize).abs() + (from_y as isize - to_y as isize).abs()) as usize
-}
-
-pub fn manhattan_distance_i(from: (isize, isize), to: (isize, isize)) -> isize {
-    let (from_x, from_y) = from;
-    let (to_x, to_y) = to;
-    (from_x - to_x).abs() + (from_y - to_y).abs()
-}
-
 pub fn digits_to_int(digits: &[isize]) -> isize {
     digits
         .iter()
@@ -428,6 +550,46 @@ mod tests {
         assert_eq!(paths[0], vec![a]);
     }
 
+    #[test]
+    fn test_get_lines_from_contents() {
+        assert_eq!(
+            vec!["3   4", "4   3"],
+            get_lines(Contents("3   4\n4   3\n")).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_get_lines_from_reader() {
+        let reader = "3   4\r\n4   3".as_bytes();
+        assert_eq!(
+            vec!["3   4", "4   3"],
+            get_lines(FromReader(reader)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_get_lines_from_path_matches_contents() {
+        let contents = std::fs::read_to_string("input/2024/day01_test01.txt").unwrap();
+        assert_eq!(
+            get_lines(Contents(&contents)).unwrap(),
+            get_lines("input/2024/day01_test01.txt").unwrap()
+        );
+        assert_eq!(
+            get_lines(Contents(&contents)).unwrap(),
+            get_lines(Path::new("input/2024/day01_test01.txt")).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_get_lines_missing_file() {
+        let err = get_lines("input/2024/day99.txt").unwrap_err();
+        assert!(matches!(err, AocError::Io { .. }));
+        assert!(
+            err.to_string()
+                .starts_with("couldn't read input/2024/day99.txt")
+        );
+    }
+
     #[test]
     fn test_digits_to_int() {
         let digits = vec![1, 0, 0, 2];